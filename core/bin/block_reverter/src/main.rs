@@ -82,7 +82,7 @@ async fn main() -> anyhow::Result<()> {
             .context("Invalid Sentry URL")?
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     let eth_sender = ETHSenderConfig::from_env().context("ETHSenderConfig::from_env()")?;
     let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
@@ -91,7 +91,8 @@ async fn main() -> anyhow::Result<()> {
         U256::from(eth_sender.gas_adjuster.default_priority_fee_per_gas);
     let contracts = ContractsConfig::from_env().context("ContractsConfig::from_env()")?;
     let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
-    let config = BlockReverterEthConfig::new(eth_sender, contracts, eth_client.web3_url.clone());
+    let config =
+        BlockReverterEthConfig::new(eth_sender, contracts, eth_client.web3_url.expose().clone());
 
     let connection_pool = ConnectionPool::builder(
         postgres_config.master_url()?,
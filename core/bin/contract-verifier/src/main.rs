@@ -154,7 +154,7 @@ async fn main() -> anyhow::Result<()> {
             .expect("Invalid Sentry URL")
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     // Report whether sentry is running after the logging subsystem was initialized.
     if let Some(sentry_url) = sentry_url {
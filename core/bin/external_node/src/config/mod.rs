@@ -1,9 +1,10 @@
-use std::{env, time::Duration};
+use std::{collections::BTreeMap, env, fs, path::Path, time::Duration};
 
 use anyhow::Context;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use url::Url;
 use zksync_basic_types::{Address, L1ChainId, L2ChainId};
+use zksync_config::configs::Secret;
 use zksync_consensus_roles::node;
 use zksync_core::{
     api_server::{
@@ -12,12 +13,14 @@ use zksync_core::{
     },
     consensus,
 };
+use zksync_protobuf::build::prost_reflect::{ReflectMessage, SerializeOptions, Value};
 use zksync_types::api::BridgeAddresses;
 use zksync_web3_decl::{
     jsonrpsee::http_client::{HttpClient, HttpClientBuilder},
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
 };
 
+mod proto;
 #[cfg(test)]
 mod tests;
 
@@ -88,7 +91,7 @@ pub enum BlockFetcher {
 /// This part of the external node config is completely optional to provide.
 /// It can tweak limits of the API, delay intervals of certain components, etc.
 /// If any of the fields are not provided, the default values will be used.
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OptionalENConfig {
     // User-facing API limits
     /// Max possible limit of filters to be in the API state at once.
@@ -105,8 +108,10 @@ pub struct OptionalENConfig {
     pub max_tx_size: usize,
     /// Max number of cache misses during one VM execution. If the number of cache misses exceeds this value, the API server panics.
     /// This is a temporary solution to mitigate API request resulting in thousands of DB queries.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vm_execution_cache_misses_limit: Option<usize>,
     /// Note: Deprecated option, no longer in use. Left to display a warning in case someone used them.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transactions_per_sec_limit: Option<u32>,
     /// Limit for fee history block range.
     #[serde(default = "OptionalENConfig::default_fee_history_limit")]
@@ -143,6 +148,7 @@ pub struct OptionalENConfig {
     #[serde(default = "OptionalENConfig::default_latest_values_cache_size_mb")]
     latest_values_cache_size_mb: usize,
     /// Enabled JSON RPC API namespaces.
+    #[serde(skip_serializing_if = "Option::is_none")]
     api_namespaces: Option<Vec<Namespace>>,
 
     // Gas estimation config
@@ -193,6 +199,7 @@ pub struct OptionalENConfig {
 
     // Other config settings
     /// Port on which the Prometheus exporter server is listening.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub prometheus_port: Option<u16>,
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     #[serde(default = "OptionalENConfig::default_enum_index_migration_chunk_size")]
@@ -358,7 +365,7 @@ impl OptionalENConfig {
 }
 
 /// This part of the external node config is required for its operation.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RequiredENConfig {
     /// Port on which the HTTP RPC server is listening.
     pub http_port: u16,
@@ -368,23 +375,27 @@ pub struct RequiredENConfig {
     pub healthcheck_port: u16,
     /// Address of the Ethereum node API.
     /// Intentionally private: use getter method as it manages the missing port.
-    eth_client_url: String,
+    eth_client_url: Secret<String>,
     /// Main node URL - used by external node to proxy transactions to, query state from, etc.
     /// Intentionally private: use getter method as it manages the missing port.
-    main_node_url: String,
+    main_node_url: Secret<String>,
     /// Path to the database data directory that serves state cache.
     pub state_cache_path: String,
     /// Fast SSD path. Used as a RocksDB dir for the Merkle tree (*new* implementation).
     pub merkle_tree_path: String,
+    /// L1 chain ID. Checked against the chain IDs reported by the main node and the Ethereum node.
+    pub l1_chain_id: u64,
+    /// L2 chain ID. Checked against the chain ID reported by the main node.
+    pub l2_chain_id: L2ChainId,
 }
 
 impl RequiredENConfig {
     pub fn main_node_url(&self) -> anyhow::Result<String> {
-        Self::get_url(&self.main_node_url).context("Could not parse main node URL")
+        Self::get_url(self.main_node_url.expose()).context("Could not parse main node URL")
     }
 
     pub fn eth_client_url(&self) -> anyhow::Result<String> {
-        Self::get_url(&self.eth_client_url).context("Could not parse L1 client URL")
+        Self::get_url(self.eth_client_url.expose()).context("Could not parse L1 client URL")
     }
 
     fn get_url(url_str: &str) -> anyhow::Result<String> {
@@ -397,9 +408,9 @@ impl RequiredENConfig {
 /// While also mandatory, it historically used different naming scheme for corresponding
 /// environment variables.
 /// Thus it is kept separately for backward compatibility and ease of deserialization.
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PostgresConfig {
-    pub database_url: String,
+    pub database_url: Secret<String>,
     pub max_connections: u32,
 }
 
@@ -407,7 +418,8 @@ impl PostgresConfig {
     pub fn from_env() -> anyhow::Result<Self> {
         Ok(Self {
             database_url: env::var("DATABASE_URL")
-                .context("DATABASE_URL env variable is not set")?,
+                .context("DATABASE_URL env variable is not set")?
+                .into(),
             max_connections: env::var("DATABASE_POOL_SIZE")
                 .context("DATABASE_POOL_SIZE env variable is not set")?
                 .parse()
@@ -428,6 +440,104 @@ pub(crate) fn read_consensus_config() -> anyhow::Result<consensus::FetcherConfig
     })
 }
 
+/// Decodes the config file according to the `ExternalNodeConfig` proto schema and maps the specified values
+/// to the corresponding environment variables (e.g., `optional.filters_limit` to `EN_FILTERS_LIMIT`).
+/// Unknown fields are rejected.
+fn parse_config_file(yaml: &str) -> anyhow::Result<BTreeMap<String, String>> {
+    let d = serde_yaml::Deserializer::from_str(yaml);
+    let config: proto::ExternalNodeConfig = zksync_protobuf::serde::deserialize_proto(d)?;
+    let config = config.transcode_to_dynamic();
+
+    let mut vars = BTreeMap::new();
+    for (section, section_value) in config.fields() {
+        let Value::Message(section_value) = section_value else {
+            anyhow::bail!("unexpected value for section `{}`", section.name());
+        };
+        for (field, value) in section_value.fields() {
+            let value = config_value_to_string(value)
+                .with_context(|| format!("{}.{}", section.name(), field.name()))?;
+            vars.insert(config_var_name(section.name(), field.name()), value);
+        }
+    }
+    Ok(vars)
+}
+
+/// Returns the name of the environment variable corresponding to a config file field.
+fn config_var_name(section: &str, field: &str) -> String {
+    match (section, field) {
+        ("postgres", "database_url") => "DATABASE_URL".to_owned(),
+        ("postgres", "max_connections") => "DATABASE_POOL_SIZE".to_owned(),
+        _ => format!("EN_{}", field.to_uppercase()),
+    }
+}
+
+/// Formats a config value as expected by the environment variables parser; sequences are joined with commas.
+fn config_value_to_string(value: &Value) -> anyhow::Result<String> {
+    Ok(match value {
+        Value::Bool(value) => value.to_string(),
+        Value::U32(value) => value.to_string(),
+        Value::U64(value) => value.to_string(),
+        Value::F64(value) => value.to_string(),
+        Value::String(value) => value.clone(),
+        Value::List(items) => items
+            .iter()
+            .map(config_value_to_string)
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .join(","),
+        _ => anyhow::bail!("unexpected value {value:?}"),
+    })
+}
+
+/// Loads the values from a YAML or JSON config file into the environment.
+/// Variables already set in the environment take precedence over the file.
+pub(crate) fn load_config_file(path: &Path) -> anyhow::Result<()> {
+    let yaml = fs::read_to_string(path).with_context(|| path.display().to_string())?;
+    let vars = parse_config_file(&yaml)
+        .with_context(|| format!("invalid config file {}", path.display()))?;
+    for (name, value) in vars {
+        if env::var_os(&name).is_none() {
+            env::set_var(name, value);
+        }
+    }
+    Ok(())
+}
+
+/// Encodes the config in the config file format. Secrets are redacted by the `Secret` serialization.
+fn encode_config(
+    required: &RequiredENConfig,
+    optional: &OptionalENConfig,
+    postgres: &PostgresConfig,
+) -> anyhow::Result<String> {
+    let config = serde_json::json!({
+        "required": required,
+        "optional": optional,
+        "postgres": postgres,
+    });
+    // Passing the config through the proto schema ensures that the output can be read back.
+    let config: proto::ExternalNodeConfig = zksync_protobuf::serde::deserialize_proto(config)
+        .context("config doesn't match the config file schema")?;
+    let options = SerializeOptions::new()
+        .use_proto_field_name(true)
+        .stringify_64_bit_integers(false);
+    let config = config
+        .transcode_to_dynamic()
+        .serialize_with_options(serde_json::value::Serializer, &options)?;
+    Ok(serde_yaml::to_string(&config)?)
+}
+
+/// Validates the config specified in the environment and returns it in the config file format,
+/// with the secrets redacted.
+pub(crate) fn print_config() -> anyhow::Result<String> {
+    let required = envy::prefixed("EN_")
+        .from_env::<RequiredENConfig>()
+        .context("could not load external node config")?;
+    let optional = envy::prefixed("EN_")
+        .from_env::<OptionalENConfig>()
+        .context("could not load external node config")?;
+    let postgres = PostgresConfig::from_env()?;
+    encode_config(&required, &optional, &postgres)
+}
+
 /// External Node Config contains all the configuration required for the EN operation.
 /// It is split into three parts: required, optional and remote for easier navigation.
 #[derive(Debug, Clone)]
@@ -466,8 +576,8 @@ impl ExternalNodeConfig {
             .await
             .context("Unable to check L1 chain ID through the configured L1 client")?;
 
-        let l2_chain_id: L2ChainId = env_var("EN_L2_CHAIN_ID");
-        let l1_chain_id: u64 = env_var("EN_L1_CHAIN_ID");
+        let l2_chain_id = required.l2_chain_id;
+        let l1_chain_id = required.l1_chain_id;
         if l2_chain_id != remote.l2_chain_id {
             anyhow::bail!(
                 "Configured L2 chain id doesn't match the one from main node.
@@ -506,17 +616,6 @@ impl ExternalNodeConfig {
     }
 }
 
-fn env_var<T>(name: &str) -> T
-where
-    T: std::str::FromStr,
-    T::Err: std::fmt::Debug,
-{
-    env::var(name)
-        .unwrap_or_else(|_| panic!("{} env variable is not set", name))
-        .parse()
-        .unwrap_or_else(|_| panic!("unable to parse {} env variable", name))
-}
-
 impl From<ExternalNodeConfig> for InternalApiConfig {
     fn from(config: ExternalNodeConfig) -> Self {
         Self {
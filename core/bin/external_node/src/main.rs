@@ -1,4 +1,4 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use clap::Parser;
@@ -172,7 +172,7 @@ async fn init_tasks(
 
     let main_node_client = <dyn MainNodeClient>::json_rpc(&main_node_url)
         .context("Failed creating JSON-RPC client for main node")?;
-    let singleton_pool_builder = ConnectionPool::singleton(&config.postgres.database_url);
+    let singleton_pool_builder = ConnectionPool::singleton(config.postgres.database_url.expose());
 
     let fetcher_handle = if let Some(cfg) = config.consensus.clone() {
         let pool = connection_pool.clone();
@@ -385,12 +385,26 @@ struct Cli {
     revert_pending_l1_batch: bool,
     #[arg(long)]
     enable_consensus: bool,
+    /// Path to the YAML or JSON config file mapping environment variable names to their values.
+    /// Variables set in the environment take precedence over the file.
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Print the effective config with the secrets redacted and exit.
+    #[arg(long)]
+    print_config: bool,
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initial setup.
     let opt = Cli::parse();
+    if let Some(path) = &opt.config_path {
+        config::load_config_file(path)?;
+    }
+    if opt.print_config {
+        print!("{}", config::print_config()?);
+        return Ok(());
+    }
 
     #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
     let log_format = vlog::log_format_from_env();
@@ -406,7 +420,7 @@ async fn main() -> anyhow::Result<()> {
             .expect("Invalid Sentry URL")
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     // Report whether sentry is running after the logging subsystem was initialized.
     if let Some(sentry_url) = sentry_url {
@@ -428,7 +442,7 @@ async fn main() -> anyhow::Result<()> {
         .context("Main node URL is incorrect")?;
 
     let connection_pool = ConnectionPool::builder(
-        &config.postgres.database_url,
+        config.postgres.database_url.expose(),
         config.postgres.max_connections,
     )
     .build()
@@ -0,0 +1,214 @@
+//! Structured diffs between stored and re-executed transaction outputs.
+
+use serde::Serialize;
+use zksync_types::{Address, Bytes, H256};
+
+/// Storage write produced by a transaction. Writes are deduplicated within a miniblock in the same way
+/// as they are persisted by the state keeper, i.e., each slot is attributed to the last transaction modifying it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub(crate) struct StorageWrite {
+    pub address: Address,
+    pub key: H256,
+    pub value: H256,
+}
+
+/// Event emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Event {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+/// User L2-to-L1 log emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct L2ToL1Log {
+    pub shard_id: u64,
+    pub is_service: bool,
+    pub sender: Address,
+    pub key: H256,
+    pub value: H256,
+}
+
+/// Transaction outputs compared during re-execution.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TxOutputs {
+    pub success: bool,
+    pub refunded_gas: u64,
+    /// Gas used during execution (i.e., before the refund). May be unknown for stored transactions.
+    pub gas_used: Option<u64>,
+    pub storage_writes: Vec<StorageWrite>,
+    pub events: Vec<Event>,
+    pub l2_to_l1_logs: Vec<L2ToL1Log>,
+}
+
+/// Difference between a stored and re-executed value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ValueDiff<T> {
+    pub stored: T,
+    pub reexecuted: T,
+}
+
+impl<T: PartialEq> ValueDiff<T> {
+    fn new(stored: T, reexecuted: T) -> Option<Self> {
+        (stored != reexecuted).then_some(Self { stored, reexecuted })
+    }
+}
+
+/// Difference between stored and re-executed lists. Only the first mismatching item is recorded;
+/// if one of the lists is a prefix of the other one, the corresponding item is `None`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ListDiff<T> {
+    pub stored_len: usize,
+    pub reexecuted_len: usize,
+    pub first_mismatch_index: usize,
+    pub stored: Option<T>,
+    pub reexecuted: Option<T>,
+}
+
+impl<T: Clone + PartialEq> ListDiff<T> {
+    fn new(stored: &[T], reexecuted: &[T]) -> Option<Self> {
+        if stored == reexecuted {
+            return None;
+        }
+        let first_mismatch_index = stored
+            .iter()
+            .zip(reexecuted)
+            .position(|(stored, reexecuted)| stored != reexecuted)
+            .unwrap_or_else(|| stored.len().min(reexecuted.len()));
+        Some(Self {
+            stored_len: stored.len(),
+            reexecuted_len: reexecuted.len(),
+            first_mismatch_index,
+            stored: stored.get(first_mismatch_index).cloned(),
+            reexecuted: reexecuted.get(first_mismatch_index).cloned(),
+        })
+    }
+}
+
+/// Structured diff between stored and re-executed outputs of a transaction. Only differing fields are set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TxDiff {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<ValueDiff<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refunded_gas: Option<ValueDiff<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_used: Option<ValueDiff<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_writes: Option<ListDiff<StorageWrite>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<ListDiff<Event>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l2_to_l1_logs: Option<ListDiff<L2ToL1Log>>,
+}
+
+impl TxDiff {
+    pub fn new(stored: &TxOutputs, reexecuted: &TxOutputs) -> Self {
+        // Gas used is not compared if it's unknown for either of the outputs.
+        let gas_used = match (stored.gas_used, reexecuted.gas_used) {
+            (Some(stored), Some(reexecuted)) => ValueDiff::new(stored, reexecuted),
+            _ => None,
+        };
+        Self {
+            success: ValueDiff::new(stored.success, reexecuted.success),
+            refunded_gas: ValueDiff::new(stored.refunded_gas, reexecuted.refunded_gas),
+            gas_used,
+            storage_writes: ListDiff::new(&stored.storage_writes, &reexecuted.storage_writes),
+            events: ListDiff::new(&stored.events, &reexecuted.events),
+            l2_to_l1_logs: ListDiff::new(&stored.l2_to_l1_logs, &reexecuted.l2_to_l1_logs),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage_write(byte: u8) -> StorageWrite {
+        StorageWrite {
+            address: Address::repeat_byte(byte),
+            key: H256::repeat_byte(byte),
+            value: H256::repeat_byte(byte),
+        }
+    }
+
+    fn outputs() -> TxOutputs {
+        TxOutputs {
+            success: true,
+            refunded_gas: 100,
+            gas_used: Some(1_000),
+            storage_writes: vec![storage_write(1), storage_write(2)],
+            events: vec![Event {
+                address: Address::repeat_byte(0x10),
+                topics: vec![H256::repeat_byte(0x11)],
+                data: Bytes(vec![1, 2, 3]),
+            }],
+            l2_to_l1_logs: vec![],
+        }
+    }
+
+    #[test]
+    fn list_diff_with_mismatching_item() {
+        let stored = [storage_write(1), storage_write(2), storage_write(3)];
+        let reexecuted = [storage_write(1), storage_write(4), storage_write(3)];
+        let diff = ListDiff::new(&stored, &reexecuted).unwrap();
+        assert_eq!(diff.stored_len, 3);
+        assert_eq!(diff.reexecuted_len, 3);
+        assert_eq!(diff.first_mismatch_index, 1);
+        assert_eq!(diff.stored, Some(storage_write(2)));
+        assert_eq!(diff.reexecuted, Some(storage_write(4)));
+
+        assert!(ListDiff::new(&stored, &stored).is_none());
+    }
+
+    #[test]
+    fn list_diff_with_prefix() {
+        let stored = [storage_write(1)];
+        let reexecuted = [storage_write(1), storage_write(2)];
+        let diff = ListDiff::new(&stored, &reexecuted).unwrap();
+        assert_eq!(diff.first_mismatch_index, 1);
+        assert_eq!(diff.stored, None);
+        assert_eq!(diff.reexecuted, Some(storage_write(2)));
+    }
+
+    #[test]
+    fn tx_diff() {
+        let stored = outputs();
+        assert!(TxDiff::new(&stored, &stored).is_empty());
+
+        let mut reexecuted = outputs();
+        reexecuted.gas_used = Some(1_100);
+        reexecuted.events.clear();
+        let diff = TxDiff::new(&stored, &reexecuted);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.gas_used,
+            Some(ValueDiff {
+                stored: 1_000,
+                reexecuted: 1_100
+            })
+        );
+        let events_diff = diff.events.unwrap();
+        assert_eq!(events_diff.first_mismatch_index, 0);
+        assert_eq!(events_diff.reexecuted, None);
+        assert!(diff.success.is_none());
+        assert!(diff.storage_writes.is_none());
+    }
+
+    #[test]
+    fn tx_diff_with_unknown_gas_used() {
+        let mut stored = outputs();
+        stored.gas_used = None;
+        let mut reexecuted = outputs();
+        reexecuted.gas_used = Some(1_100);
+        assert!(TxDiff::new(&stored, &reexecuted).is_empty());
+    }
+}
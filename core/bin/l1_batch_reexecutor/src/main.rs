@@ -0,0 +1,115 @@
+//! L1 batch re-execution utility. Re-executes a range of sealed L1 batches stored in Postgres with the VM version
+//! corresponding to the protocol version of each batch, and compares storage logs, events, L2-to-L1 logs,
+//! refunds and gas usage of each transaction with the stored ones. For each L1 batch, the first diverging
+//! transaction is reported together with a structured diff.
+//!
+//! The utility never modifies Postgres.
+
+use anyhow::Context as _;
+use clap::Parser;
+use zksync_config::{configs::chain::NetworkConfig, PostgresConfig};
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_types::L1BatchNumber;
+
+use crate::reexecutor::{L1BatchReexecutor, L1BatchReport};
+
+mod diff;
+mod reexecutor;
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "L1 batch re-execution utility", long_about = None)]
+struct Cli {
+    /// First sealed L1 batch to re-execute.
+    #[arg(long)]
+    from_l1_batch: u32,
+    /// Last sealed L1 batch to re-execute (inclusive). If not specified, the latest sealed batch is used.
+    #[arg(long)]
+    to_l1_batch: Option<u32>,
+    /// Outputs reports as a JSON array, so that they are machine-readable.
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    let _guard = vlog::ObservabilityBuilder::new()
+        .with_log_format(log_format)
+        .build()?;
+
+    let opt = Cli::parse();
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let pool = ConnectionPool::builder(postgres_config.replica_url()?, 2)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+
+    let reexecutor = L1BatchReexecutor::new(pool, network_config.zksync_network_id);
+    let sealed_l1_batch_number = reexecutor.sealed_l1_batch_number().await?;
+    let from = L1BatchNumber(opt.from_l1_batch);
+    let to = opt
+        .to_l1_batch
+        .map_or(sealed_l1_batch_number, L1BatchNumber);
+    anyhow::ensure!(
+        to <= sealed_l1_batch_number,
+        "L1 batch #{to} is not sealed; the latest sealed batch is #{sealed_l1_batch_number}"
+    );
+    anyhow::ensure!(from <= to, "empty L1 batch range: #{from}..=#{to}");
+
+    let mut reports = vec![];
+    for number in from.0..=to.0 {
+        let l1_batch_number = L1BatchNumber(number);
+        let report = reexecutor
+            .reexecute(l1_batch_number)
+            .await
+            .with_context(|| format!("failed re-executing L1 batch #{l1_batch_number}"))?;
+        if !opt.json {
+            print_report(&report)?;
+        }
+        reports.push(report);
+    }
+
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    let diverged_count = reports
+        .iter()
+        .filter(|report| report.divergence.is_some())
+        .count();
+    anyhow::ensure!(
+        diverged_count == 0,
+        "{diverged_count} out of {} L1 batches diverged",
+        reports.len()
+    );
+    Ok(())
+}
+
+fn print_report(report: &L1BatchReport) -> anyhow::Result<()> {
+    let header = format!(
+        "L1 batch #{} (protocol version {:?}, VM {})",
+        report.l1_batch_number, report.protocol_version, report.vm_version
+    );
+    let Some(divergence) = &report.divergence else {
+        println!(
+            "{header}: {} transactions re-executed, no divergence",
+            report.executed_transactions
+        );
+        return Ok(());
+    };
+
+    println!(
+        "{header}: divergence at transaction {:?} (miniblock #{}, index {} in L1 batch)",
+        divergence.tx_hash, divergence.miniblock_number, divergence.index_in_l1_batch
+    );
+    if let Some(failure) = &divergence.reexecuted_failure {
+        println!("  Re-executed transaction {failure}");
+    }
+    let diff = serde_json::to_string_pretty(&divergence.diff)?;
+    for line in diff.lines() {
+        println!("  {line}");
+    }
+    Ok(())
+}
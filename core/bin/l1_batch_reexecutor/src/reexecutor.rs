@@ -0,0 +1,487 @@
+//! Re-execution of sealed L1 batches and comparison of the results with the ones stored in Postgres.
+
+use std::{collections::HashMap, ops};
+
+use anyhow::Context as _;
+use multivm::{
+    interface::{ExecutionResult, L2BlockEnv, VmExecutionResultAndLogs, VmInterface},
+    VmVersion,
+};
+use serde::Serialize;
+use tokio::runtime::Handle;
+use vm_utils::{create_vm_with_env, execute_tx, load_vm_env};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    storage_writes_deduplicator::StorageWritesDeduplicator, L1BatchNumber, L2ChainId,
+    MiniblockNumber, ProtocolVersionId, StorageLog, Transaction, H256, U64,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::diff::{Event, L2ToL1Log, StorageWrite, TxDiff, TxOutputs};
+
+/// First transaction in an L1 batch with re-executed outputs differing from the stored ones.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TxDivergence {
+    pub miniblock_number: MiniblockNumber,
+    pub index_in_l1_batch: usize,
+    pub tx_hash: H256,
+    /// Revert or halt reason of the re-executed transaction if it has failed. Postgres doesn't store
+    /// detailed failure reasons, so this is provided for information only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reexecuted_failure: Option<String>,
+    pub diff: TxDiff,
+}
+
+/// Report on re-executing a single L1 batch.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct L1BatchReport {
+    pub l1_batch_number: L1BatchNumber,
+    pub protocol_version: ProtocolVersionId,
+    /// VM version used for re-execution.
+    pub vm_version: String,
+    /// Number of re-executed transactions. Re-execution stops at the first divergence.
+    pub executed_transactions: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub divergence: Option<TxDivergence>,
+}
+
+/// Re-executes sealed L1 batches on top of the state stored in Postgres with the VM version
+/// corresponding to the batch protocol version. Postgres is never modified; all VM writes are kept in memory.
+#[derive(Debug, Clone)]
+pub(crate) struct L1BatchReexecutor {
+    pool: ConnectionPool,
+    l2_chain_id: L2ChainId,
+}
+
+impl L1BatchReexecutor {
+    pub fn new(pool: ConnectionPool, l2_chain_id: L2ChainId) -> Self {
+        Self { pool, l2_chain_id }
+    }
+
+    pub async fn sealed_l1_batch_number(&self) -> anyhow::Result<L1BatchNumber> {
+        self.pool
+            .access_storage_tagged("l1_batch_reexecutor")
+            .await?
+            .blocks_dal()
+            .get_sealed_l1_batch_number()
+            .await?
+            .context("no sealed L1 batches in Postgres")
+    }
+
+    /// Re-executes the specified L1 batch until the first transaction with diverging outputs.
+    pub async fn reexecute(&self, l1_batch_number: L1BatchNumber) -> anyhow::Result<L1BatchReport> {
+        let this = self.clone();
+        let rt_handle = Handle::current();
+        tokio::task::spawn_blocking(move || this.reexecute_blocking(rt_handle, l1_batch_number))
+            .await
+            .context("re-execution panicked")?
+    }
+
+    fn reexecute_blocking(
+        &self,
+        rt_handle: Handle,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<L1BatchReport> {
+        let mut connection =
+            rt_handle.block_on(self.pool.access_storage_tagged("l1_batch_reexecutor"))?;
+        let (system_env, l1_batch_env) = load_vm_env(
+            &rt_handle,
+            l1_batch_number,
+            &mut connection,
+            self.l2_chain_id,
+        )?;
+        let protocol_version = system_env.version;
+        let vm_version = VmVersion::from(protocol_version);
+        tracing::info!(
+            "Re-executing L1 batch #{l1_batch_number} with protocol version {protocol_version:?} \
+             using VM {vm_version:?}"
+        );
+
+        let miniblocks = rt_handle.block_on(
+            connection
+                .transactions_dal()
+                .get_miniblocks_to_execute_for_l1_batch(l1_batch_number),
+        )?;
+        let (Some(first_miniblock), Some(last_miniblock)) = (miniblocks.first(), miniblocks.last())
+        else {
+            anyhow::bail!("no miniblocks persisted for L1 batch #{l1_batch_number}");
+        };
+        let miniblock_numbers = first_miniblock.number..=last_miniblock.number;
+        let tx_hashes: Vec<_> = miniblocks
+            .iter()
+            .flat_map(|miniblock| &miniblock.txs)
+            .map(Transaction::hash)
+            .collect();
+        let mut stored_outputs =
+            load_stored_outputs(&rt_handle, &mut connection, miniblock_numbers, &tx_hashes)?;
+
+        let (mut vm, _) = create_vm_with_env(rt_handle, connection, system_env, l1_batch_env);
+        let mut report = L1BatchReport {
+            l1_batch_number,
+            protocol_version,
+            vm_version: format!("{vm_version:?}"),
+            executed_transactions: 0,
+            divergence: None,
+        };
+
+        for (i, miniblock) in miniblocks.iter().enumerate() {
+            if i > 0 {
+                vm.start_new_l2_block(L2BlockEnv::from_miniblock_data(miniblock));
+            }
+
+            // Storage writes are deduplicated across the entire miniblock, so all its transactions
+            // must be executed before comparing outputs.
+            let mut deduplicator = StorageWritesDeduplicator::new();
+            let mut results = Vec::with_capacity(miniblock.txs.len());
+            for tx in &miniblock.txs {
+                let result = execute_tx(tx, &mut vm)
+                    .with_context(|| format!("failed executing transaction {:?}", tx.hash()))?;
+                deduplicator.apply(&result.logs.storage_logs);
+                results.push(result);
+            }
+            let mut storage_writes = HashMap::<_, Vec<_>>::new();
+            for (key, slot) in deduplicator.into_modified_key_values() {
+                let write = StorageWrite {
+                    address: *key.address(),
+                    key: *key.key(),
+                    value: u256_to_h256(slot.value),
+                };
+                storage_writes
+                    .entry(usize::from(slot.tx_index))
+                    .or_default()
+                    .push(write);
+            }
+
+            for (tx, result) in miniblock.txs.iter().zip(results) {
+                let index_in_l1_batch = report.executed_transactions;
+                report.executed_transactions += 1;
+                let tx_hash = tx.hash();
+
+                let stored = stored_outputs
+                    .remove(&tx_hash)
+                    .with_context(|| format!("no stored outputs for transaction {tx_hash:?}"))?;
+                let storage_writes = storage_writes
+                    .remove(&index_in_l1_batch)
+                    .unwrap_or_default();
+                let reexecuted = reexecuted_outputs(&result, storage_writes);
+                let diff = TxDiff::new(&stored, &reexecuted);
+                if !diff.is_empty() {
+                    report.divergence = Some(TxDivergence {
+                        miniblock_number: miniblock.number,
+                        index_in_l1_batch,
+                        tx_hash,
+                        reexecuted_failure: failure_reason(&result.result),
+                        diff,
+                    });
+                    return Ok(report);
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Loads stored outputs of the specified transactions executed in the specified miniblock range.
+fn load_stored_outputs(
+    rt_handle: &Handle,
+    connection: &mut StorageProcessor<'_>,
+    miniblock_numbers: ops::RangeInclusive<MiniblockNumber>,
+    tx_hashes: &[H256],
+) -> anyhow::Result<HashMap<H256, TxOutputs>> {
+    let receipts = rt_handle.block_on(
+        connection
+            .transactions_web3_dal()
+            .get_transaction_receipts(tx_hashes),
+    )?;
+    let mut gas_usage = rt_handle.block_on(
+        connection
+            .transactions_dal()
+            .get_gas_usage_by_hashes(tx_hashes),
+    )?;
+    let mut storage_logs = rt_handle.block_on(
+        connection
+            .storage_logs_dal()
+            .get_storage_logs_by_tx_in_miniblocks(miniblock_numbers),
+    )?;
+
+    receipts
+        .into_iter()
+        .map(|receipt| {
+            let tx_hash = receipt.transaction_hash;
+            let gas_usage = gas_usage
+                .remove(&tx_hash)
+                .with_context(|| format!("no gas usage stored for transaction {tx_hash:?}"))?;
+            let mut storage_writes: Vec<_> = storage_logs
+                .remove(&tx_hash)
+                .unwrap_or_default()
+                .iter()
+                .map(storage_write)
+                .collect();
+            storage_writes.sort_unstable();
+
+            let events = receipt
+                .logs
+                .into_iter()
+                .map(|log| Event {
+                    address: log.address,
+                    topics: log.topics,
+                    data: log.data,
+                })
+                .collect();
+            let l2_to_l1_logs = receipt
+                .l2_to_l1_logs
+                .into_iter()
+                .map(|log| L2ToL1Log {
+                    shard_id: log.shard_id.as_u64(),
+                    is_service: log.is_service,
+                    sender: log.sender,
+                    key: log.key,
+                    value: log.value,
+                })
+                .collect();
+            let outputs = TxOutputs {
+                success: receipt.status == U64::one(),
+                refunded_gas: gas_usage.refunded_gas,
+                gas_used: gas_usage.gas_used,
+                storage_writes,
+                events,
+                l2_to_l1_logs,
+            };
+            Ok((tx_hash, outputs))
+        })
+        .collect()
+}
+
+fn storage_write(log: &StorageLog) -> StorageWrite {
+    StorageWrite {
+        address: *log.key.address(),
+        key: *log.key.key(),
+        value: log.value,
+    }
+}
+
+fn reexecuted_outputs(
+    result: &VmExecutionResultAndLogs,
+    mut storage_writes: Vec<StorageWrite>,
+) -> TxOutputs {
+    storage_writes.sort_unstable();
+    let events = result
+        .logs
+        .events
+        .iter()
+        .map(|event| Event {
+            address: event.address,
+            topics: event.indexed_topics.clone(),
+            data: event.value.clone().into(),
+        })
+        .collect();
+    let l2_to_l1_logs = result
+        .logs
+        .user_l2_to_l1_logs
+        .iter()
+        .map(|log| L2ToL1Log {
+            shard_id: log.0.shard_id.into(),
+            is_service: log.0.is_service,
+            sender: log.0.sender,
+            key: log.0.key,
+            value: log.0.value,
+        })
+        .collect();
+
+    TxOutputs {
+        success: !result.result.is_failed(),
+        refunded_gas: u64::from(result.refunds.gas_refunded),
+        gas_used: Some(u64::from(result.statistics.gas_used)),
+        storage_writes,
+        events,
+        l2_to_l1_logs,
+    }
+}
+
+fn failure_reason(result: &ExecutionResult) -> Option<String> {
+    match result {
+        ExecutionResult::Success { .. } => None,
+        ExecutionResult::Revert { output } => Some(format!("reverted: {output}")),
+        ExecutionResult::Halt { reason } => Some(format!("halted: {reason}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_core::testonly::seal_l1_batch_after_genesis;
+    use zksync_types::{
+        l1::{L1Tx, OpProcessingType, PriorityQueueType},
+        tx::{
+            tx_execution_info::TxExecutionStatus, IncludedTxLocation, TransactionExecutionResult,
+        },
+        Address, Execute, L1BlockNumber, L1TxCommonData, PriorityOpId, U256,
+    };
+
+    use super::*;
+
+    fn create_l1_transaction() -> L1Tx {
+        let common_data = L1TxCommonData {
+            sender: Address::repeat_byte(0x10),
+            serial_id: PriorityOpId(0),
+            deadline_block: 100_000,
+            layer_2_tip_fee: U256::zero(),
+            full_fee: U256::zero(),
+            max_fee_per_gas: 1_000_000_000_u64.into(),
+            gas_limit: 10_000_000_u64.into(),
+            gas_per_pubdata_limit: 800_u64.into(),
+            op_processing_type: OpProcessingType::Common,
+            priority_queue_type: PriorityQueueType::Deque,
+            eth_hash: H256::repeat_byte(0x11),
+            eth_block: 1,
+            canonical_tx_hash: H256::repeat_byte(0x12),
+            to_mint: 1_000_000_000_000_000_000_u64.into(),
+            refund_recipient: Address::repeat_byte(0x10),
+        };
+        L1Tx {
+            execute: Execute {
+                contract_address: Address::repeat_byte(0x20),
+                calldata: vec![],
+                value: U256::zero(),
+                factory_deps: None,
+            },
+            common_data,
+            received_timestamp_ms: 0,
+        }
+    }
+
+    fn execute_in_l1_batch(
+        rt_handle: Handle,
+        pool: ConnectionPool,
+        tx: &Transaction,
+    ) -> VmExecutionResultAndLogs {
+        let mut connection = rt_handle.block_on(pool.access_storage()).unwrap();
+        let (system_env, l1_batch_env) = load_vm_env(
+            &rt_handle,
+            L1BatchNumber(1),
+            &mut connection,
+            L2ChainId::default(),
+        )
+        .unwrap();
+        let (mut vm, _) = create_vm_with_env(rt_handle, connection, system_env, l1_batch_env);
+        execute_tx(tx, &mut vm).unwrap()
+    }
+
+    /// Creates a genesis state and seals L1 batch #1 with a single L1 transaction on top of it. Transaction outputs
+    /// are obtained by executing the transaction in the VM and are persisted in the same way as by the state keeper;
+    /// `tamper_outputs` allows modifying them before they are persisted.
+    async fn prepare_storage(
+        pool: &ConnectionPool,
+        tamper_outputs: impl FnOnce(&mut TransactionExecutionResult),
+    ) -> H256 {
+        let mut storage = pool.access_storage().await.unwrap();
+        let miniblock = seal_l1_batch_after_genesis(&mut storage, L2ChainId::default(), 1).await;
+
+        let l1_tx = create_l1_transaction();
+        storage
+            .transactions_dal()
+            .insert_transaction_l1(l1_tx.clone(), L1BlockNumber(1))
+            .await;
+        let tx = Transaction::from(l1_tx);
+        let tx_hash = tx.hash();
+        let rt_handle = Handle::current();
+        let result = {
+            let pool = pool.clone();
+            let tx = tx.clone();
+            tokio::task::spawn_blocking(move || execute_in_l1_batch(rt_handle, pool, &tx))
+                .await
+                .unwrap()
+        };
+
+        let mut deduplicator = StorageWritesDeduplicator::new();
+        deduplicator.apply(&result.logs.storage_logs);
+        let storage_logs: Vec<_> = deduplicator
+            .into_modified_key_values()
+            .into_iter()
+            .map(|(key, slot)| StorageLog::new_write_log(key, u256_to_h256(slot.value)))
+            .collect();
+        let location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_miniblock: 0,
+            tx_initiator_address: tx.initiator_account(),
+        };
+        let mut tx_result = TransactionExecutionResult {
+            hash: tx_hash,
+            execution_info: result.get_execution_metrics(Some(&tx)),
+            execution_status: if result.result.is_failed() {
+                TxExecutionStatus::Failure
+            } else {
+                TxExecutionStatus::Success
+            },
+            refunded_gas: result.refunds.gas_refunded,
+            operator_suggested_refund: result.refunds.operator_suggested_refund,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+            transaction: tx,
+        };
+        tamper_outputs(&mut tx_result);
+
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_miniblock(
+                miniblock.number,
+                &[tx_result],
+                miniblock.base_fee_per_gas.into(),
+            )
+            .await;
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(MiniblockNumber(1), &[(tx_hash, storage_logs)])
+            .await;
+        let events = result.logs.events.iter().collect();
+        storage
+            .events_dal()
+            .save_events(MiniblockNumber(1), &[(location, events)])
+            .await;
+        let l2_to_l1_logs = result.logs.user_l2_to_l1_logs.iter().collect();
+        storage
+            .events_dal()
+            .save_user_l2_to_l1_logs(MiniblockNumber(1), &[(location, l2_to_l1_logs)])
+            .await;
+        tx_hash
+    }
+
+    #[tokio::test]
+    async fn reexecuting_l1_batch_without_divergence() {
+        let pool = ConnectionPool::test_pool().await;
+        prepare_storage(&pool, |_| {}).await;
+
+        let reexecutor = L1BatchReexecutor::new(pool, L2ChainId::default());
+        let l1_batch_number = reexecutor.sealed_l1_batch_number().await.unwrap();
+        assert_eq!(l1_batch_number, L1BatchNumber(1));
+        let report = reexecutor.reexecute(l1_batch_number).await.unwrap();
+
+        assert_eq!(report.protocol_version, ProtocolVersionId::latest());
+        assert_eq!(report.executed_transactions, 1);
+        assert!(report.divergence.is_none(), "{report:#?}");
+    }
+
+    #[tokio::test]
+    async fn reexecuting_l1_batch_with_tampered_outputs() {
+        let pool = ConnectionPool::test_pool().await;
+        let tx_hash = prepare_storage(&pool, |result| result.refunded_gas += 1).await;
+
+        let reexecutor = L1BatchReexecutor::new(pool, L2ChainId::default());
+        let report = reexecutor.reexecute(L1BatchNumber(1)).await.unwrap();
+
+        assert_eq!(report.executed_transactions, 1);
+        let divergence = report.divergence.expect("no divergence");
+        assert_eq!(divergence.miniblock_number, MiniblockNumber(1));
+        assert_eq!(divergence.index_in_l1_batch, 0);
+        assert_eq!(divergence.tx_hash, tx_hash);
+        let refunded_gas = divergence.diff.refunded_gas.clone().unwrap();
+        assert_eq!(refunded_gas.stored, refunded_gas.reexecuted + 1);
+        let expected_diff = TxDiff {
+            refunded_gas: Some(refunded_gas),
+            ..TxDiff::default()
+        };
+        assert_eq!(divergence.diff, expected_diff);
+    }
+}
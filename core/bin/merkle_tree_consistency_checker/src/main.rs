@@ -62,7 +62,7 @@ fn main() -> anyhow::Result<()> {
             .context("Invalid Sentry URL")?
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
     Cli::parse().run(&db_config);
@@ -0,0 +1,508 @@
+//! Dry run of a protocol upgrade on top of the state stored in Postgres.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Context as _;
+use multivm::interface::{ExecutionResult, L2BlockEnv, VmExecutionResultAndLogs, VmInterface};
+use serde::Serialize;
+use tokio::runtime::Handle;
+use vm_utils::{create_vm_with_env, execute_tx, load_vm_env};
+use zksync_contracts::{BaseSystemContracts, SystemContractCode};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
+use zksync_types::{
+    Address, L1BatchNumber, L2ChainId, ProtocolUpgrade, ProtocolVersionId, Transaction,
+    TransactionType, H256, U256, U64,
+};
+use zksync_utils::{
+    bytecode::hash_bytecode, bytes_to_be_words, u256_to_account_address, u256_to_h256,
+};
+
+/// System contracts (including precompiles) are deployed in the kernel space, i.e., at addresses below 2^16.
+const MAX_KERNEL_SPACE_ADDRESS: u64 = 0xffff;
+
+/// Change of a contract code hash caused by the upgrade.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct CodeHashChange {
+    pub previous: H256,
+    pub new: H256,
+}
+
+impl CodeHashChange {
+    pub fn is_changed(&self) -> bool {
+        self.previous != self.new
+    }
+}
+
+/// Outcome of transaction execution in the VM.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "result", rename_all = "camelCase")]
+pub(crate) enum TxOutcome {
+    Success,
+    Reverted { reason: String },
+    Halted { reason: String },
+}
+
+impl From<&ExecutionResult> for TxOutcome {
+    fn from(result: &ExecutionResult) -> Self {
+        match result {
+            ExecutionResult::Success { .. } => Self::Success,
+            ExecutionResult::Revert { output } => Self::Reverted {
+                reason: output.to_string(),
+            },
+            ExecutionResult::Halt { reason } => Self::Halted {
+                reason: reason.to_string(),
+            },
+        }
+    }
+}
+
+/// Outcome of the transaction stored in Postgres.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StoredTxOutcome {
+    pub success: bool,
+    pub gas_used: Option<U256>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TxReport {
+    pub hash: H256,
+    pub outcome: TxOutcome,
+    /// Gas used by the transaction after refunds.
+    pub gas_used: U256,
+    /// Stored outcome of the transaction; `None` for the upgrade transaction.
+    pub stored: Option<StoredTxOutcome>,
+}
+
+impl TxReport {
+    fn new(
+        tx: &Transaction,
+        result: &VmExecutionResultAndLogs,
+        stored: Option<StoredTxOutcome>,
+    ) -> Self {
+        Self {
+            hash: tx.hash(),
+            outcome: TxOutcome::from(&result.result),
+            gas_used: tx
+                .gas_limit()
+                .saturating_sub(result.refunds.gas_refunded.into()),
+            stored,
+        }
+    }
+
+    /// Checks whether the transaction failed even though it has succeeded originally. The upgrade transaction
+    /// is considered to have succeeded originally.
+    pub fn is_failure(&self) -> bool {
+        self.outcome != TxOutcome::Success
+            && self.stored.as_ref().map_or(true, |stored| stored.success)
+    }
+}
+
+/// Results of a protocol upgrade dry run.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DryRunReport {
+    /// L1 batch at the start of which the upgrade was applied.
+    pub l1_batch_number: L1BatchNumber,
+    pub previous_protocol_version: ProtocolVersionId,
+    pub protocol_version: ProtocolVersionId,
+    pub bootloader: CodeHashChange,
+    pub default_account: CodeHashChange,
+    pub upgrade_tx: Option<TxReport>,
+    /// System contracts which code was changed by the upgrade transaction.
+    pub system_contracts: BTreeMap<Address, CodeHashChange>,
+    /// Re-executed transactions from the L1 batch.
+    pub transactions: Vec<TxReport>,
+    pub batch_tip: TxOutcome,
+}
+
+impl DryRunReport {
+    /// Returns transactions that failed after the upgrade, starting from the upgrade transaction.
+    pub fn failures(&self) -> impl Iterator<Item = &TxReport> + '_ {
+        self.upgrade_tx
+            .iter()
+            .chain(&self.transactions)
+            .filter(|tx| tx.is_failure())
+    }
+
+    pub fn is_successful(&self) -> bool {
+        self.failures().next().is_none() && self.batch_tip == TxOutcome::Success
+    }
+}
+
+/// Applies a protocol upgrade at the start of a sealed L1 batch and re-executes transactions from this batch
+/// with the VM version corresponding to the upgraded protocol version. Postgres is never modified; all VM writes
+/// are kept in memory.
+#[derive(Debug)]
+pub(crate) struct UpgradeDryRun {
+    pool: ConnectionPool,
+    l2_chain_id: L2ChainId,
+    max_transactions: usize,
+}
+
+impl UpgradeDryRun {
+    pub fn new(pool: ConnectionPool, l2_chain_id: L2ChainId, max_transactions: usize) -> Self {
+        Self {
+            pool,
+            l2_chain_id,
+            max_transactions,
+        }
+    }
+
+    /// Runs the upgrade on top of the specified L1 batch, or the latest sealed batch if not specified.
+    pub async fn run(
+        self,
+        upgrade: ProtocolUpgrade,
+        l1_batch_number: Option<L1BatchNumber>,
+    ) -> anyhow::Result<DryRunReport> {
+        let l1_batch_number = match l1_batch_number {
+            Some(number) => number,
+            None => self
+                .pool
+                .access_storage_tagged("upgrade_dry_run")
+                .await?
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await?
+                .context("no sealed L1 batches in Postgres")?,
+        };
+        tracing::info!(
+            "Running upgrade to protocol version {:?} on top of L1 batch #{l1_batch_number}",
+            upgrade.id
+        );
+
+        let rt_handle = Handle::current();
+        tokio::task::spawn_blocking(move || self.run_blocking(rt_handle, upgrade, l1_batch_number))
+            .await
+            .context("dry run panicked")?
+    }
+
+    fn run_blocking(
+        &self,
+        rt_handle: Handle,
+        upgrade: ProtocolUpgrade,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<DryRunReport> {
+        let mut connection =
+            rt_handle.block_on(self.pool.access_storage_tagged("upgrade_dry_run"))?;
+        let (mut system_env, l1_batch_env) = load_vm_env(
+            &rt_handle,
+            l1_batch_number,
+            &mut connection,
+            self.l2_chain_id,
+        )?;
+        let previous_protocol_version = system_env.version;
+        anyhow::ensure!(
+            upgrade.id > previous_protocol_version,
+            "Upgrade to protocol version {:?} cannot be applied to L1 batch #{l1_batch_number} \
+             with protocol version {previous_protocol_version:?}",
+            upgrade.id
+        );
+
+        let factory_deps = upgrade
+            .tx
+            .as_ref()
+            .and_then(|tx| tx.execute.factory_deps.as_deref())
+            .unwrap_or_default();
+        let previous_contracts = &system_env.base_system_smart_contracts;
+        let bootloader = match upgrade.bootloader_code_hash {
+            Some(hash) => load_code(&rt_handle, &mut connection, factory_deps, hash)
+                .context("failed loading bootloader")?,
+            None => previous_contracts.bootloader.clone(),
+        };
+        let default_aa = match upgrade.default_account_code_hash {
+            Some(hash) => load_code(&rt_handle, &mut connection, factory_deps, hash)
+                .context("failed loading default account")?,
+            None => previous_contracts.default_aa.clone(),
+        };
+        let bootloader_change = CodeHashChange {
+            previous: previous_contracts.bootloader.hash,
+            new: bootloader.hash,
+        };
+        let default_account_change = CodeHashChange {
+            previous: previous_contracts.default_aa.hash,
+            new: default_aa.hash,
+        };
+        system_env.version = upgrade.id;
+        system_env.base_system_smart_contracts = BaseSystemContracts {
+            bootloader,
+            default_aa,
+        };
+
+        let miniblocks = rt_handle.block_on(
+            connection
+                .transactions_dal()
+                .get_miniblocks_to_execute_for_l1_batch(l1_batch_number),
+        )?;
+        let sampled_tx_hashes: Vec<_> = miniblocks
+            .iter()
+            .flat_map(|miniblock| &miniblock.txs)
+            .filter(|tx| !is_upgrade_tx(tx))
+            .take(self.max_transactions)
+            .map(Transaction::hash)
+            .collect();
+        let receipts = rt_handle.block_on(
+            connection
+                .transactions_web3_dal()
+                .get_transaction_receipts(&sampled_tx_hashes),
+        )?;
+        let mut stored_outcomes: HashMap<_, _> = receipts
+            .into_iter()
+            .map(|receipt| {
+                let outcome = StoredTxOutcome {
+                    success: receipt.status == U64::one(),
+                    gas_used: receipt.gas_used,
+                };
+                (receipt.transaction_hash, outcome)
+            })
+            .collect();
+
+        let (mut vm, _) = create_vm_with_env(rt_handle, connection, system_env, l1_batch_env);
+
+        let mut upgrade_tx_report = None;
+        let mut system_contracts = BTreeMap::new();
+        if let Some(tx) = upgrade.tx {
+            let tx = Transaction::from(tx);
+            let result =
+                execute_tx(&tx, &mut vm).context("failed executing upgrade transaction")?;
+            system_contracts = changed_system_contracts(&result);
+            upgrade_tx_report = Some(TxReport::new(&tx, &result, None));
+        }
+
+        let mut transactions = Vec::with_capacity(sampled_tx_hashes.len());
+        'miniblocks: for (i, miniblock) in miniblocks.iter().enumerate() {
+            if i > 0 {
+                vm.start_new_l2_block(L2BlockEnv::from_miniblock_data(miniblock));
+            }
+            for tx in miniblock.txs.iter().filter(|tx| !is_upgrade_tx(tx)) {
+                if transactions.len() == sampled_tx_hashes.len() {
+                    break 'miniblocks;
+                }
+                let tx_hash = tx.hash();
+                let result = execute_tx(tx, &mut vm)
+                    .with_context(|| format!("failed executing transaction {tx_hash:?}"))?;
+                let stored_outcome = stored_outcomes.remove(&tx_hash);
+                transactions.push(TxReport::new(tx, &result, stored_outcome));
+            }
+        }
+        let batch_tip = TxOutcome::from(&vm.finish_batch().block_tip_execution_result.result);
+
+        Ok(DryRunReport {
+            l1_batch_number,
+            previous_protocol_version,
+            protocol_version: upgrade.id,
+            bootloader: bootloader_change,
+            default_account: default_account_change,
+            upgrade_tx: upgrade_tx_report,
+            system_contracts,
+            transactions,
+            batch_tip,
+        })
+    }
+}
+
+fn is_upgrade_tx(tx: &Transaction) -> bool {
+    tx.tx_format() == TransactionType::ProtocolUpgradeTransaction
+}
+
+/// Loads contract code with the specified hash either from the upgrade factory deps, or from Postgres.
+fn load_code(
+    rt_handle: &Handle,
+    connection: &mut StorageProcessor<'_>,
+    factory_deps: &[Vec<u8>],
+    hash: H256,
+) -> anyhow::Result<SystemContractCode> {
+    let bytecode = factory_deps
+        .iter()
+        .find(|bytecode| hash_bytecode(bytecode) == hash)
+        .cloned();
+    let bytecode = match bytecode {
+        Some(bytecode) => bytecode,
+        None => rt_handle
+            .block_on(connection.factory_deps_dal().get_factory_dep(hash))
+            .with_context(|| {
+                format!("bytecode {hash:?} is neither in upgrade factory deps nor in Postgres")
+            })?,
+    };
+    Ok(SystemContractCode {
+        code: bytes_to_be_words(bytecode),
+        hash,
+    })
+}
+
+/// Extracts code hash changes of system contracts from storage writes to the account code storage.
+fn changed_system_contracts(
+    result: &VmExecutionResultAndLogs,
+) -> BTreeMap<Address, CodeHashChange> {
+    let code_writes = result
+        .logs
+        .storage_logs
+        .iter()
+        .map(|log| &log.log_query)
+        .filter(|query| {
+            query.rw_flag && !query.rollback && query.address == ACCOUNT_CODE_STORAGE_ADDRESS
+        });
+
+    let mut changes = BTreeMap::new();
+    for query in code_writes {
+        let address = u256_to_account_address(&query.key);
+        if U256::from_big_endian(address.as_bytes()) > MAX_KERNEL_SPACE_ADDRESS.into() {
+            continue;
+        }
+        let change = changes.entry(address).or_insert(CodeHashChange {
+            previous: u256_to_h256(query.read_value),
+            new: H256::zero(),
+        });
+        change.new = u256_to_h256(query.written_value);
+    }
+    changes.retain(|_, change| change.is_changed());
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::deployer_contract;
+    use zksync_core::testonly::seal_l1_batch_after_genesis;
+    use zksync_system_constants::{
+        CONTRACT_DEPLOYER_ADDRESS, CONTRACT_FORCE_DEPLOYER_ADDRESS, L2_ETH_TOKEN_ADDRESS,
+        REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE,
+    };
+    use zksync_types::{
+        ethabi::Token,
+        protocol_version::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
+        system_contracts::get_system_smart_contracts,
+        Execute,
+    };
+
+    use super::*;
+
+    /// Creates a genesis state with the latest protocol version and seals an empty L1 batch #1 on top of it.
+    async fn prepare_storage(pool: &ConnectionPool) {
+        let mut storage = pool.access_storage().await.unwrap();
+        seal_l1_batch_after_genesis(&mut storage, L2ChainId::default(), 0).await;
+    }
+
+    /// Creates an upgrade transaction force-deploying the specified bytecode to `address`. If `factory_dep`
+    /// is not provided, the bytecode is unknown and the deployment fails.
+    fn force_deploy_upgrade_tx(
+        address: Address,
+        bytecode_hash: H256,
+        factory_dep: Option<Vec<u8>>,
+    ) -> ProtocolUpgradeTx {
+        let deployment = Token::Tuple(vec![
+            Token::FixedBytes(bytecode_hash.as_bytes().to_vec()),
+            Token::Address(address),
+            Token::Bool(false),
+            Token::Uint(U256::zero()),
+            Token::Bytes(vec![]),
+        ]);
+        let calldata = deployer_contract()
+            .function("forceDeployOnAddresses")
+            .unwrap()
+            .encode_input(&[Token::Array(vec![deployment])])
+            .unwrap();
+
+        ProtocolUpgradeTx {
+            execute: Execute {
+                contract_address: CONTRACT_DEPLOYER_ADDRESS,
+                calldata,
+                factory_deps: Some(factory_dep.into_iter().collect()),
+                value: U256::zero(),
+            },
+            common_data: ProtocolUpgradeTxCommonData {
+                sender: CONTRACT_FORCE_DEPLOYER_ADDRESS,
+                upgrade_id: ProtocolVersionId::next(),
+                gas_limit: U256::from(200_000_000_u32),
+                gas_per_pubdata_limit: REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE.into(),
+                canonical_tx_hash: H256::repeat_byte(0x42),
+                ..ProtocolUpgradeTxCommonData::default()
+            },
+            received_timestamp_ms: 0,
+        }
+    }
+
+    fn eth_token_bytecode() -> Vec<u8> {
+        get_system_smart_contracts()
+            .into_iter()
+            .find(|contract| *contract.account_id.address() == L2_ETH_TOKEN_ADDRESS)
+            .expect("no ETH token contract")
+            .bytecode
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_changed_system_contracts() {
+        let pool = ConnectionPool::test_pool().await;
+        prepare_storage(&pool).await;
+
+        let address = Address::from_low_u64_be(0xfff0);
+        let bytecode = eth_token_bytecode();
+        let bytecode_hash = hash_bytecode(&bytecode);
+        let upgrade = ProtocolUpgrade {
+            id: ProtocolVersionId::next(),
+            tx: Some(force_deploy_upgrade_tx(
+                address,
+                bytecode_hash,
+                Some(bytecode),
+            )),
+            ..ProtocolUpgrade::default()
+        };
+        let dry_run = UpgradeDryRun::new(pool, L2ChainId::default(), 100);
+        let report = dry_run.run(upgrade, None).await.unwrap();
+
+        assert_eq!(report.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(
+            report.previous_protocol_version,
+            ProtocolVersionId::latest()
+        );
+        assert_eq!(report.protocol_version, ProtocolVersionId::next());
+        assert!(!report.bootloader.is_changed());
+        assert!(!report.default_account.is_changed());
+        assert!(report.transactions.is_empty());
+        assert!(report.is_successful(), "{report:#?}");
+
+        let upgrade_tx = report.upgrade_tx.as_ref().unwrap();
+        assert_eq!(upgrade_tx.outcome, TxOutcome::Success);
+        assert_eq!(report.system_contracts.len(), 1, "{report:#?}");
+        let change = report.system_contracts[&address];
+        assert_eq!(change.previous, H256::zero());
+        assert_eq!(change.new, bytecode_hash);
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_failed_upgrade_transaction() {
+        let pool = ConnectionPool::test_pool().await;
+        prepare_storage(&pool).await;
+
+        let address = Address::from_low_u64_be(0xfff0);
+        let bytecode_hash = hash_bytecode(&eth_token_bytecode());
+        let upgrade = ProtocolUpgrade {
+            id: ProtocolVersionId::next(),
+            tx: Some(force_deploy_upgrade_tx(address, bytecode_hash, None)),
+            ..ProtocolUpgrade::default()
+        };
+        let dry_run = UpgradeDryRun::new(pool, L2ChainId::default(), 100);
+        let report = dry_run.run(upgrade, Some(L1BatchNumber(1))).await.unwrap();
+
+        assert!(!report.is_successful());
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_ne!(failures[0].outcome, TxOutcome::Success);
+        assert!(report.system_contracts.is_empty(), "{report:#?}");
+    }
+
+    #[tokio::test]
+    async fn dry_run_rejects_non_newer_protocol_version() {
+        let pool = ConnectionPool::test_pool().await;
+        prepare_storage(&pool).await;
+
+        let upgrade = ProtocolUpgrade {
+            id: ProtocolVersionId::latest(),
+            ..ProtocolUpgrade::default()
+        };
+        let dry_run = UpgradeDryRun::new(pool, L2ChainId::default(), 100);
+        let err = dry_run.run(upgrade, None).await.unwrap_err().to_string();
+        assert!(err.contains("cannot be applied"), "{err}");
+    }
+}
@@ -0,0 +1,151 @@
+//! Protocol upgrade dry-run utility. Applies a proposed protocol upgrade on a copy of the state stored in Postgres,
+//! executes the upgrade transaction and a sample of transactions from a sealed L1 batch (by default, the latest one)
+//! with the VM corresponding to the new protocol version, and reports failures, gas usage and changed system contracts.
+//!
+//! The utility never modifies Postgres.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use clap::{ArgGroup, Parser};
+use zksync_config::{configs::chain::NetworkConfig, PostgresConfig};
+use zksync_dal::ConnectionPool;
+use zksync_env_config::FromEnv;
+use zksync_types::L1BatchNumber;
+
+use crate::{
+    dry_run::{CodeHashChange, DryRunReport, TxOutcome, TxReport, UpgradeDryRun},
+    upgrade::{load_upgrade_from_calldata, load_upgrade_from_json},
+};
+
+mod dry_run;
+mod upgrade;
+
+#[derive(Debug, Parser)]
+#[command(author = "Matter Labs", version, about = "Protocol upgrade dry-run utility", long_about = None)]
+#[command(group(ArgGroup::new("upgrade").required(true).args(["calldata", "upgrade_json"])))]
+struct Cli {
+    /// Path to a file with hex-encoded calldata of the governance operation or the diamond proxy upgrade.
+    #[arg(long)]
+    calldata: Option<PathBuf>,
+    /// Path to a file with the JSON-serialized `ProtocolUpgrade`.
+    #[arg(long)]
+    upgrade_json: Option<PathBuf>,
+    /// Sealed L1 batch at the start of which the upgrade is applied. If not specified, the latest sealed batch is used.
+    #[arg(long)]
+    l1_batch_number: Option<u32>,
+    /// Maximum number of transactions from the L1 batch to re-execute after the upgrade.
+    #[arg(long, default_value_t = 100)]
+    max_transactions: usize,
+    /// Outputs the report as a JSON object, so that it is machine-readable.
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    let _guard = vlog::ObservabilityBuilder::new()
+        .with_log_format(log_format)
+        .build()?;
+
+    let opt = Cli::parse();
+    let upgrade = match (&opt.calldata, &opt.upgrade_json) {
+        (Some(path), _) => load_upgrade_from_calldata(path)?,
+        (None, Some(path)) => load_upgrade_from_json(path)?,
+        (None, None) => unreachable!("ensured by `clap`"),
+    };
+
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let pool = ConnectionPool::builder(postgres_config.replica_url()?, 2)
+        .build()
+        .await
+        .context("failed to build a connection pool")?;
+
+    let dry_run = UpgradeDryRun::new(pool, network_config.zksync_network_id, opt.max_transactions);
+    let report = dry_run
+        .run(upgrade, opt.l1_batch_number.map(L1BatchNumber))
+        .await?;
+
+    if opt.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    anyhow::ensure!(report.is_successful(), "upgrade dry run failed");
+    Ok(())
+}
+
+fn print_report(report: &DryRunReport) {
+    println!(
+        "Upgrade from protocol version {:?} to {:?} applied at the start of L1 batch #{}",
+        report.previous_protocol_version, report.protocol_version, report.l1_batch_number
+    );
+    print_code_hash_change("Bootloader", &report.bootloader);
+    print_code_hash_change("Default account", &report.default_account);
+
+    match &report.upgrade_tx {
+        Some(tx) => {
+            print!("Upgrade transaction: ");
+            print_tx(tx);
+        }
+        None => println!("Upgrade transaction: none"),
+    }
+    if report.system_contracts.is_empty() {
+        println!("Changed system contracts: none");
+    } else {
+        println!("Changed system contracts:");
+        for (address, change) in &report.system_contracts {
+            println!("  {address:?}: {:?} -> {:?}", change.previous, change.new);
+        }
+    }
+
+    let failure_count = report.failures().count();
+    println!(
+        "Re-executed transactions: {}, failures: {failure_count}",
+        report.transactions.len()
+    );
+    for tx in &report.transactions {
+        print!("  ");
+        print_tx(tx);
+    }
+    println!("Batch tip: {}", format_outcome(&report.batch_tip));
+}
+
+fn print_code_hash_change(name: &str, change: &CodeHashChange) {
+    if change.is_changed() {
+        println!("{name}: {:?} -> {:?}", change.previous, change.new);
+    } else {
+        println!("{name}: unchanged ({:?})", change.previous);
+    }
+}
+
+fn print_tx(tx: &TxReport) {
+    let stored = match &tx.stored {
+        Some(stored) => {
+            let stored_outcome = if stored.success { "success" } else { "failure" };
+            let stored_gas = stored
+                .gas_used
+                .map_or_else(|| "unknown".to_owned(), |gas| gas.to_string());
+            format!(" (stored: {stored_outcome}, gas used: {stored_gas})")
+        }
+        None => String::new(),
+    };
+    let failure_marker = if tx.is_failure() { " [FAILURE]" } else { "" };
+    println!(
+        "{:?}: {}, gas used: {}{stored}{failure_marker}",
+        tx.hash,
+        format_outcome(&tx.outcome),
+        tx.gas_used
+    );
+}
+
+fn format_outcome(outcome: &TxOutcome) -> String {
+    match outcome {
+        TxOutcome::Success => "success".to_owned(),
+        TxOutcome::Reverted { reason } => format!("reverted: {reason}"),
+        TxOutcome::Halted { reason } => format!("halted: {reason}"),
+    }
+}
@@ -0,0 +1,299 @@
+//! Loading of protocol upgrade proposals.
+
+use std::{fs, panic, path::Path};
+
+use anyhow::Context as _;
+use zksync_contracts::{governance_contract, zksync_contract};
+use zksync_types::{
+    ethabi::{encode, Token},
+    protocol_version::{Call, GovernanceOperation},
+    web3::types::Log,
+    Address, ProtocolUpgrade, H256, U256,
+};
+
+/// Loads a protocol upgrade from a file with hex-encoded calldata. The calldata may either be a governance
+/// operation call (e.g., `scheduleTransparent` or `execute`), or an upgrade call to the diamond proxy.
+pub(crate) fn load_upgrade_from_calldata(path: &Path) -> anyhow::Result<ProtocolUpgrade> {
+    let calldata = fs::read_to_string(path)
+        .with_context(|| format!("failed reading calldata from {}", path.display()))?;
+    let calldata = parse_hex(&calldata).context("calldata is not a valid hex string")?;
+    parse_calldata(&calldata)
+}
+
+/// Loads a protocol upgrade serialized as JSON.
+pub(crate) fn load_upgrade_from_json(path: &Path) -> anyhow::Result<ProtocolUpgrade> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("failed reading upgrade from {}", path.display()))?;
+    serde_json::from_str(&json).context("failed deserializing `ProtocolUpgrade`")
+}
+
+fn parse_hex(hex_str: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    let hex_str = hex_str.trim();
+    hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str))
+}
+
+fn parse_calldata(calldata: &[u8]) -> anyhow::Result<ProtocolUpgrade> {
+    anyhow::ensure!(calldata.len() >= 4, "calldata is too short");
+    let (selector, data) = calldata.split_at(4);
+    let upgrade_selector = diamond_proxy_upgrade_selector();
+    if selector == upgrade_selector {
+        return parse_diamond_proxy_upgrade(calldata.to_vec());
+    }
+
+    let governance_contract = governance_contract();
+    let function = governance_contract
+        .functions()
+        .find(|function| function.short_signature() == selector)
+        .with_context(|| {
+            format!(
+                "selector 0x{} corresponds neither to a governance call nor to the diamond proxy upgrade",
+                hex::encode(selector)
+            )
+        })?;
+    let tokens = function
+        .decode_input(data)
+        .with_context(|| format!("failed decoding `{}` governance call", function.name))?;
+    let operation = governance_operation(tokens).with_context(|| {
+        format!(
+            "`{}` governance call doesn't contain an operation",
+            function.name
+        )
+    })?;
+
+    // Calls that are not protocol upgrades (e.g., `acceptGovernor`) are skipped.
+    let mut upgrade_calls = operation
+        .calls
+        .into_iter()
+        .filter(|call| call.data.starts_with(&upgrade_selector));
+    let upgrade_call = upgrade_calls
+        .next()
+        .context("governance operation doesn't contain protocol upgrade calls")?;
+    anyhow::ensure!(
+        upgrade_calls.next().is_none(),
+        "governance operation contains multiple protocol upgrade calls"
+    );
+    parse_diamond_proxy_upgrade(upgrade_call.data)
+}
+
+/// Returns the selector of the diamond proxy function executing protocol upgrades.
+fn diamond_proxy_upgrade_selector() -> [u8; 4] {
+    zksync_contract()
+        .function("executeUpgrade")
+        .expect("`executeUpgrade` function is missing in zkSync contract ABI")
+        .short_signature()
+}
+
+/// Parses calldata of the diamond proxy upgrade call. [`ProtocolUpgrade`] parsing panics on some malformed inputs
+/// (e.g., an unknown upgrade transaction type), so panics are converted to errors.
+fn parse_diamond_proxy_upgrade(data: Vec<u8>) -> anyhow::Result<ProtocolUpgrade> {
+    // Only `data` is used when parsing a `ProtocolUpgrade` from a call.
+    let call = Call {
+        target: Address::zero(),
+        value: U256::zero(),
+        data,
+        eth_hash: H256::zero(),
+        eth_block: 0,
+    };
+    match panic::catch_unwind(move || ProtocolUpgrade::try_from(call)) {
+        Ok(upgrade) => upgrade.context("failed decoding diamond proxy upgrade"),
+        Err(payload) => {
+            let message = if let Some(message) = payload.downcast_ref::<&'static str>() {
+                message
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.as_str()
+            } else {
+                "unknown panic"
+            };
+            anyhow::bail!("malformed diamond proxy upgrade: {message}")
+        }
+    }
+}
+
+/// Parses the first argument of a governance function, which must be an `Operation`. Reuses [`GovernanceOperation`]
+/// parsing from the `TransparentOperationScheduled` event, which encodes the operation after its ID.
+fn governance_operation(tokens: Vec<Token>) -> anyhow::Result<GovernanceOperation> {
+    let operation = tokens
+        .into_iter()
+        .next()
+        .context("governance call has no arguments")?;
+    let data = encode(&[Token::Uint(U256::zero()), operation]);
+    let log = Log {
+        address: Default::default(),
+        topics: Default::default(),
+        data: data.into(),
+        block_hash: Default::default(),
+        block_number: Some(0.into()),
+        transaction_hash: Some(H256::zero()),
+        transaction_index: Default::default(),
+        log_index: Default::default(),
+        transaction_log_index: Default::default(),
+        log_type: Default::default(),
+        removed: Default::default(),
+    };
+    GovernanceOperation::try_from(log).context("failed decoding governance operation")
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersionId;
+
+    use super::*;
+
+    #[test]
+    fn parsing_hex_calldata() {
+        assert_eq!(parse_hex("0x0102ff\n").unwrap(), [1, 2, 0xff]);
+        assert_eq!(parse_hex("  0102ff").unwrap(), [1, 2, 0xff]);
+        parse_hex("0x0").unwrap_err();
+    }
+
+    /// Encodes calldata of the diamond proxy upgrade without an upgrade transaction.
+    fn diamond_proxy_upgrade_calldata(version_id: U256, bootloader_code_hash: H256) -> Vec<u8> {
+        let empty_tx = Token::Tuple(vec![
+            Token::Uint(U256::zero()),
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::FixedArray(vec![Token::Uint(U256::zero()); 4]),
+            Token::Bytes(vec![]),
+            Token::Bytes(vec![]),
+            Token::Array(vec![]),
+            Token::Bytes(vec![]),
+            Token::Bytes(vec![]),
+        ]);
+        let proposed_upgrade = Token::Tuple(vec![
+            empty_tx,
+            Token::Array(vec![]),
+            Token::FixedBytes(bootloader_code_hash.as_bytes().to_vec()),
+            Token::FixedBytes(vec![0; 32]),
+            Token::Address(Address::zero()),
+            Token::Tuple(vec![Token::FixedBytes(vec![0; 32]); 3]),
+            Token::Bytes(vec![]),
+            Token::Bytes(vec![]),
+            Token::Uint(U256::zero()),
+            Token::Uint(version_id),
+            Token::Address(Address::zero()),
+        ]);
+        // The selector of the upgrade initializer is not checked.
+        let init_calldata = [0_u8; 4]
+            .into_iter()
+            .chain(encode(&[proposed_upgrade]))
+            .collect();
+        let diamond_cut = Token::Tuple(vec![
+            Token::Array(vec![]),
+            Token::Address(Address::zero()),
+            Token::Bytes(init_calldata),
+        ]);
+        diamond_proxy_upgrade_selector()
+            .into_iter()
+            .chain(encode(&[diamond_cut]))
+            .collect()
+    }
+
+    fn governance_operation_token(calls: Vec<Vec<u8>>) -> Token {
+        let calls = calls
+            .into_iter()
+            .map(|data| {
+                Token::Tuple(vec![
+                    Token::Address(Address::repeat_byte(1)),
+                    Token::Uint(U256::zero()),
+                    Token::Bytes(data),
+                ])
+            })
+            .collect();
+        Token::Tuple(vec![
+            Token::Array(calls),
+            Token::FixedBytes(vec![0; 32]),
+            Token::FixedBytes(vec![0; 32]),
+        ])
+    }
+
+    #[test]
+    fn parsing_governance_operation() {
+        let operation = governance_operation_token(vec![vec![1, 2, 3]]);
+        let operation = governance_operation(vec![operation, Token::Uint(U256::one())]).unwrap();
+        assert_eq!(operation.calls.len(), 1);
+        assert_eq!(operation.calls[0].target, Address::repeat_byte(1));
+        assert_eq!(operation.calls[0].data, [1, 2, 3]);
+
+        governance_operation(vec![]).unwrap_err();
+    }
+
+    #[test]
+    fn parsing_diamond_proxy_upgrade_calldata() {
+        let version_id = U256::from(ProtocolVersionId::next() as u16);
+        let calldata = diamond_proxy_upgrade_calldata(version_id, H256::repeat_byte(1));
+
+        let upgrade = parse_calldata(&calldata).unwrap();
+        assert_eq!(upgrade.id, ProtocolVersionId::next());
+        assert_eq!(upgrade.bootloader_code_hash, Some(H256::repeat_byte(1)));
+        assert_eq!(upgrade.default_account_code_hash, None);
+        assert!(upgrade.tx.is_none());
+    }
+
+    #[test]
+    fn parsing_governance_operation_calldata() {
+        let version_id = U256::from(ProtocolVersionId::next() as u16);
+        let upgrade_calldata = diamond_proxy_upgrade_calldata(version_id, H256::repeat_byte(1));
+        // Calls without the upgrade selector (e.g., `acceptGovernor`) should be skipped.
+        let operation = governance_operation_token(vec![vec![0xff; 4], upgrade_calldata.clone()]);
+        let execute_calldata = governance_contract()
+            .function("execute")
+            .unwrap()
+            .encode_input(&[operation])
+            .unwrap();
+
+        let upgrade = parse_calldata(&execute_calldata).unwrap();
+        assert_eq!(upgrade.id, ProtocolVersionId::next());
+        assert_eq!(upgrade.bootloader_code_hash, Some(H256::repeat_byte(1)));
+
+        let operation =
+            governance_operation_token(vec![upgrade_calldata.clone(), upgrade_calldata]);
+        let execute_calldata = governance_contract()
+            .function("execute")
+            .unwrap()
+            .encode_input(&[operation])
+            .unwrap();
+        let err = parse_calldata(&execute_calldata).unwrap_err().to_string();
+        assert!(err.contains("multiple protocol upgrade calls"), "{err}");
+    }
+
+    #[test]
+    fn parsing_malformed_calldata() {
+        let err = parse_calldata(&[0xff; 36]).unwrap_err().to_string();
+        assert!(err.contains("neither to a governance call"), "{err}");
+
+        // Truncated calldata with the upgrade selector.
+        let mut calldata = diamond_proxy_upgrade_calldata(U256::zero(), H256::zero());
+        calldata.truncate(100);
+        parse_calldata(&calldata).unwrap_err();
+
+        // Parsing an unsupported protocol version panics; it should be converted to an error.
+        let calldata = diamond_proxy_upgrade_calldata(U256::from(u32::MAX), H256::zero());
+        let err = parse_calldata(&calldata).unwrap_err().to_string();
+        assert!(err.contains("malformed diamond proxy upgrade"), "{err}");
+    }
+
+    #[test]
+    fn deserializing_upgrade_from_json() {
+        let json = serde_json::json!({
+            "id": ProtocolVersionId::latest(),
+            "bootloaderCodeHash": H256::repeat_byte(1),
+            "defaultAccountCodeHash": null,
+            "verifierParams": null,
+            "verifierAddress": null,
+            "timestamp": 0,
+            "tx": null,
+        });
+        let upgrade: ProtocolUpgrade = serde_json::from_value(json).unwrap();
+        assert_eq!(upgrade.id, ProtocolVersionId::latest());
+        assert_eq!(upgrade.bootloader_code_hash, Some(H256::repeat_byte(1)));
+        assert_eq!(upgrade.default_account_code_hash, None);
+        assert!(upgrade.tx.is_none());
+    }
+}
@@ -1,27 +1,11 @@
-use std::{str::FromStr, time::Duration};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
 use anyhow::Context as _;
 use clap::Parser;
-use zksync_config::{
-    configs::{
-        api::{HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig},
-        chain::{
-            CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig,
-            StateKeeperConfig,
-        },
-        fri_prover_group::FriProverGroupConfig,
-        house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, PrometheusConfig,
-        ProofDataHandlerConfig, WitnessGeneratorConfig,
-    },
-    ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
-    GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
-};
 use zksync_core::{
     genesis_init, initialize_components, is_genesis_needed, setup_sigint_handler,
     temp_config_store::TempConfigStore, Component, Components,
 };
-use zksync_env_config::FromEnv;
 use zksync_storage::RocksDB;
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
@@ -46,6 +30,18 @@ struct Cli {
         default_value = "api,tree,eth,state_keeper,housekeeper,basic_witness_input_producer"
     )]
     components: ComponentsToRun,
+    /// Path to the YAML or JSON config file. Config sections fully specified
+    /// by the environment variables take precedence over the file.
+    #[arg(long)]
+    config_path: Option<PathBuf>,
+    /// Path to the YAML or JSON file with the secrets (database URLs, operator and API account keys,
+    /// object store credentials). Secrets specified by the `SECRETS_*` environment variables take precedence
+    /// over the file.
+    #[arg(long)]
+    secrets_path: Option<PathBuf>,
+    /// Print the effective config with the secrets redacted and exit.
+    #[arg(long)]
+    print_config: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +64,21 @@ impl FromStr for ComponentsToRun {
 async fn main() -> anyhow::Result<()> {
     let opt = Cli::parse();
 
+    let env_config = config::load_env_config()?;
+    let general_config = match &opt.config_path {
+        Some(path) => {
+            let file_config = config::read_general_config(path)?;
+            config::merge_general_config(&file_config, &env_config)?
+        }
+        None => env_config,
+    };
+    let secrets = config::load_secrets(opt.secrets_path.as_deref())?;
+    let general_config = general_config.with_secrets(secrets);
+    if opt.print_config {
+        print!("{}", config::print_general_config(&general_config)?);
+        return Ok(());
+    }
+
     #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
     let log_format = vlog::log_format_from_env();
     #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
@@ -82,47 +93,22 @@ async fn main() -> anyhow::Result<()> {
             .expect("Invalid Sentry URL")
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
-
-    // Report whether sentry is running after the logging subsystem was initialized.
-    if let Some(sentry_url) = sentry_url {
-        tracing::info!("Sentry configured with URL: {sentry_url}");
-    } else {
-        tracing::info!("No sentry URL was provided");
+    if let Some(config) = &general_config.opentelemetry_config {
+        builder = builder.with_opentelemetry(
+            config.endpoint.clone(),
+            config.service_name.clone(),
+            config.sampling_ratio,
+        );
     }
+    let _guard = builder.build()?;
+    config::log_startup_info(&general_config, sentry_url.as_deref());
 
-    // TODO (QIT-22): Only deserialize configs on demand.
-    // Right now, we are trying to deserialize all the configs that may be needed by `zksync_core`.
-    // "May" is the key word here, since some configs are only used by certain component configuration,
-    // hence we are using `Option`s.
-    let mut configs: TempConfigStore = TempConfigStore {
-        postgres_config: PostgresConfig::from_env().ok(),
-        health_check_config: HealthCheckConfig::from_env().ok(),
-        merkle_tree_api_config: MerkleTreeApiConfig::from_env().ok(),
-        web3_json_rpc_config: Web3JsonRpcConfig::from_env().ok(),
-        circuit_breaker_config: CircuitBreakerConfig::from_env().ok(),
-        mempool_config: MempoolConfig::from_env().ok(),
-        network_config: NetworkConfig::from_env().ok(),
-        operations_manager_config: OperationsManagerConfig::from_env().ok(),
-        state_keeper_config: StateKeeperConfig::from_env().ok(),
-        house_keeper_config: HouseKeeperConfig::from_env().ok(),
-        fri_proof_compressor_config: FriProofCompressorConfig::from_env().ok(),
-        fri_prover_config: Some(FriProverConfig::from_env().context("fri_prover_config")?),
-        fri_prover_group_config: FriProverGroupConfig::from_env().ok(),
-        fri_witness_generator_config: FriWitnessGeneratorConfig::from_env().ok(),
-        prometheus_config: PrometheusConfig::from_env().ok(),
-        proof_data_handler_config: ProofDataHandlerConfig::from_env().ok(),
-        witness_generator_config: WitnessGeneratorConfig::from_env().ok(),
-        api_config: ApiConfig::from_env().ok(),
-        contracts_config: ContractsConfig::from_env().ok(),
-        db_config: DBConfig::from_env().ok(),
-        eth_client_config: ETHClientConfig::from_env().ok(),
-        eth_sender_config: ETHSenderConfig::from_env().ok(),
-        eth_watch_config: ETHWatchConfig::from_env().ok(),
-        gas_adjuster_config: GasAdjusterConfig::from_env().ok(),
-        object_store_config: ObjectStoreConfig::from_env().ok(),
-        consensus_config: None,
-    };
+    general_config
+        .fri_prover_config
+        .as_ref()
+        .context("fri_prover_config")?;
+    let mut configs = TempConfigStore::from(general_config);
+    config::fill_missing_configs_from_env(&mut configs);
 
     if opt.components.0.contains(&Component::Consensus) {
         configs.consensus_config =
@@ -132,16 +118,25 @@ async fn main() -> anyhow::Result<()> {
     let postgres_config = configs.postgres_config.clone().context("PostgresConfig")?;
 
     if opt.genesis || is_genesis_needed(&postgres_config).await {
-        let network = NetworkConfig::from_env().context("NetworkConfig")?;
-        let eth_sender = ETHSenderConfig::from_env().context("ETHSenderConfig")?;
-        let contracts = ContractsConfig::from_env().context("ContractsConfig")?;
-        let eth_client = ETHClientConfig::from_env().context("EthClientConfig")?;
+        let network = configs.network_config.clone().context("NetworkConfig")?;
+        let eth_sender = configs
+            .eth_sender_config
+            .clone()
+            .context("ETHSenderConfig")?;
+        let contracts = configs
+            .contracts_config
+            .clone()
+            .context("ContractsConfig")?;
+        let eth_client = configs
+            .eth_client_config
+            .clone()
+            .context("EthClientConfig")?;
         genesis_init(
             &postgres_config,
             &eth_sender,
             &network,
             &contracts,
-            &eth_client.web3_url,
+            eth_client.web3_url.expose(),
         )
         .await
         .context("genesis_init")?;
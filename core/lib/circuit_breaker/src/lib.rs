@@ -1,32 +1,66 @@
-use std::time::Duration;
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::Mutex,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use futures::channel::oneshot;
 use thiserror::Error;
 use tokio::sync::watch;
 use zksync_config::configs::chain::CircuitBreakerConfig;
+use zksync_types::U256;
 
 pub mod l1_txs;
+pub mod operator_balance;
+pub mod prover_lag;
 pub mod replication_lag;
 pub mod utils;
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum CircuitBreakerError {
     #[error("System has failed L1 transaction")]
     FailedL1Transaction,
     #[error("Replication lag ({0:?}) is above the threshold ({1:?})")]
     ReplicationLag(u32, u32),
+    #[error("Operator L1 balance ({balance} wei) is below the threshold ({threshold} wei)")]
+    LowOperatorBalance { balance: U256, threshold: U256 },
+    #[error("Oldest committed L1 batch is unproved for {lag_sec}s, above the threshold ({threshold_sec}s)")]
+    ProverLag { lag_sec: u64, threshold_sec: u64 },
+    #[error("{unproved_batches} L1 batches are not proved, above the threshold ({threshold})")]
+    ProverBatchLag {
+        unproved_batches: u32,
+        threshold: u32,
+    },
+    /// Error returned by a circuit breaker defined outside of this crate.
+    #[error("Circuit breaker `{name}` is triggered: {reason}")]
+    Custom { name: &'static str, reason: String },
+    /// Error that has occurred while checking a circuit breaker (e.g., a DB or L1 client error).
+    /// Such errors are logged, but do not trigger the breaker.
+    #[error("Failed checking circuit breaker: {0:#}")]
+    Internal(#[from] anyhow::Error),
 }
 
-/// Checks circuit breakers
+/// Checks circuit breakers.
+///
+/// By default, a triggered circuit breaker causes the node to shut down. Breakers listed in
+/// [`CircuitBreakerConfig::pausing_breakers`] pause sealing in the state keeper instead (see
+/// [`Self::sealing_pause_receiver()`]); sealing is resumed once all such breakers are no longer triggered.
 #[derive(Debug)]
 pub struct CircuitBreakerChecker {
     circuit_breakers: Vec<Box<dyn CircuitBreaker>>,
+    pausing_breakers: HashSet<String>,
     sync_interval: Duration,
+    triggered_pausing_breakers: Mutex<BTreeSet<&'static str>>,
+    sealing_pause_sender: watch::Sender<bool>,
 }
 
 #[async_trait::async_trait]
 pub trait CircuitBreaker: std::fmt::Debug + Send + Sync {
+    /// Name of the circuit breaker. Used in logs and to refer to the breaker in [`CircuitBreakerConfig`].
+    fn name(&self) -> &'static str;
+
     async fn check(&self) -> Result<(), CircuitBreakerError>;
 }
 
@@ -37,17 +71,69 @@ impl CircuitBreakerChecker {
     ) -> Self {
         Self {
             circuit_breakers,
+            pausing_breakers: config.pausing_breakers.iter().cloned().collect(),
             sync_interval: config.sync_interval(),
+            triggered_pausing_breakers: Mutex::default(),
+            sealing_pause_sender: watch::channel(false).0,
         }
     }
 
+    /// Returns a receiver for the sealing pause flag. The flag is set to `true` while at least one
+    /// of the pausing circuit breakers is triggered.
+    pub fn sealing_pause_receiver(&self) -> watch::Receiver<bool> {
+        self.sealing_pause_sender.subscribe()
+    }
+
+    /// Checks all circuit breakers. Returns the first error from a breaker that should shut down the node;
+    /// errors from pausing breakers update the sealing pause flag instead.
     pub async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut triggered_pausing_breakers = BTreeSet::new();
         for circuit_breaker in &self.circuit_breakers {
-            circuit_breaker.check().await?;
+            let name = circuit_breaker.name();
+            let is_pausing = self.pausing_breakers.contains(name);
+            match circuit_breaker.check().await {
+                Ok(()) => {}
+                Err(CircuitBreakerError::Internal(err)) => {
+                    tracing::warn!("Failed checking circuit breaker `{name}`: {err:#}");
+                    // Retain the previous state of the breaker; we don't know whether it's still triggered.
+                    if is_pausing
+                        && self
+                            .triggered_pausing_breakers
+                            .lock()
+                            .unwrap()
+                            .contains(name)
+                    {
+                        triggered_pausing_breakers.insert(name);
+                    }
+                }
+                Err(err) if is_pausing => {
+                    tracing::warn!("Pausing circuit breaker `{name}` is triggered: {err}");
+                    triggered_pausing_breakers.insert(name);
+                }
+                Err(err) => return Err(err),
+            }
         }
+        self.update_sealing_pause(triggered_pausing_breakers);
         Ok(())
     }
 
+    fn update_sealing_pause(&self, triggered_breakers: BTreeSet<&'static str>) {
+        let is_paused = !triggered_breakers.is_empty();
+        let was_paused = self.sealing_pause_sender.send_replace(is_paused);
+        match (was_paused, is_paused) {
+            (false, true) => {
+                tracing::warn!("Sealing is paused by circuit breakers {triggered_breakers:?}");
+            }
+            (true, false) => tracing::info!("Sealing is resumed"),
+            _ => {}
+        }
+        metrics::gauge!(
+            "circuit_breaker.sealing_paused",
+            f64::from(u8::from(is_paused))
+        );
+        *self.triggered_pausing_breakers.lock().unwrap() = triggered_breakers;
+    }
+
     pub async fn run(
         self,
         circuit_breaker_sender: oneshot::Sender<CircuitBreakerError>,
@@ -69,3 +155,106 @@ impl CircuitBreakerChecker {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockBreaker {
+        name: &'static str,
+        triggered: Arc<AtomicBool>,
+        failing: Arc<AtomicBool>,
+    }
+
+    #[async_trait::async_trait]
+    impl CircuitBreaker for MockBreaker {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn check(&self) -> Result<(), CircuitBreakerError> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(anyhow::anyhow!("RPC error").into());
+            }
+            if self.triggered.load(Ordering::SeqCst) {
+                return Err(CircuitBreakerError::Custom {
+                    name: self.name,
+                    reason: "triggered".to_owned(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    fn config(pausing_breakers: &[&str]) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            sync_interval_ms: 10,
+            http_req_max_retry_number: 1,
+            http_req_retry_interval_sec: 1,
+            replication_lag_limit_sec: None,
+            min_operator_balance_gwei: None,
+            max_unproved_l1_batches: None,
+            max_unproved_l1_batch_age_sec: None,
+            max_l1_gas_price: None,
+            pausing_breakers: pausing_breakers
+                .iter()
+                .map(|&name| name.to_owned())
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn pausing_and_shutting_down_breakers() {
+        let pausing = MockBreaker {
+            name: "pausing",
+            ..MockBreaker::default()
+        };
+        let (pausing_triggered, pausing_failing) =
+            (pausing.triggered.clone(), pausing.failing.clone());
+        let shutdown = MockBreaker {
+            name: "shutdown",
+            ..MockBreaker::default()
+        };
+        let shutdown_triggered = shutdown.triggered.clone();
+
+        let checker = CircuitBreakerChecker::new(
+            vec![Box::new(pausing), Box::new(shutdown)],
+            &config(&["pausing"]),
+        );
+        let pause_receiver = checker.sealing_pause_receiver();
+        checker.check().await.unwrap();
+        assert!(!*pause_receiver.borrow());
+
+        pausing_triggered.store(true, Ordering::SeqCst);
+        checker.check().await.unwrap();
+        assert!(*pause_receiver.borrow());
+
+        // Internal errors retain the previous breaker state.
+        pausing_failing.store(true, Ordering::SeqCst);
+        checker.check().await.unwrap();
+        assert!(*pause_receiver.borrow());
+
+        pausing_failing.store(false, Ordering::SeqCst);
+        pausing_triggered.store(false, Ordering::SeqCst);
+        checker.check().await.unwrap();
+        assert!(!*pause_receiver.borrow());
+
+        shutdown_triggered.store(true, Ordering::SeqCst);
+        let err = checker.check().await.unwrap_err();
+        assert_matches!(
+            err,
+            CircuitBreakerError::Custom {
+                name: "shutdown",
+                ..
+            }
+        );
+    }
+}
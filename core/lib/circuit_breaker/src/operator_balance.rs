@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_eth_client::EthInterface;
+use zksync_types::{Address, U256};
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Checks that the L1 balance of the operator account is sufficient to send L1 transactions.
+#[derive(Debug)]
+pub struct OperatorBalanceChecker {
+    pub eth_client: Arc<dyn EthInterface>,
+    pub operator_address: Address,
+    /// Minimum balance in wei.
+    pub min_balance: U256,
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for OperatorBalanceChecker {
+    fn name(&self) -> &'static str {
+        "operator_balance"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let balance = self
+            .eth_client
+            .eth_balance(self.operator_address, "circuit_breaker")
+            .await
+            .context("failed getting operator balance")?;
+        if balance < self.min_balance {
+            return Err(CircuitBreakerError::LowOperatorBalance {
+                balance,
+                threshold: self.min_balance,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use anyhow::Context as _;
+use zksync_dal::ConnectionPool;
+use zksync_utils::time::seconds_since_epoch;
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Checks that the prover doesn't lag behind the sealed L1 batches.
+///
+/// Two thresholds are supported, and the breaker is triggered if any of the set thresholds is exceeded:
+///
+/// - The number of sealed L1 batches starting from the oldest batch with unfinished FRI prover jobs. If there
+///   are no unfinished prover jobs, this lag is considered to be zero.
+/// - The age of the oldest L1 batch that is committed on L1, but not proven yet. If there are no such batches,
+///   this lag is considered to be zero. Unlike the number of unproved batches, the age doesn't depend
+///   on the batch sealing rate, which may vary greatly with the network load.
+#[derive(Debug)]
+pub struct ProverLagChecker {
+    /// Pool for the main node database.
+    pub pool: ConnectionPool,
+    /// Pool for the prover database.
+    pub prover_pool: ConnectionPool,
+    pub max_unproved_l1_batches: Option<u32>,
+    pub max_unproved_l1_batch_age_sec: Option<u64>,
+}
+
+impl ProverLagChecker {
+    async fn check_unproved_batches(&self, threshold: u32) -> Result<(), CircuitBreakerError> {
+        let mut prover_storage = self.prover_pool.access_storage().await?;
+        let oldest_unproved_batch = prover_storage
+            .fri_prover_jobs_dal()
+            .min_unproved_l1_batch_number()
+            .await
+            .into_values()
+            .min();
+        drop(prover_storage);
+
+        let unproved_batches = if let Some(oldest_unproved_batch) = oldest_unproved_batch {
+            let mut storage = self.pool.access_storage().await?;
+            let last_sealed_batch = storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .context("failed getting last sealed L1 batch")?;
+            drop(storage);
+
+            last_sealed_batch.map_or(0, |last_sealed_batch| {
+                (last_sealed_batch.0 + 1).saturating_sub(oldest_unproved_batch.0)
+            })
+        } else {
+            0
+        };
+
+        metrics::gauge!(
+            "circuit_breaker.prover_lag_l1_batches",
+            unproved_batches as f64
+        );
+        if unproved_batches > threshold {
+            return Err(CircuitBreakerError::ProverBatchLag {
+                unproved_batches,
+                threshold,
+            });
+        }
+        Ok(())
+    }
+
+    async fn check_unproved_batch_age(
+        &self,
+        threshold_sec: u64,
+    ) -> Result<(), CircuitBreakerError> {
+        let mut storage = self.pool.access_storage().await?;
+        let oldest_unproved_timestamp = storage
+            .blocks_dal()
+            .oldest_committed_unproved_batch_timestamp()
+            .await
+            .context("failed getting oldest unproved L1 batch")?;
+        drop(storage);
+
+        let lag_sec = oldest_unproved_timestamp.map_or(0, |timestamp| {
+            seconds_since_epoch().saturating_sub(timestamp)
+        });
+        metrics::gauge!("circuit_breaker.prover_lag_sec", lag_sec as f64);
+        if lag_sec > threshold_sec {
+            return Err(CircuitBreakerError::ProverLag {
+                lag_sec,
+                threshold_sec,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for ProverLagChecker {
+    fn name(&self) -> &'static str {
+        "prover_lag"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        if let Some(threshold) = self.max_unproved_l1_batches {
+            self.check_unproved_batches(threshold).await?;
+        }
+        if let Some(threshold_sec) = self.max_unproved_l1_batch_age_sec {
+            self.check_unproved_batch_age(threshold_sec).await?;
+        }
+        Ok(())
+    }
+}
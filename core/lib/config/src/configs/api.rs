@@ -1,9 +1,14 @@
-use std::{net::SocketAddr, num::NonZeroU32, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU32,
+    time::Duration,
+};
 
 use serde::Deserialize;
 use zksync_basic_types::H256;
 
 pub use crate::configs::PrometheusConfig;
+use crate::configs::Secret;
 
 /// API configuration.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -46,7 +51,7 @@ pub struct Web3JsonRpcConfig {
     /// Timeout for requests (in s)
     pub request_timeout: Option<u64>,
     /// Private keys for accounts managed by node
-    pub account_pks: Option<Vec<H256>>,
+    pub account_pks: Option<Secret<Vec<H256>>>,
     /// The factor by which to scale the gasLimit
     pub estimate_gas_scale_factor: f64,
     /// The max possible number of gas that `eth_estimateGas` is allowed to overestimate.
@@ -83,10 +88,46 @@ pub struct Web3JsonRpcConfig {
     pub max_response_body_size_mb: Option<usize>,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
+    /// Note: requests are additionally limited per client; see `http_requests_per_minute_per_api_key`
+    /// and `http_requests_per_minute_per_ip`.
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// API keys accepted by the server. A key can be supplied either in the `x-api-key` HTTP header,
+    /// or as the URL path (e.g., `http://localhost:3050/<key>`). Requests with an unknown key are rejected.
+    #[serde(default)]
+    pub api_keys: Secret<Vec<String>>,
+    /// Whether requests without an API key should be rejected. Has no effect if `api_keys` is empty.
+    #[serde(default)]
+    pub api_key_required: bool,
+    /// Maximum number of weighted requests per minute for a single API key. Despite the name, applies both to HTTP
+    /// requests and to calls in WebSocket sessions. If not set, requests authenticated with an API key
+    /// are not rate-limited.
+    pub http_requests_per_minute_per_api_key: Option<NonZeroU32>,
+    /// Maximum number of weighted requests per minute for a single client IP address. Despite the name, applies both
+    /// to HTTP requests and to calls in WebSocket sessions. Applies only to requests without an API key.
+    /// If not set, such requests are not rate-limited.
+    ///
+    /// The client IP address is the peer address of the connection, unless the peer is one of `trusted_proxies`.
+    /// In the latter case, the address is taken from the `x-forwarded-for` header.
+    pub http_requests_per_minute_per_ip: Option<NonZeroU32>,
+    /// Addresses of reverse proxies between the client and the server, including the one connecting to the server.
+    /// For requests received from these addresses, the client IP address is determined from the `x-forwarded-for`
+    /// header: trusted proxies are skipped in it starting from its end, and the first remaining address is used.
+    /// Requests sent by the trusted proxies themselves (i.e., without other addresses in the header) are considered
+    /// internal and are not rate-limited per IP.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// Weight of requests invoking the VM (e.g., `eth_call`, `eth_estimateGas` or `debug_*` methods)
+    /// when checking per-client rate limits. The default value is 10; other requests have weight 1.
+    pub vm_request_weight: Option<NonZeroU32>,
+    /// If set, only the listed methods are served. Entries ending with `*` match method name prefixes
+    /// (e.g., `eth_*`).
+    pub allowed_methods: Option<Vec<String>>,
+    /// Methods that are never served, even if they are allowed by `allowed_methods`. Entries ending with `*`
+    /// match method name prefixes.
+    #[serde(default)]
+    pub denied_methods: Vec<String>,
 }
 
 impl Web3JsonRpcConfig {
@@ -121,6 +162,14 @@ impl Web3JsonRpcConfig {
             max_response_body_size_mb: Default::default(),
             websocket_requests_per_minute_limit: Default::default(),
             tree_api_url: None,
+            api_keys: Secret::default(),
+            api_key_required: false,
+            http_requests_per_minute_per_api_key: None,
+            http_requests_per_minute_per_ip: None,
+            vm_request_weight: None,
+            allowed_methods: None,
+            denied_methods: vec![],
+            trusted_proxies: vec![],
         }
     }
 
@@ -153,7 +202,10 @@ impl Web3JsonRpcConfig {
     }
 
     pub fn account_pks(&self) -> Vec<H256> {
-        self.account_pks.clone().unwrap_or_default()
+        self.account_pks
+            .as_ref()
+            .map(|keys| keys.expose().clone())
+            .unwrap_or_default()
     }
 
     pub fn vm_concurrency_limit(&self) -> usize {
@@ -200,6 +252,11 @@ impl Web3JsonRpcConfig {
     pub fn tree_api_url(&self) -> Option<String> {
         self.tree_api_url.clone()
     }
+
+    pub fn vm_request_weight(&self) -> NonZeroU32 {
+        self.vm_request_weight
+            .unwrap_or(NonZeroU32::new(10).unwrap())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
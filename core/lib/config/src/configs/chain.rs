@@ -176,6 +176,22 @@ pub struct CircuitBreakerConfig {
     pub http_req_max_retry_number: usize,
     pub http_req_retry_interval_sec: u8,
     pub replication_lag_limit_sec: Option<u32>,
+    /// Minimum L1 balance of the operator account (in gwei). If set, the `operator_balance` circuit breaker
+    /// is triggered when the balance falls below this value.
+    pub min_operator_balance_gwei: Option<u64>,
+    /// Maximum number of sealed L1 batches starting from the oldest batch with unfinished FRI prover jobs.
+    /// If set, the `prover_lag` circuit breaker is triggered when the number of such batches exceeds this value.
+    pub max_unproved_l1_batches: Option<u32>,
+    /// Maximum age (in seconds) of the oldest committed, but not yet proven L1 batch. If set, the `prover_lag`
+    /// circuit breaker is triggered when the age exceeds this value.
+    pub max_unproved_l1_batch_age_sec: Option<u64>,
+    /// Maximum L1 gas price (in wei) observed by the gas adjuster. If set, the `l1_gas_price` circuit breaker
+    /// is triggered when the price exceeds this value.
+    pub max_l1_gas_price: Option<u64>,
+    /// Names of circuit breakers that pause sealing in the state keeper instead of shutting down the node
+    /// when triggered (e.g., `prover_lag`). Sealing is resumed once all pausing breakers are no longer triggered.
+    #[serde(default)]
+    pub pausing_breakers: Vec<String>,
 }
 
 impl CircuitBreakerConfig {
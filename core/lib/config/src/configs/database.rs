@@ -3,6 +3,8 @@ use std::time::Duration;
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 
+use crate::configs::Secret;
+
 /// Mode of operation for the Merkle tree.
 ///
 /// The mode does not influence how tree data is stored; i.e., a mode can be switched on the fly.
@@ -123,14 +125,14 @@ impl DBConfig {
 /// Collection of different database URLs and general PostgreSQL options.
 /// All the entries are optional, since some components may only require a subset of them,
 /// and any component may have overrides.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct PostgresConfig {
     /// URL for the main (sequencer) database.
-    pub master_url: Option<String>,
+    pub master_url: Option<Secret<String>>,
     /// URL for the replica database.
-    pub replica_url: Option<String>,
+    pub replica_url: Option<Secret<String>>,
     /// URL for the prover database.
-    pub prover_url: Option<String>,
+    pub prover_url: Option<Secret<String>>,
     /// Maximum size of the connection pool.
     pub max_connections: Option<u32>,
     /// Statement timeout in seconds for Postgres connections. Applies only to the replica
@@ -142,21 +144,24 @@ impl PostgresConfig {
     /// Returns a copy of the master database URL as a `Result` to simplify error propagation.
     pub fn master_url(&self) -> anyhow::Result<&str> {
         self.master_url
-            .as_deref()
+            .as_ref()
+            .map(|url| url.expose().as_str())
             .context("Master DB URL is absent")
     }
 
     /// Returns a copy of the replica database URL as a `Result` to simplify error propagation.
     pub fn replica_url(&self) -> anyhow::Result<&str> {
         self.replica_url
-            .as_deref()
+            .as_ref()
+            .map(|url| url.expose().as_str())
             .context("Replica DB URL is absent")
     }
 
     /// Returns a copy of the prover database URL as a `Result` to simplify error propagation.
     pub fn prover_url(&self) -> anyhow::Result<&str> {
         self.prover_url
-            .as_deref()
+            .as_ref()
+            .map(|url| url.expose().as_str())
             .context("Prover DB URL is absent")
     }
 
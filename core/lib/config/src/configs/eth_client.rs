@@ -1,11 +1,57 @@
+use std::time::Duration;
+
 // External uses
 use serde::Deserialize;
 
+use crate::configs::Secret;
+
 /// Configuration for the Ethereum gateways.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ETHClientConfig {
     /// Numeric identifier of the L1 network (e.g. `9` for localhost).
     pub chain_id: u64,
     /// Address of the Ethereum node API.
-    pub web3_url: String,
+    pub web3_url: Secret<String>,
+    /// Addresses of additional Ethereum node APIs. If set, L1 requests fail over to these nodes
+    /// when the main node returns transport errors or times out.
+    #[serde(default)]
+    pub fallback_web3_urls: Vec<Secret<String>>,
+    /// Number of nodes that must return the same response for critical reads (the latest block number,
+    /// transaction receipts and logs). If not set, the response of a single node is trusted.
+    #[serde(default)]
+    pub read_quorum: Option<usize>,
+    /// Timeout for a single request to a node in milliseconds. A node that doesn't respond in time is treated
+    /// as failed, and the request is retried with the next node.
+    #[serde(default = "ETHClientConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl ETHClientConfig {
+    pub const fn default_request_timeout_ms() -> u64 {
+        30_000
+    }
+
+    /// Checks that the read quorum can be reached by the configured nodes.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(quorum) = self.read_quorum {
+            let node_count = 1 + self.fallback_web3_urls.len();
+            anyhow::ensure!(
+                quorum > 0 && quorum <= node_count,
+                "L1 read quorum must be between 1 and the number of L1 nodes ({node_count}), got {quorum}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Converts `self.request_timeout_ms` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+
+    /// Returns all configured node URLs, starting from the main one.
+    pub fn web3_urls(&self) -> impl Iterator<Item = &str> + '_ {
+        std::iter::once(&self.web3_url)
+            .chain(&self.fallback_web3_urls)
+            .map(|url| url.expose().as_str())
+    }
 }
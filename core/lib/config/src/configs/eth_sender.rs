@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use serde::Deserialize;
-use zksync_basic_types::H256;
+use zksync_basic_types::{Address, H256};
+
+use crate::configs::Secret;
 
 /// Configuration for the Ethereum sender crate.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -10,6 +12,8 @@ pub struct ETHSenderConfig {
     pub sender: SenderConfig,
     /// Options related to the `GasAdjuster` submodule.
     pub gas_adjuster: GasAdjusterConfig,
+    /// Remote signer holding the operator key. If set, it is used instead of the operator private key.
+    pub remote_signer: Option<RemoteSignerConfig>,
 }
 
 impl ETHSenderConfig {
@@ -35,6 +39,11 @@ impl ETHSenderConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                operator_private_key: None,
+                prove_operator_private_key: None,
+                execute_operator_private_key: None,
+                base_fee_postpone_ratio: None,
+                max_base_fee_postpone_seconds: None,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -46,6 +55,7 @@ impl ETHSenderConfig {
                 poll_period: 5,
                 max_l1_gas_price: None,
             },
+            remote_signer: None,
         }
     }
 }
@@ -96,6 +106,25 @@ pub struct SenderConfig {
 
     /// The mode in which proofs are loaded, either from DB/GCS for FRI/Old proof.
     pub proof_loading_mode: ProofLoadingMode,
+    /// Private key of the operator account. Usually provided via secrets rather than the main config.
+    #[serde(default)]
+    pub operator_private_key: Option<Secret<H256>>,
+    /// Private key of the account sending `proveBatches` transactions. If not set, the main operator
+    /// account is used. The account must be registered as a validator on the `ValidatorTimelock` contract.
+    #[serde(default)]
+    pub prove_operator_private_key: Option<Secret<H256>>,
+    /// Private key of the account sending `executeBatches` transactions. If not set, the main operator
+    /// account is used. The account must be registered as a validator on the `ValidatorTimelock` contract.
+    #[serde(default)]
+    pub execute_operator_private_key: Option<Secret<H256>>,
+    /// If set, aggregation of L1 batches for commit, prove and execute operations is postponed while
+    /// the current L1 base fee exceeds the median base fee tracked by `GasAdjuster` by more than this ratio.
+    #[serde(default)]
+    pub base_fee_postpone_ratio: Option<f64>,
+    /// Maximum time in seconds since L1 batches became ready for an operation, up to which aggregation
+    /// can be postponed because of the high L1 base fee. Defaults to 30 minutes.
+    #[serde(default)]
+    pub max_base_fee_postpone_seconds: Option<u64>,
 }
 
 impl SenderConfig {
@@ -109,11 +138,31 @@ impl SenderConfig {
         Duration::from_secs(self.aggregate_tx_poll_period)
     }
 
-    // Don't load private key, if it's not required.
+    /// Returns the maximum time since L1 batches became ready for an operation, up to which aggregation
+    /// can be postponed because of the high L1 base fee.
+    pub fn max_base_fee_postpone(&self) -> Duration {
+        const DEFAULT_MAX_BASE_FEE_POSTPONE_SECONDS: u64 = 30 * 60;
+
+        Duration::from_secs(
+            self.max_base_fee_postpone_seconds
+                .unwrap_or(DEFAULT_MAX_BASE_FEE_POSTPONE_SECONDS),
+        )
+    }
+
     pub fn private_key(&self) -> Option<H256> {
-        std::env::var("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse().unwrap())
+        self.operator_private_key.as_ref().map(|pk| *pk.expose())
+    }
+
+    pub fn prove_private_key(&self) -> Option<H256> {
+        self.prove_operator_private_key
+            .as_ref()
+            .map(|pk| *pk.expose())
+    }
+
+    pub fn execute_private_key(&self) -> Option<H256> {
+        self.execute_operator_private_key
+            .as_ref()
+            .map(|pk| *pk.expose())
     }
 }
 
@@ -147,3 +196,47 @@ impl GasAdjusterConfig {
         self.max_l1_gas_price.unwrap_or(u64::MAX)
     }
 }
+
+/// Configuration of the remote signer for the operator account. The signer must expose
+/// a web3signer-compatible JSON-RPC API (`eth_signTransaction`).
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RemoteSignerConfig {
+    /// URL of the signer JSON-RPC endpoint.
+    pub url: String,
+    /// Address of the operator account managed by the signer.
+    pub operator_address: Address,
+    /// Address of the signer-managed account sending `proveBatches` transactions, if it differs
+    /// from the operator account.
+    #[serde(default)]
+    pub prove_operator_address: Option<Address>,
+    /// Address of the signer-managed account sending `executeBatches` transactions, if it differs
+    /// from the operator account.
+    #[serde(default)]
+    pub execute_operator_address: Option<Address>,
+    /// Token sent in the `Authorization: Bearer` header, if the signer requires it.
+    #[serde(default)]
+    pub bearer_token: Option<Secret<String>>,
+    /// Path to the PEM-encoded CA certificate used to verify the signer in addition to the system roots.
+    #[serde(default)]
+    pub ca_cert_path: Option<String>,
+    /// Path to the PEM-encoded client certificate for mTLS. Must be set together with `client_key_path`.
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    /// Path to the PEM-encoded PKCS#8 client key for mTLS.
+    #[serde(default)]
+    pub client_key_path: Option<Secret<String>>,
+    /// Timeout for a single signing request in milliseconds.
+    #[serde(default = "RemoteSignerConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl RemoteSignerConfig {
+    pub const fn default_request_timeout_ms() -> u64 {
+        10_000
+    }
+
+    /// Converts `self.request_timeout_ms` into `Duration`.
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
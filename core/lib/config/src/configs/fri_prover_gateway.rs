@@ -2,11 +2,21 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+use super::{proof_data_handler::ProofDataHandlerAuthMode, Secret};
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FriProverGatewayConfig {
     pub api_url: String,
     pub api_poll_duration_secs: u16,
 
+    /// Authentication scheme expected by the proof data handler API.
+    #[serde(default)]
+    pub api_auth_mode: ProofDataHandlerAuthMode,
+    /// Client ID of this gateway, as configured in the proof data handler.
+    pub api_client_id: Option<String>,
+    /// Secret shared with the proof data handler.
+    pub api_client_secret: Option<Secret<String>>,
+
     /// Configurations for prometheus
     pub prometheus_listener_port: u16,
     pub prometheus_pushgateway_url: String,
@@ -0,0 +1,98 @@
+use crate::configs::{
+    chain::{
+        CircuitBreakerConfig, MempoolConfig, NetworkConfig, OperationsManagerConfig,
+        StateKeeperConfig,
+    },
+    fri_prover_group::FriProverGroupConfig,
+    house_keeper::HouseKeeperConfig,
+    ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
+    FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, ObjectStoreConfig,
+    OpentelemetryConfig, PostgresConfig, ProofDataHandlerConfig, Secrets,
+    WithdrawalFinalizerConfig, WitnessGeneratorConfig,
+};
+
+/// Configuration of the main node, as read from a single config file.
+/// All the sections are optional, since some components combinations do not need all of them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeneralConfig {
+    pub postgres_config: Option<PostgresConfig>,
+    pub api_config: Option<ApiConfig>,
+    pub contracts_config: Option<ContractsConfig>,
+    pub db_config: Option<DBConfig>,
+    pub eth_client_config: Option<ETHClientConfig>,
+    pub eth_sender_config: Option<ETHSenderConfig>,
+    pub eth_watch_config: Option<ETHWatchConfig>,
+    pub object_store_config: Option<ObjectStoreConfig>,
+    pub circuit_breaker_config: Option<CircuitBreakerConfig>,
+    pub mempool_config: Option<MempoolConfig>,
+    pub network_config: Option<NetworkConfig>,
+    pub operations_manager_config: Option<OperationsManagerConfig>,
+    pub state_keeper_config: Option<StateKeeperConfig>,
+    pub house_keeper_config: Option<HouseKeeperConfig>,
+    pub proof_data_handler_config: Option<ProofDataHandlerConfig>,
+    pub witness_generator_config: Option<WitnessGeneratorConfig>,
+    pub fri_proof_compressor_config: Option<FriProofCompressorConfig>,
+    pub fri_prover_config: Option<FriProverConfig>,
+    pub fri_prover_group_config: Option<FriProverGroupConfig>,
+    pub fri_witness_generator_config: Option<FriWitnessGeneratorConfig>,
+    pub opentelemetry_config: Option<OpentelemetryConfig>,
+    pub withdrawal_finalizer_config: Option<WithdrawalFinalizerConfig>,
+}
+
+impl GeneralConfig {
+    /// Applies secrets on top of this config. Secrets take precedence over the values
+    /// (if any) specified in the config sections themselves.
+    pub fn with_secrets(mut self, secrets: Secrets) -> Self {
+        if let Some(database) = secrets.database {
+            let postgres = self.postgres_config.get_or_insert_with(Default::default);
+            postgres.replica_url = database.server_replica_url.or(postgres.replica_url.take());
+            postgres.prover_url = database.prover_url.or(postgres.prover_url.take());
+            if let Some(server_url) = database.server_url {
+                // The server URL is only a fallback for the replica and prover URLs specified neither
+                // in the secrets nor in the config.
+                if postgres.replica_url.is_none() {
+                    postgres.replica_url = Some(server_url.clone());
+                }
+                if postgres.prover_url.is_none() {
+                    postgres.prover_url = Some(server_url.clone());
+                }
+                postgres.master_url = Some(server_url);
+            }
+        }
+
+        if let Some(operator) = secrets.operator {
+            if let Some(finalizer) = &mut self.withdrawal_finalizer_config {
+                finalizer.private_key = operator
+                    .withdrawal_finalizer_private_key
+                    .or(finalizer.private_key.take());
+            }
+            if let Some(eth_sender) = &mut self.eth_sender_config {
+                let sender = &mut eth_sender.sender;
+                sender.operator_private_key = operator
+                    .operator_private_key
+                    .or(sender.operator_private_key.take());
+                sender.prove_operator_private_key = operator
+                    .prove_operator_private_key
+                    .or(sender.prove_operator_private_key.take());
+                sender.execute_operator_private_key = operator
+                    .execute_operator_private_key
+                    .or(sender.execute_operator_private_key.take());
+            }
+        }
+        if let Some(api) = secrets.api {
+            if let Some(api_config) = &mut self.api_config {
+                let web3_json_rpc = &mut api_config.web3_json_rpc;
+                web3_json_rpc.account_pks = api.account_pks.or(web3_json_rpc.account_pks.take());
+            }
+        }
+        if let Some(object_store) = secrets.object_store {
+            if let (Some(object_store_config), Some(path)) = (
+                &mut self.object_store_config,
+                object_store.gcs_credential_file_path,
+            ) {
+                object_store_config.gcs_credential_file_path = path;
+            }
+        }
+        self
+    }
+}
@@ -13,10 +13,14 @@ pub use self::{
     fri_prover_gateway::FriProverGatewayConfig,
     fri_witness_generator::FriWitnessGeneratorConfig,
     fri_witness_vector_generator::FriWitnessVectorGeneratorConfig,
+    general::GeneralConfig,
     object_store::ObjectStoreConfig,
+    observability::OpentelemetryConfig,
     proof_data_handler::ProofDataHandlerConfig,
+    secrets::{Secret, Secrets},
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
+    withdrawal_finalizer::WithdrawalFinalizerConfig,
     witness_generator::WitnessGeneratorConfig,
 };
 
@@ -35,11 +39,15 @@ pub mod fri_prover_gateway;
 pub mod fri_prover_group;
 pub mod fri_witness_generator;
 pub mod fri_witness_vector_generator;
+pub mod general;
 pub mod house_keeper;
 pub mod object_store;
+pub mod observability;
 pub mod proof_data_handler;
+pub mod secrets;
 pub mod snapshots_creator;
 pub mod utils;
+pub mod withdrawal_finalizer;
 pub mod witness_generator;
 
 const BYTES_IN_MEGABYTE: usize = 1_024 * 1_024;
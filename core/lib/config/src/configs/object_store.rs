@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::configs::Secret;
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Clone, Copy)]
 pub enum ObjectStoreMode {
     GCS,
@@ -14,6 +16,6 @@ pub struct ObjectStoreConfig {
     pub bucket_base_url: String,
     pub mode: ObjectStoreMode,
     pub file_backed_base_path: String,
-    pub gcs_credential_file_path: String,
+    pub gcs_credential_file_path: Secret<String>,
     pub max_retries: u16,
 }
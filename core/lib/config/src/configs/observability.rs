@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+/// Configuration for the OpenTelemetry traces export.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct OpentelemetryConfig {
+    /// Base URL of the OTLP/HTTP collector to which the spans are exported, e.g. `http://127.0.0.1:4318`.
+    pub endpoint: String,
+    /// Name of the service reported in the exported traces.
+    #[serde(default = "OpentelemetryConfig::default_service_name")]
+    pub service_name: String,
+    /// Fraction of the root traces to be sampled, in the `[0.0, 1.0]` range.
+    /// Child spans follow the sampling decision of their parent.
+    #[serde(default = "OpentelemetryConfig::default_sampling_ratio")]
+    pub sampling_ratio: f64,
+}
+
+impl OpentelemetryConfig {
+    pub fn default_service_name() -> String {
+        "zksync".to_owned()
+    }
+
+    pub const fn default_sampling_ratio() -> f64 {
+        1.0
+    }
+
+    /// Checks that the config values are in their allowed ranges.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            (0.0..=1.0).contains(&self.sampling_ratio),
+            "OpenTelemetry sampling ratio must be in [0.0, 1.0], got {}",
+            self.sampling_ratio
+        );
+        Ok(())
+    }
+}
@@ -2,22 +2,62 @@ use std::time::Duration;
 
 use serde::Deserialize;
 
+use crate::configs::Secret;
+
 #[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
 pub enum ProtocolVersionLoadingMode {
     FromDb,
     FromEnvVar,
 }
 
+/// Authentication scheme that proof data handler clients (i.e., prover gateways) must use.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProofDataHandlerAuthMode {
+    /// Requests are not authenticated. Should only be used if the API is not reachable from outside.
+    #[default]
+    Disabled,
+    /// Clients send `Authorization: Bearer <secret>` header.
+    BearerToken,
+    /// Clients sign each request (timestamp, method, path and body) with HMAC-SHA256 keyed by their secret.
+    Hmac,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ProofDataHandlerConfig {
     pub http_port: u16,
     pub proof_generation_timeout_in_secs: u16,
     pub protocol_version_loading_mode: ProtocolVersionLoadingMode,
     pub fri_protocol_version_id: u16,
+    /// Authentication scheme required from clients.
+    #[serde(default)]
+    pub auth_mode: ProofDataHandlerAuthMode,
+    /// Clients allowed to access the API, each in the `<client_id>:<secret>` format.
+    /// Must be non-empty if authentication is enabled.
+    #[serde(default)]
+    pub api_clients: Secret<Vec<String>>,
+    /// Maximum allowed difference between the timestamp of an HMAC-signed request and the server time.
+    pub hmac_max_clock_skew_secs: Option<u64>,
+    /// Path to the PEM-encoded certificate chain. If set together with `tls_key_path`,
+    /// the API is served over HTTPS.
+    pub tls_cert_path: Option<String>,
+    /// Path to the PEM-encoded private key for `tls_cert_path`.
+    pub tls_key_path: Option<String>,
+    /// Paths to the JSON-encoded verification keys for SNARK-wrapped scheduler proofs (i.e.,
+    /// `snark_verification_scheduler_key.json` generated by the prover). A submitted proof is verified
+    /// against the key whose hash is equal to `recursion_scheduler_level_vk_hash` of the batch protocol version;
+    /// thus, keys for all protocol versions with unproven batches must be provided. Must be non-empty.
+    pub scheduler_vk_paths: Vec<String>,
 }
 
 impl ProofDataHandlerConfig {
+    const DEFAULT_HMAC_MAX_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
     pub fn proof_generation_timeout(&self) -> Duration {
         Duration::from_secs(self.proof_generation_timeout_in_secs as u64)
     }
+
+    pub fn hmac_max_clock_skew(&self) -> Duration {
+        self.hmac_max_clock_skew_secs
+            .map_or(Self::DEFAULT_HMAC_MAX_CLOCK_SKEW, Duration::from_secs)
+    }
 }
@@ -0,0 +1,153 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize, Serializer};
+use zksync_basic_types::H256;
+
+/// Placeholder used instead of the secret values in `Debug` and `Serialize` output.
+const REDACTED: &str = "<redacted>";
+
+/// Wrapper for sensitive config values (credentials, private keys, URLs embedding them).
+/// Redacts the wrapped value in `Debug` and `Serialize` output, so that it cannot leak
+/// into logs by accident. The value can be accessed with [`Self::expose()`].
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwraps the secret value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(REDACTED)
+    }
+}
+
+impl<T> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+/// Secrets required by the main node, kept apart from the rest of the configuration
+/// so that they can be provided from a separate file or environment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Secrets {
+    pub database: Option<DatabaseSecrets>,
+    pub operator: Option<OperatorSecrets>,
+    pub api: Option<ApiSecrets>,
+    pub object_store: Option<ObjectStoreSecrets>,
+}
+
+impl Secrets {
+    /// Overrides the secrets with the ones present in `overrides`.
+    pub fn merge(self, overrides: Self) -> Self {
+        let database = match (self.database, overrides.database) {
+            (Some(database), Some(overrides)) => Some(database.merge(overrides)),
+            (database, overrides) => overrides.or(database),
+        };
+        let operator = match (self.operator, overrides.operator) {
+            (Some(operator), Some(overrides)) => Some(OperatorSecrets {
+                operator_private_key: overrides
+                    .operator_private_key
+                    .or(operator.operator_private_key),
+                prove_operator_private_key: overrides
+                    .prove_operator_private_key
+                    .or(operator.prove_operator_private_key),
+                execute_operator_private_key: overrides
+                    .execute_operator_private_key
+                    .or(operator.execute_operator_private_key),
+                withdrawal_finalizer_private_key: overrides
+                    .withdrawal_finalizer_private_key
+                    .or(operator.withdrawal_finalizer_private_key),
+            }),
+            (operator, overrides) => overrides.or(operator),
+        };
+        let api = match (self.api, overrides.api) {
+            (Some(api), Some(overrides)) => Some(ApiSecrets {
+                account_pks: overrides.account_pks.or(api.account_pks),
+            }),
+            (api, overrides) => overrides.or(api),
+        };
+        let object_store = match (self.object_store, overrides.object_store) {
+            (Some(object_store), Some(overrides)) => Some(ObjectStoreSecrets {
+                gcs_credential_file_path: overrides
+                    .gcs_credential_file_path
+                    .or(object_store.gcs_credential_file_path),
+            }),
+            (object_store, overrides) => overrides.or(object_store),
+        };
+        Self {
+            database,
+            operator,
+            api,
+            object_store,
+        }
+    }
+}
+
+/// Database URLs; these usually embed the credentials.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct DatabaseSecrets {
+    /// URL for the main (sequencer) database.
+    pub server_url: Option<Secret<String>>,
+    /// URL for the replica database. Defaults to the main database URL.
+    pub server_replica_url: Option<Secret<String>>,
+    /// URL for the prover database. Defaults to the main database URL.
+    pub prover_url: Option<Secret<String>>,
+}
+
+impl DatabaseSecrets {
+    fn merge(self, overrides: Self) -> Self {
+        Self {
+            server_url: overrides.server_url.or(self.server_url),
+            server_replica_url: overrides.server_replica_url.or(self.server_replica_url),
+            prover_url: overrides.prover_url.or(self.prover_url),
+        }
+    }
+}
+
+/// Keys of the L1 operator accounts.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct OperatorSecrets {
+    /// Private key of the operator account sending the L1 transactions.
+    pub operator_private_key: Option<Secret<H256>>,
+    /// Private key of the account sending `proveBatches` transactions, if it is separate.
+    pub prove_operator_private_key: Option<Secret<H256>>,
+    /// Private key of the account sending `executeBatches` transactions, if it is separate.
+    pub execute_operator_private_key: Option<Secret<H256>>,
+    /// Private key of the account sending withdrawal finalization transactions.
+    pub withdrawal_finalizer_private_key: Option<Secret<H256>>,
+}
+
+/// Keys of the accounts managed by the API server.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ApiSecrets {
+    /// Private keys for accounts managed by the node.
+    pub account_pks: Option<Secret<Vec<H256>>>,
+}
+
+/// Object store credentials.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ObjectStoreSecrets {
+    /// Path to the GCS credentials file.
+    pub gcs_credential_file_path: Option<Secret<String>>,
+}
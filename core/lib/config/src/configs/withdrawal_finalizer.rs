@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use zksync_basic_types::H256;
+
+use crate::configs::Secret;
+
+/// Configuration for the withdrawal finalizer, which finalizes L2→L1 withdrawals on L1
+/// once the batches they were initiated in are executed.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WithdrawalFinalizerConfig {
+    /// How often the finalizer checks for new executed L1 batches and in-flight transactions.
+    /// Value in milliseconds.
+    pub poll_interval_ms: u64,
+    /// Maximum number of withdrawals finalized in a single L1 transaction.
+    pub batch_size: usize,
+    /// Gas limit reserved for a single withdrawal in the finalization transaction.
+    pub gas_limit_per_withdrawal: u64,
+    /// Number of failed finalization attempts after which a withdrawal is no longer retried.
+    pub max_attempts: u32,
+    /// Time in milliseconds after which a finalization transaction still pending on L1 is resent
+    /// with the same nonce and bumped fees.
+    #[serde(default = "WithdrawalFinalizerConfig::default_resend_timeout_ms")]
+    pub resend_timeout_ms: u64,
+    /// Time in milliseconds after sending during which a finalization transaction unknown to the L1 node
+    /// is not considered dropped (e.g., because it has not propagated to the node yet).
+    #[serde(default = "WithdrawalFinalizerConfig::default_dropped_tx_grace_period_ms")]
+    pub dropped_tx_grace_period_ms: u64,
+    /// L1 batch to start indexing withdrawals from if none were indexed yet.
+    /// If not set, indexing starts from the first executed L1 batch.
+    #[serde(default)]
+    pub start_from_l1_batch: Option<u32>,
+    /// Private key of the account sending finalization transactions. Usually provided via secrets
+    /// rather than the main config. Must not coincide with any of the operator accounts.
+    #[serde(default)]
+    pub private_key: Option<Secret<H256>>,
+}
+
+impl WithdrawalFinalizerConfig {
+    pub const fn default_resend_timeout_ms() -> u64 {
+        300_000
+    }
+
+    pub const fn default_dropped_tx_grace_period_ms() -> u64 {
+        120_000
+    }
+
+    /// Converts `self.poll_interval_ms` into `Duration`.
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+
+    /// Converts `self.resend_timeout_ms` into `Duration`.
+    pub fn resend_timeout(&self) -> Duration {
+        Duration::from_millis(self.resend_timeout_ms)
+    }
+
+    /// Converts `self.dropped_tx_grace_period_ms` into `Duration`.
+    pub fn dropped_tx_grace_period(&self) -> Duration {
+        Duration::from_millis(self.dropped_tx_grace_period_ms)
+    }
+
+    pub fn private_key(&self) -> Option<H256> {
+        self.private_key.as_ref().map(|pk| *pk.expose())
+    }
+}
@@ -48,6 +48,12 @@ impl<T: RandomConfig> RandomConfig for Option<T> {
     }
 }
 
+impl<T: RandomConfig> RandomConfig for configs::Secret<T> {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self::new(g.gen())
+    }
+}
+
 impl<T: RandomConfig> RandomConfig for Vec<T> {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         if g.required_only {
@@ -66,6 +72,16 @@ impl<T: RandomConfig + Eq + std::hash::Hash> RandomConfig for HashSet<T> {
     }
 }
 
+impl RandomConfig for std::net::IpAddr {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        if g.rng.gen() {
+            std::net::Ipv4Addr::from(g.rng.gen::<u32>()).into()
+        } else {
+            std::net::Ipv6Addr::from(g.rng.gen::<u128>()).into()
+        }
+    }
+}
+
 impl RandomConfig for bool {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         g.rng.gen()
@@ -203,6 +219,14 @@ impl RandomConfig for configs::api::Web3JsonRpcConfig {
             max_response_body_size_mb: g.gen(),
             websocket_requests_per_minute_limit: g.gen(),
             tree_api_url: g.gen(),
+            api_keys: g.gen(),
+            api_key_required: g.gen(),
+            http_requests_per_minute_per_api_key: g.gen(),
+            http_requests_per_minute_per_ip: g.gen(),
+            vm_request_weight: g.gen(),
+            allowed_methods: g.gen(),
+            denied_methods: g.gen(),
+            trusted_proxies: g.gen(),
         }
     }
 }
@@ -296,6 +320,11 @@ impl RandomConfig for configs::chain::CircuitBreakerConfig {
             http_req_max_retry_number: g.gen(),
             http_req_retry_interval_sec: g.gen(),
             replication_lag_limit_sec: g.gen(),
+            min_operator_balance_gwei: g.gen(),
+            max_unproved_l1_batches: g.gen(),
+            max_unproved_l1_batch_age_sec: g.gen(),
+            max_l1_gas_price: g.gen(),
+            pausing_breakers: g.gen(),
         }
     }
 }
@@ -413,9 +442,19 @@ impl RandomConfig for configs::database::PostgresConfig {
 
 impl RandomConfig for configs::ETHClientConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
+        let fallback_web3_urls: Vec<_> = g.gen();
+        // The read quorum must be reachable by the configured nodes.
+        let read_quorum = if g.required_only {
+            None
+        } else {
+            Some(g.rng.gen_range(1..=fallback_web3_urls.len() + 1))
+        };
         Self {
             chain_id: g.gen(),
             web3_url: g.gen(),
+            fallback_web3_urls,
+            read_quorum,
+            request_timeout_ms: g.gen(),
         }
     }
 }
@@ -425,6 +464,23 @@ impl RandomConfig for configs::ETHSenderConfig {
         Self {
             sender: g.gen(),
             gas_adjuster: g.gen(),
+            remote_signer: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::eth_sender::RemoteSignerConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            url: g.gen(),
+            operator_address: g.gen(),
+            prove_operator_address: g.gen(),
+            execute_operator_address: g.gen(),
+            bearer_token: g.gen(),
+            ca_cert_path: g.gen(),
+            client_cert_path: g.gen(),
+            client_key_path: g.gen(),
+            request_timeout_ms: g.gen(),
         }
     }
 }
@@ -468,6 +524,12 @@ impl RandomConfig for configs::eth_sender::SenderConfig {
             l1_batch_min_age_before_execute_seconds: g.gen(),
             max_acceptable_priority_fee_in_gwei: g.gen(),
             proof_loading_mode: g.gen(),
+            // Not a part of the config file; provided via secrets.
+            operator_private_key: None,
+            prove_operator_private_key: None,
+            execute_operator_private_key: None,
+            base_fee_postpone_ratio: g.gen(),
+            max_base_fee_postpone_seconds: g.gen(),
         }
     }
 }
@@ -496,6 +558,31 @@ impl RandomConfig for configs::ETHWatchConfig {
     }
 }
 
+impl RandomConfig for configs::WithdrawalFinalizerConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            poll_interval_ms: g.gen(),
+            batch_size: g.gen(),
+            gas_limit_per_withdrawal: g.gen(),
+            max_attempts: g.gen(),
+            resend_timeout_ms: g.gen(),
+            dropped_tx_grace_period_ms: g.gen(),
+            start_from_l1_batch: g.gen(),
+            private_key: None,
+        }
+    }
+}
+
+impl RandomConfig for configs::OpentelemetryConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            endpoint: g.gen(),
+            service_name: g.gen(),
+            sampling_ratio: g.gen(),
+        }
+    }
+}
+
 impl RandomConfig for configs::FriProofCompressorConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -546,6 +633,9 @@ impl RandomConfig for configs::FriProverGatewayConfig {
         Self {
             api_url: g.gen(),
             api_poll_duration_secs: g.gen(),
+            api_auth_mode: g.gen(),
+            api_client_id: g.gen(),
+            api_client_secret: g.gen(),
             prometheus_listener_port: g.gen(),
             prometheus_pushgateway_url: g.gen(),
             prometheus_push_interval_ms: g.gen(),
@@ -662,6 +752,16 @@ impl RandomConfig for configs::proof_data_handler::ProtocolVersionLoadingMode {
     }
 }
 
+impl RandomConfig for configs::proof_data_handler::ProofDataHandlerAuthMode {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        match g.rng.gen_range(0..3) {
+            0 => Self::Disabled,
+            1 => Self::BearerToken,
+            _ => Self::Hmac,
+        }
+    }
+}
+
 impl RandomConfig for configs::ProofDataHandlerConfig {
     fn sample(g: &mut Gen<impl Rng>) -> Self {
         Self {
@@ -669,6 +769,12 @@ impl RandomConfig for configs::ProofDataHandlerConfig {
             proof_generation_timeout_in_secs: g.gen(),
             protocol_version_loading_mode: g.gen(),
             fri_protocol_version_id: g.gen(),
+            auth_mode: g.gen(),
+            api_clients: g.gen(),
+            hmac_max_clock_skew_secs: g.gen(),
+            tls_cert_path: g.gen(),
+            tls_key_path: g.gen(),
+            scheduler_vk_paths: g.gen(),
         }
     }
 }
@@ -706,3 +812,80 @@ impl RandomConfig for configs::WitnessGeneratorConfig {
         }
     }
 }
+
+impl RandomConfig for configs::GeneralConfig {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            postgres_config: g.gen(),
+            api_config: g.gen(),
+            contracts_config: g.gen(),
+            db_config: g.gen(),
+            eth_client_config: g.gen(),
+            eth_sender_config: g.gen(),
+            eth_watch_config: g.gen(),
+            object_store_config: g.gen(),
+            circuit_breaker_config: g.gen(),
+            mempool_config: g.gen(),
+            network_config: g.gen(),
+            operations_manager_config: g.gen(),
+            state_keeper_config: g.gen(),
+            house_keeper_config: g.gen(),
+            proof_data_handler_config: g.gen(),
+            witness_generator_config: g.gen(),
+            fri_proof_compressor_config: g.gen(),
+            fri_prover_config: g.gen(),
+            fri_prover_group_config: g.gen(),
+            fri_witness_generator_config: g.gen(),
+            opentelemetry_config: g.gen(),
+            withdrawal_finalizer_config: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::Secrets {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            database: g.gen(),
+            operator: g.gen(),
+            api: g.gen(),
+            object_store: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::secrets::ApiSecrets {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            account_pks: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::secrets::ObjectStoreSecrets {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            gcs_credential_file_path: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::secrets::DatabaseSecrets {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            server_url: g.gen(),
+            server_replica_url: g.gen(),
+            prover_url: g.gen(),
+        }
+    }
+}
+
+impl RandomConfig for configs::secrets::OperatorSecrets {
+    fn sample(g: &mut Gen<impl Rng>) -> Self {
+        Self {
+            operator_private_key: g.gen(),
+            prove_operator_private_key: g.gen(),
+            execute_operator_private_key: g.gen(),
+            withdrawal_finalizer_private_key: g.gen(),
+        }
+    }
+}
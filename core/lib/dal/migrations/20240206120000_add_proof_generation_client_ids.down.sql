@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details DROP COLUMN IF EXISTS picked_by_client;
+ALTER TABLE proof_generation_details DROP COLUMN IF EXISTS submitted_by_client;
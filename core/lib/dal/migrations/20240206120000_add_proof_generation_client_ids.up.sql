@@ -0,0 +1,2 @@
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS picked_by_client TEXT;
+ALTER TABLE proof_generation_details ADD COLUMN IF NOT EXISTS submitted_by_client TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE protocol_versions DROP COLUMN IF EXISTS l1_block_number;
+
+DROP TABLE IF EXISTS eth_watch_l1_blocks;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS eth_watch_l1_blocks (
+    number BIGINT PRIMARY KEY,
+    hash BYTEA NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+ALTER TABLE protocol_versions ADD COLUMN IF NOT EXISTS l1_block_number INT;
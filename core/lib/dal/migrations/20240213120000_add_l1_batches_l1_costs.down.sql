@@ -0,0 +1 @@
+DROP TABLE IF EXISTS l1_batches_l1_costs;
@@ -0,0 +1,17 @@
+-- Costs are recorded only for L1 transactions mined after this migration; costs of previously mined
+-- transactions are not backfilled since gas usage of individual attempts is not stored in the DB.
+CREATE TABLE IF NOT EXISTS l1_batches_l1_costs (
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    tx_type TEXT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    -- Failed transactions may be removed from `eth_txs`, but their costs are retained.
+    eth_tx_id INT REFERENCES eth_txs (id) ON DELETE SET NULL,
+    succeeded BOOLEAN NOT NULL,
+    gas_used BIGINT NOT NULL,
+    effective_gas_price NUMERIC(80) NOT NULL,
+    cost NUMERIC(80) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, tx_hash)
+);
+
+CREATE INDEX IF NOT EXISTS l1_batches_l1_costs_eth_tx_id_idx ON l1_batches_l1_costs (eth_tx_id);
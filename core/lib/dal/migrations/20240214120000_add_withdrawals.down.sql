@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS withdrawals_indexed_l1_batches;
+DROP TABLE IF EXISTS withdrawals;
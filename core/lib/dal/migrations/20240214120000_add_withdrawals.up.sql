@@ -0,0 +1,25 @@
+CREATE TABLE IF NOT EXISTS withdrawals (
+    id BIGSERIAL PRIMARY KEY,
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    l2_message_index INT NOT NULL,
+    l1_batch_tx_index INT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    sender BYTEA NOT NULL,
+    message BYTEA NOT NULL,
+    status TEXT NOT NULL,
+    attempts INT NOT NULL DEFAULT 0,
+    finalization_tx_hash BYTEA,
+    finalization_tx_nonce BIGINT,
+    finalization_tx_sent_at TIMESTAMP,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE (l1_batch_number, l2_message_index)
+);
+
+CREATE INDEX IF NOT EXISTS withdrawals_status_idx ON withdrawals (status);
+
+CREATE TABLE IF NOT EXISTS withdrawals_indexed_l1_batches (
+    l1_batch_number BIGINT PRIMARY KEY REFERENCES l1_batches (number) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL
+);
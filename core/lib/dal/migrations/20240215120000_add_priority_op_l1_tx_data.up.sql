@@ -0,0 +1,6 @@
+-- L1 transaction hashes and expiration timestamps were not persisted before this migration, so they are `NULL`
+-- for previously received priority operations and aren't backfilled. Such operations cannot be looked up
+-- by the L1 transaction hash; they are still available by their serial ID.
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS l1_tx_hash BYTEA;
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS l1_tx_expiration_timestamp BIGINT;
+CREATE INDEX IF NOT EXISTS transactions_l1_tx_hash_idx ON transactions (l1_tx_hash) WHERE l1_tx_hash IS NOT NULL;
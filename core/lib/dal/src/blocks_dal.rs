@@ -1977,6 +1977,29 @@ impl BlocksDal<'_, '_> {
         .map(|row| row.timestamp as u64))
     }
 
+    /// Returns the timestamp of the oldest L1 batch that is committed, but not proven yet.
+    pub async fn oldest_committed_unproved_batch_timestamp(&mut self) -> sqlx::Result<Option<u64>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                timestamp
+            FROM
+                l1_batches
+            WHERE
+                eth_commit_tx_id IS NOT NULL
+                AND eth_prove_tx_id IS NULL
+                AND number > 0
+            ORDER BY
+                number
+            LIMIT
+                1
+            "#,
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        .map(|row| row.timestamp as u64))
+    }
+
     pub async fn oldest_unexecuted_batch_timestamp(&mut self) -> Result<Option<u64>, sqlx::Error> {
         Ok(sqlx::query!(
             r#"
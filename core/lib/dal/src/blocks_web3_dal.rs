@@ -1,7 +1,10 @@
+use std::str::FromStr;
+
 use bigdecimal::BigDecimal;
 use sqlx::Row;
 use zksync_system_constants::EMPTY_UNCLES_HASH;
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     api,
     l2_to_l1_log::L2ToL1Log,
     vm_trace::Call,
@@ -643,6 +646,66 @@ impl BlocksWeb3Dal<'_, '_> {
 
         Ok(l1_batch_details.map(Into::into))
     }
+
+    /// Returns L1 costs recorded for the specified L1 batch, or `None` if none of its L1 transactions
+    /// are mined yet. Costs of failed L1 transactions are included.
+    pub async fn get_l1_batch_costs(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Option<api::L1BatchCosts>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_type,
+                tx_hash,
+                succeeded,
+                gas_used,
+                effective_gas_price,
+                cost
+            FROM
+                l1_batches_l1_costs
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                created_at,
+                tx_hash
+            "#,
+            l1_batch_number.0 as i64
+        )
+        .instrument("get_l1_batch_costs")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let mut costs = api::L1BatchCosts {
+            number: l1_batch_number,
+            commit: vec![],
+            prove: vec![],
+            execute: vec![],
+            total_cost: U256::zero(),
+        };
+        for row in rows {
+            let tx_cost = api::L1TxCost {
+                tx_hash: H256::from_str(&row.tx_hash).expect("Incorrect L1 tx hash"),
+                succeeded: row.succeeded,
+                gas_used: U64::from(row.gas_used as u64),
+                effective_gas_price: bigdecimal_to_u256(row.effective_gas_price),
+                cost: bigdecimal_to_u256(row.cost),
+            };
+            costs.total_cost += tx_cost.cost;
+            let tx_type = row.tx_type.parse().expect("Incorrect L1 tx type");
+            let txs = match tx_type {
+                AggregatedActionType::Commit => &mut costs.commit,
+                AggregatedActionType::PublishProofOnchain => &mut costs.prove,
+                AggregatedActionType::Execute => &mut costs.execute,
+            };
+            txs.push(tx_cost);
+        }
+        Ok(Some(costs))
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +158,16 @@ impl TestTemplate {
     /// The database is not cleaned up automatically, but rather the whole Postgres
     /// container is recreated whenever you call "zk test rust".
     pub async fn create_db(&self) -> anyhow::Result<ConnectionPool> {
+        self.create_db_builder()
+            .await?
+            .build()
+            .await
+            .context("ConnectionPool::builder()")
+    }
+
+    /// Same as [`Self::create_db()`], but returns a builder for connection pools to the created database.
+    /// Useful if the consumer of the database needs to build pools itself.
+    pub async fn create_db_builder(&self) -> anyhow::Result<ConnectionPoolBuilder> {
         use rand::Rng as _;
         use sqlx::Executor as _;
 
@@ -171,10 +181,10 @@ impl TestTemplate {
             .context("CREATE DATABASE")?;
 
         const TEST_MAX_CONNECTIONS: u32 = 50; // Expected to be enough for any unit test.
-        ConnectionPool::builder(self.url(&db_new).as_ref(), TEST_MAX_CONNECTIONS)
-            .build()
-            .await
-            .context("ConnectionPool::builder()")
+        Ok(ConnectionPool::builder(
+            self.url(&db_new).as_ref(),
+            TEST_MAX_CONNECTIONS,
+        ))
     }
 }
 
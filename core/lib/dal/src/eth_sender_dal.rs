@@ -7,9 +7,10 @@ use sqlx::{
 };
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, TxHistory, TxHistoryToSend},
+    eth_sender::{EthTx, L1BatchTxCost, TxHistory, TxHistoryToSend},
     Address, L1BatchNumber, H256, U256,
 };
+use zksync_utils::u256_to_big_decimal;
 
 use crate::{
     models::storage_eth_tx::{
@@ -24,7 +25,12 @@ pub struct EthSenderDal<'a, 'c> {
 }
 
 impl EthSenderDal<'_, '_> {
-    pub async fn get_inflight_txs(&mut self) -> sqlx::Result<Vec<EthTx>> {
+    /// Returns transactions sent from `from_addr` (`None` corresponds to the main operator account)
+    /// that are not confirmed yet, ordered by ID.
+    pub async fn get_inflight_txs(
+        &mut self,
+        from_addr: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
         let txs = sqlx::query_as!(
             StorageEthTx,
             r#"
@@ -33,18 +39,22 @@ impl EthSenderDal<'_, '_> {
             FROM
                 eth_txs
             WHERE
-                confirmed_eth_tx_history_id IS NULL
+                from_addr IS NOT DISTINCT FROM $1
+                AND confirmed_eth_tx_history_id IS NULL
                 AND id <= (
                     SELECT
                         COALESCE(MAX(eth_tx_id), 0)
                     FROM
                         eth_txs_history
+                        JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id
                     WHERE
-                        sent_at_block IS NOT NULL
+                        eth_txs_history.sent_at_block IS NOT NULL
+                        AND eth_txs.from_addr IS NOT DISTINCT FROM $1
                 )
             ORDER BY
                 id
-            "#
+            "#,
+            from_addr.as_ref().map(Address::as_bytes)
         )
         .fetch_all(self.storage.conn())
         .await?;
@@ -113,7 +123,13 @@ impl EthSenderDal<'_, '_> {
         .map(Into::into))
     }
 
-    pub async fn get_new_eth_txs(&mut self, limit: u64) -> sqlx::Result<Vec<EthTx>> {
+    /// Returns transactions from `from_addr` (`None` corresponds to the main operator account)
+    /// that were never sent, ordered by ID.
+    pub async fn get_new_eth_txs(
+        &mut self,
+        limit: u64,
+        from_addr: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
         let txs = sqlx::query_as!(
             StorageEthTx,
             r#"
@@ -122,18 +138,23 @@ impl EthSenderDal<'_, '_> {
             FROM
                 eth_txs
             WHERE
-                id > (
+                from_addr IS NOT DISTINCT FROM $2
+                AND id > (
                     SELECT
                         COALESCE(MAX(eth_tx_id), 0)
                     FROM
                         eth_txs_history
+                        JOIN eth_txs ON eth_txs.id = eth_txs_history.eth_tx_id
+                    WHERE
+                        eth_txs.from_addr IS NOT DISTINCT FROM $2
                 )
             ORDER BY
                 id
             LIMIT
                 $1
             "#,
-            limit as i64
+            limit as i64,
+            from_addr.as_ref().map(Address::as_bytes)
         )
         .fetch_all(self.storage.conn())
         .await?;
@@ -174,6 +195,7 @@ impl EthSenderDal<'_, '_> {
         tx_type: AggregatedActionType,
         contract_address: Address,
         predicted_gas_cost: u32,
+        from_addr: Option<Address>,
     ) -> sqlx::Result<EthTx> {
         let address = format!("{:#x}", contract_address);
         let eth_tx = sqlx::query_as!(
@@ -186,11 +208,12 @@ impl EthSenderDal<'_, '_> {
                     tx_type,
                     contract_address,
                     predicted_gas_cost,
+                    from_addr,
                     created_at,
                     updated_at
                 )
             VALUES
-                ($1, $2, $3, $4, $5, NOW(), NOW())
+                ($1, $2, $3, $4, $5, $6, NOW(), NOW())
             RETURNING
                 *
             "#,
@@ -198,7 +221,8 @@ impl EthSenderDal<'_, '_> {
             nonce as i64,
             tx_type.to_string(),
             address,
-            predicted_gas_cost as i64
+            predicted_gas_cost as i64,
+            from_addr.as_ref().map(Address::as_bytes)
         )
         .fetch_one(self.storage.conn())
         .await?;
@@ -358,6 +382,97 @@ impl EthSenderDal<'_, '_> {
         Ok(Some(H256::from_str(tx_hash).context("invalid tx_hash")?))
     }
 
+    /// Saves shares of mined L1 transaction costs attributed to L1 batches. Existing costs
+    /// for the same L1 batch and transaction are overwritten.
+    pub async fn save_l1_batch_costs(&mut self, costs: &[L1BatchTxCost]) -> sqlx::Result<()> {
+        let mut l1_batch_numbers = Vec::with_capacity(costs.len());
+        let mut tx_types = Vec::with_capacity(costs.len());
+        let mut tx_hashes = Vec::with_capacity(costs.len());
+        let mut eth_tx_ids = Vec::with_capacity(costs.len());
+        let mut succeeded = Vec::with_capacity(costs.len());
+        let mut gas_used = Vec::with_capacity(costs.len());
+        let mut effective_gas_prices = Vec::with_capacity(costs.len());
+        let mut tx_costs = Vec::with_capacity(costs.len());
+        for cost in costs {
+            l1_batch_numbers.push(cost.l1_batch_number.0 as i64);
+            tx_types.push(cost.tx_type.to_string());
+            tx_hashes.push(format!("{:#x}", cost.tx_hash));
+            eth_tx_ids.push(cost.eth_tx_id as i32);
+            succeeded.push(cost.succeeded);
+            gas_used.push(cost.gas_used as i64);
+            effective_gas_prices.push(u256_to_big_decimal(cost.effective_gas_price));
+            tx_costs.push(u256_to_big_decimal(cost.cost));
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                l1_batches_l1_costs (
+                    l1_batch_number,
+                    tx_type,
+                    tx_hash,
+                    eth_tx_id,
+                    succeeded,
+                    gas_used,
+                    effective_gas_price,
+                    cost,
+                    created_at
+                )
+            SELECT
+                u.l1_batch_number,
+                u.tx_type,
+                u.tx_hash,
+                u.eth_tx_id,
+                u.succeeded,
+                u.gas_used,
+                u.effective_gas_price,
+                u.cost,
+                NOW()
+            FROM
+                UNNEST(
+                    $1::BIGINT[],
+                    $2::TEXT[],
+                    $3::TEXT[],
+                    $4::INT[],
+                    $5::BOOLEAN[],
+                    $6::BIGINT[],
+                    $7::NUMERIC[],
+                    $8::NUMERIC[]
+                ) AS u (
+                    l1_batch_number,
+                    tx_type,
+                    tx_hash,
+                    eth_tx_id,
+                    succeeded,
+                    gas_used,
+                    effective_gas_price,
+                    cost
+                )
+            ON CONFLICT (l1_batch_number, tx_hash) DO
+            UPDATE
+            SET
+                tx_type = excluded.tx_type,
+                eth_tx_id = excluded.eth_tx_id,
+                succeeded = excluded.succeeded,
+                gas_used = excluded.gas_used,
+                effective_gas_price = excluded.effective_gas_price,
+                cost = excluded.cost,
+                created_at = NOW()
+            "#,
+            &l1_batch_numbers,
+            &tx_types,
+            &tx_hashes,
+            &eth_tx_ids,
+            &succeeded,
+            &gas_used,
+            &effective_gas_prices,
+            &tx_costs
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     /// This method inserts a fake transaction into the database that would make the corresponding L1 batch
     /// to be considered committed/proven/executed.
     ///
@@ -510,18 +625,26 @@ impl EthSenderDal<'_, '_> {
         Ok(history_item.map(|tx| tx.into()))
     }
 
-    pub async fn get_next_nonce(&mut self) -> sqlx::Result<Option<u64>> {
+    /// Returns the next nonce for transactions sent from `from_addr` (`None` corresponds
+    /// to the main operator account), or `None` if there are no such transactions.
+    pub async fn get_next_nonce(
+        &mut self,
+        from_addr: Option<Address>,
+    ) -> sqlx::Result<Option<u64>> {
         let row = sqlx::query!(
             r#"
             SELECT
                 nonce
             FROM
                 eth_txs
+            WHERE
+                from_addr IS NOT DISTINCT FROM $1
             ORDER BY
                 id DESC
             LIMIT
                 1
-            "#
+            "#,
+            from_addr.as_ref().map(Address::as_bytes)
         )
         .fetch_optional(self.storage.conn())
         .await?;
@@ -561,23 +684,54 @@ impl EthSenderDal<'_, '_> {
         .context("count field is missing")
     }
 
-    pub async fn clear_failed_transactions(&mut self) -> sqlx::Result<()> {
-        sqlx::query!(
+    /// Returns senders (`None` corresponds to the main operator account) that have failed transactions.
+    pub async fn get_senders_with_failed_transactions(
+        &mut self,
+    ) -> sqlx::Result<Vec<Option<Address>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                from_addr
+            FROM
+                eth_txs
+            WHERE
+                has_failed = TRUE
+            "#
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| row.from_addr.map(|addr| Address::from_slice(&addr)))
+            .collect())
+    }
+
+    /// Removes failed transactions from `from_addr` (`None` corresponds to the main operator account)
+    /// together with all subsequent transactions from the same sender. Transactions from other senders
+    /// are not affected since they have independent nonce sequences. Returns the number of removed transactions.
+    pub async fn clear_failed_transactions(
+        &mut self,
+        from_addr: Option<Address>,
+    ) -> sqlx::Result<u64> {
+        let result = sqlx::query!(
             r#"
             DELETE FROM eth_txs
             WHERE
-                id >= (
+                from_addr IS NOT DISTINCT FROM $1
+                AND id >= (
                     SELECT
                         MIN(id)
                     FROM
                         eth_txs
                     WHERE
                         has_failed = TRUE
+                        AND from_addr IS NOT DISTINCT FROM $1
                 )
-            "#
+            "#,
+            from_addr.as_ref().map(Address::as_bytes)
         )
         .execute(self.storage.conn())
         .await?;
-        Ok(())
+        Ok(result.rows_affected())
     }
 }
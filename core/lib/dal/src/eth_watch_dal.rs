@@ -0,0 +1,146 @@
+use zksync_types::{L1BlockNumber, H256};
+
+use crate::StorageProcessor;
+
+/// Stores hashes of L1 blocks up to which `EthWatch` has processed events. These checkpoints
+/// are used to detect L1 reorgs affecting already processed block ranges.
+#[derive(Debug)]
+pub struct EthWatchDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl EthWatchDal<'_, '_> {
+    pub async fn save_l1_block(&mut self, number: L1BlockNumber, hash: H256) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_watch_l1_blocks (number, hash, created_at)
+            VALUES
+                ($1, $2, NOW())
+            ON CONFLICT (number) DO
+            UPDATE
+            SET
+                hash = $2,
+                created_at = NOW()
+            "#,
+            number.0 as i64,
+            hash.as_bytes()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all stored L1 block checkpoints, starting from the most recent one.
+    pub async fn get_l1_blocks(&mut self) -> sqlx::Result<Vec<(L1BlockNumber, H256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                hash
+            FROM
+                eth_watch_l1_blocks
+            ORDER BY
+                number DESC
+            "#
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L1BlockNumber(row.number as u32),
+                    H256::from_slice(&row.hash),
+                )
+            })
+            .collect())
+    }
+
+    /// Removes checkpoints for L1 blocks with numbers greater than `last_block_to_keep`.
+    pub async fn delete_l1_blocks_after(
+        &mut self,
+        last_block_to_keep: L1BlockNumber,
+    ) -> sqlx::Result<usize> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_blocks
+            WHERE
+                number > $1
+            RETURNING
+                number
+            "#,
+            last_block_to_keep.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.len())
+    }
+
+    /// Keeps only `count` most recent checkpoints.
+    pub async fn prune_l1_blocks(&mut self, count: usize) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM eth_watch_l1_blocks
+            WHERE
+                number NOT IN (
+                    SELECT
+                        number
+                    FROM
+                        eth_watch_l1_blocks
+                    ORDER BY
+                        number DESC
+                    LIMIT
+                        $1
+                )
+            "#,
+            count as i64
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{L1BlockNumber, H256};
+
+    use crate::ConnectionPool;
+
+    #[tokio::test]
+    async fn manipulating_l1_block_checkpoints() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        let mut dal = conn.eth_watch_dal();
+        assert!(dal.get_l1_blocks().await.unwrap().is_empty());
+
+        for number in 1..=5 {
+            dal.save_l1_block(L1BlockNumber(number), H256::repeat_byte(number as u8))
+                .await
+                .unwrap();
+        }
+        // Overwriting a checkpoint should replace its hash.
+        dal.save_l1_block(L1BlockNumber(5), H256::repeat_byte(0xff))
+            .await
+            .unwrap();
+        let blocks = dal.get_l1_blocks().await.unwrap();
+        assert_eq!(blocks.len(), 5);
+        assert_eq!(blocks[0], (L1BlockNumber(5), H256::repeat_byte(0xff)));
+        assert_eq!(blocks[4], (L1BlockNumber(1), H256::repeat_byte(1)));
+
+        let deleted = dal.delete_l1_blocks_after(L1BlockNumber(3)).await.unwrap();
+        assert_eq!(deleted, 2);
+        dal.prune_l1_blocks(2).await.unwrap();
+        let blocks = dal.get_l1_blocks().await.unwrap();
+        assert_eq!(
+            blocks,
+            [
+                (L1BlockNumber(3), H256::repeat_byte(3)),
+                (L1BlockNumber(2), H256::repeat_byte(2)),
+            ]
+        );
+    }
+}
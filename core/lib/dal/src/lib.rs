@@ -8,8 +8,9 @@ use crate::{
     basic_witness_input_producer_dal::BasicWitnessInputProducerDal, blocks_dal::BlocksDal,
     blocks_web3_dal::BlocksWeb3Dal, connection::holder::ConnectionHolder,
     consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
-    eth_sender_dal::EthSenderDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
-    factory_deps_dal::FactoryDepsDal, fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
+    eth_sender_dal::EthSenderDal, eth_watch_dal::EthWatchDal, events_dal::EventsDal,
+    events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
+    fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
     fri_proof_compressor_dal::FriProofCompressorDal,
     fri_protocol_versions_dal::FriProtocolVersionsDal, fri_prover_dal::FriProverDal,
     fri_scheduler_dependency_tracker_dal::FriSchedulerDependencyTrackerDal,
@@ -21,7 +22,7 @@ use crate::{
     storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
     sync_dal::SyncDal, system_dal::SystemDal, tokens_dal::TokensDal,
     tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal,
+    transactions_web3_dal::TransactionsWeb3Dal, withdrawals_dal::WithdrawalsDal,
 };
 
 #[macro_use]
@@ -33,6 +34,7 @@ pub mod connection;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
 pub mod eth_sender_dal;
+pub mod eth_watch_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
@@ -63,6 +65,7 @@ pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
 pub mod transactions_web3_dal;
+pub mod withdrawals_dal;
 
 #[cfg(test)]
 mod tests;
@@ -149,6 +152,10 @@ impl<'a> StorageProcessor<'a> {
         EthSenderDal { storage: self }
     }
 
+    pub fn eth_watch_dal(&mut self) -> EthWatchDal<'_, 'a> {
+        EthWatchDal { storage: self }
+    }
+
     pub fn events_dal(&mut self) -> EventsDal<'_, 'a> {
         EventsDal { storage: self }
     }
@@ -248,4 +255,8 @@ impl<'a> StorageProcessor<'a> {
     pub fn snapshot_recovery_dal(&mut self) -> SnapshotRecoveryDal<'_, 'a> {
         SnapshotRecoveryDal { storage: self }
     }
+
+    pub fn withdrawals_dal(&mut self) -> WithdrawalsDal<'_, 'a> {
+        WithdrawalsDal { storage: self }
+    }
 }
@@ -10,4 +10,5 @@ pub mod storage_sync;
 pub mod storage_token;
 pub mod storage_transaction;
 pub mod storage_verification_request;
+pub mod storage_withdrawal;
 pub mod storage_witness_job_info;
@@ -21,6 +21,7 @@ pub struct StorageProtocolVersion {
     pub verifier_address: Vec<u8>,
     pub created_at: NaiveDateTime,
     pub upgrade_tx_hash: Option<Vec<u8>>,
+    pub l1_block_number: Option<i32>,
 }
 
 pub(crate) fn protocol_version_from_storage(
@@ -17,9 +17,10 @@ use zksync_types::{
     transaction_request::PaymasterParams,
     vm_trace::Call,
     web3::types::U64,
-    Address, Bytes, Execute, ExecuteTransactionCommon, L1TxCommonData, L2ChainId, L2TxCommonData,
-    Nonce, PackedEthSignature, PriorityOpId, Transaction, EIP_1559_TX_TYPE, EIP_2930_TX_TYPE,
-    EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    Address, Bytes, Execute, ExecuteTransactionCommon, L1BatchNumber, L1TxCommonData, L2ChainId,
+    L2TxCommonData, MiniblockNumber, Nonce, PackedEthSignature, PriorityOpId, Transaction,
+    EIP_1559_TX_TYPE, EIP_2930_TX_TYPE, EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE,
+    PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use zksync_utils::{bigdecimal_to_u256, h256_to_account_address};
 
@@ -70,6 +71,9 @@ pub struct StorageTransaction {
 
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+
+    pub l1_tx_hash: Option<Vec<u8>>,
+    pub l1_tx_expiration_timestamp: Option<i64>,
 }
 
 impl From<StorageTransaction> for L1TxCommonData {
@@ -141,8 +145,11 @@ impl From<StorageTransaction> for L1TxCommonData {
                 .gas_per_pubdata_limit
                 .map(bigdecimal_to_u256)
                 .unwrap_or_else(|| U256::from(1u32)),
-            deadline_block: 0,
-            eth_hash: Default::default(),
+            deadline_block: tx.l1_tx_expiration_timestamp.unwrap_or_default() as u64,
+            eth_hash: tx
+                .l1_tx_hash
+                .map(|hash| H256::from_slice(&hash))
+                .unwrap_or_default(),
             eth_block: tx.l1_block_number.unwrap_or_default() as u64,
             canonical_tx_hash,
         }
@@ -556,6 +563,70 @@ impl From<StorageTransactionDetails> for TransactionDetails {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct StoragePriorityOpStatus {
+    pub priority_op_id: i64,
+    pub hash: Vec<u8>,
+    pub l1_tx_hash: Option<Vec<u8>>,
+    pub l1_tx_expiration_timestamp: Option<i64>,
+    pub l1_block_number: Option<i32>,
+    pub received_at: NaiveDateTime,
+    pub error: Option<String>,
+    pub miniblock_number: Option<i64>,
+    pub l1_batch_number: Option<i64>,
+    pub eth_commit_tx_hash: Option<String>,
+    pub eth_prove_tx_hash: Option<String>,
+    pub eth_execute_tx_hash: Option<String>,
+}
+
+impl StoragePriorityOpStatus {
+    fn stage(&self) -> api::PriorityOpStage {
+        if self.eth_execute_tx_hash.is_some() {
+            api::PriorityOpStage::Executed
+        } else if self.eth_prove_tx_hash.is_some() {
+            api::PriorityOpStage::Proven
+        } else if self.eth_commit_tx_hash.is_some() {
+            api::PriorityOpStage::Committed
+        } else if self.miniblock_number.is_some() {
+            api::PriorityOpStage::Included
+        } else {
+            api::PriorityOpStage::Queued
+        }
+    }
+}
+
+impl From<StoragePriorityOpStatus> for api::PriorityOpStatus {
+    fn from(status: StoragePriorityOpStatus) -> Self {
+        let stage = status.stage();
+        let parse_eth_tx_hash =
+            |hash: Option<String>| hash.map(|hash| H256::from_str(&hash).unwrap());
+
+        api::PriorityOpStatus {
+            serial_id: PriorityOpId(status.priority_op_id as u64),
+            l1_tx_hash: status.l1_tx_hash.map(|hash| H256::from_slice(&hash)),
+            l2_tx_hash: H256::from_slice(&status.hash),
+            stage,
+            failure_reason: status.error,
+            received_at: DateTime::<Utc>::from_naive_utc_and_offset(status.received_at, Utc),
+            l1_block_number: status
+                .l1_block_number
+                .map(|number| U64::from(number as u64)),
+            expiration_timestamp: status
+                .l1_tx_expiration_timestamp
+                .map(|timestamp| U64::from(timestamp as u64)),
+            miniblock_number: status
+                .miniblock_number
+                .map(|number| MiniblockNumber(number as u32)),
+            l1_batch_number: status
+                .l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
+            eth_commit_tx_hash: parse_eth_tx_hash(status.eth_commit_tx_hash),
+            eth_prove_tx_hash: parse_eth_tx_hash(status.eth_prove_tx_hash),
+            eth_execute_tx_hash: parse_eth_tx_hash(status.eth_execute_tx_hash),
+        }
+    }
+}
+
 pub fn web3_transaction_select_sql() -> &'static str {
     r#"
          transactions.hash as tx_hash,
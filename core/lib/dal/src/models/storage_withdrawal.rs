@@ -0,0 +1,51 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use zksync_types::{
+    withdrawals::{StoredWithdrawal, Withdrawal},
+    Address, L1BatchNumber, H256,
+};
+
+#[derive(Debug, Clone)]
+pub struct StorageWithdrawal {
+    pub id: i64,
+    pub l1_batch_number: i64,
+    pub l2_message_index: i32,
+    pub l1_batch_tx_index: i32,
+    pub tx_hash: Vec<u8>,
+    pub sender: Vec<u8>,
+    pub message: Vec<u8>,
+    pub status: String,
+    pub attempts: i32,
+    pub finalization_tx_hash: Option<Vec<u8>>,
+    pub finalization_tx_nonce: Option<i64>,
+    pub finalization_tx_sent_at: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+}
+
+impl From<StorageWithdrawal> for StoredWithdrawal {
+    fn from(withdrawal: StorageWithdrawal) -> Self {
+        Self {
+            id: withdrawal.id as u64,
+            withdrawal: Withdrawal {
+                l1_batch_number: L1BatchNumber(withdrawal.l1_batch_number as u32),
+                l2_message_index: withdrawal.l2_message_index as u32,
+                l1_batch_tx_index: withdrawal.l1_batch_tx_index as u16,
+                tx_hash: H256::from_slice(&withdrawal.tx_hash),
+                sender: Address::from_slice(&withdrawal.sender),
+                message: withdrawal.message,
+            },
+            status: withdrawal
+                .status
+                .parse()
+                .expect("Incorrect withdrawal status in DB"),
+            attempts: withdrawal.attempts as u32,
+            finalization_tx_hash: withdrawal
+                .finalization_tx_hash
+                .map(|hash| H256::from_slice(&hash)),
+            finalization_tx_nonce: withdrawal.finalization_tx_nonce.map(|nonce| nonce as u64),
+            finalization_tx_sent_at: withdrawal
+                .finalization_tx_sent_at
+                .map(|sent_at| DateTime::from_naive_utc_and_offset(sent_at, Utc)),
+            last_error: withdrawal.last_error,
+        }
+    }
+}
@@ -23,9 +23,12 @@ enum ProofGenerationJobStatus {
 }
 
 impl ProofGenerationDal<'_, '_> {
+    /// Picks the next L1 batch to be proven and marks it as handed out to the specified client.
+    /// `client_id` is `None` if the proof data handler API is unauthenticated.
     pub async fn get_next_block_to_be_proven(
         &mut self,
         processing_timeout: Duration,
+        client_id: Option<&str>,
     ) -> Option<L1BatchNumber> {
         let processing_timeout = pg_interval_from_duration(processing_timeout);
         let result: Option<L1BatchNumber> = sqlx::query!(
@@ -34,7 +37,8 @@ impl ProofGenerationDal<'_, '_> {
             SET
                 status = 'picked_by_prover',
                 updated_at = NOW(),
-                prover_taken_at = NOW()
+                prover_taken_at = NOW(),
+                picked_by_client = $2
             WHERE
                 l1_batch_number = (
                     SELECT
@@ -58,6 +62,7 @@ impl ProofGenerationDal<'_, '_> {
                 proof_generation_details.l1_batch_number
             "#,
             &processing_timeout,
+            client_id,
         )
         .fetch_optional(self.storage.conn())
         .await
@@ -71,6 +76,7 @@ impl ProofGenerationDal<'_, '_> {
         &mut self,
         block_number: L1BatchNumber,
         proof_blob_url: &str,
+        client_id: Option<&str>,
     ) -> Result<(), SqlxError> {
         sqlx::query!(
             r#"
@@ -78,12 +84,14 @@ impl ProofGenerationDal<'_, '_> {
             SET
                 status = 'generated',
                 proof_blob_url = $1,
-                updated_at = NOW()
+                updated_at = NOW(),
+                submitted_by_client = $3
             WHERE
                 l1_batch_number = $2
             "#,
             proof_blob_url,
             block_number.0 as i64,
+            client_id,
         )
         .execute(self.storage.conn())
         .await?
@@ -117,18 +125,21 @@ impl ProofGenerationDal<'_, '_> {
     pub async fn mark_proof_generation_job_as_skipped(
         &mut self,
         block_number: L1BatchNumber,
+        client_id: Option<&str>,
     ) -> Result<(), SqlxError> {
         sqlx::query!(
             r#"
             UPDATE proof_generation_details
             SET
                 status = $1,
-                updated_at = NOW()
+                updated_at = NOW(),
+                submitted_by_client = $3
             WHERE
                 l1_batch_number = $2
             "#,
             ProofGenerationJobStatus::Skipped.to_string(),
             block_number.0 as i64,
+            client_id,
         )
         .execute(self.storage.conn())
         .await?
@@ -183,4 +194,25 @@ impl ProofGenerationDal<'_, '_> {
 
         result
     }
+
+    /// Returns the client that the specified L1 batch was last handed out to, if any.
+    pub async fn get_batch_client(
+        &mut self,
+        block_number: L1BatchNumber,
+    ) -> Result<Option<String>, SqlxError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                picked_by_client
+            FROM
+                proof_generation_details
+            WHERE
+                l1_batch_number = $1
+            "#,
+            block_number.0 as i64,
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+        Ok(row.and_then(|row| row.picked_by_client))
+    }
 }
@@ -3,7 +3,7 @@ use std::convert::TryInto;
 use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes};
 use zksync_types::{
     protocol_version::{L1VerifierConfig, ProtocolUpgradeTx, ProtocolVersion, VerifierParams},
-    Address, ProtocolVersionId, H256,
+    Address, L1BlockNumber, ProtocolVersionId, H256,
 };
 
 use crate::{
@@ -98,6 +98,69 @@ impl ProtocolVersionsDal<'_, '_> {
         db_transaction.commit().await.unwrap();
     }
 
+    /// Records the L1 block in which the upgrade for the given protocol version was proposed.
+    pub async fn save_l1_block_number(
+        &mut self,
+        id: ProtocolVersionId,
+        l1_block_number: L1BlockNumber,
+    ) {
+        sqlx::query!(
+            r#"
+            UPDATE protocol_versions
+            SET
+                l1_block_number = $2
+            WHERE
+                id = $1
+            "#,
+            id as i32,
+            l1_block_number.0 as i32
+        )
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Removes protocol versions proposed in L1 blocks after `last_canonical_block`
+    /// that are not used by any miniblock or L1 batch yet. Returns IDs of removed versions.
+    pub async fn delete_unused_versions_after_l1_block(
+        &mut self,
+        last_canonical_block: L1BlockNumber,
+    ) -> Vec<ProtocolVersionId> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM protocol_versions
+            WHERE
+                l1_block_number > $1
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        miniblocks
+                    WHERE
+                        miniblocks.protocol_version = protocol_versions.id
+                )
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        l1_batches
+                    WHERE
+                        l1_batches.protocol_version = protocol_versions.id
+                )
+            RETURNING
+                id
+            "#,
+            last_canonical_block.0 as i32
+        )
+        .fetch_all(self.storage.conn())
+        .await
+        .unwrap();
+
+        rows.into_iter()
+            .map(|row| (row.id as u16).try_into().unwrap())
+            .collect()
+    }
+
     pub async fn base_system_contracts_by_timestamp(
         &mut self,
         current_timestamp: u64,
@@ -547,6 +547,50 @@ impl StorageLogsDal<'_, '_> {
             .collect())
     }
 
+    /// Returns storage logs for the specified miniblock range grouped by the transaction hash.
+    /// Logs for each transaction are ordered by their operation number.
+    pub async fn get_storage_logs_by_tx_in_miniblocks(
+        &mut self,
+        miniblock_numbers: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<HashMap<H256, Vec<StorageLog>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                address,
+                key,
+                value
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                operation_number
+            "#,
+            miniblock_numbers.start().0 as i64,
+            miniblock_numbers.end().0 as i64
+        )
+        .instrument("get_storage_logs_by_tx_in_miniblocks")
+        .with_arg("miniblock_numbers", &miniblock_numbers)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut logs_by_tx = HashMap::<_, Vec<_>>::new();
+        for row in rows {
+            let key = StorageKey::new(
+                AccountTreeId::new(Address::from_slice(&row.address)),
+                H256::from_slice(&row.key),
+            );
+            let log = StorageLog::new_write_log(key, H256::from_slice(&row.value));
+            logs_by_tx
+                .entry(H256::from_slice(&row.tx_hash))
+                .or_default()
+                .push(log);
+        }
+        Ok(logs_by_tx)
+    }
+
     /// Retrieves all storage log entries for testing purposes.
     pub async fn dump_all_storage_logs_for_tests(&mut self) -> Vec<DbStorageLog> {
         let rows = sqlx::query!(
@@ -865,6 +909,52 @@ mod tests {
         test_rollback(&mut conn, first_key, second_key).await;
     }
 
+    #[tokio::test]
+    async fn getting_storage_logs_by_tx_in_miniblocks() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let first_key = StorageKey::new(account, H256::zero());
+        let second_key = StorageKey::new(account, H256::from_low_u64_be(1));
+        let log = StorageLog::new_write_log(first_key, H256::repeat_byte(1));
+        let other_log = StorageLog::new_write_log(second_key, H256::repeat_byte(2));
+        insert_miniblock(&mut conn, 1, vec![log, other_log]).await;
+        let tx_log = StorageLog::new_write_log(first_key, H256::repeat_byte(3));
+        conn.storage_logs_dal()
+            .append_storage_logs(MiniblockNumber(1), &[(H256::repeat_byte(1), vec![tx_log])])
+            .await;
+        let next_log = StorageLog::new_write_log(second_key, H256::repeat_byte(4));
+        insert_miniblock(&mut conn, 2, vec![next_log]).await;
+
+        let logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_by_tx_in_miniblocks(MiniblockNumber(1)..=MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[&H256::zero()], [log, other_log]);
+        assert_eq!(logs[&H256::repeat_byte(1)], [tx_log]);
+
+        let logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_by_tx_in_miniblocks(MiniblockNumber(2)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[&H256::zero()], [next_log]);
+
+        let logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_by_tx_in_miniblocks(MiniblockNumber(1)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(logs[&H256::zero()], [log, other_log, next_log]);
+    }
+
     async fn test_rollback(
         conn: &mut StorageProcessor<'_>,
         key: StorageKey,
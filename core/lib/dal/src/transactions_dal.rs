@@ -45,6 +45,16 @@ impl fmt::Display for L2TxSubmissionResult {
     }
 }
 
+/// Gas usage of an executed transaction as persisted by the state keeper.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxGasUsage {
+    /// Gas refunded to the transaction initiator.
+    pub refunded_gas: u64,
+    /// Gas used during transaction execution according to its execution metrics. May be missing
+    /// for transactions without execution metrics.
+    pub gas_used: Option<u64>,
+}
+
 #[derive(Debug)]
 pub struct TransactionsDal<'c, 'a> {
     pub(crate) storage: &'c mut StorageProcessor<'a>,
@@ -101,6 +111,8 @@ impl TransactionsDal<'_, '_> {
                         l1_tx_mint,
                         l1_tx_refund_recipient,
                         received_at,
+                        l1_tx_hash,
+                        l1_tx_expiration_timestamp,
                         created_at,
                         updated_at
                     )
@@ -125,6 +137,8 @@ impl TransactionsDal<'_, '_> {
                         $16,
                         $17,
                         $18,
+                        $19,
+                        NULLIF($20, 0),
                         NOW(),
                         NOW()
                     )
@@ -148,6 +162,8 @@ impl TransactionsDal<'_, '_> {
                 to_mint,
                 refund_recipient,
                 received_at,
+                tx.common_data.eth_hash.as_bytes(),
+                tx.common_data.deadline_block as i64,
             )
             .fetch_optional(self.storage.conn())
             .await
@@ -876,6 +892,86 @@ impl TransactionsDal<'_, '_> {
         Ok(rows.len())
     }
 
+    /// Returns hashes of priority operations and protocol upgrade transactions received in L1 blocks
+    /// after `last_canonical_block` that are already executed or, if `include_mempool` is set, loaded to the mempool.
+    /// Such transactions cannot be safely rolled back after an L1 reorg.
+    pub async fn get_processed_l1_txs_after(
+        &mut self,
+        last_canonical_block: L1BlockNumber,
+        include_mempool: bool,
+    ) -> sqlx::Result<Vec<H256>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash
+            FROM
+                transactions
+            WHERE
+                l1_block_number > $1
+                AND (
+                    is_priority = TRUE
+                    OR upgrade_id IS NOT NULL
+                )
+                AND (
+                    miniblock_number IS NOT NULL
+                    OR (
+                        $2
+                        AND in_mempool = TRUE
+                    )
+                )
+            ORDER BY
+                l1_block_number,
+                priority_op_id
+            "#,
+            last_canonical_block.0 as i32,
+            include_mempool
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| H256::from_slice(&row.hash))
+            .collect())
+    }
+
+    /// Removes priority operations and protocol upgrade transactions received in L1 blocks
+    /// after `last_canonical_block` that are not executed yet. Upgrade transactions still referenced
+    /// by a protocol version are kept. Used to roll back the effects of an L1 reorg; callers should check
+    /// [`Self::get_processed_l1_txs_after()`] first and evict removed transactions from the mempool.
+    pub async fn remove_unexecuted_l1_txs_after(
+        &mut self,
+        last_canonical_block: L1BlockNumber,
+    ) -> sqlx::Result<usize> {
+        let rows = sqlx::query!(
+            r#"
+            DELETE FROM transactions
+            WHERE
+                l1_block_number > $1
+                AND miniblock_number IS NULL
+                AND (
+                    is_priority = TRUE
+                    OR upgrade_id IS NOT NULL
+                )
+                AND hash NOT IN (
+                    SELECT
+                        upgrade_tx_hash
+                    FROM
+                        protocol_versions
+                    WHERE
+                        upgrade_tx_hash IS NOT NULL
+                )
+            RETURNING
+                hash
+            "#,
+            last_canonical_block.0 as i32
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.len())
+    }
+
     /// Fetches new updates for mempool. Returns new transactions and current nonces for related accounts;
     /// the latter are only used to bootstrap mempool for given account.
     pub async fn sync_mempool(
@@ -1052,6 +1148,73 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Returns the serial ID and the receipt timestamp (in seconds since epoch) of the first priority operation
+    /// not included into an L1 batch up to and including `last_executed_l1_batch`.
+    pub async fn get_first_unexecuted_priority_op(
+        &mut self,
+        last_executed_l1_batch: L1BatchNumber,
+    ) -> sqlx::Result<Option<(PriorityOpId, u64)>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!",
+                received_at
+            FROM
+                transactions
+            WHERE
+                priority_op_id IS NOT NULL
+                AND (
+                    l1_batch_number IS NULL
+                    OR l1_batch_number > $1
+                )
+            ORDER BY
+                priority_op_id
+            LIMIT
+                1
+            "#,
+            last_executed_l1_batch.0 as i64
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| {
+            (
+                PriorityOpId(row.priority_op_id as u64),
+                row.received_at.timestamp() as u64,
+            )
+        }))
+    }
+
+    /// Returns the number of priority operations not included into an L1 batch up to and including
+    /// `last_executed_l1_batch` with the expiration timestamp before `timestamp`.
+    pub async fn count_expired_unexecuted_priority_ops(
+        &mut self,
+        last_executed_l1_batch: L1BatchNumber,
+        timestamp: u64,
+    ) -> sqlx::Result<usize> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                priority_op_id IS NOT NULL
+                AND (
+                    l1_batch_number IS NULL
+                    OR l1_batch_number > $1
+                )
+                AND l1_tx_expiration_timestamp < $2
+            "#,
+            last_executed_l1_batch.0 as i64,
+            timestamp as i64
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.count as usize)
+    }
+
     pub async fn insert_trace(&mut self, hash: H256, trace: VmExecutionTrace) {
         {
             sqlx::query!(
@@ -1308,6 +1471,44 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Returns gas usage for the specified executed transactions. Transactions that are not executed
+    /// are not included into the returned map.
+    pub async fn get_gas_usage_by_hashes(
+        &mut self,
+        hashes: &[H256],
+    ) -> sqlx::Result<HashMap<H256, TxGasUsage>> {
+        let hashes: Vec<_> = hashes.iter().map(H256::as_bytes).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                refunded_gas,
+                (execution_info ->> 'gas_used')::BIGINT AS "gas_used?"
+            FROM
+                transactions
+            WHERE
+                hash = ANY ($1)
+                AND miniblock_number IS NOT NULL
+            "#,
+            &hashes as &[&[u8]]
+        )
+        .instrument("get_gas_usage_by_hashes")
+        .with_arg("hashes.len", &hashes.len())
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let gas_usage = TxGasUsage {
+                    refunded_gas: row.refunded_gas as u64,
+                    gas_used: row.gas_used.map(|gas| gas as u64),
+                };
+                (H256::from_slice(&row.hash), gas_usage)
+            })
+            .collect())
+    }
+
     pub async fn get_call_trace(&mut self, tx_hash: H256) -> sqlx::Result<Option<Call>> {
         Ok(sqlx::query_as!(
             CallTrace,
@@ -1,7 +1,8 @@
 use sqlx::types::chrono::NaiveDateTime;
 use zksync_types::{
-    api, api::TransactionReceipt, Address, L2ChainId, MiniblockNumber, Transaction,
-    ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+    api, api::TransactionReceipt, Address, L1BatchNumber, L2ChainId, MiniblockNumber, PriorityOpId,
+    Transaction, ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256,
+    U256,
 };
 
 use crate::{
@@ -9,8 +10,8 @@ use crate::{
     models::{
         storage_block::{bind_block_where_sql_params, web3_block_where_sql},
         storage_transaction::{
-            extract_web3_transaction, web3_transaction_select_sql, StorageTransaction,
-            StorageTransactionDetails, StorageTransactionReceipt,
+            extract_web3_transaction, web3_transaction_select_sql, StoragePriorityOpStatus,
+            StorageTransaction, StorageTransactionDetails, StorageTransactionReceipt,
         },
     },
     SqlxError, StorageProcessor,
@@ -329,6 +330,169 @@ impl TransactionsWeb3Dal<'_, '_> {
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
+
+    /// Returns the serial ID of the first priority operation submitted by the specified L1 transaction.
+    pub async fn get_priority_op_id_by_l1_tx_hash(
+        &mut self,
+        l1_tx_hash: H256,
+    ) -> sqlx::Result<Option<PriorityOpId>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                priority_op_id AS "priority_op_id!"
+            FROM
+                transactions
+            WHERE
+                l1_tx_hash = $1
+                AND priority_op_id IS NOT NULL
+            ORDER BY
+                priority_op_id
+            LIMIT
+                1
+            "#,
+            l1_tx_hash.as_bytes()
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| PriorityOpId(row.priority_op_id as u64)))
+    }
+
+    /// Returns statuses of up to `limit` priority operations starting from the specified serial ID (inclusive),
+    /// ordered by serial ID.
+    pub async fn get_priority_op_statuses(
+        &mut self,
+        from_id: PriorityOpId,
+        limit: usize,
+    ) -> sqlx::Result<Vec<api::PriorityOpStatus>> {
+        let rows = sqlx::query_as!(
+            StoragePriorityOpStatus,
+            r#"
+            SELECT
+                transactions.priority_op_id AS "priority_op_id!",
+                transactions.hash,
+                transactions.l1_tx_hash,
+                transactions.l1_tx_expiration_timestamp,
+                transactions.l1_block_number,
+                transactions.received_at,
+                transactions.error,
+                transactions.miniblock_number,
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                commit_tx.tx_hash AS "eth_commit_tx_hash?",
+                prove_tx.tx_hash AS "eth_prove_tx_hash?",
+                execute_tx.tx_hash AS "eth_execute_tx_hash?"
+            FROM
+                transactions
+                LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+                LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number
+                LEFT JOIN eth_txs_history AS commit_tx ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS prove_tx ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS execute_tx ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                transactions.priority_op_id >= $1
+            ORDER BY
+                transactions.priority_op_id
+            LIMIT
+                $2
+            "#,
+            from_id.0 as i64,
+            limit as i64
+        )
+        .instrument("get_priority_op_statuses")
+        .with_arg("from_id", &from_id)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns statuses of up to `limit` priority operations starting from the specified serial ID (inclusive)
+    /// which could have changed since the previous check: operations with serial ID `new_ops_from_id` or greater,
+    /// operations included into miniblocks after `miniblocks_after`, and operations from the specified L1 batches.
+    /// The statuses are ordered by serial ID.
+    pub async fn get_updated_priority_op_statuses(
+        &mut self,
+        from_id: PriorityOpId,
+        new_ops_from_id: PriorityOpId,
+        miniblocks_after: Option<MiniblockNumber>,
+        l1_batches: &[L1BatchNumber],
+        limit: usize,
+    ) -> sqlx::Result<Vec<api::PriorityOpStatus>> {
+        let miniblocks_after = miniblocks_after.map_or(-1, |number| i64::from(number.0));
+        let l1_batches: Vec<_> = l1_batches
+            .iter()
+            .map(|number| i64::from(number.0))
+            .collect();
+        let rows = sqlx::query_as!(
+            StoragePriorityOpStatus,
+            r#"
+            SELECT
+                transactions.priority_op_id AS "priority_op_id!",
+                transactions.hash,
+                transactions.l1_tx_hash,
+                transactions.l1_tx_expiration_timestamp,
+                transactions.l1_block_number,
+                transactions.received_at,
+                transactions.error,
+                transactions.miniblock_number,
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                commit_tx.tx_hash AS "eth_commit_tx_hash?",
+                prove_tx.tx_hash AS "eth_prove_tx_hash?",
+                execute_tx.tx_hash AS "eth_execute_tx_hash?"
+            FROM
+                transactions
+                LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+                LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number
+                LEFT JOIN eth_txs_history AS commit_tx ON (
+                    l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id
+                    AND commit_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS prove_tx ON (
+                    l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id
+                    AND prove_tx.confirmed_at IS NOT NULL
+                )
+                LEFT JOIN eth_txs_history AS execute_tx ON (
+                    l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id
+                    AND execute_tx.confirmed_at IS NOT NULL
+                )
+            WHERE
+                transactions.priority_op_id >= $1
+                AND (
+                    transactions.priority_op_id >= $2
+                    OR transactions.miniblock_number > $3
+                    OR miniblocks.l1_batch_number = ANY ($4)
+                )
+            ORDER BY
+                transactions.priority_op_id
+            LIMIT
+                $5
+            "#,
+            from_id.0 as i64,
+            new_ops_from_id.0 as i64,
+            miniblocks_after,
+            &l1_batches,
+            limit as i64
+        )
+        .instrument("get_updated_priority_op_statuses")
+        .with_arg("from_id", &from_id)
+        .with_arg("new_ops_from_id", &new_ops_from_id)
+        .with_arg("miniblocks_after", &miniblocks_after)
+        .with_arg("l1_batches.len", &l1_batches.len())
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
 }
 
 #[cfg(test)]
@@ -336,13 +500,18 @@ mod tests {
     use std::collections::HashMap;
 
     use zksync_types::{
-        block::MiniblockHasher, fee::TransactionExecutionMetrics, l2::L2Tx, Nonce, ProtocolVersion,
-        ProtocolVersionId,
+        block::{L1BatchHeader, MiniblockHasher},
+        fee::TransactionExecutionMetrics,
+        l2::L2Tx,
+        tx::{tx_execution_info::TxExecutionStatus, ExecutionMetrics, TransactionExecutionResult},
+        L1BatchNumber, L1BlockNumber, Nonce, ProtocolVersion, ProtocolVersionId,
     };
 
     use super::*;
     use crate::{
-        tests::{create_miniblock_header, mock_execution_result, mock_l2_transaction},
+        tests::{
+            create_miniblock_header, mock_execution_result, mock_l1_execute, mock_l2_transaction,
+        },
         ConnectionPool,
     };
 
@@ -593,4 +762,160 @@ mod tests {
             .unwrap();
         assert_eq!(next_nonce, 2.into());
     }
+
+    #[tokio::test]
+    async fn getting_priority_op_statuses() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let l1_tx = mock_l1_execute();
+        let l1_tx_hash = l1_tx.common_data.eth_hash;
+        conn.transactions_dal()
+            .insert_transaction_l1(l1_tx.clone(), L1BlockNumber(1))
+            .await;
+
+        let serial_id = conn
+            .transactions_web3_dal()
+            .get_priority_op_id_by_l1_tx_hash(l1_tx_hash)
+            .await
+            .unwrap();
+        assert_eq!(serial_id, Some(PriorityOpId(1)));
+        let serial_id = conn
+            .transactions_web3_dal()
+            .get_priority_op_id_by_l1_tx_hash(H256::zero())
+            .await
+            .unwrap();
+        assert_eq!(serial_id, None);
+
+        let statuses = conn
+            .transactions_web3_dal()
+            .get_priority_op_statuses(PriorityOpId(0), 10)
+            .await
+            .unwrap();
+        assert_eq!(statuses.len(), 1);
+        let status = &statuses[0];
+        assert_eq!(status.serial_id, PriorityOpId(1));
+        assert_eq!(status.l1_tx_hash, Some(l1_tx_hash));
+        assert_eq!(status.l2_tx_hash, l1_tx.hash());
+        assert_eq!(status.stage, api::PriorityOpStage::Queued);
+        assert_eq!(status.l1_block_number, Some(1.into()));
+        assert_eq!(status.expiration_timestamp, Some(100_000.into()));
+        assert_eq!(status.failure_reason, None);
+
+        let updated_statuses = conn
+            .transactions_web3_dal()
+            .get_updated_priority_op_statuses(PriorityOpId(0), PriorityOpId(1), None, &[], 10)
+            .await
+            .unwrap();
+        assert_eq!(updated_statuses, statuses);
+        let updated_statuses = conn
+            .transactions_web3_dal()
+            .get_updated_priority_op_statuses(PriorityOpId(0), PriorityOpId(2), None, &[], 10)
+            .await
+            .unwrap();
+        assert!(updated_statuses.is_empty());
+
+        let first_unexecuted_op = conn
+            .transactions_dal()
+            .get_first_unexecuted_priority_op(L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(first_unexecuted_op, Some((PriorityOpId(1), 0)));
+        for (timestamp, expected_count) in [(100_000, 0), (100_001, 1)] {
+            let expired_count = conn
+                .transactions_dal()
+                .count_expired_unexecuted_priority_ops(L1BatchNumber(0), timestamp)
+                .await
+                .unwrap();
+            assert_eq!(expired_count, expected_count);
+        }
+
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(1))
+            .await
+            .unwrap();
+        let tx_result = TransactionExecutionResult {
+            hash: l1_tx.hash(),
+            transaction: l1_tx.into(),
+            execution_info: ExecutionMetrics::default(),
+            execution_status: TxExecutionStatus::Failure,
+            refunded_gas: 0,
+            operator_suggested_refund: 0,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+        };
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &[tx_result], U256::from(1))
+            .await;
+
+        let statuses = conn
+            .transactions_web3_dal()
+            .get_priority_op_statuses(PriorityOpId(1), 10)
+            .await
+            .unwrap();
+        assert_eq!(statuses.len(), 1);
+        let status = &statuses[0];
+        assert_eq!(status.stage, api::PriorityOpStage::Included);
+        assert_eq!(status.miniblock_number, Some(MiniblockNumber(1)));
+        assert_eq!(status.l1_batch_number, None);
+        assert!(status.failure_reason.is_some());
+
+        for (miniblocks_after, expected_len) in [(None, 1), (Some(0), 1), (Some(1), 0)] {
+            let updated_statuses = conn
+                .transactions_web3_dal()
+                .get_updated_priority_op_statuses(
+                    PriorityOpId(1),
+                    PriorityOpId(2),
+                    miniblocks_after.map(MiniblockNumber),
+                    &[],
+                    10,
+                )
+                .await
+                .unwrap();
+            assert_eq!(updated_statuses.len(), expected_len, "{miniblocks_after:?}");
+        }
+
+        let l1_batch_header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            0,
+            Default::default(),
+            ProtocolVersionId::default(),
+        );
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&l1_batch_header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        for (l1_batch_number, expected_len) in [(1, 1), (2, 0)] {
+            let updated_statuses = conn
+                .transactions_web3_dal()
+                .get_updated_priority_op_statuses(
+                    PriorityOpId(1),
+                    PriorityOpId(2),
+                    Some(MiniblockNumber(1)),
+                    &[L1BatchNumber(l1_batch_number)],
+                    10,
+                )
+                .await
+                .unwrap();
+            assert_eq!(updated_statuses.len(), expected_len, "{l1_batch_number}");
+            if let Some(status) = updated_statuses.first() {
+                assert_eq!(status.l1_batch_number, Some(L1BatchNumber(1)));
+            }
+        }
+
+        let statuses = conn
+            .transactions_web3_dal()
+            .get_priority_op_statuses(PriorityOpId(2), 10)
+            .await
+            .unwrap();
+        assert!(statuses.is_empty());
+    }
 }
@@ -0,0 +1,369 @@
+use zksync_types::{
+    withdrawals::{StoredWithdrawal, Withdrawal, WithdrawalStatus},
+    L1BatchNumber, H256,
+};
+
+use crate::{models::storage_withdrawal::StorageWithdrawal, StorageProcessor};
+
+/// Tracks L2→L1 withdrawals and the state of their finalization on L1.
+#[derive(Debug)]
+pub struct WithdrawalsDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl WithdrawalsDal<'_, '_> {
+    /// Saves withdrawals initiated in the specified L1 batch and marks the batch as indexed.
+    /// Withdrawals that are already stored are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the withdrawals belongs to another L1 batch.
+    pub async fn insert_l1_batch_withdrawals(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        withdrawals: &[Withdrawal],
+    ) -> anyhow::Result<()> {
+        for withdrawal in withdrawals {
+            anyhow::ensure!(
+                withdrawal.l1_batch_number == l1_batch_number,
+                "withdrawal with message index {} belongs to L1 batch #{} rather than #{l1_batch_number}",
+                withdrawal.l2_message_index,
+                withdrawal.l1_batch_number
+            );
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        for withdrawal in withdrawals {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    withdrawals (
+                        l1_batch_number,
+                        l2_message_index,
+                        l1_batch_tx_index,
+                        tx_hash,
+                        sender,
+                        message,
+                        status,
+                        created_at,
+                        updated_at
+                    )
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, 'pending', NOW(), NOW())
+                ON CONFLICT (l1_batch_number, l2_message_index) DO NOTHING
+                "#,
+                l1_batch_number.0 as i64,
+                withdrawal.l2_message_index as i32,
+                i32::from(withdrawal.l1_batch_tx_index),
+                withdrawal.tx_hash.as_bytes(),
+                withdrawal.sender.as_bytes(),
+                &withdrawal.message
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                withdrawals_indexed_l1_batches (l1_batch_number, created_at)
+            VALUES
+                ($1, NOW())
+            ON CONFLICT (l1_batch_number) DO NOTHING
+            "#,
+            l1_batch_number.0 as i64
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    /// Returns the number of the latest L1 batch for which withdrawals were indexed.
+    pub async fn get_last_indexed_l1_batch(&mut self) -> sqlx::Result<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(l1_batch_number) AS "number"
+            FROM
+                withdrawals_indexed_l1_batches
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns up to `limit` withdrawals with the specified status, oldest first.
+    pub async fn get_withdrawals_with_status(
+        &mut self,
+        status: WithdrawalStatus,
+        limit: usize,
+    ) -> sqlx::Result<Vec<StoredWithdrawal>> {
+        let withdrawals = sqlx::query_as!(
+            StorageWithdrawal,
+            r#"
+            SELECT
+                id,
+                l1_batch_number,
+                l2_message_index,
+                l1_batch_tx_index,
+                tx_hash,
+                sender,
+                message,
+                status,
+                attempts,
+                finalization_tx_hash,
+                finalization_tx_nonce,
+                finalization_tx_sent_at,
+                last_error
+            FROM
+                withdrawals
+            WHERE
+                status = $1
+            ORDER BY
+                l1_batch_number,
+                l2_message_index
+            LIMIT
+                $2
+            "#,
+            status.as_str(),
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(withdrawals.into_iter().map(Into::into).collect())
+    }
+
+    /// Marks withdrawals as included into the finalization transaction with the specified hash and nonce.
+    /// Also used when the transaction is resent with bumped fees, in which case the sending time is updated.
+    pub async fn mark_withdrawals_as_sent(
+        &mut self,
+        ids: &[u64],
+        finalization_tx_hash: H256,
+        finalization_tx_nonce: u64,
+    ) -> sqlx::Result<()> {
+        let ids: Vec<_> = ids.iter().map(|&id| id as i64).collect();
+        sqlx::query!(
+            r#"
+            UPDATE withdrawals
+            SET
+                status = 'sent',
+                finalization_tx_hash = $2,
+                finalization_tx_nonce = $3,
+                finalization_tx_sent_at = NOW(),
+                updated_at = NOW()
+            WHERE
+                id = ANY ($1)
+            "#,
+            &ids,
+            finalization_tx_hash.as_bytes(),
+            finalization_tx_nonce as i64
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn mark_withdrawals_as_finalized(&mut self, ids: &[u64]) -> sqlx::Result<()> {
+        let ids: Vec<_> = ids.iter().map(|&id| id as i64).collect();
+        sqlx::query!(
+            r#"
+            UPDATE withdrawals
+            SET
+                status = 'finalized',
+                last_error = NULL,
+                updated_at = NOW()
+            WHERE
+                id = ANY ($1)
+            "#,
+            &ids
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Records a failed finalization attempt. Withdrawals are returned to the pending state
+    /// unless they have reached `max_attempts`, in which case they are marked as failed.
+    pub async fn record_failed_attempts(
+        &mut self,
+        ids: &[u64],
+        error: &str,
+        max_attempts: u32,
+    ) -> sqlx::Result<()> {
+        let ids: Vec<_> = ids.iter().map(|&id| id as i64).collect();
+        sqlx::query!(
+            r#"
+            UPDATE withdrawals
+            SET
+                attempts = attempts + 1,
+                last_error = $2,
+                status = CASE
+                    WHEN attempts + 1 >= $3 THEN 'failed'
+                    ELSE 'pending'
+                END,
+                finalization_tx_hash = NULL,
+                finalization_tx_nonce = NULL,
+                finalization_tx_sent_at = NULL,
+                updated_at = NOW()
+            WHERE
+                id = ANY ($1)
+            "#,
+            &ids,
+            error,
+            max_attempts as i32
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{block::L1BatchHeader, Address, ProtocolVersion, ProtocolVersionId};
+
+    use super::*;
+    use crate::ConnectionPool;
+
+    fn mock_withdrawal(l1_batch_number: L1BatchNumber, l2_message_index: u32) -> Withdrawal {
+        Withdrawal {
+            l1_batch_number,
+            l2_message_index,
+            l1_batch_tx_index: 1,
+            tx_hash: H256::repeat_byte(l2_message_index as u8),
+            sender: Address::repeat_byte(0x80),
+            message: vec![l2_message_index as u8; 56],
+        }
+    }
+
+    #[tokio::test]
+    async fn withdrawal_lifecycle() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        let header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            0,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+
+        let mut dal = conn.withdrawals_dal();
+        assert_eq!(dal.get_last_indexed_l1_batch().await.unwrap(), None);
+        let withdrawals = [
+            mock_withdrawal(L1BatchNumber(1), 0),
+            mock_withdrawal(L1BatchNumber(1), 3),
+        ];
+        dal.insert_l1_batch_withdrawals(L1BatchNumber(1), &withdrawals)
+            .await
+            .unwrap();
+        // Repeated indexing must be idempotent.
+        dal.insert_l1_batch_withdrawals(L1BatchNumber(1), &withdrawals)
+            .await
+            .unwrap();
+        assert_eq!(
+            dal.get_last_indexed_l1_batch().await.unwrap(),
+            Some(L1BatchNumber(1))
+        );
+
+        let pending = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Pending, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].withdrawal, withdrawals[0]);
+        assert_eq!(pending[1].withdrawal, withdrawals[1]);
+        assert_eq!(pending[0].attempts, 0);
+        let ids: Vec<_> = pending.iter().map(|withdrawal| withdrawal.id).collect();
+
+        let tx_hash = H256::repeat_byte(0xff);
+        dal.mark_withdrawals_as_sent(&ids, tx_hash, 5)
+            .await
+            .unwrap();
+        let sent = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Sent, 10)
+            .await
+            .unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].finalization_tx_hash, Some(tx_hash));
+        assert_eq!(sent[0].finalization_tx_nonce, Some(5));
+        let sent_at = sent[0].finalization_tx_sent_at.unwrap();
+
+        // Resending the transaction retains the nonce and updates the sending time.
+        let resent_tx_hash = H256::repeat_byte(0xfe);
+        dal.mark_withdrawals_as_sent(&ids, resent_tx_hash, 5)
+            .await
+            .unwrap();
+        let sent = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Sent, 10)
+            .await
+            .unwrap();
+        assert_eq!(sent[0].finalization_tx_hash, Some(resent_tx_hash));
+        assert_eq!(sent[0].finalization_tx_nonce, Some(5));
+        assert!(sent[0].finalization_tx_sent_at.unwrap() >= sent_at);
+
+        dal.record_failed_attempts(&ids, "reverted", 2)
+            .await
+            .unwrap();
+        let pending = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Pending, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].finalization_tx_hash, None);
+        assert_eq!(pending[0].finalization_tx_nonce, None);
+        assert_eq!(pending[0].finalization_tx_sent_at, None);
+        assert_eq!(pending[0].last_error.as_deref(), Some("reverted"));
+
+        dal.mark_withdrawals_as_finalized(&ids[..1]).await.unwrap();
+        dal.record_failed_attempts(&ids[1..], "reverted", 2)
+            .await
+            .unwrap();
+        let finalized = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Finalized, 10)
+            .await
+            .unwrap();
+        assert_eq!(finalized.len(), 1);
+        assert_eq!(finalized[0].id, ids[0]);
+        assert_eq!(finalized[0].last_error, None);
+        let failed = dal
+            .get_withdrawals_with_status(WithdrawalStatus::Failed, 10)
+            .await
+            .unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id, ids[1]);
+        assert_eq!(failed[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn inserting_withdrawals_from_another_l1_batch_is_an_error() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let withdrawals = [mock_withdrawal(L1BatchNumber(2), 0)];
+        let err = conn
+            .withdrawals_dal()
+            .insert_l1_batch_withdrawals(L1BatchNumber(1), &withdrawals)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("L1 batch #2"), "{err}");
+        assert_eq!(
+            conn.withdrawals_dal()
+                .get_last_indexed_l1_batch()
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
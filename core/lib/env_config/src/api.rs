@@ -68,10 +68,13 @@ mod tests {
                 pubsub_polling_interval: Some(200),
                 max_nonce_ahead: 5,
                 request_timeout: Some(10),
-                account_pks: Some(vec![
-                    hash("0x0000000000000000000000000000000000000000000000000000000000000001"),
-                    hash("0x0000000000000000000000000000000000000000000000000000000000000002"),
-                ]),
+                account_pks: Some(
+                    vec![
+                        hash("0x0000000000000000000000000000000000000000000000000000000000000001"),
+                        hash("0x0000000000000000000000000000000000000000000000000000000000000002"),
+                    ]
+                    .into(),
+                ),
                 estimate_gas_scale_factor: 1.0f64,
                 gas_price_scale_factor: 1.2,
                 estimate_gas_acceptable_overestimation: 1000,
@@ -87,6 +90,14 @@ mod tests {
                 max_response_body_size_mb: Some(10),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
                 tree_api_url: None,
+                api_keys: vec!["key0".into(), "key1".into()].into(),
+                api_key_required: true,
+                http_requests_per_minute_per_api_key: Some(NonZeroU32::new(1000).unwrap()),
+                http_requests_per_minute_per_ip: Some(NonZeroU32::new(100).unwrap()),
+                vm_request_weight: Some(NonZeroU32::new(5).unwrap()),
+                allowed_methods: Some(vec!["eth_*".into(), "net_version".into()]),
+                denied_methods: vec!["eth_call".into()],
+                trusted_proxies: vec!["10.0.0.1".parse().unwrap(), "::1".parse().unwrap()],
             },
             contract_verification: ContractVerificationApiConfig {
                 port: 3070,
@@ -129,6 +140,14 @@ mod tests {
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_API_KEYS="key0,key1"
+            API_WEB3_JSON_RPC_API_KEY_REQUIRED=true
+            API_WEB3_JSON_RPC_HTTP_REQUESTS_PER_MINUTE_PER_API_KEY=1000
+            API_WEB3_JSON_RPC_HTTP_REQUESTS_PER_MINUTE_PER_IP=100
+            API_WEB3_JSON_RPC_VM_REQUEST_WEIGHT=5
+            API_WEB3_JSON_RPC_ALLOWED_METHODS="eth_*,net_version"
+            API_WEB3_JSON_RPC_DENIED_METHODS="eth_call"
+            API_WEB3_JSON_RPC_TRUSTED_PROXIES="10.0.0.1,::1"
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_WEB3_JSON_RPC_MAX_RESPONSE_BODY_SIZE_MB=10
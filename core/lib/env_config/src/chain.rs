@@ -184,6 +184,11 @@ mod tests {
             http_req_max_retry_number: 5,
             http_req_retry_interval_sec: 2,
             replication_lag_limit_sec: Some(10),
+            min_operator_balance_gwei: Some(1_000_000_000),
+            max_unproved_l1_batches: Some(100),
+            max_unproved_l1_batch_age_sec: Some(3_600),
+            max_l1_gas_price: None,
+            pausing_breakers: vec!["prover_lag".into(), "l1_gas_price".into()],
         }
     }
 
@@ -195,6 +200,10 @@ mod tests {
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
             CHAIN_CIRCUIT_BREAKER_REPLICATION_LAG_LIMIT_SEC="10"
+            CHAIN_CIRCUIT_BREAKER_MIN_OPERATOR_BALANCE_GWEI="1000000000"
+            CHAIN_CIRCUIT_BREAKER_MAX_UNPROVED_L1_BATCHES="100"
+            CHAIN_CIRCUIT_BREAKER_MAX_UNPROVED_L1_BATCH_AGE_SEC="3600"
+            CHAIN_CIRCUIT_BREAKER_PAUSING_BREAKERS="prover_lag,l1_gas_price"
         "#;
         lock.set_env(config);
 
@@ -5,12 +5,12 @@ use crate::{envy_load, FromEnv};
 impl FromEnv for ETHClientConfig {
     fn from_env() -> anyhow::Result<Self> {
         let config: Self = envy_load("eth_client", "ETH_CLIENT_")?;
-        if config.web3_url.find(',').is_some() {
-            anyhow::bail!(
-                "Multiple web3 URLs aren't supported anymore. Provided invalid value: {}",
-                config.web3_url
-            );
-        }
+        // The URL is not included into the error message since it may contain credentials.
+        anyhow::ensure!(
+            !config.web3_url.expose().contains(','),
+            "Multiple web3 URLs aren't supported in ETH_CLIENT_WEB3_URL; use ETH_CLIENT_FALLBACK_WEB3_URLS instead"
+        );
+        config.validate()?;
         Ok(config)
     }
 }
@@ -25,7 +25,13 @@ mod tests {
     fn expected_config() -> ETHClientConfig {
         ETHClientConfig {
             chain_id: 9,
-            web3_url: "http://127.0.0.1:8545".into(),
+            web3_url: "http://127.0.0.1:8545".to_owned().into(),
+            fallback_web3_urls: vec![
+                "http://127.0.0.1:8546".to_owned().into(),
+                "http://127.0.0.1:8547".to_owned().into(),
+            ],
+            read_quorum: Some(2),
+            request_timeout_ms: 5_000,
         }
     }
 
@@ -35,10 +41,48 @@ mod tests {
         let config = r#"
             ETH_CLIENT_CHAIN_ID="9"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8546,http://127.0.0.1:8547"
+            ETH_CLIENT_READ_QUORUM="2"
+            ETH_CLIENT_REQUEST_TIMEOUT_MS="5000"
         "#;
         lock.set_env(config);
 
         let actual = ETHClientConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
     }
+
+    #[test]
+    fn from_env_without_fallback_urls() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            ETH_CLIENT_CHAIN_ID="9"
+            ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&[
+            "ETH_CLIENT_FALLBACK_WEB3_URLS",
+            "ETH_CLIENT_READ_QUORUM",
+            "ETH_CLIENT_REQUEST_TIMEOUT_MS",
+        ]);
+
+        let actual = ETHClientConfig::from_env().unwrap();
+        assert!(actual.fallback_web3_urls.is_empty());
+        assert_eq!(actual.read_quorum, None);
+        assert_eq!(actual.request_timeout_ms, 30_000);
+    }
+
+    #[test]
+    fn from_env_with_unreachable_quorum() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            ETH_CLIENT_CHAIN_ID="9"
+            ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8546"
+            ETH_CLIENT_READ_QUORUM="3"
+        "#;
+        lock.set_env(config);
+
+        let err = ETHClientConfig::from_env().unwrap_err();
+        assert!(err.to_string().contains("read quorum"), "{err}");
+    }
 }
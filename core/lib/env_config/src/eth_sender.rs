@@ -1,5 +1,10 @@
+use std::env;
+
 use anyhow::Context as _;
-use zksync_config::{configs::eth_sender::SenderConfig, ETHSenderConfig, GasAdjusterConfig};
+use zksync_config::{
+    configs::eth_sender::{RemoteSignerConfig, SenderConfig},
+    ETHSenderConfig, GasAdjusterConfig,
+};
 
 use crate::{envy_load, FromEnv};
 
@@ -8,6 +13,11 @@ impl FromEnv for ETHSenderConfig {
         Ok(Self {
             sender: SenderConfig::from_env().context("SenderConfig")?,
             gas_adjuster: GasAdjusterConfig::from_env().context("GasAdjusterConfig")?,
+            remote_signer: if env::var_os("ETH_SENDER_REMOTE_SIGNER_URL").is_some() {
+                Some(RemoteSignerConfig::from_env().context("RemoteSignerConfig")?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -18,6 +28,12 @@ impl FromEnv for SenderConfig {
     }
 }
 
+impl FromEnv for RemoteSignerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("eth_sender.remote_signer", "ETH_SENDER_REMOTE_SIGNER_")
+    }
+}
+
 impl FromEnv for GasAdjusterConfig {
     fn from_env() -> anyhow::Result<Self> {
         envy_load("eth_sender.gas_adjuster", "ETH_SENDER_GAS_ADJUSTER_")
@@ -29,7 +45,7 @@ mod tests {
     use zksync_config::configs::eth_sender::{ProofLoadingMode, ProofSendingMode};
 
     use super::*;
-    use crate::test_utils::{hash, EnvMutex};
+    use crate::test_utils::{addr, hash, EnvMutex};
 
     static MUTEX: EnvMutex = EnvMutex::new();
 
@@ -54,6 +70,15 @@ mod tests {
                 l1_batch_min_age_before_execute_seconds: Some(1000),
                 max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                 proof_loading_mode: ProofLoadingMode::OldProofFromDb,
+                operator_private_key: Some(
+                    hash("27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be").into(),
+                ),
+                prove_operator_private_key: Some(
+                    hash("0e6c5cb1b5a74ab38d4a1d0c2dab2be85f0a1b9ae1f4cbd88d1c5b7c1a91a9c2").into(),
+                ),
+                execute_operator_private_key: None,
+                base_fee_postpone_ratio: Some(1.5),
+                max_base_fee_postpone_seconds: None,
             },
             gas_adjuster: GasAdjusterConfig {
                 default_priority_fee_per_gas: 20000000000,
@@ -65,6 +90,7 @@ mod tests {
                 poll_period: 15,
                 max_l1_gas_price: Some(100000000),
             },
+            remote_signer: None,
         }
     }
 
@@ -77,6 +103,7 @@ mod tests {
             ETH_SENDER_SENDER_AGGREGATE_TX_POLL_PERIOD="3"
             ETH_SENDER_SENDER_MAX_TXS_IN_FLIGHT="3"
             ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+            ETH_SENDER_SENDER_PROVE_OPERATOR_PRIVATE_KEY="0x0e6c5cb1b5a74ab38d4a1d0c2dab2be85f0a1b9ae1f4cbd88d1c5b7c1a91a9c2"
             ETH_SENDER_SENDER_PROOF_SENDING_MODE="SkipEveryProof"
             ETH_SENDER_GAS_ADJUSTER_DEFAULT_PRIORITY_FEE_PER_GAS="20000000000"
             ETH_SENDER_GAS_ADJUSTER_MAX_BASE_FEE_SAMPLES="10000"
@@ -98,8 +125,14 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PROOF_LOADING_MODE="OldProofFromDb"
+            ETH_SENDER_SENDER_BASE_FEE_POSTPONE_RATIO="1.5"
         "#;
         lock.set_env(config);
+        lock.remove_env(&[
+            "ETH_SENDER_REMOTE_SIGNER_URL",
+            "ETH_SENDER_SENDER_EXECUTE_OPERATOR_PRIVATE_KEY",
+            "ETH_SENDER_SENDER_MAX_BASE_FEE_POSTPONE_SECONDS",
+        ]);
 
         let actual = ETHSenderConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
@@ -108,4 +141,40 @@ mod tests {
             hash("27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be")
         );
     }
+
+    #[test]
+    fn remote_signer_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            ETH_SENDER_REMOTE_SIGNER_URL="https://signer.example.com/"
+            ETH_SENDER_REMOTE_SIGNER_OPERATOR_ADDRESS="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+            ETH_SENDER_REMOTE_SIGNER_PROVE_OPERATOR_ADDRESS="0x1cd2e2f3b64a2cf1d7f6bc5b7bf79e4bd8d95a63"
+            ETH_SENDER_REMOTE_SIGNER_BEARER_TOKEN="s3cr3t"
+            ETH_SENDER_REMOTE_SIGNER_CLIENT_CERT_PATH="/etc/signer/client.pem"
+            ETH_SENDER_REMOTE_SIGNER_CLIENT_KEY_PATH="/etc/signer/client.key"
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&[
+            "ETH_SENDER_REMOTE_SIGNER_CA_CERT_PATH",
+            "ETH_SENDER_REMOTE_SIGNER_REQUEST_TIMEOUT_MS",
+            "ETH_SENDER_REMOTE_SIGNER_EXECUTE_OPERATOR_ADDRESS",
+        ]);
+
+        let actual = RemoteSignerConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            RemoteSignerConfig {
+                url: "https://signer.example.com/".to_owned(),
+                operator_address: addr("de03a0b5963f75f1c8485b355ff6d30f3093bde7"),
+                prove_operator_address: Some(addr("1cd2e2f3b64a2cf1d7f6bc5b7bf79e4bd8d95a63")),
+                execute_operator_address: None,
+                bearer_token: Some("s3cr3t".to_owned().into()),
+                ca_cert_path: None,
+                client_cert_path: Some("/etc/signer/client.pem".to_owned()),
+                client_key_path: Some("/etc/signer/client.key".to_owned().into()),
+                request_timeout_ms: 10_000,
+            }
+        );
+        assert!(!format!("{actual:?}").contains("s3cr3t"));
+    }
 }
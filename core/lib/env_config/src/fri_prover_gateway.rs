@@ -10,6 +10,8 @@ impl FromEnv for FriProverGatewayConfig {
 
 #[cfg(test)]
 mod tests {
+    use zksync_config::configs::proof_data_handler::ProofDataHandlerAuthMode;
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -19,6 +21,9 @@ mod tests {
         FriProverGatewayConfig {
             api_url: "http://private-dns-for-server".to_string(),
             api_poll_duration_secs: 100,
+            api_auth_mode: ProofDataHandlerAuthMode::BearerToken,
+            api_client_id: Some("gateway-a".to_string()),
+            api_client_secret: Some("secret-a".to_string().into()),
             prometheus_listener_port: 3316,
             prometheus_pushgateway_url: "http://127.0.0.1:9091".to_string(),
             prometheus_push_interval_ms: Some(100),
@@ -30,6 +35,9 @@ mod tests {
         let config = r#"
             FRI_PROVER_GATEWAY_API_URL="http://private-dns-for-server"
             FRI_PROVER_GATEWAY_API_POLL_DURATION_SECS="100"
+            FRI_PROVER_GATEWAY_API_AUTH_MODE="BearerToken"
+            FRI_PROVER_GATEWAY_API_CLIENT_ID="gateway-a"
+            FRI_PROVER_GATEWAY_API_CLIENT_SECRET="secret-a"
             FRI_PROVER_GATEWAY_PROMETHEUS_LISTENER_PORT=3316
             FRI_PROVER_GATEWAY_PROMETHEUS_PUSHGATEWAY_URL="http://127.0.0.1:9091"
             FRI_PROVER_GATEWAY_PROMETHEUS_PUSH_INTERVAL_MS=100
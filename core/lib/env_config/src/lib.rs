@@ -18,9 +18,12 @@ mod fri_witness_generator;
 mod fri_witness_vector_generator;
 mod house_keeper;
 pub mod object_store;
+mod observability;
 mod proof_data_handler;
+mod secrets;
 mod snapshots_creator;
 mod utils;
+mod withdrawal_finalizer;
 mod witness_generator;
 
 #[cfg(test)]
@@ -0,0 +1,64 @@
+use zksync_config::configs::OpentelemetryConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for OpentelemetryConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let config: Self = envy_load("opentelemetry", "OPENTELEMETRY_")?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OPENTELEMETRY_ENDPOINT="http://127.0.0.1:4318"
+            OPENTELEMETRY_SERVICE_NAME="zksync_server"
+            OPENTELEMETRY_SAMPLING_RATIO="0.25"
+        "#;
+        lock.set_env(config);
+
+        let actual = OpentelemetryConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            OpentelemetryConfig {
+                endpoint: "http://127.0.0.1:4318".to_owned(),
+                service_name: "zksync_server".to_owned(),
+                sampling_ratio: 0.25,
+            }
+        );
+    }
+
+    #[test]
+    fn from_env_with_defaults() {
+        let mut lock = MUTEX.lock();
+        lock.remove_env(&["OPENTELEMETRY_SERVICE_NAME", "OPENTELEMETRY_SAMPLING_RATIO"]);
+        lock.set_env(r#"OPENTELEMETRY_ENDPOINT="http://collector:4318""#);
+
+        let actual = OpentelemetryConfig::from_env().unwrap();
+        assert_eq!(actual.service_name, "zksync");
+        assert_eq!(actual.sampling_ratio, 1.0);
+    }
+
+    #[test]
+    fn from_env_with_invalid_sampling_ratio() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            OPENTELEMETRY_ENDPOINT="http://collector:4318"
+            OPENTELEMETRY_SAMPLING_RATIO="1.5"
+        "#;
+        lock.set_env(config);
+
+        let err = OpentelemetryConfig::from_env().unwrap_err().to_string();
+        assert!(err.contains("sampling ratio"), "{err}");
+    }
+}
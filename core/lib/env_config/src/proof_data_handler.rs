@@ -10,7 +10,9 @@ impl FromEnv for ProofDataHandlerConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::proof_data_handler::ProtocolVersionLoadingMode;
+    use zksync_config::configs::proof_data_handler::{
+        ProofDataHandlerAuthMode, ProtocolVersionLoadingMode,
+    };
 
     use super::*;
     use crate::test_utils::EnvMutex;
@@ -23,6 +25,19 @@ mod tests {
             proof_generation_timeout_in_secs: 18000,
             protocol_version_loading_mode: ProtocolVersionLoadingMode::FromEnvVar,
             fri_protocol_version_id: 2,
+            auth_mode: ProofDataHandlerAuthMode::Hmac,
+            api_clients: vec![
+                "gateway-a:secret-a".to_owned(),
+                "gateway-b:secret-b".to_owned(),
+            ]
+            .into(),
+            hmac_max_clock_skew_secs: Some(30),
+            tls_cert_path: Some("/etc/tls/cert.pem".to_owned()),
+            tls_key_path: Some("/etc/tls/key.pem".to_owned()),
+            scheduler_vk_paths: vec![
+                "/etc/keys/snark_verification_scheduler_key_v22.json".to_owned(),
+                "/etc/keys/snark_verification_scheduler_key_v23.json".to_owned(),
+            ],
         }
     }
 
@@ -33,6 +48,12 @@ mod tests {
             PROOF_DATA_HANDLER_HTTP_PORT="3320"
             PROOF_DATA_HANDLER_PROTOCOL_VERSION_LOADING_MODE="FromEnvVar"
             PROOF_DATA_HANDLER_FRI_PROTOCOL_VERSION_ID="2"
+            PROOF_DATA_HANDLER_AUTH_MODE="Hmac"
+            PROOF_DATA_HANDLER_API_CLIENTS="gateway-a:secret-a,gateway-b:secret-b"
+            PROOF_DATA_HANDLER_HMAC_MAX_CLOCK_SKEW_SECS="30"
+            PROOF_DATA_HANDLER_TLS_CERT_PATH="/etc/tls/cert.pem"
+            PROOF_DATA_HANDLER_TLS_KEY_PATH="/etc/tls/key.pem"
+            PROOF_DATA_HANDLER_SCHEDULER_VK_PATHS="/etc/keys/snark_verification_scheduler_key_v22.json,/etc/keys/snark_verification_scheduler_key_v23.json"
         "#;
         let mut lock = MUTEX.lock();
         lock.set_env(config);
@@ -0,0 +1,51 @@
+use zksync_config::configs::WithdrawalFinalizerConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for WithdrawalFinalizerConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("withdrawal_finalizer", "WITHDRAWAL_FINALIZER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{hash, EnvMutex};
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    fn expected_config() -> WithdrawalFinalizerConfig {
+        WithdrawalFinalizerConfig {
+            poll_interval_ms: 5000,
+            batch_size: 20,
+            gas_limit_per_withdrawal: 200_000,
+            max_attempts: 5,
+            resend_timeout_ms: 600_000,
+            dropped_tx_grace_period_ms: 60_000,
+            start_from_l1_batch: Some(100),
+            private_key: Some(
+                hash("d293c684d884d56f8d6abd64fc76757d3664904e309a0645baf8522ab6366d9e").into(),
+            ),
+        }
+    }
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            WITHDRAWAL_FINALIZER_POLL_INTERVAL_MS="5000"
+            WITHDRAWAL_FINALIZER_BATCH_SIZE="20"
+            WITHDRAWAL_FINALIZER_GAS_LIMIT_PER_WITHDRAWAL="200000"
+            WITHDRAWAL_FINALIZER_MAX_ATTEMPTS="5"
+            WITHDRAWAL_FINALIZER_RESEND_TIMEOUT_MS="600000"
+            WITHDRAWAL_FINALIZER_DROPPED_TX_GRACE_PERIOD_MS="60000"
+            WITHDRAWAL_FINALIZER_START_FROM_L1_BATCH="100"
+            WITHDRAWAL_FINALIZER_PRIVATE_KEY="0xd293c684d884d56f8d6abd64fc76757d3664904e309a0645baf8522ab6366d9e"
+        "#;
+        lock.set_env(config);
+
+        let actual = WithdrawalFinalizerConfig::from_env().unwrap();
+        assert_eq!(actual, expected_config());
+    }
+}
@@ -0,0 +1,646 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::future;
+use vise::{Counter, Gauge, LabeledFamily, Metrics};
+use zksync_config::ETHClientConfig;
+use zksync_types::web3::{
+    self,
+    contract::Error as ContractError,
+    error::TransportError,
+    ethabi,
+    types::{
+        Address, Block, BlockId, BlockNumber, Filter, Log, Transaction, TransactionReceipt, H256,
+        U256, U64,
+    },
+};
+
+use super::http::QueryClient;
+use crate::{
+    types::{Error, ExecutedTxStatus, FailureInfo},
+    ContractCall, EthInterface, RawTransactionBytes,
+};
+
+/// Health score of a provider without recent failures.
+const MAX_HEALTH: u64 = 100;
+/// Health score deducted from a provider on each failed or timed out request.
+const FAILURE_PENALTY: u64 = 25;
+/// Health score restored on each successful request.
+const SUCCESS_REWARD: u64 = 5;
+
+/// JSON-RPC code of an internal node error.
+const INTERNAL_ERROR_CODE: i64 = -32603;
+
+type ClientFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'a>>;
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "eth_client_failover")]
+struct FailoverMetrics {
+    /// Health score of an L1 provider. Providers with higher scores are queried first.
+    #[metrics(labels = ["provider"])]
+    health: LabeledFamily<String, Gauge<u64>>,
+    /// Number of failed or timed out requests to an L1 provider.
+    #[metrics(labels = ["provider"])]
+    failures: LabeledFamily<String, Counter>,
+    /// Number of critical reads for which L1 providers failed to reach the quorum.
+    #[metrics(labels = ["method"])]
+    quorum_failures: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<FailoverMetrics> = vise::Global::new();
+
+#[derive(Debug)]
+struct Provider {
+    name: String,
+    client: Box<dyn EthInterface>,
+    health: AtomicU64,
+}
+
+impl Provider {
+    fn new(name: String, client: Box<dyn EthInterface>) -> Self {
+        METRICS.health[&name].set(MAX_HEALTH);
+        Self {
+            name,
+            client,
+            health: AtomicU64::new(MAX_HEALTH),
+        }
+    }
+
+    fn health(&self) -> u64 {
+        self.health.load(Ordering::Relaxed)
+    }
+
+    fn update_health(&self, update: impl Fn(u64) -> u64) {
+        let prev_health = self
+            .health
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |health| {
+                Some(update(health))
+            })
+            .unwrap(); // The closure never returns `None`
+        METRICS.health[&self.name].set(update(prev_health));
+    }
+
+    fn report_success(&self) {
+        self.update_health(|health| (health + SUCCESS_REWARD).min(MAX_HEALTH));
+    }
+
+    fn report_failure(&self, method: &'static str, err: &Error) {
+        tracing::warn!(
+            "Request `{method}` to L1 provider `{}` failed: {err}",
+            self.name
+        );
+        METRICS.failures[&self.name].inc();
+        self.update_health(|health| health.saturating_sub(FAILURE_PENALTY));
+    }
+}
+
+/// Checks whether the error is caused by the provider rather than by the request itself, i.e.,
+/// whether the request may succeed if sent to another provider.
+fn is_provider_failure(err: &Error) -> bool {
+    let web3_err = match err {
+        Error::EthereumGateway(err) | Error::Contract(ContractError::Api(err)) => err,
+        _ => return false,
+    };
+    match web3_err {
+        web3::Error::Rpc(err) => err.code.code() == INTERNAL_ERROR_CODE,
+        web3::Error::Unreachable
+        | web3::Error::Decoder(_)
+        | web3::Error::InvalidResponse(_)
+        | web3::Error::Transport(_)
+        | web3::Error::Io(_)
+        | web3::Error::Internal => true,
+        web3::Error::Recovery(_) => false,
+    }
+}
+
+/// Returns a response returned by at least `quorum` providers.
+fn agreed_response<T: Clone + PartialEq>(responses: &[T], quorum: usize) -> Option<T> {
+    responses
+        .iter()
+        .find(|&response| responses.iter().filter(|&other| other == response).count() >= quorum)
+        .cloned()
+}
+
+/// Returns the block number reached by at least `quorum` providers.
+fn agreed_block_number(block_numbers: &[U64], quorum: usize) -> Option<U64> {
+    let mut block_numbers = block_numbers.to_vec();
+    block_numbers.sort_unstable_by(|a, b| b.cmp(a));
+    block_numbers.get(quorum - 1).copied()
+}
+
+/// L1 client wrapping several providers.
+///
+/// Requests are sent to the provider with the best health score; if it fails with a transport error or times out,
+/// the request fails over to the next provider. Each failure decreases the health score of a provider,
+/// and each successful request restores it, so that a degraded provider is queried last until it recovers.
+///
+/// Critical reads used to track L1 state (the latest block number, transaction receipts and logs) can require
+/// agreement of several providers; see [`Self::with_read_quorum()`]. Such reads, as well as raw transactions
+/// which are broadcast to all providers, are sent to all providers concurrently, so that a slow provider
+/// delays the response by at most the request timeout.
+#[derive(Debug)]
+pub struct FailoverClient {
+    providers: Vec<Provider>,
+    read_quorum: usize,
+    request_timeout: Duration,
+}
+
+impl FailoverClient {
+    const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Creates a client with a single provider. Other providers can be added using [`Self::with_provider()`].
+    pub fn new(name: impl Into<String>, client: Box<dyn EthInterface>) -> Self {
+        Self {
+            providers: vec![Provider::new(name.into(), client)],
+            read_quorum: 1,
+            request_timeout: Self::DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Creates a client for all nodes specified in the config. The main node is named `main`, and fallback nodes
+    /// are named `fallback_{i}`; node URLs are not used as names since they may contain credentials.
+    pub fn from_config(config: &ETHClientConfig) -> anyhow::Result<Self> {
+        let main_client = QueryClient::new(config.web3_url.expose()).context("main L1 node")?;
+        let mut client = Self::new("main", Box::new(main_client));
+        for (i, url) in config.fallback_web3_urls.iter().enumerate() {
+            let fallback_client =
+                QueryClient::new(url.expose()).with_context(|| format!("fallback L1 node #{i}"))?;
+            client = client.with_provider(format!("fallback_{i}"), Box::new(fallback_client));
+        }
+        if let Some(quorum) = config.read_quorum {
+            client = client.with_read_quorum(quorum)?;
+        }
+        Ok(client.with_request_timeout(config.request_timeout()))
+    }
+
+    /// Adds a provider. Providers with equal health scores are queried in the order they were added.
+    pub fn with_provider(mut self, name: impl Into<String>, client: Box<dyn EthInterface>) -> Self {
+        self.providers.push(Provider::new(name.into(), client));
+        self
+    }
+
+    /// Sets the number of providers that must return the same response for critical reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `quorum` is 0 or exceeds the number of providers.
+    pub fn with_read_quorum(mut self, quorum: usize) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            quorum > 0 && quorum <= self.providers.len(),
+            "L1 read quorum must be between 1 and the number of L1 nodes ({}), got {quorum}",
+            self.providers.len()
+        );
+        self.read_quorum = quorum;
+        Ok(self)
+    }
+
+    /// Sets the timeout for a single request to a provider.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    fn providers_by_health(&self) -> Vec<&Provider> {
+        let mut providers: Vec<_> = self.providers.iter().collect();
+        // The sort is stable, so providers with equal health retain their order.
+        providers.sort_by_key(|provider| std::cmp::Reverse(provider.health()));
+        providers
+    }
+
+    async fn call_provider<T, F>(
+        &self,
+        provider: &Provider,
+        method: &'static str,
+        call: &F,
+    ) -> Result<T, Error>
+    where
+        F: for<'a> Fn(&'a dyn EthInterface) -> ClientFuture<'a, T>,
+    {
+        let result = tokio::time::timeout(self.request_timeout, call(provider.client.as_ref()))
+            .await
+            .unwrap_or_else(|_| {
+                let message = format!("request to L1 provider `{}` timed out", provider.name);
+                Err(web3::Error::Transport(TransportError::Message(message)).into())
+            });
+        match &result {
+            Err(err) if is_provider_failure(err) => provider.report_failure(method, err),
+            _ => provider.report_success(),
+        }
+        result
+    }
+
+    async fn with_failover<T, F>(&self, method: &'static str, call: F) -> Result<T, Error>
+    where
+        F: for<'a> Fn(&'a dyn EthInterface) -> ClientFuture<'a, T> + Send + Sync,
+        T: Send,
+    {
+        let mut last_err = None;
+        for provider in self.providers_by_health() {
+            match self.call_provider(provider, method, &call).await {
+                Err(err) if is_provider_failure(&err) => last_err = Some(err),
+                result => return result,
+            }
+        }
+        Err(last_err.expect("failover client has no providers"))
+    }
+
+    /// Sends a request to all providers concurrently. Each request is limited by the request timeout.
+    async fn call_all_providers<T, F>(
+        &self,
+        method: &'static str,
+        call: F,
+    ) -> Vec<(&Provider, Result<T, Error>)>
+    where
+        F: for<'a> Fn(&'a dyn EthInterface) -> ClientFuture<'a, T> + Send + Sync,
+        T: Send,
+    {
+        let providers = self.providers_by_health();
+        let results = future::join_all(
+            providers
+                .iter()
+                .map(|&provider| self.call_provider(provider, method, &call)),
+        )
+        .await;
+        providers.into_iter().zip(results).collect()
+    }
+
+    /// Queries all providers concurrently and returns the response satisfying the read quorum according to `resolve`.
+    async fn with_quorum<T, F>(
+        &self,
+        method: &'static str,
+        call: F,
+        resolve: fn(&[T], usize) -> Option<T>,
+    ) -> Result<T, Error>
+    where
+        F: for<'a> Fn(&'a dyn EthInterface) -> ClientFuture<'a, T> + Send + Sync,
+        T: Send + Sync,
+    {
+        if self.read_quorum == 1 {
+            return self.with_failover(method, call).await;
+        }
+
+        let results = self.call_all_providers(method, call).await;
+        let mut responses = Vec::with_capacity(results.len());
+        let mut last_err = None;
+        for (_, result) in results {
+            match result {
+                Ok(response) => responses.push(response),
+                Err(err) if is_provider_failure(&err) => last_err = Some(err),
+                Err(err) => return Err(err),
+            }
+        }
+        if let Some(response) = resolve(&responses, self.read_quorum) {
+            return Ok(response);
+        }
+
+        METRICS.quorum_failures[&method].inc();
+        tracing::warn!(
+            "L1 providers failed to reach quorum {} for `{method}`: got {} responses",
+            self.read_quorum,
+            responses.len()
+        );
+        Err(match last_err {
+            Some(err) if responses.is_empty() => err,
+            _ => web3::Error::InvalidResponse(format!(
+                "L1 providers failed to reach quorum {} for `{method}`",
+                self.read_quorum
+            ))
+            .into(),
+        })
+    }
+}
+
+#[async_trait]
+impl EthInterface for FailoverClient {
+    async fn nonce_at_for_account(
+        &self,
+        account: Address,
+        block: BlockNumber,
+        component: &'static str,
+    ) -> Result<U256, Error> {
+        self.with_failover("nonce_at_for_account", |client| {
+            client.nonce_at_for_account(account, block, component)
+        })
+        .await
+    }
+
+    async fn base_fee_history(
+        &self,
+        from_block: usize,
+        block_count: usize,
+        component: &'static str,
+    ) -> Result<Vec<u64>, Error> {
+        self.with_failover("base_fee_history", |client| {
+            client.base_fee_history(from_block, block_count, component)
+        })
+        .await
+    }
+
+    async fn get_pending_block_base_fee_per_gas(
+        &self,
+        component: &'static str,
+    ) -> Result<U256, Error> {
+        self.with_failover("get_pending_block_base_fee_per_gas", |client| {
+            client.get_pending_block_base_fee_per_gas(component)
+        })
+        .await
+    }
+
+    async fn get_gas_price(&self, component: &'static str) -> Result<U256, Error> {
+        self.with_failover("get_gas_price", |client| client.get_gas_price(component))
+            .await
+    }
+
+    async fn block_number(&self, component: &'static str) -> Result<U64, Error> {
+        self.with_quorum(
+            "block_number",
+            |client| client.block_number(component),
+            agreed_block_number,
+        )
+        .await
+    }
+
+    async fn send_raw_tx(&self, tx: RawTransactionBytes) -> Result<H256, Error> {
+        // Broadcast the transaction to all providers, so that it's included even if some of them
+        // are unable to propagate it.
+        let results = self
+            .call_all_providers("send_raw_tx", |client| client.send_raw_tx(tx.clone()))
+            .await;
+        let mut tx_hash = None;
+        let mut first_err = None;
+        for (provider, result) in results {
+            match result {
+                Ok(hash) => {
+                    tx_hash.get_or_insert(hash);
+                }
+                Err(err) => {
+                    tracing::info!(
+                        "L1 provider `{}` didn't accept transaction: {err}",
+                        provider.name
+                    );
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+        tx_hash.ok_or_else(|| first_err.expect("failover client has no providers"))
+    }
+
+    async fn get_tx_status(
+        &self,
+        hash: H256,
+        component: &'static str,
+    ) -> Result<Option<ExecutedTxStatus>, Error> {
+        self.with_failover("get_tx_status", |client| {
+            client.get_tx_status(hash, component)
+        })
+        .await
+    }
+
+    async fn failure_reason(&self, tx_hash: H256) -> Result<Option<FailureInfo>, Error> {
+        self.with_failover("failure_reason", |client| client.failure_reason(tx_hash))
+            .await
+    }
+
+    async fn get_tx(
+        &self,
+        hash: H256,
+        component: &'static str,
+    ) -> Result<Option<Transaction>, Error> {
+        self.with_failover("get_tx", |client| client.get_tx(hash, component))
+            .await
+    }
+
+    async fn tx_receipt(
+        &self,
+        tx_hash: H256,
+        component: &'static str,
+    ) -> Result<Option<TransactionReceipt>, Error> {
+        self.with_quorum(
+            "tx_receipt",
+            |client| client.tx_receipt(tx_hash, component),
+            agreed_response,
+        )
+        .await
+    }
+
+    async fn eth_balance(&self, address: Address, component: &'static str) -> Result<U256, Error> {
+        self.with_failover("eth_balance", |client| {
+            client.eth_balance(address, component)
+        })
+        .await
+    }
+
+    async fn call_contract_function(
+        &self,
+        call: ContractCall,
+    ) -> Result<Vec<ethabi::Token>, Error> {
+        self.with_failover("call_contract_function", |client| {
+            client.call_contract_function(call.clone())
+        })
+        .await
+    }
+
+    async fn logs(&self, filter: Filter, component: &'static str) -> Result<Vec<Log>, Error> {
+        self.with_quorum(
+            "logs",
+            |client| client.logs(filter.clone(), component),
+            agreed_response,
+        )
+        .await
+    }
+
+    async fn block(
+        &self,
+        block_id: BlockId,
+        component: &'static str,
+    ) -> Result<Option<Block<H256>>, Error> {
+        self.with_failover("block", |client| client.block(block_id, component))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use zksync_types::web3::contract::Options;
+
+    use super::*;
+    use crate::{clients::MockEthereum, SignedCallResult};
+
+    fn client_with_mocks(mocks: &[Arc<MockEthereum>]) -> FailoverClient {
+        let (first, rest) = mocks.split_first().unwrap();
+        let client = FailoverClient::new("mock_0", Box::new(first.clone()));
+        rest.iter().enumerate().fold(client, |client, (i, mock)| {
+            client.with_provider(format!("mock_{}", i + 1), Box::new(mock.clone()))
+        })
+    }
+
+    fn sign_tx(mock: &MockEthereum, nonce: u64) -> SignedCallResult {
+        let options = Options {
+            nonce: Some(nonce.into()),
+            ..Options::default()
+        };
+        mock.sign_prepared_tx(b"test".to_vec(), options).unwrap()
+    }
+
+    fn create_log(block_number: u64) -> Log {
+        Log {
+            address: Address::repeat_byte(1),
+            topics: vec![H256::repeat_byte(2)],
+            data: vec![].into(),
+            block_hash: Some(H256::repeat_byte(3)),
+            block_number: Some(block_number.into()),
+            transaction_hash: Some(H256::repeat_byte(4)),
+            transaction_index: Some(0.into()),
+            log_index: Some(0.into()),
+            transaction_log_index: None,
+            log_type: None,
+            removed: Some(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_over_to_healthy_provider() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        mocks[0].advance_block_number(1);
+        mocks[1].advance_block_number(2);
+        let client = client_with_mocks(&mocks);
+
+        assert_eq!(client.block_number("test").await.unwrap(), 1.into());
+
+        mocks[0].set_unreachable(true);
+        assert_eq!(client.block_number("test").await.unwrap(), 2.into());
+        assert!(client.providers[0].health() < MAX_HEALTH);
+        assert_eq!(client.providers[1].health(), MAX_HEALTH);
+
+        // The degraded provider should be queried last even after it has recovered.
+        mocks[0].set_unreachable(false);
+        assert_eq!(client.block_number("test").await.unwrap(), 2.into());
+        assert_eq!(client.providers[0].health(), MAX_HEALTH - FAILURE_PENALTY);
+    }
+
+    #[tokio::test]
+    async fn all_providers_failing() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        let client = client_with_mocks(&mocks);
+        for mock in &mocks {
+            mock.set_unreachable(true);
+        }
+
+        let err = client.get_gas_price("test").await.unwrap_err();
+        assert!(
+            matches!(err, Error::EthereumGateway(web3::Error::Unreachable)),
+            "{err:?}"
+        );
+        for provider in &client.providers {
+            assert_eq!(provider.health(), MAX_HEALTH - FAILURE_PENALTY);
+        }
+    }
+
+    #[tokio::test]
+    async fn block_number_with_quorum() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        mocks[0].advance_block_number(10);
+        mocks[1].advance_block_number(8);
+        mocks[2].advance_block_number(9);
+        let client = client_with_mocks(&mocks).with_read_quorum(2).unwrap();
+
+        // Only 9 blocks are confirmed by 2 of 3 providers.
+        assert_eq!(client.block_number("test").await.unwrap(), 9.into());
+
+        mocks[1].set_unreachable(true);
+        assert_eq!(client.block_number("test").await.unwrap(), 9.into());
+
+        mocks[2].set_unreachable(true);
+        let err = client.block_number("test").await.unwrap_err();
+        assert!(
+            matches!(err, Error::EthereumGateway(web3::Error::InvalidResponse(_))),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn tx_receipt_with_quorum() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        let client = client_with_mocks(&mocks).with_read_quorum(2).unwrap();
+
+        let signed_tx = sign_tx(&mocks[0], 0);
+        let tx_hash = client.send_raw_tx(signed_tx.raw_tx).await.unwrap();
+        assert_eq!(tx_hash, signed_tx.hash);
+        for mock in &mocks {
+            assert_eq!(mock.sent_tx_count(), 1);
+        }
+
+        // The transaction is executed only according to the first provider, which is not trusted.
+        mocks[0].execute_tx(tx_hash, true, 1);
+        let receipt = client.tx_receipt(tx_hash, "test").await.unwrap();
+        assert_eq!(receipt, None);
+
+        mocks[1].execute_tx(tx_hash, true, 1);
+        let receipt = client.tx_receipt(tx_hash, "test").await.unwrap();
+        assert_eq!(receipt.unwrap().transaction_hash, tx_hash);
+    }
+
+    #[tokio::test]
+    async fn logs_with_quorum() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        let client = client_with_mocks(&mocks).with_read_quorum(2).unwrap();
+        let log = create_log(1);
+        let forged_log = create_log(2);
+        mocks[0].add_logs([forged_log]);
+        mocks[1].add_logs([log.clone()]);
+        mocks[2].add_logs([log.clone()]);
+
+        let logs = client.logs(Filter::default(), "test").await.unwrap();
+        assert_eq!(logs, [log]);
+
+        mocks[2].set_unreachable(true);
+        let err = client.logs(Filter::default(), "test").await.unwrap_err();
+        assert!(
+            matches!(err, Error::EthereumGateway(web3::Error::InvalidResponse(_))),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcasting_raw_tx_with_unreachable_provider() {
+        let mocks = [
+            Arc::new(MockEthereum::default()),
+            Arc::new(MockEthereum::default()),
+        ];
+        let client = client_with_mocks(&mocks);
+        mocks[0].set_unreachable(true);
+
+        let signed_tx = sign_tx(&mocks[1], 0);
+        let tx_hash = client.send_raw_tx(signed_tx.raw_tx).await.unwrap();
+        assert_eq!(tx_hash, signed_tx.hash);
+        assert_eq!(mocks[0].sent_tx_count(), 0);
+        assert_eq!(mocks[1].sent_tx_count(), 1);
+    }
+}
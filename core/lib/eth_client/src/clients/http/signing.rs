@@ -1,10 +1,17 @@
-use std::{fmt, sync::Arc};
+use std::{fmt, fs, sync::Arc};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
-use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_config::{
+    configs::eth_sender::RemoteSignerConfig, ContractsConfig, ETHClientConfig, ETHSenderConfig,
+};
 use zksync_contracts::zksync_contract;
-use zksync_eth_signer::{raw_ethereum_tx::TransactionParameters, EthereumSigner, PrivateKeySigner};
+use zksync_eth_signer::{
+    raw_ethereum_tx::TransactionParameters, EthereumSigner, OperatorSigner, PrivateKeySigner,
+    RemoteSigner,
+};
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     web3::{
         self,
         contract::{tokens::Detokenize, Options},
@@ -20,6 +27,7 @@ use zksync_types::{
 
 use super::{query::QueryClient, Method, LATENCIES};
 use crate::{
+    clients::FailoverClient,
     types::{Error, ExecutedTxStatus, FailureInfo, SignedCallResult},
     BoundEthInterface, CallFunctionArgs, ContractCall, EthInterface, RawTransactionBytes,
 };
@@ -35,7 +43,7 @@ impl PKSigningClient {
     ) -> Self {
         // Gather required data from the config.
         // It's done explicitly to simplify getting rid of this function later.
-        let main_node_url = &eth_client.web3_url;
+        let main_node_url = eth_client.web3_url.expose();
         let operator_private_key = eth_sender
             .sender
             .private_key()
@@ -62,6 +70,184 @@ impl PKSigningClient {
     }
 }
 
+impl PKSigningClient {
+    /// Creates a client for an auxiliary account that is not related to the operator
+    /// (e.g., the account finalizing withdrawals).
+    pub fn from_private_key(
+        private_key: H256,
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+    ) -> anyhow::Result<Self> {
+        let address = PackedEthSignature::address_from_private_key(&private_key)
+            .context("Failed to get address from private key")?;
+        let transport =
+            Http::new(eth_client.web3_url.expose()).context("Failed to create transport")?;
+        let query_client =
+            FailoverClient::from_config(eth_client).context("FailoverClient::from_config()")?;
+        let client = SigningClient::new(
+            transport,
+            zksync_contract(),
+            address,
+            PrivateKeySigner::new(private_key),
+            contracts_config.diamond_proxy_addr,
+            eth_sender.gas_adjuster.default_priority_fee_per_gas.into(),
+            L1ChainId(eth_client.chain_id),
+        );
+        Ok(client.with_query_client(Arc::new(query_client)))
+    }
+}
+
+/// HTTP-based Ethereum client for the operator account. Transactions are signed by the remote signer
+/// if it is configured, and with the operator private key otherwise.
+pub type OperatorSigningClient = SigningClient<OperatorSigner>;
+
+impl OperatorSigningClient {
+    pub fn from_config(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+    ) -> anyhow::Result<Self> {
+        let (operator_address, signer) = if let Some(remote_signer) = &eth_sender.remote_signer {
+            remote_operator_signer(remote_signer, remote_signer.operator_address)?
+        } else {
+            let operator_private_key = eth_sender
+                .sender
+                .private_key()
+                .context("Operator private key or remote signer is required for signing client")?;
+            private_key_operator_signer(operator_private_key)?
+        };
+        tracing::info!("Operator address: {operator_address:?}");
+        Self::with_operator_signer(
+            operator_address,
+            signer,
+            eth_sender,
+            contracts_config,
+            eth_client,
+        )
+    }
+
+    /// Creates a client for the dedicated account sending transactions of the specified type.
+    /// Returns `None` if there is no dedicated account for `action_type` in the config; in this case,
+    /// the transactions should be sent from the main operator account.
+    pub fn for_action(
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+        action_type: AggregatedActionType,
+    ) -> anyhow::Result<Option<Self>> {
+        let operator = if let Some(remote_signer) = &eth_sender.remote_signer {
+            let address = match action_type {
+                AggregatedActionType::Commit => None,
+                AggregatedActionType::PublishProofOnchain => remote_signer.prove_operator_address,
+                AggregatedActionType::Execute => remote_signer.execute_operator_address,
+            };
+            address
+                .map(|address| remote_operator_signer(remote_signer, address))
+                .transpose()?
+        } else {
+            let private_key = match action_type {
+                AggregatedActionType::Commit => None,
+                AggregatedActionType::PublishProofOnchain => eth_sender.sender.prove_private_key(),
+                AggregatedActionType::Execute => eth_sender.sender.execute_private_key(),
+            };
+            private_key.map(private_key_operator_signer).transpose()?
+        };
+
+        let Some((operator_address, signer)) = operator else {
+            return Ok(None);
+        };
+        tracing::info!("Operator address for {action_type} transactions: {operator_address:?}");
+        Self::with_operator_signer(
+            operator_address,
+            signer,
+            eth_sender,
+            contracts_config,
+            eth_client,
+        )
+        .map(Some)
+    }
+
+    /// Checks that the remote signer (if any) is reachable and manages the sender account, so that
+    /// a misconfigured signer is detected on startup rather than when sending the first transaction.
+    pub async fn check_signer(&self) -> anyhow::Result<()> {
+        if let OperatorSigner::Remote(signer) = &self.inner.eth_signer {
+            signer.check_account().await.with_context(|| {
+                format!(
+                    "remote signer doesn't manage account {:?}",
+                    signer.address()
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn with_operator_signer(
+        operator_address: Address,
+        signer: OperatorSigner,
+        eth_sender: &ETHSenderConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: &ETHClientConfig,
+    ) -> anyhow::Result<Self> {
+        let transport =
+            Http::new(eth_client.web3_url.expose()).context("Failed to create transport")?;
+        let query_client =
+            FailoverClient::from_config(eth_client).context("FailoverClient::from_config()")?;
+        let client = SigningClient::new(
+            transport,
+            zksync_contract(),
+            operator_address,
+            signer,
+            contracts_config.diamond_proxy_addr,
+            eth_sender.gas_adjuster.default_priority_fee_per_gas.into(),
+            L1ChainId(eth_client.chain_id),
+        );
+        Ok(client.with_query_client(Arc::new(query_client)))
+    }
+}
+
+fn private_key_operator_signer(private_key: H256) -> anyhow::Result<(Address, OperatorSigner)> {
+    let address = PackedEthSignature::address_from_private_key(&private_key)
+        .context("Failed to get address from private key")?;
+    let signer = OperatorSigner::PrivateKey(PrivateKeySigner::new(private_key));
+    Ok((address, signer))
+}
+
+fn remote_operator_signer(
+    config: &RemoteSignerConfig,
+    address: Address,
+) -> anyhow::Result<(Address, OperatorSigner)> {
+    let signer = remote_signer_from_config(config, address)?;
+    Ok((address, OperatorSigner::Remote(signer)))
+}
+
+fn remote_signer_from_config(
+    config: &RemoteSignerConfig,
+    address: Address,
+) -> anyhow::Result<RemoteSigner> {
+    let mut builder =
+        RemoteSigner::builder(&config.url, address).with_timeout(config.request_timeout());
+    if let Some(token) = &config.bearer_token {
+        builder = builder.with_bearer_token(token.expose().as_str());
+    }
+    if let Some(path) = &config.ca_cert_path {
+        let pem =
+            fs::read(path).with_context(|| format!("failed reading CA certificate {path}"))?;
+        builder = builder.with_ca_cert_pem(pem);
+    }
+    match (&config.client_cert_path, &config.client_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let cert_pem = fs::read(cert_path)
+                .with_context(|| format!("failed reading client certificate {cert_path}"))?;
+            let key_pem = fs::read(key_path.expose()).context("failed reading client key")?;
+            builder = builder.with_client_identity_pem(&cert_pem, &key_pem);
+        }
+        (None, None) => { /* mTLS is not used */ }
+        _ => anyhow::bail!("client certificate and key for the remote signer must be set together"),
+    }
+    builder.build().context("failed building remote signer")
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -73,7 +259,7 @@ const FALLBACK_GAS_LIMIT: u64 = 3_000_000;
 #[derive(Clone)]
 pub struct SigningClient<S: EthereumSigner> {
     inner: Arc<ETHDirectClientInner<S>>,
-    query_client: QueryClient,
+    query_client: Arc<dyn EthInterface>,
 }
 
 struct ETHDirectClientInner<S: EthereumSigner> {
@@ -270,6 +456,8 @@ impl<S: EthereumSigner> BoundEthInterface for SigningClient<S> {
             transaction_type: Some(EIP_1559_TX_TYPE.into()),
             access_list: None,
             max_fee_per_gas,
+            max_fee_per_blob_gas: None,
+            blob_versioned_hashes: None,
         };
 
         let signed_tx = self.inner.eth_signer.sign_transaction(tx).await?;
@@ -318,7 +506,14 @@ impl<S: EthereumSigner> SigningClient<S> {
                 contract,
                 default_priority_fee_per_gas,
             }),
-            query_client: transport.into(),
+            query_client: Arc::new(QueryClient::from(transport)),
         }
     }
+
+    /// Replaces the client used for L1 queries and for broadcasting signed transactions
+    /// (by default, a [`QueryClient`] for the transport provided to the constructor).
+    pub fn with_query_client(mut self, query_client: Arc<dyn EthInterface>) -> Self {
+        self.query_client = query_client;
+        self
+    }
 }
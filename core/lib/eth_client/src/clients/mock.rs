@@ -1,10 +1,12 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    sync::RwLock,
+    fmt,
+    sync::{Arc, RwLock},
 };
 
 use async_trait::async_trait;
 use jsonrpc_core::types::error::Error as RpcError;
+use zksync_eth_signer::error::SignerError;
 use zksync_types::{
     web3::{
         contract::{tokens::Tokenize, Options},
@@ -73,6 +75,9 @@ struct MockEthereumInner {
     current_nonce: u64,
     pending_nonce: u64,
     nonces: BTreeMap<u64, u64>,
+    logs: Vec<Log>,
+    is_unreachable: bool,
+    is_signer_failing: bool,
 }
 
 impl MockEthereumInner {
@@ -112,6 +117,18 @@ impl MockEthereumInner {
     }
 }
 
+type CallHandlerFn = dyn Fn(&ContractCall) -> Vec<ethabi::Token> + Send + Sync;
+
+/// Custom handler for [`EthInterface::call_contract_function()`] calls.
+#[derive(Clone)]
+struct CallHandler(Arc<CallHandlerFn>);
+
+impl fmt::Debug for CallHandler {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("CallHandler").finish_non_exhaustive()
+    }
+}
+
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
 #[derive(Debug)]
 pub struct MockEthereum {
@@ -122,6 +139,8 @@ pub struct MockEthereum {
     /// This is useful for testing the cases when the transactions are executed out of order.
     non_ordering_confirmations: bool,
     multicall_address: Address,
+    sender_account: Address,
+    call_handler: Option<CallHandler>,
     inner: RwLock<MockEthereumInner>,
 }
 
@@ -133,6 +152,8 @@ impl Default for MockEthereum {
             base_fee_history: vec![],
             non_ordering_confirmations: false,
             multicall_address: Address::default(),
+            sender_account: Address::repeat_byte(0x11),
+            call_handler: None,
             inner: RwLock::default(),
         }
     }
@@ -171,6 +192,10 @@ impl MockEthereum {
         mut raw_tx: Vec<u8>,
         options: Options,
     ) -> Result<SignedCallResult, Error> {
+        if self.inner.read().unwrap().is_signer_failing {
+            let message = "Signer responded with a non-OK response: 500 Internal Server Error";
+            return Err(SignerError::SigningFailed(message.to_owned()).into());
+        }
         let max_fee_per_gas = options.max_fee_per_gas.unwrap_or(self.max_fee_per_gas);
         let max_priority_fee_per_gas = options
             .max_priority_fee_per_gas
@@ -196,6 +221,28 @@ impl MockEthereum {
         })
     }
 
+    /// Adds logs returned by [`EthInterface::logs()`].
+    pub fn add_logs(&self, logs: impl IntoIterator<Item = Log>) {
+        self.inner.write().unwrap().logs.extend(logs);
+    }
+
+    /// Makes all L1 requests to this client fail as if the node was unreachable.
+    pub fn set_unreachable(&self, is_unreachable: bool) {
+        self.inner.write().unwrap().is_unreachable = is_unreachable;
+    }
+
+    /// Makes signing transactions fail as if a remote signer responded with an HTTP 500 error.
+    pub fn set_signer_failing(&self, is_failing: bool) {
+        self.inner.write().unwrap().is_signer_failing = is_failing;
+    }
+
+    fn check_reachable(&self) -> Result<(), Error> {
+        if self.inner.read().unwrap().is_unreachable {
+            return Err(Error::EthereumGateway(Web3Error::Unreachable));
+        }
+        Ok(())
+    }
+
     pub fn advance_block_number(&self, val: u64) -> u64 {
         let mut inner = self.inner.write().unwrap();
         inner.block_number += val;
@@ -222,6 +269,25 @@ impl MockEthereum {
             ..self
         }
     }
+
+    pub fn with_sender_account(self, address: Address) -> Self {
+        Self {
+            sender_account: address,
+            ..self
+        }
+    }
+
+    /// Sets a handler for contract calls. If set, the handler takes precedence over the canned
+    /// response returned for calls to the multicall contract.
+    pub fn with_call_handler<F>(self, handler: F) -> Self
+    where
+        F: Fn(&ContractCall) -> Vec<ethabi::Token> + Send + Sync + 'static,
+    {
+        Self {
+            call_handler: Some(CallHandler(Arc::new(handler))),
+            ..self
+        }
+    }
 }
 
 #[async_trait]
@@ -231,14 +297,17 @@ impl EthInterface for MockEthereum {
         hash: H256,
         _: &'static str,
     ) -> Result<Option<ExecutedTxStatus>, Error> {
+        self.check_reachable()?;
         Ok(self.inner.read().unwrap().tx_statuses.get(&hash).cloned())
     }
 
     async fn block_number(&self, _: &'static str) -> Result<U64, Error> {
+        self.check_reachable()?;
         Ok(self.inner.read().unwrap().block_number.into())
     }
 
     async fn send_raw_tx(&self, tx: RawTransactionBytes) -> Result<H256, Error> {
+        self.check_reachable()?;
         let mock_tx = MockTx::from(tx.0);
         let mock_tx_hash = mock_tx.hash;
         let mut inner = self.inner.write().unwrap();
@@ -268,6 +337,7 @@ impl EthInterface for MockEthereum {
     }
 
     async fn get_gas_price(&self, _: &'static str) -> Result<U256, Error> {
+        self.check_reachable()?;
         Ok(self.max_fee_per_gas)
     }
 
@@ -277,6 +347,7 @@ impl EthInterface for MockEthereum {
         block_count: usize,
         _component: &'static str,
     ) -> Result<Vec<u64>, Error> {
+        self.check_reachable()?;
         let start_block = from_block.saturating_sub(block_count - 1);
         Ok(self.base_fee_history[start_block..=from_block].to_vec())
     }
@@ -285,10 +356,12 @@ impl EthInterface for MockEthereum {
         &self,
         _component: &'static str,
     ) -> Result<U256, Error> {
+        self.check_reachable()?;
         Ok(U256::from(*self.base_fee_history.last().unwrap()))
     }
 
     async fn failure_reason(&self, tx_hash: H256) -> Result<Option<FailureInfo>, Error> {
+        self.check_reachable()?;
         let tx_status = self.get_tx_status(tx_hash, "failure_reason").await.unwrap();
 
         Ok(tx_status.map(|status| FailureInfo {
@@ -305,6 +378,10 @@ impl EthInterface for MockEthereum {
     ) -> Result<Vec<ethabi::Token>, Error> {
         use ethabi::Token;
 
+        self.check_reachable()?;
+        if let Some(CallHandler(handler)) = &self.call_handler {
+            return Ok(handler(&call));
+        }
         if call.contract_address == self.multicall_address {
             let token = Token::Array(vec![
                 Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![1u8; 32])]),
@@ -330,6 +407,7 @@ impl EthInterface for MockEthereum {
         hash: H256,
         _component: &'static str,
     ) -> Result<Option<Transaction>, Error> {
+        self.check_reachable()?;
         let txs = &self.inner.read().unwrap().sent_txs;
         let Some(tx) = txs.get(&hash) else {
             return Ok(None);
@@ -339,10 +417,14 @@ impl EthInterface for MockEthereum {
 
     async fn tx_receipt(
         &self,
-        _tx_hash: H256,
+        tx_hash: H256,
         _component: &'static str,
     ) -> Result<Option<TransactionReceipt>, Error> {
-        unimplemented!("Not needed right now")
+        self.check_reachable()?;
+        let tx_statuses = &self.inner.read().unwrap().tx_statuses;
+        Ok(tx_statuses
+            .get(&tx_hash)
+            .map(|status| status.receipt.clone()))
     }
 
     async fn eth_balance(
@@ -354,7 +436,8 @@ impl EthInterface for MockEthereum {
     }
 
     async fn logs(&self, _filter: Filter, _component: &'static str) -> Result<Vec<Log>, Error> {
-        unimplemented!("Not needed right now")
+        self.check_reachable()?;
+        Ok(self.inner.read().unwrap().logs.clone())
     }
 
     async fn block(
@@ -381,7 +464,7 @@ impl BoundEthInterface for MockEthereum {
     }
 
     fn sender_account(&self) -> Address {
-        Address::repeat_byte(0x11)
+        self.sender_account
     }
 
     async fn sign_prepared_tx_for_addr(
@@ -1,10 +1,12 @@
 //! Various Ethereum client implementations.
 
+mod failover;
 mod generic;
 mod http;
 mod mock;
 
 pub use self::{
-    http::{PKSigningClient, QueryClient, SigningClient},
+    failover::FailoverClient,
+    http::{OperatorSigningClient, PKSigningClient, QueryClient, SigningClient},
     mock::MockEthereum,
 };
@@ -8,7 +8,7 @@ use zksync_types::web3::{
 };
 
 /// Wrapper for `Vec<ethabi::Token>` that doesn't wrap them in an additional array in `Tokenize` implementation.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct RawTokens(pub Vec<ethabi::Token>);
 
 impl Tokenize for RawTokens {
@@ -24,7 +24,7 @@ impl Detokenize for RawTokens {
 }
 
 /// Arguments for calling a function in an unspecified Ethereum smart contract.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CallFunctionArgs {
     pub(crate) name: String,
     pub(crate) from: Option<Address>,
@@ -69,13 +69,27 @@ impl CallFunctionArgs {
 
 /// Information sufficient for calling a function in a specific Ethereum smart contract. Instantiated
 /// using [`CallFunctionArgs::for_contract()`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ContractCall {
     pub(crate) contract_address: Address,
     pub(crate) contract_abi: ethabi::Contract,
     pub(crate) inner: CallFunctionArgs,
 }
 
+impl ContractCall {
+    pub fn contract_address(&self) -> Address {
+        self.contract_address
+    }
+
+    pub fn function_name(&self) -> &str {
+        &self.inner.name
+    }
+
+    pub fn args(&self) -> &[ethabi::Token] {
+        &self.inner.params.0
+    }
+}
+
 /// Common error type exposed by the crate,
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
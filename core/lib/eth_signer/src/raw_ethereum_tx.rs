@@ -9,19 +9,25 @@
 //! In the case where it will be possible to use only the web3 library without copy-paste, the changes will be small and simple
 //! Link to @Deniallugo's PR to web3: https://github.com/tomusdrw/rust-web3/pull/630
 
-use rlp::RlpStream;
+use rlp::{DecoderError, Rlp, RlpStream};
 use zksync_types::{
     ethabi::Address,
+    tx::primitives::PackedEthSignature,
     web3::{
         signing::{self, Signature},
-        types::{AccessList, SignedTransaction},
+        types::{AccessList, AccessListItem, SignedTransaction},
     },
-    U256, U64,
+    H256, U256, U64,
 };
 
+use crate::SignerError;
+
 const LEGACY_TX_ID: u64 = 0;
 const ACCESSLISTS_TX_ID: u64 = 1;
-const EIP1559_TX_ID: u64 = 2;
+/// EIP-1559 transaction type.
+pub const EIP1559_TX_ID: u64 = 2;
+/// EIP-4844 blob transaction type.
+pub const EIP4844_TX_ID: u64 = 3;
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct TransactionParameters {
@@ -47,6 +53,10 @@ pub struct TransactionParameters {
     pub max_fee_per_gas: U256,
     /// miner bribe
     pub max_priority_fee_per_gas: U256,
+    /// Max fee per blob gas (EIP-4844 transactions only)
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// Versioned hashes of the blobs (EIP-4844 transactions only)
+    pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
 /// A transaction used for RLP encoding, hashing and signing.
@@ -61,6 +71,8 @@ pub struct Transaction {
     pub transaction_type: Option<U64>,
     pub access_list: AccessList,
     pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_blob_gas: U256,
+    pub blob_versioned_hashes: Vec<H256>,
 }
 
 impl Transaction {
@@ -147,6 +159,36 @@ impl Transaction {
         stream
     }
 
+    /// Encodes the EIP-4844 transaction payload. Blobs themselves (as well as their commitments
+    /// and proofs) are not a part of the signed payload, so they are not required for signing.
+    fn encode_eip4844_payload(&self, chain_id: u64, signature: Option<&Signature>) -> RlpStream {
+        let mut stream = RlpStream::new();
+
+        let list_size = if signature.is_some() { 14 } else { 11 };
+        stream.begin_list(list_size);
+
+        stream.append(&chain_id);
+        stream.append(&self.nonce);
+        stream.append(&self.max_priority_fee_per_gas);
+        stream.append(&self.gas_price);
+        stream.append(&self.gas);
+        // Blob transactions cannot create contracts, so `to` is always set.
+        stream.append(&self.to.unwrap_or_default());
+        stream.append(&self.value);
+        stream.append(&self.data);
+
+        self.rlp_append_access_list(&mut stream);
+
+        stream.append(&self.max_fee_per_blob_gas);
+        stream.append_list(&self.blob_versioned_hashes);
+
+        if let Some(signature) = signature {
+            self.rlp_append_signature(&mut stream, signature);
+        }
+
+        stream
+    }
+
     fn rlp_append_signature(&self, stream: &mut RlpStream, signature: &Signature) {
         stream.append(&signature.v);
         stream.append(&U256::from_big_endian(signature.r.as_bytes()));
@@ -184,6 +226,12 @@ impl Transaction {
                 [&[tx_id], stream.as_raw()].concat()
             }
 
+            Some(EIP4844_TX_ID) => {
+                let tx_id: u8 = EIP4844_TX_ID as u8;
+                let stream = self.encode_eip4844_payload(chain_id, signature);
+                [&[tx_id], stream.as_raw()].concat()
+            }
+
             _ => {
                 panic!("Unsupported transaction type");
             }
@@ -222,3 +270,155 @@ impl Transaction {
         }
     }
 }
+
+/// Signed transaction decoded from its RLP encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedTransaction {
+    /// Transaction parameters. For legacy and access list transactions, the gas price is set both
+    /// as `gas_price` and `max_fee_per_gas`.
+    pub params: TransactionParameters,
+    /// Sender recovered from the transaction signature.
+    pub from: Address,
+}
+
+fn decoding_error(err: DecoderError) -> SignerError {
+    SignerError::DecodeRawTxFailed(err.to_string())
+}
+
+fn decode_to(rlp: &Rlp<'_>, index: usize) -> Result<Option<Address>, DecoderError> {
+    let to = rlp.at(index)?;
+    if to.is_empty() {
+        Ok(None)
+    } else {
+        to.as_val().map(Some)
+    }
+}
+
+fn decode_access_list(rlp: &Rlp<'_>, index: usize) -> Result<AccessList, DecoderError> {
+    rlp.at(index)?
+        .iter()
+        .map(|item| {
+            Ok(AccessListItem {
+                address: item.val_at(0)?,
+                storage_keys: item.list_at(1)?,
+            })
+        })
+        .collect()
+}
+
+/// Decodes a signed legacy, access list, EIP-1559 or EIP-4844 transaction (the latter without
+/// the blob sidecar) and recovers its sender.
+pub fn decode_signed_transaction(raw_tx: &[u8]) -> Result<DecodedTransaction, SignerError> {
+    let (tx_type, payload) = match raw_tx.first() {
+        Some(&byte) if byte >= 0xc0 => (LEGACY_TX_ID, raw_tx),
+        Some(&byte) => (u64::from(byte), &raw_tx[1..]),
+        None => {
+            return Err(SignerError::DecodeRawTxFailed(
+                "empty transaction".to_owned(),
+            ))
+        }
+    };
+    let expected_item_count = match tx_type {
+        LEGACY_TX_ID => 9,
+        ACCESSLISTS_TX_ID => 11,
+        EIP1559_TX_ID => 12,
+        EIP4844_TX_ID => 14,
+        _ => {
+            return Err(SignerError::DecodeRawTxFailed(format!(
+                "unsupported transaction type {tx_type}"
+            )))
+        }
+    };
+    let rlp = Rlp::new(payload);
+    let item_count = rlp.item_count().map_err(decoding_error)?;
+    if item_count != expected_item_count {
+        return Err(SignerError::DecodeRawTxFailed(format!(
+            "expected {expected_item_count} items for transaction type {tx_type}, got {item_count}"
+        )));
+    }
+
+    let (params, signature_index, recovery_id) = if tx_type == LEGACY_TX_ID {
+        let gas_price: U256 = rlp.val_at(1).map_err(decoding_error)?;
+        let v: u64 = rlp.val_at(6).map_err(decoding_error)?;
+        let (recovery_id, chain_id) = PackedEthSignature::unpack_v(v)
+            .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string()))?;
+        let chain_id = chain_id.ok_or_else(|| {
+            SignerError::DecodeRawTxFailed("transaction is not replay-protected".to_owned())
+        })?;
+        let params = TransactionParameters {
+            nonce: rlp.val_at(0).map_err(decoding_error)?,
+            gas_price: Some(gas_price),
+            gas: rlp.val_at(2).map_err(decoding_error)?,
+            to: decode_to(&rlp, 3).map_err(decoding_error)?,
+            value: rlp.val_at(4).map_err(decoding_error)?,
+            data: rlp.val_at(5).map_err(decoding_error)?,
+            chain_id,
+            max_fee_per_gas: gas_price,
+            ..TransactionParameters::default()
+        };
+        (params, 7, recovery_id)
+    } else {
+        let mut params = TransactionParameters {
+            chain_id: rlp.val_at(0).map_err(decoding_error)?,
+            nonce: rlp.val_at(1).map_err(decoding_error)?,
+            transaction_type: Some(tx_type.into()),
+            ..TransactionParameters::default()
+        };
+        // Access list transactions have a single gas price instead of two EIP-1559 fee fields.
+        let fee_offset = if tx_type == ACCESSLISTS_TX_ID {
+            let gas_price: U256 = rlp.val_at(2).map_err(decoding_error)?;
+            params.gas_price = Some(gas_price);
+            params.max_fee_per_gas = gas_price;
+            3
+        } else {
+            params.max_priority_fee_per_gas = rlp.val_at(2).map_err(decoding_error)?;
+            params.max_fee_per_gas = rlp.val_at(3).map_err(decoding_error)?;
+            4
+        };
+        params.gas = rlp.val_at(fee_offset).map_err(decoding_error)?;
+        params.to = decode_to(&rlp, fee_offset + 1).map_err(decoding_error)?;
+        params.value = rlp.val_at(fee_offset + 2).map_err(decoding_error)?;
+        params.data = rlp.val_at(fee_offset + 3).map_err(decoding_error)?;
+        params.access_list =
+            Some(decode_access_list(&rlp, fee_offset + 4).map_err(decoding_error)?);
+        let mut signature_index = fee_offset + 5;
+        if tx_type == EIP4844_TX_ID {
+            params.max_fee_per_blob_gas =
+                Some(rlp.val_at(signature_index).map_err(decoding_error)?);
+            params.blob_versioned_hashes =
+                Some(rlp.list_at(signature_index + 1).map_err(decoding_error)?);
+            signature_index += 2;
+        }
+        let recovery_id: u8 = rlp.val_at(signature_index).map_err(decoding_error)?;
+        (params, signature_index + 1, recovery_id)
+    };
+
+    let r: U256 = rlp.val_at(signature_index).map_err(decoding_error)?;
+    let s: U256 = rlp.val_at(signature_index + 1).map_err(decoding_error)?;
+    let mut r_bytes = H256::zero();
+    r.to_big_endian(r_bytes.as_bytes_mut());
+    let mut s_bytes = H256::zero();
+    s.to_big_endian(s_bytes.as_bytes_mut());
+    let signature = PackedEthSignature::from_rsv(&r_bytes, &s_bytes, recovery_id);
+
+    let unsigned_tx = Transaction {
+        to: params.to,
+        nonce: params.nonce,
+        gas: params.gas,
+        gas_price: params.max_fee_per_gas,
+        value: params.value,
+        data: params.data.clone(),
+        transaction_type: params.transaction_type,
+        access_list: params.access_list.clone().unwrap_or_default(),
+        max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+        max_fee_per_blob_gas: params.max_fee_per_blob_gas.unwrap_or_default(),
+        blob_versioned_hashes: params.blob_versioned_hashes.clone().unwrap_or_default(),
+    };
+    let signed_bytes = H256(signing::keccak256(
+        &unsigned_tx.encode(params.chain_id, None),
+    ));
+    let from = signature
+        .signature_recover_signer(&signed_bytes)
+        .map_err(|err| SignerError::RecoverAddress(err.to_string()))?;
+    Ok(DecodedTransaction { params, from })
+}
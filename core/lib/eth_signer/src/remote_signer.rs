@@ -0,0 +1,601 @@
+//! Signer delegating to a remote service holding the private key, so that the key never
+//! leaves the signer. The service must expose a web3signer-compatible JSON-RPC API
+//! (`eth_sign`, `eth_signTypedData` and `eth_signTransaction`).
+
+use std::{fmt, time::Duration};
+
+use jsonrpc_core::types::response::Output;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use serde_json::json;
+use zksync_types::{
+    eip712_signature::utils::get_eip712_json, tx::primitives::PackedEthSignature, Address,
+    EIP712TypedStructure, Eip712Domain, H256, U64,
+};
+
+use crate::{
+    error::{RpcSignerError, SignerError},
+    json_rpc_signer::is_signature_from_address,
+    raw_ethereum_tx::{
+        decode_signed_transaction, DecodedTransaction, TransactionParameters, EIP1559_TX_ID,
+        EIP4844_TX_ID,
+    },
+    EthereumSigner,
+};
+
+/// Builder for [`RemoteSigner`].
+pub struct RemoteSignerBuilder {
+    url: String,
+    address: Address,
+    bearer_token: Option<String>,
+    ca_cert_pem: Option<Vec<u8>>,
+    client_identity_pem: Option<Vec<u8>>,
+    timeout: Duration,
+}
+
+impl fmt::Debug for RemoteSignerBuilder {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not expose the token or the client key.
+        formatter
+            .debug_struct("RemoteSignerBuilder")
+            .field("url", &self.url)
+            .field("address", &self.address)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl RemoteSignerBuilder {
+    /// Sets the token sent in the `Authorization: Bearer` header.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// Adds a PEM-encoded CA certificate used to verify the signer in addition to the system roots.
+    pub fn with_ca_cert_pem(mut self, pem: Vec<u8>) -> Self {
+        self.ca_cert_pem = Some(pem);
+        self
+    }
+
+    /// Sets the PEM-encoded client certificate and PKCS#8 private key used for mTLS.
+    pub fn with_client_identity_pem(mut self, cert_pem: &[u8], key_pem: &[u8]) -> Self {
+        self.client_identity_pem = Some([cert_pem, b"\n", key_pem].concat());
+        self
+    }
+
+    /// Sets the timeout for a single signing request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn build(self) -> Result<RemoteSigner, SignerError> {
+        let mut client = reqwest::Client::builder()
+            .use_rustls_tls()
+            .timeout(self.timeout);
+        if let Some(token) = &self.bearer_token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(|_| SignerError::CustomError("invalid bearer token".to_owned()))?;
+            value.set_sensitive(true);
+            let mut headers = HeaderMap::new();
+            headers.insert(header::AUTHORIZATION, value);
+            client = client.default_headers(headers);
+        }
+        if let Some(pem) = &self.ca_cert_pem {
+            let cert = reqwest::Certificate::from_pem(pem).map_err(|err| {
+                SignerError::CustomError(format!("invalid CA certificate: {err}"))
+            })?;
+            client = client.add_root_certificate(cert);
+        }
+        if let Some(pem) = &self.client_identity_pem {
+            let identity = reqwest::Identity::from_pem(pem).map_err(|err| {
+                SignerError::CustomError(format!("invalid client certificate or key: {err}"))
+            })?;
+            client = client.identity(identity);
+        }
+        let client = client.build().map_err(|err| {
+            SignerError::CustomError(format!("failed building HTTP client: {err}"))
+        })?;
+
+        Ok(RemoteSigner {
+            url: self.url,
+            address: self.address,
+            client,
+        })
+    }
+}
+
+/// Signer for a single account managed by a remote signing service.
+#[derive(Clone)]
+pub struct RemoteSigner {
+    url: String,
+    address: Address,
+    client: reqwest::Client,
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("RemoteSigner")
+            .field("url", &self.url)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+impl RemoteSigner {
+    /// Creates a builder for the signer of the `address` account served at `url`.
+    pub fn builder(url: impl Into<String>, address: Address) -> RemoteSignerBuilder {
+        RemoteSignerBuilder {
+            url: url.into(),
+            address,
+            bearer_token: None,
+            ca_cert_pem: None,
+            client_identity_pem: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Address of the account managed by this signer.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Checks that the signer is reachable and manages the account.
+    pub async fn check_account(&self) -> Result<(), SignerError> {
+        let accounts = self
+            .call("eth_accounts", vec![])
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let accounts: Vec<Address> = serde_json::from_value(accounts)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        if accounts.contains(&self.address) {
+            Ok(())
+        } else {
+            Err(SignerError::DefineAddress)
+        }
+    }
+
+    async fn sign(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value, SignerError> {
+        self.call(method, params)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))
+    }
+
+    async fn call(
+        &self,
+        method: &str,
+        params: Vec<serde_json::Value>,
+    ) -> Result<serde_json::Value, RpcSignerError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| RpcSignerError::NetworkError(err.to_string()))?;
+        if response.status() != reqwest::StatusCode::OK {
+            let error = format!(
+                "Signer responded with a non-OK response: {}",
+                response.status()
+            );
+            return Err(RpcSignerError::NetworkError(error));
+        }
+        let output: Output = response
+            .json()
+            .await
+            .map_err(|err| RpcSignerError::MalformedResponse(err.to_string()))?;
+        match output {
+            Output::Success(success) => Ok(success.result),
+            Output::Failure(failure) => Err(RpcSignerError::RpcError(failure)),
+        }
+    }
+
+    fn check_signature(
+        &self,
+        signature: PackedEthSignature,
+        signed_bytes: &H256,
+    ) -> Result<PackedEthSignature, SignerError> {
+        if is_signature_from_address(&signature, signed_bytes, self.address)? {
+            Ok(signature)
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from RemoteSigner".to_owned(),
+            ))
+        }
+    }
+}
+
+/// Serializes the transaction as expected by `eth_signTransaction`.
+fn transaction_json(from: Address, tx: &TransactionParameters) -> serde_json::Value {
+    let mut json = json!({
+        "from": from,
+        "gas": tx.gas,
+        "value": tx.value,
+        "data": format!("0x{}", hex::encode(&tx.data)),
+        "nonce": tx.nonce,
+        "chainId": U64::from(tx.chain_id),
+    });
+    if let Some(to) = tx.to {
+        json["to"] = json!(to);
+    }
+
+    let tx_type = tx.transaction_type.map_or(0, |ty| ty.as_u64());
+    if tx_type == EIP1559_TX_ID || tx_type == EIP4844_TX_ID {
+        json["type"] = json!(U64::from(tx_type));
+        json["maxFeePerGas"] = json!(tx.max_fee_per_gas);
+        json["maxPriorityFeePerGas"] = json!(tx.max_priority_fee_per_gas);
+    } else {
+        json["gasPrice"] = json!(tx.gas_price.unwrap_or(tx.max_fee_per_gas));
+    }
+    if tx_type == EIP4844_TX_ID {
+        json["maxFeePerBlobGas"] = json!(tx.max_fee_per_blob_gas.unwrap_or_default());
+        json["blobVersionedHashes"] = json!(tx.blob_versioned_hashes.clone().unwrap_or_default());
+    }
+    json
+}
+
+/// Checks that the transaction signed by the remote signer is the requested one and is sent from `address`,
+/// so that a compromised or misbehaving signer cannot make the operator broadcast an arbitrary transaction.
+fn check_signed_transaction(
+    address: Address,
+    requested: &TransactionParameters,
+    signed: &DecodedTransaction,
+) -> Result<(), SignerError> {
+    let mismatch = |field: &str| {
+        Err(SignerError::SigningFailed(format!(
+            "transaction signed by RemoteSigner has unexpected {field}"
+        )))
+    };
+
+    let params = &signed.params;
+    if signed.from != address {
+        return mismatch("sender");
+    }
+    if params.nonce != requested.nonce {
+        return mismatch("nonce");
+    }
+    if params.to != requested.to {
+        return mismatch("recipient");
+    }
+    if params.data != requested.data {
+        return mismatch("calldata");
+    }
+    if params.gas != requested.gas {
+        return mismatch("gas limit");
+    }
+    if params.value != requested.value {
+        return mismatch("value");
+    }
+    if params.chain_id != requested.chain_id {
+        return mismatch("chain ID");
+    }
+
+    let tx_type = requested.transaction_type.map_or(0, |ty| ty.as_u64());
+    if params.transaction_type.map_or(0, |ty| ty.as_u64()) != tx_type {
+        return mismatch("type");
+    }
+    if tx_type == EIP1559_TX_ID || tx_type == EIP4844_TX_ID {
+        if params.max_fee_per_gas != requested.max_fee_per_gas
+            || params.max_priority_fee_per_gas != requested.max_priority_fee_per_gas
+        {
+            return mismatch("fees");
+        }
+    } else if params.gas_price != Some(requested.gas_price.unwrap_or(requested.max_fee_per_gas)) {
+        return mismatch("gas price");
+    }
+    if tx_type == EIP4844_TX_ID
+        && (params.max_fee_per_blob_gas != Some(requested.max_fee_per_blob_gas.unwrap_or_default())
+            || params.blob_versioned_hashes.as_deref().unwrap_or_default()
+                != requested
+                    .blob_versioned_hashes
+                    .as_deref()
+                    .unwrap_or_default())
+    {
+        return mismatch("blob parameters");
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl EthereumSigner for RemoteSigner {
+    /// Signs the message with the `\x19Ethereum Signed Message:\n` prefix added by the signer.
+    async fn sign_message(&self, message: &[u8]) -> Result<PackedEthSignature, SignerError> {
+        let params = vec![
+            json!(self.address),
+            json!(format!("0x{}", hex::encode(message))),
+        ];
+        let signature = self.sign("eth_sign", params).await?;
+        let signature: PackedEthSignature = serde_json::from_value(signature)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signed_bytes = PackedEthSignature::message_to_signed_bytes(message);
+        self.check_signature(signature, &signed_bytes)
+    }
+
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let params = vec![json!(self.address), get_eip712_json(domain, typed_struct)];
+        let signature = self.sign("eth_signTypedData", params).await?;
+        let signature: PackedEthSignature = serde_json::from_value(signature)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signed_bytes = PackedEthSignature::typed_data_to_signed_bytes(domain, typed_struct);
+        self.check_signature(signature, &signed_bytes)
+    }
+
+    /// Signs the transaction and returns it RLP-encoded. Supports legacy, EIP-1559 and EIP-4844
+    /// transactions; for the latter, the returned transaction does not contain the blob sidecar.
+    /// The signed transaction is decoded and checked against `raw_tx`.
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let params = vec![transaction_json(self.address, &raw_tx)];
+        let signed_tx = self.sign("eth_signTransaction", params).await?;
+        let signed_tx = signed_tx.as_str().ok_or_else(|| {
+            SignerError::DecodeRawTxFailed(format!("expected hex string, got {signed_tx}"))
+        })?;
+        let signed_tx = signed_tx.strip_prefix("0x").unwrap_or(signed_tx);
+        let signed_tx = hex::decode(signed_tx)
+            .map_err(|err| SignerError::DecodeRawTxFailed(err.to_string()))?;
+        let decoded_tx = decode_signed_transaction(&signed_tx)?;
+        check_signed_transaction(self.address, &raw_tx, &decoded_tx)?;
+        Ok(signed_tx)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{
+        post,
+        web::{self, Data},
+        App, HttpRequest, HttpResponse, HttpServer, Responder,
+    };
+    use futures::future::{AbortHandle, Abortable};
+    use serde_json::Value;
+    use zksync_types::{H160, U256};
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    const TOKEN: &str = "test-token";
+
+    /// Fake web3signer backed by a local private key.
+    #[derive(Clone)]
+    struct FakeSigner {
+        signer: PrivateKeySigner,
+        /// Added to the nonce of the signed transactions to emulate a misbehaving signer.
+        nonce_offset: u64,
+    }
+
+    fn parse_u256(value: &Value) -> U256 {
+        serde_json::from_value(value.clone()).unwrap()
+    }
+
+    fn parse_transaction(json: &Value) -> TransactionParameters {
+        let transaction_type: U64 = json.get("type").map_or(U64::zero(), |ty| {
+            serde_json::from_value(ty.clone()).unwrap()
+        });
+        let data = json["data"].as_str().unwrap();
+        TransactionParameters {
+            nonce: parse_u256(&json["nonce"]),
+            to: json
+                .get("to")
+                .map(|to| serde_json::from_value(to.clone()).unwrap()),
+            gas: parse_u256(&json["gas"]),
+            gas_price: json.get("gasPrice").map(parse_u256),
+            value: parse_u256(&json["value"]),
+            data: hex::decode(&data[2..]).unwrap(),
+            chain_id: serde_json::from_value::<U64>(json["chainId"].clone())
+                .unwrap()
+                .as_u64(),
+            transaction_type: Some(transaction_type),
+            access_list: None,
+            max_fee_per_gas: json
+                .get("maxFeePerGas")
+                .or_else(|| json.get("gasPrice"))
+                .map(parse_u256)
+                .unwrap(),
+            max_priority_fee_per_gas: json
+                .get("maxPriorityFeePerGas")
+                .map(parse_u256)
+                .unwrap_or_default(),
+            max_fee_per_blob_gas: json.get("maxFeePerBlobGas").map(parse_u256),
+            blob_versioned_hashes: json
+                .get("blobVersionedHashes")
+                .map(|hashes| serde_json::from_value(hashes.clone()).unwrap()),
+        }
+    }
+
+    #[post("/")]
+    async fn index(
+        request: HttpRequest,
+        body: web::Json<Value>,
+        state: Data<FakeSigner>,
+    ) -> impl Responder {
+        let auth = request.headers().get(header::AUTHORIZATION);
+        if auth.and_then(|value| value.to_str().ok()) != Some(format!("Bearer {TOKEN}").as_str()) {
+            return HttpResponse::Unauthorized().finish();
+        }
+
+        let address = state.signer.get_address().await.unwrap();
+        let params = body["params"].as_array().unwrap();
+        let result = match body["method"].as_str().unwrap() {
+            "eth_accounts" => json!([address]),
+            "eth_sign" => {
+                let message = hex::decode(&params[1].as_str().unwrap()[2..]).unwrap();
+                json!(state.signer.sign_message(&message).await.unwrap())
+            }
+            "eth_signTransaction" => {
+                let mut tx = parse_transaction(&params[0]);
+                tx.nonce += state.nonce_offset.into();
+                let signed = state.signer.sign_transaction(tx).await.unwrap();
+                json!(format!("0x{}", hex::encode(signed)))
+            }
+            method => {
+                let error =
+                    json!({ "code": -32601, "message": format!("unknown method {method}") });
+                return HttpResponse::Ok()
+                    .json(json!({ "jsonrpc": "2.0", "id": 1, "error": error }));
+            }
+        };
+        HttpResponse::Ok().json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+    }
+
+    fn run_server(signer: FakeSigner) -> (String, AbortHandle) {
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(Data::new(signer.clone()))
+                .service(index)
+        })
+        .bind("127.0.0.1:0")
+        .unwrap();
+        let addr = server.addrs()[0];
+
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        tokio::spawn(Abortable::new(server.run(), abort_registration));
+        (format!("http://{addr}/"), abort_handle)
+    }
+
+    fn test_transactions() -> Vec<TransactionParameters> {
+        let eip1559_tx = TransactionParameters {
+            nonce: 5.into(),
+            to: Some(H160::repeat_byte(1)),
+            gas: 1_000_000.into(),
+            value: 0.into(),
+            data: vec![1, 2, 3],
+            chain_id: 9,
+            transaction_type: Some(EIP1559_TX_ID.into()),
+            max_fee_per_gas: 100.into(),
+            max_priority_fee_per_gas: 10.into(),
+            ..TransactionParameters::default()
+        };
+        let blob_tx = TransactionParameters {
+            transaction_type: Some(EIP4844_TX_ID.into()),
+            max_fee_per_blob_gas: Some(50.into()),
+            blob_versioned_hashes: Some(vec![H256::repeat_byte(1), H256::repeat_byte(2)]),
+            ..eip1559_tx.clone()
+        };
+        vec![eip1559_tx, blob_tx]
+    }
+
+    #[actix_rt::test]
+    async fn remote_signer_signs_like_local_signer() {
+        let local_signer = PrivateKeySigner::new(H256::repeat_byte(0x17));
+        let address = local_signer.get_address().await.unwrap();
+        let (url, abort_handle) = run_server(FakeSigner {
+            signer: local_signer.clone(),
+            nonce_offset: 0,
+        });
+        let signer = RemoteSigner::builder(url, address)
+            .with_bearer_token(TOKEN)
+            .build()
+            .unwrap();
+        signer.check_account().await.unwrap();
+
+        for tx in test_transactions() {
+            let expected = local_signer.sign_transaction(tx.clone()).await.unwrap();
+            let signed = signer.sign_transaction(tx.clone()).await.unwrap();
+            assert_eq!(signed, expected, "{tx:?}");
+        }
+
+        let message = b"some_text_message";
+        let signature = signer.sign_message(message).await.unwrap();
+        let signed_bytes = PackedEthSignature::message_to_signed_bytes(message);
+        assert!(is_signature_from_address(&signature, &signed_bytes, address).unwrap());
+
+        abort_handle.abort();
+    }
+
+    #[actix_rt::test]
+    async fn remote_signer_errors() {
+        let local_signer = PrivateKeySigner::new(H256::repeat_byte(0x17));
+        let address = local_signer.get_address().await.unwrap();
+        let (url, abort_handle) = run_server(FakeSigner {
+            signer: local_signer,
+            nonce_offset: 0,
+        });
+
+        let unauthorized_signer = RemoteSigner::builder(&url, address).build().unwrap();
+        let err = unauthorized_signer
+            .sign_transaction(test_transactions().remove(0))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("401"), "{err}");
+
+        // The signer doesn't manage this account.
+        let wrong_signer = RemoteSigner::builder(&url, Address::repeat_byte(1))
+            .with_bearer_token(TOKEN)
+            .build()
+            .unwrap();
+        let err = wrong_signer.check_account().await.unwrap_err();
+        assert_eq!(err, SignerError::DefineAddress);
+        let err = wrong_signer.sign_message(b"test").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid signature"), "{err}");
+        let err = wrong_signer
+            .sign_transaction(test_transactions().remove(0))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unexpected sender"), "{err}");
+
+        abort_handle.abort();
+    }
+
+    #[actix_rt::test]
+    async fn remote_signer_rejects_tampered_transactions() {
+        let local_signer = PrivateKeySigner::new(H256::repeat_byte(0x17));
+        let address = local_signer.get_address().await.unwrap();
+        let (url, abort_handle) = run_server(FakeSigner {
+            signer: local_signer,
+            nonce_offset: 1,
+        });
+        let signer = RemoteSigner::builder(url, address)
+            .with_bearer_token(TOKEN)
+            .build()
+            .unwrap();
+
+        for tx in test_transactions() {
+            let err = signer.sign_transaction(tx).await.unwrap_err();
+            assert!(err.to_string().contains("unexpected nonce"), "{err}");
+        }
+        abort_handle.abort();
+    }
+
+    #[tokio::test]
+    async fn decoding_signed_transactions() {
+        let local_signer = PrivateKeySigner::new(H256::repeat_byte(0x17));
+        let address = local_signer.get_address().await.unwrap();
+        let legacy_tx = TransactionParameters {
+            nonce: 3.into(),
+            to: Some(H160::repeat_byte(2)),
+            gas: 21_000.into(),
+            value: 1_000.into(),
+            chain_id: 9,
+            max_fee_per_gas: 100.into(),
+            ..TransactionParameters::default()
+        };
+
+        for tx in test_transactions().into_iter().chain([legacy_tx]) {
+            let signed = local_signer.sign_transaction(tx.clone()).await.unwrap();
+            let decoded = decode_signed_transaction(&signed).unwrap();
+            assert_eq!(decoded.from, address);
+            check_signed_transaction(address, &tx, &decoded).unwrap();
+        }
+    }
+}
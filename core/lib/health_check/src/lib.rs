@@ -4,7 +4,7 @@ use std::{collections::HashMap, thread, time::Duration};
 pub use async_trait::async_trait;
 use futures::future;
 use serde::Serialize;
-use tokio::sync::watch;
+use tokio::{sync::watch, time::Instant};
 
 /// Health status returned as a part of `Health`.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize)]
@@ -17,6 +17,8 @@ pub enum HealthStatus {
     Ready,
     /// Component is shut down.
     ShutDown,
+    /// Component hasn't updated its health within the expected deadline; it is probably stuck.
+    Stale,
     /// Component has been abnormally interrupted by a panic.
     Panicked,
 }
@@ -27,12 +29,57 @@ impl HealthStatus {
         matches!(self, Self::Ready)
     }
 
+    /// Checks whether a component is alive according to this status, i.e. doesn't require restarting the process.
+    /// Note that a component that is not ready (e.g., still initializing) is considered alive.
+    pub fn is_alive(self) -> bool {
+        !matches!(self, Self::Stale | Self::Panicked)
+    }
+
+    fn is_terminal(self) -> bool {
+        matches!(self, Self::ShutDown | Self::Panicked)
+    }
+
     fn priority_for_aggregation(self) -> usize {
         match self {
             Self::Ready => 0,
             Self::ShutDown => 1,
             Self::NotReady => 2,
-            Self::Panicked => 3,
+            Self::Stale => 3,
+            Self::Panicked => 4,
+        }
+    }
+}
+
+/// Kind of a health check determining which application probes it affects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HealthCheckKind {
+    /// Check only affects application readiness. A non-ready component (e.g., the external node still syncing)
+    /// means that the application shouldn't receive traffic, but doesn't need to be restarted.
+    #[default]
+    Readiness,
+    /// Check affects both application readiness and liveness. If the component has panicked or its health
+    /// has become stale, the application is considered stuck and should be restarted.
+    Liveness,
+}
+
+/// Options for a [`ReactiveHealthCheck`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HealthCheckOptions {
+    /// Kind of the health check. Defaults to [`HealthCheckKind::Readiness`].
+    pub kind: HealthCheckKind,
+    /// If set, the health check is reported as [`HealthStatus::Stale`] if the health wasn't updated
+    /// via [`HealthUpdater::update()`] within this duration. Thus, components using a deadline should
+    /// update their health periodically, even if it doesn't change. The deadline only starts after the first update,
+    /// so that slow component initialization isn't reported as a stale health.
+    pub stale_after: Option<Duration>,
+}
+
+impl HealthCheckOptions {
+    /// Creates options for a liveness check with the specified deadline for health updates.
+    pub fn liveness(stale_after: Duration) -> Self {
+        Self {
+            kind: HealthCheckKind::Liveness,
+            stale_after: Some(stale_after),
         }
     }
 }
@@ -59,6 +106,11 @@ impl Health {
     pub fn status(&self) -> HealthStatus {
         self.status
     }
+
+    /// Returns component-specific health details, if any.
+    pub fn details(&self) -> Option<&serde_json::Value> {
+        self.details.as_ref()
+    }
 }
 
 impl From<HealthStatus> for Health {
@@ -79,11 +131,36 @@ pub struct AppHealth {
 }
 
 impl AppHealth {
-    /// Aggregates health info from the provided checks.
+    /// Aggregates health info from the provided checks. This is used to determine application readiness.
     pub async fn new<T: AsRef<dyn CheckHealth>>(health_checks: &[T]) -> Self {
-        let check_futures = health_checks
+        Self::aggregate(health_checks.iter().map(AsRef::as_ref)).await
+    }
+
+    /// Aggregates health info from the provided checks of the [`HealthCheckKind::Liveness`] kind; other checks
+    /// are ignored. The application is alive if [`Self::is_alive()`] holds for the returned value.
+    pub async fn liveness<T: AsRef<dyn CheckHealth>>(health_checks: &[T]) -> Self {
+        let liveness_checks = health_checks
             .iter()
-            .map(|check| Self::check_health_with_time_limit(check.as_ref()));
+            .map(AsRef::as_ref)
+            .filter(|check| check.kind() == HealthCheckKind::Liveness);
+        Self::aggregate(liveness_checks).await
+    }
+
+    /// Checks health of a single component with the specified name. Returns `None` if there is no such component.
+    /// If multiple checks have the same name, the last one is used, similarly to [`Self::new()`].
+    pub async fn component<T: AsRef<dyn CheckHealth>>(
+        health_checks: &[T],
+        name: &str,
+    ) -> Option<Health> {
+        let check = health_checks
+            .iter()
+            .rev()
+            .find(|check| check.as_ref().name() == name)?;
+        Some(Self::check_health_with_time_limit(check.as_ref()).await.1)
+    }
+
+    async fn aggregate(health_checks: impl Iterator<Item = &dyn CheckHealth>) -> Self {
+        let check_futures = health_checks.map(Self::check_health_with_time_limit);
         let components: HashMap<_, _> = future::join_all(check_futures).await.into_iter().collect();
 
         let aggregated_status = components
@@ -132,6 +209,12 @@ impl AppHealth {
     pub fn is_ready(&self) -> bool {
         self.inner.status.is_ready()
     }
+
+    pub fn is_alive(&self) -> bool {
+        self.components
+            .values()
+            .all(|health| health.status.is_alive())
+    }
 }
 
 /// Interface to be used for health checks.
@@ -139,28 +222,69 @@ impl AppHealth {
 pub trait CheckHealth: Send + Sync + 'static {
     /// Unique name of the component.
     fn name(&self) -> &'static str;
+    /// Kind of this check. By default, checks only affect application readiness.
+    fn kind(&self) -> HealthCheckKind {
+        HealthCheckKind::Readiness
+    }
     /// Checks health of the component.
     async fn check_health(&self) -> Health;
 }
 
+/// Health shared between [`ReactiveHealthCheck`] and [`HealthUpdater`].
+#[derive(Debug, Clone)]
+struct HealthState {
+    health: Health,
+    /// Time of the last update via [`HealthUpdater`], or `None` if the health wasn't updated yet.
+    updated_at: Option<Instant>,
+}
+
+impl From<Health> for HealthState {
+    fn from(health: Health) -> Self {
+        Self {
+            health,
+            updated_at: Some(Instant::now()),
+        }
+    }
+}
+
+/// Details reported for a [`HealthStatus::Stale`] component.
+#[derive(Debug, Serialize)]
+struct StaleHealthDetails {
+    last_health: Health,
+    since_last_update_ms: u128,
+}
+
 /// Basic implementation of [`CheckHealth`] trait that can be updated using a matching [`HealthUpdater`].
 #[derive(Debug)]
 pub struct ReactiveHealthCheck {
     name: &'static str,
-    health_receiver: watch::Receiver<Health>,
+    options: HealthCheckOptions,
+    health_receiver: watch::Receiver<HealthState>,
 }
 
 impl ReactiveHealthCheck {
-    /// Creates a health check together with an updater that can be used to update it.
+    /// Creates a readiness health check together with an updater that can be used to update it.
     /// The check will return [`HealthStatus::NotReady`] initially.
     pub fn new(name: &'static str) -> (Self, HealthUpdater) {
-        let (health_sender, health_receiver) = watch::channel(HealthStatus::NotReady.into());
+        Self::with_options(name, HealthCheckOptions::default())
+    }
+
+    /// Creates a health check with the specified options together with an updater that can be used to update it.
+    /// The check will return [`HealthStatus::NotReady`] initially.
+    pub fn with_options(name: &'static str, options: HealthCheckOptions) -> (Self, HealthUpdater) {
+        let initial_state = HealthState {
+            health: HealthStatus::NotReady.into(),
+            updated_at: None,
+        };
+        let (health_sender, health_receiver) = watch::channel(initial_state);
         let this = Self {
             name,
+            options,
             health_receiver,
         };
         let updater = HealthUpdater {
             name,
+            options,
             health_sender,
         };
         (this, updater)
@@ -173,8 +297,29 @@ impl CheckHealth for ReactiveHealthCheck {
         self.name
     }
 
+    fn kind(&self) -> HealthCheckKind {
+        self.options.kind
+    }
+
     async fn check_health(&self) -> Health {
-        self.health_receiver.borrow().clone()
+        let state = self.health_receiver.borrow().clone();
+        let (Some(stale_after), Some(updated_at)) = (self.options.stale_after, state.updated_at)
+        else {
+            return state.health;
+        };
+        let since_last_update = updated_at.elapsed();
+        if since_last_update <= stale_after || state.health.status.is_terminal() {
+            return state.health;
+        }
+
+        tracing::warn!(
+            "Health of `{}` wasn't updated for {since_last_update:?} (deadline: {stale_after:?}); marking as stale",
+            self.name
+        );
+        Health::from(HealthStatus::Stale).with_details(StaleHealthDetails {
+            last_health: state.health,
+            since_last_update_ms: since_last_update.as_millis(),
+        })
     }
 }
 
@@ -185,15 +330,22 @@ impl CheckHealth for ReactiveHealthCheck {
 #[derive(Debug)]
 pub struct HealthUpdater {
     name: &'static str,
-    health_sender: watch::Sender<Health>,
+    options: HealthCheckOptions,
+    health_sender: watch::Sender<HealthState>,
 }
 
 impl HealthUpdater {
     /// Updates the health check information, returning if a change occurred from previous state.
     /// Note, description change on Health is counted as a change, even if status is the same.
     /// I.e., `Health { Ready, None }` to `Health { Ready, Some(_) }` is considered a change.
+    ///
+    /// Each call resets the deadline after which the health is considered stale (if the deadline is configured),
+    /// regardless of whether the health has changed.
     pub fn update(&self, health: Health) -> bool {
-        let old_health = self.health_sender.send_replace(health.clone());
+        let old_health = self
+            .health_sender
+            .send_replace(health.clone().into())
+            .health;
         if old_health != health {
             tracing::debug!(
                 "Changed health of `{}` from {} to {}",
@@ -211,6 +363,7 @@ impl HealthUpdater {
     pub fn subscribe(&self) -> ReactiveHealthCheck {
         ReactiveHealthCheck {
             name: self.name,
+            options: self.options,
             health_receiver: self.health_sender.subscribe(),
         }
     }
@@ -218,12 +371,13 @@ impl HealthUpdater {
 
 impl Drop for HealthUpdater {
     fn drop(&mut self) {
-        let terminal_health = if thread::panicking() {
-            HealthStatus::Panicked.into()
+        let terminal_status = if thread::panicking() {
+            HealthStatus::Panicked
         } else {
-            HealthStatus::ShutDown.into()
+            HealthStatus::ShutDown
         };
-        self.health_sender.send_replace(terminal_health);
+        self.health_sender
+            .send_replace(Health::from(terminal_status).into());
     }
 }
 
@@ -293,4 +447,94 @@ mod tests {
         let updated = health_updater.update(health);
         assert!(updated);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn health_becomes_stale_after_deadline() {
+        let options = HealthCheckOptions::liveness(Duration::from_secs(10));
+        let (health_check, health_updater) = ReactiveHealthCheck::with_options("test", options);
+        assert_eq!(health_check.kind(), HealthCheckKind::Liveness);
+        health_updater.update(HealthStatus::Ready.into());
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_matches!(
+            health_check.check_health().await.status(),
+            HealthStatus::Ready
+        );
+
+        tokio::time::advance(Duration::from_secs(6)).await;
+        let health = health_check.check_health().await;
+        assert_matches!(health.status(), HealthStatus::Stale);
+        let details = health.details().unwrap();
+        assert_eq!(details["last_health"]["status"], "ready");
+        assert_eq!(details["since_last_update_ms"], 11_000);
+
+        // Updating health with the same value resets the deadline.
+        let updated = health_updater.update(HealthStatus::Ready.into());
+        assert!(!updated);
+        assert_matches!(
+            health_check.check_health().await.status(),
+            HealthStatus::Ready
+        );
+
+        // Terminal statuses are never considered stale.
+        drop(health_updater);
+        tokio::time::advance(Duration::from_secs(20)).await;
+        assert_matches!(
+            health_check.check_health().await.status(),
+            HealthStatus::ShutDown
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stale_deadline_starts_after_first_update() {
+        let options = HealthCheckOptions::liveness(Duration::from_secs(10));
+        let (health_check, health_updater) = ReactiveHealthCheck::with_options("test", options);
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert_matches!(
+            health_check.check_health().await.status(),
+            HealthStatus::NotReady
+        );
+
+        health_updater.update(HealthStatus::Ready.into());
+        tokio::time::advance(Duration::from_secs(11)).await;
+        assert_matches!(
+            health_check.check_health().await.status(),
+            HealthStatus::Stale
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn aggregating_readiness_and_liveness() {
+        let (readiness_check, readiness_updater) = ReactiveHealthCheck::new("readiness");
+        let options = HealthCheckOptions::liveness(Duration::from_secs(10));
+        let (liveness_check, liveness_updater) =
+            ReactiveHealthCheck::with_options("liveness", options);
+        let checks: Vec<Box<dyn CheckHealth>> =
+            vec![Box::new(readiness_check), Box::new(liveness_check)];
+
+        liveness_updater.update(HealthStatus::Ready.into());
+        let app_health = AppHealth::new(&checks).await;
+        assert!(!app_health.is_ready());
+        assert!(app_health.is_alive());
+        let app_health = AppHealth::liveness(&checks).await;
+        assert!(app_health.is_ready());
+        assert_eq!(app_health.components.len(), 1);
+        assert!(app_health.components.contains_key("liveness"));
+
+        readiness_updater.update(HealthStatus::Ready.into());
+        assert!(AppHealth::new(&checks).await.is_ready());
+
+        tokio::time::advance(Duration::from_secs(15)).await;
+        let app_health = AppHealth::new(&checks).await;
+        assert!(!app_health.is_ready());
+        assert!(!app_health.is_alive());
+        assert!(!AppHealth::liveness(&checks).await.is_alive());
+
+        let component_health = AppHealth::component(&checks, "liveness").await.unwrap();
+        assert_matches!(component_health.status(), HealthStatus::Stale);
+        let component_health = AppHealth::component(&checks, "readiness").await.unwrap();
+        assert_matches!(component_health.status(), HealthStatus::Ready);
+        assert!(AppHealth::component(&checks, "missing").await.is_none());
+    }
 }
@@ -0,0 +1,178 @@
+//! Off-chain counterpart of the proof verification performed by `IExecutor.sol` and `IVerifier.sol`.
+
+use std::fmt;
+
+use codegen::serialize_proof;
+use zkevm_test_harness_1_3_3::{
+    abstract_zksync_circuit::concrete_circuits::{
+        ZkSyncCircuit, ZkSyncProof, ZkSyncVerificationKey,
+    },
+    bellman::{
+        bn256::{Bn256, Fq, G1Affine},
+        plonk::better_better_cs::setup::VerificationKey,
+        CurveAffine, PrimeField, PrimeFieldRepr,
+    },
+    witness::oracle::VmWitnessOracle,
+};
+use zksync_prover_interface::outputs::L1BatchProofForL1;
+use zksync_types::{web3::signing::keccak256, H256, U256};
+
+/// Number of bits the public input hash is shifted by, so that it fits into the scalar field.
+/// Must be kept in sync with `PUBLIC_INPUT_SHIFT` in `Executor.sol`.
+const PUBLIC_INPUT_SHIFT: usize = 32;
+
+/// Verification key for SNARK-wrapped scheduler proofs (i.e., the key used by the L1 verifier contract).
+pub type SnarkWrapperVerificationKey =
+    VerificationKey<Bn256, ZkSyncCircuit<Bn256, VmWitnessOracle<Bn256>>>;
+
+/// Computes the public input of a batch proof in the same way as the `Executor` contract
+/// (`_getBatchProofPublicInput`). Only applicable to post-boojum batches.
+pub fn batch_proof_public_input(prev_batch_commitment: H256, batch_commitment: H256) -> U256 {
+    let mut preimage = [0_u8; 64];
+    preimage[..32].copy_from_slice(prev_batch_commitment.as_bytes());
+    preimage[32..].copy_from_slice(batch_commitment.as_bytes());
+    U256::from_big_endian(&keccak256(&preimage)) >> PUBLIC_INPUT_SHIFT
+}
+
+/// Computes the hash of a SNARK wrapper verification key in the same way as the L1 verifier contract
+/// (`verificationKeyHash()`). This is the value stored as `recursion_scheduler_level_vk_hash` for protocol versions.
+pub fn snark_wrapper_vk_hash(vk: &SnarkWrapperVerificationKey) -> H256 {
+    fn write_point(buffer: &mut Vec<u8>, point: &G1Affine) {
+        let (x, y) = point.as_xy();
+        x.into_repr().write_be(&mut *buffer).unwrap();
+        y.into_repr().write_be(&mut *buffer).unwrap();
+    }
+
+    let mut buffer = vec![];
+    for point in &vk.gate_setup_commitments {
+        write_point(&mut buffer, point);
+    }
+    for point in &vk.gate_selectors_commitments {
+        write_point(&mut buffer, point);
+    }
+    for point in &vk.permutation_commitments {
+        write_point(&mut buffer, point);
+    }
+    if let Some(point) = &vk.lookup_selector_commitment {
+        write_point(&mut buffer, point);
+    }
+    for point in &vk.lookup_tables_commitments {
+        write_point(&mut buffer, point);
+    }
+    if let Some(point) = &vk.lookup_table_type_commitment {
+        write_point(&mut buffer, point);
+    }
+    // Flag for the recursive part of the proof, which is not used by the SNARK wrapper.
+    Fq::default().into_repr().write_be(&mut buffer).unwrap();
+    H256(keccak256(&buffer))
+}
+
+/// Errors that can occur when verifying an [`L1BatchProofForL1`].
+#[derive(Debug, thiserror::Error)]
+pub enum ProofVerificationError {
+    #[error("proof has {0} public inputs, while exactly 1 is expected")]
+    UnexpectedPublicInputCount(usize),
+    #[error("proof public input {actual:#x} doesn't match expected value {expected:#x}")]
+    PublicInputMismatch { expected: U256, actual: U256 },
+    #[error("proof is not valid for the verification key")]
+    InvalidProof,
+}
+
+/// Verifier of SNARK-wrapped scheduler proofs.
+pub struct L1BatchProofVerifier {
+    vk: ZkSyncVerificationKey<Bn256>,
+    vk_hash: H256,
+}
+
+impl fmt::Debug for L1BatchProofVerifier {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("L1BatchProofVerifier")
+            .field("vk_hash", &self.vk_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl L1BatchProofVerifier {
+    pub fn new(vk: SnarkWrapperVerificationKey) -> Self {
+        let vk_hash = snark_wrapper_vk_hash(&vk);
+        Self {
+            vk: ZkSyncVerificationKey::from_verification_key_and_numeric_type(0, vk),
+            vk_hash,
+        }
+    }
+
+    /// Parses a JSON-encoded verification key (e.g., `snark_verification_scheduler_key.json` generated by the prover).
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str::<SnarkWrapperVerificationKey>(json).map(Self::new)
+    }
+
+    /// Returns the hash of the verification key as computed by the L1 verifier contract.
+    pub fn vk_hash(&self) -> H256 {
+        self.vk_hash
+    }
+
+    /// Verifies the proof against the verification key and checks that its public input is equal
+    /// to `expected_public_input` (see [`batch_proof_public_input()`]). This is the same check
+    /// that the L1 contract performs in `proveBatches`.
+    pub fn verify(
+        &self,
+        proof: &L1BatchProofForL1,
+        expected_public_input: U256,
+    ) -> Result<(), ProofVerificationError> {
+        let (public_inputs, _) = serialize_proof(&proof.scheduler_proof);
+        let [actual] = public_inputs.as_slice() else {
+            return Err(ProofVerificationError::UnexpectedPublicInputCount(
+                public_inputs.len(),
+            ));
+        };
+        if *actual != expected_public_input {
+            return Err(ProofVerificationError::PublicInputMismatch {
+                expected: expected_public_input,
+                actual: *actual,
+            });
+        }
+
+        let proof = ZkSyncProof::from_proof_and_numeric_type(0, proof.scheduler_proof.clone());
+        if self.vk.verify_proof(&proof) {
+            Ok(())
+        } else {
+            Err(ProofVerificationError::InvalidProof)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_input_is_shifted_hash() {
+        let prev_commitment = H256::repeat_byte(1);
+        let commitment = H256::repeat_byte(2);
+        let public_input = batch_proof_public_input(prev_commitment, commitment);
+
+        let hash = keccak256(&[prev_commitment.as_bytes(), commitment.as_bytes()].concat());
+        let mut expected = [0_u8; 32];
+        expected[4..].copy_from_slice(&hash[..28]);
+        assert_eq!(public_input, U256::from_big_endian(&expected));
+        // The public input must fit into the BN254 scalar field.
+        assert!(public_input.bits() <= 256 - PUBLIC_INPUT_SHIFT);
+    }
+
+    #[test]
+    fn vk_hash_matches_contracts_config() {
+        let vk_path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../../prover/vk_setup_data_generator_server_fri/data/snark_verification_scheduler_key.json"
+        );
+        let vk = std::fs::read_to_string(vk_path).unwrap();
+        let verifier = L1BatchProofVerifier::from_json(&vk).unwrap();
+        // `SNARK_WRAPPER_VK_HASH` from `etc/env/base/contracts.toml`
+        let expected_vk_hash: H256 =
+            "0x8574e152c41dc39a2ecab984545e1cf21cb3ec250b919018a8053f2fa270784f"
+                .parse()
+                .unwrap();
+        assert_eq!(verifier.vk_hash(), expected_vk_hash);
+    }
+}
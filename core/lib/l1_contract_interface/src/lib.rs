@@ -12,6 +12,8 @@ pub use zksync_types::web3::contract::tokens::{Detokenize, Tokenizable, Tokenize
 
 /// Rust interface for (subset of) `IExector.sol`.
 pub mod i_executor;
+/// Off-chain verification of batch proofs.
+pub mod i_verifier;
 /// Utilities for interacting with `Multicall3` contract.
 pub mod multicall3;
 /// Utilities for interacting with the old verifier contract.
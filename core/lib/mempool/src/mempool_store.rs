@@ -205,6 +205,19 @@ impl MempoolStore {
         }
     }
 
+    /// Removes pending L1 transactions with serial IDs starting from `first_id`, e.g. after they were reorged out on L1.
+    /// Returns the number of removed transactions, or `None` (without modifying the mempool) if some of these transactions
+    /// were already returned for execution.
+    pub fn remove_l1_transactions_from(&mut self, first_id: PriorityOpId) -> Option<usize> {
+        if self.next_priority_id > first_id {
+            return None;
+        }
+        let len_before = self.l1_transactions.len();
+        self.l1_transactions
+            .retain(|&serial_id, _| serial_id < first_id);
+        Some(len_before - self.l1_transactions.len())
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
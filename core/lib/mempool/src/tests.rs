@@ -142,6 +142,33 @@ fn l1_txns_priority_id() {
     }
 }
 
+#[test]
+fn removing_l1_txns() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let transactions = (0..4).map(|id| gen_l1_tx(PriorityOpId(id))).collect();
+    mempool.insert(transactions, HashMap::new());
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_some());
+
+    // The transaction #0 is already returned for execution, so it cannot be removed.
+    assert_eq!(mempool.remove_l1_transactions_from(PriorityOpId(0)), None);
+    assert_eq!(mempool.stats().l1_transaction_count, 3);
+
+    assert_eq!(
+        mempool.remove_l1_transactions_from(PriorityOpId(2)),
+        Some(2)
+    );
+    assert_eq!(mempool.stats().l1_transaction_count, 1);
+    let data = mempool
+        .next_transaction(&L2TxFilter::default())
+        .unwrap()
+        .common_data;
+    match data {
+        ExecuteTransactionCommon::L1(data) => assert_eq!(data.serial_id, PriorityOpId(1)),
+        _ => unreachable!("expected L1 transaction"),
+    }
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
+}
+
 #[test]
 fn rejected_tx() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -191,7 +191,9 @@ impl ObjectStoreFactory {
 
     async fn create_from_config(config: &ObjectStoreConfig) -> Arc<dyn ObjectStore> {
         let gcs_credential_file_path = match config.mode {
-            ObjectStoreMode::GCSWithCredentialFile => Some(config.gcs_credential_file_path.clone()),
+            ObjectStoreMode::GCSWithCredentialFile => {
+                Some(config.gcs_credential_file_path.expose().clone())
+            }
             _ => None,
         };
         match config.mode {
@@ -1,10 +1,12 @@
 use anyhow::Context as _;
-use zksync_config::configs::{api, ApiConfig};
+use zksync_basic_types::H256;
+use zksync_config::configs::{api, ApiConfig, Secret};
 use zksync_protobuf::required;
 
 use crate::{
     parse_h256, proto,
     repr::{read_required_repr, ProtoRepr},
+    secret::expose_secret,
 };
 
 impl ProtoRepr for proto::Api {
@@ -51,19 +53,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
             gas_price_scale_factor: *required(&self.gas_price_scale_factor)
                 .context("gas_price_scale_factor")?,
             request_timeout: self.request_timeout,
-            account_pks: self
-                .account_pks
-                .as_ref()
-                .map(|keys| {
-                    keys.keys
-                        .iter()
-                        .enumerate()
-                        .map(|(i, k)| parse_h256(k).context(i))
-                        .collect::<Result<_, _>>()
-                        .context("keys")
-                })
-                .transpose()
-                .context("account_pks")?,
+            account_pks: read_private_keys(&self.account_pks).context("account_pks")?,
             estimate_gas_scale_factor: *required(&self.estimate_gas_scale_factor)
                 .context("estimate_gas_scale_factor")?,
             estimate_gas_acceptable_overestimation: *required(
@@ -119,6 +109,35 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("websocket_requests_per_minute_limit")?,
             tree_api_url: self.tree_api_url.clone(),
+            api_keys: self.api_keys.clone().into(),
+            api_key_required: self.api_key_required.unwrap_or_default(),
+            http_requests_per_minute_per_api_key: self
+                .http_requests_per_minute_per_api_key
+                .map(|x| x.try_into())
+                .transpose()
+                .context("http_requests_per_minute_per_api_key")?,
+            http_requests_per_minute_per_ip: self
+                .http_requests_per_minute_per_ip
+                .map(|x| x.try_into())
+                .transpose()
+                .context("http_requests_per_minute_per_ip")?,
+            vm_request_weight: self
+                .vm_request_weight
+                .map(|x| x.try_into())
+                .transpose()
+                .context("vm_request_weight")?,
+            allowed_methods: self
+                .allowed_methods
+                .as_ref()
+                .map(|list| list.methods.clone()),
+            denied_methods: self.denied_methods.clone(),
+            trusted_proxies: self
+                .trusted_proxies
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| addr.parse::<std::net::IpAddr>().context(i))
+                .collect::<Result<_, _>>()
+                .context("trusted_proxies")?,
         })
     }
     fn build(this: &Self::Type) -> Self {
@@ -134,9 +153,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
             max_nonce_ahead: Some(this.max_nonce_ahead),
             gas_price_scale_factor: Some(this.gas_price_scale_factor),
             request_timeout: this.request_timeout,
-            account_pks: this.account_pks.as_ref().map(|keys| proto::PrivateKeys {
-                keys: keys.iter().map(|k| k.as_bytes().into()).collect(),
-            }),
+            account_pks: build_private_keys(&this.account_pks),
             estimate_gas_scale_factor: Some(this.estimate_gas_scale_factor),
             estimate_gas_acceptable_overestimation: Some(
                 this.estimate_gas_acceptable_overestimation,
@@ -167,6 +184,25 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
             tree_api_url: this.tree_api_url.clone(),
+            api_keys: expose_secret(&this.api_keys),
+            api_key_required: Some(this.api_key_required),
+            http_requests_per_minute_per_api_key: this
+                .http_requests_per_minute_per_api_key
+                .map(|x| x.into()),
+            http_requests_per_minute_per_ip: this.http_requests_per_minute_per_ip.map(|x| x.into()),
+            vm_request_weight: this.vm_request_weight.map(|x| x.into()),
+            allowed_methods: this
+                .allowed_methods
+                .as_ref()
+                .map(|methods| proto::MethodList {
+                    methods: methods.clone(),
+                }),
+            denied_methods: this.denied_methods.clone(),
+            trusted_proxies: this
+                .trusted_proxies
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
         }
     }
 }
@@ -220,3 +256,29 @@ impl ProtoRepr for proto::MerkleTreeApi {
         }
     }
 }
+
+pub(crate) fn read_private_keys(
+    keys: &Option<proto::PrivateKeys>,
+) -> anyhow::Result<Option<Secret<Vec<H256>>>> {
+    Ok(keys
+        .as_ref()
+        .map(|keys| {
+            keys.keys
+                .iter()
+                .enumerate()
+                .map(|(i, k)| parse_h256(k).context(i))
+                .collect::<Result<Vec<_>, _>>()
+                .context("keys")
+        })
+        .transpose()?
+        .map(Into::into))
+}
+
+pub(crate) fn build_private_keys(keys: &Option<Secret<Vec<H256>>>) -> Option<proto::PrivateKeys> {
+    keys.as_ref().map(|keys| proto::PrivateKeys {
+        keys: expose_secret(keys)
+            .iter()
+            .map(|k| k.as_bytes().into())
+            .collect(),
+    })
+}
@@ -234,6 +234,11 @@ impl ProtoRepr for proto::CircuitBreaker {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("http_req_retry_interval_sec")?,
             replication_lag_limit_sec: self.replication_lag_limit_sec,
+            min_operator_balance_gwei: self.min_operator_balance_gwei,
+            max_unproved_l1_batches: self.max_unproved_l1_batches,
+            max_unproved_l1_batch_age_sec: self.max_unproved_l1_batch_age_sec,
+            max_l1_gas_price: self.max_l1_gas_price,
+            pausing_breakers: self.pausing_breakers.clone(),
         })
     }
 
@@ -243,6 +248,11 @@ impl ProtoRepr for proto::CircuitBreaker {
             http_req_max_retry_number: Some(this.http_req_max_retry_number.try_into().unwrap()),
             http_req_retry_interval_sec: Some(this.http_req_retry_interval_sec.into()),
             replication_lag_limit_sec: this.replication_lag_limit_sec,
+            min_operator_balance_gwei: this.min_operator_balance_gwei,
+            max_unproved_l1_batches: this.max_unproved_l1_batches,
+            max_unproved_l1_batch_age_sec: this.max_unproved_l1_batch_age_sec,
+            max_l1_gas_price: this.max_l1_gas_price,
+            pausing_breakers: this.pausing_breakers.clone(),
         }
     }
 }
@@ -5,6 +5,7 @@ use zksync_protobuf::required;
 use crate::{
     proto,
     repr::{read_required_repr, ProtoRepr},
+    secret::expose_secret,
 };
 
 impl proto::MerkleTreeMode {
@@ -87,9 +88,9 @@ impl ProtoRepr for proto::Postgres {
     type Type = configs::database::PostgresConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
-            master_url: self.master_url.clone(),
-            replica_url: self.replica_url.clone(),
-            prover_url: self.prover_url.clone(),
+            master_url: self.master_url.clone().map(Into::into),
+            replica_url: self.replica_url.clone().map(Into::into),
+            prover_url: self.prover_url.clone().map(Into::into),
             max_connections: self.max_connections,
             statement_timeout_sec: self.statement_timeout_sec,
         })
@@ -97,9 +98,9 @@ impl ProtoRepr for proto::Postgres {
 
     fn build(this: &Self::Type) -> Self {
         Self {
-            master_url: this.master_url.clone(),
-            replica_url: this.replica_url.clone(),
-            prover_url: this.prover_url.clone(),
+            master_url: this.master_url.as_ref().map(expose_secret),
+            replica_url: this.replica_url.as_ref().map(expose_secret),
+            prover_url: this.prover_url.as_ref().map(expose_secret),
             max_connections: this.max_connections,
             statement_timeout_sec: this.statement_timeout_sec,
         }
@@ -2,21 +2,40 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::required;
 
-use crate::{proto, repr::ProtoRepr};
+use crate::{proto, repr::ProtoRepr, secret::expose_secret};
 
 impl ProtoRepr for proto::EthClient {
     type Type = configs::ETHClientConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
-        Ok(Self::Type {
+        let config = Self::Type {
             chain_id: *required(&self.chain_id).context("chain_id")?,
-            web3_url: required(&self.web3_url).context("web3_url")?.clone(),
-        })
+            web3_url: required(&self.web3_url).context("web3_url")?.clone().into(),
+            fallback_web3_urls: self
+                .fallback_web3_urls
+                .iter()
+                .map(|url| url.clone().into())
+                .collect(),
+            read_quorum: self
+                .read_quorum
+                .map(|x| x.try_into())
+                .transpose()
+                .context("read_quorum")?,
+            request_timeout_ms: self
+                .request_timeout_ms
+                .unwrap_or_else(Self::Type::default_request_timeout_ms),
+        };
+        config.validate().context("read_quorum")?;
+        Ok(config)
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             chain_id: Some(this.chain_id),
-            web3_url: Some(this.web3_url.clone()),
+            web3_url: Some(expose_secret(&this.web3_url)),
+            fallback_web3_urls: this.fallback_web3_urls.iter().map(expose_secret).collect(),
+            // `usize` always fits into `u64` on supported platforms.
+            read_quorum: this.read_quorum.map(|x| x as u64),
+            request_timeout_ms: Some(this.request_timeout_ms),
         }
     }
 }
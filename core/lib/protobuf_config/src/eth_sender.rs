@@ -3,8 +3,9 @@ use zksync_config::configs;
 use zksync_protobuf::required;
 
 use crate::{
-    proto,
-    repr::{read_required_repr, ProtoRepr},
+    parse_h160, proto,
+    repr::{read_optional_repr, read_required_repr, ProtoRepr},
+    secret::expose_secret,
 };
 
 impl proto::ProofSendingMode {
@@ -51,6 +52,7 @@ impl ProtoRepr for proto::EthSender {
         Ok(Self::Type {
             sender: read_required_repr(&self.sender).context("sender")?,
             gas_adjuster: read_required_repr(&self.gas_adjuster).context("gas_adjuster")?,
+            remote_signer: read_optional_repr(&self.remote_signer).context("remote_signer")?,
         })
     }
 
@@ -58,6 +60,56 @@ impl ProtoRepr for proto::EthSender {
         Self {
             sender: Some(ProtoRepr::build(&this.sender)),
             gas_adjuster: Some(ProtoRepr::build(&this.gas_adjuster)),
+            remote_signer: this.remote_signer.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
+
+impl ProtoRepr for proto::RemoteSigner {
+    type Type = configs::eth_sender::RemoteSignerConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            url: required(&self.url).context("url")?.clone(),
+            operator_address: required(&self.operator_address)
+                .and_then(|x| parse_h160(x))
+                .context("operator_address")?,
+            prove_operator_address: self
+                .prove_operator_address
+                .as_ref()
+                .map(|x| parse_h160(x))
+                .transpose()
+                .context("prove_operator_address")?,
+            execute_operator_address: self
+                .execute_operator_address
+                .as_ref()
+                .map(|x| parse_h160(x))
+                .transpose()
+                .context("execute_operator_address")?,
+            bearer_token: self.bearer_token.clone().map(Into::into),
+            ca_cert_path: self.ca_cert_path.clone(),
+            client_cert_path: self.client_cert_path.clone(),
+            client_key_path: self.client_key_path.clone().map(Into::into),
+            request_timeout_ms: self
+                .request_timeout_ms
+                .unwrap_or_else(Self::Type::default_request_timeout_ms),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            url: Some(this.url.clone()),
+            operator_address: Some(this.operator_address.as_bytes().into()),
+            bearer_token: this.bearer_token.as_ref().map(expose_secret),
+            ca_cert_path: this.ca_cert_path.clone(),
+            client_cert_path: this.client_cert_path.clone(),
+            client_key_path: this.client_key_path.as_ref().map(expose_secret),
+            request_timeout_ms: Some(this.request_timeout_ms),
+            prove_operator_address: this
+                .prove_operator_address
+                .map(|addr| addr.as_bytes().into()),
+            execute_operator_address: this
+                .execute_operator_address
+                .map(|addr| addr.as_bytes().into()),
         }
     }
 }
@@ -109,6 +161,12 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::ProofLoadingMode::try_from(*x)?))
                 .context("proof_loading_mode")?
                 .parse(),
+            // Provided via secrets.
+            operator_private_key: None,
+            prove_operator_private_key: None,
+            execute_operator_private_key: None,
+            base_fee_postpone_ratio: self.base_fee_postpone_ratio,
+            max_base_fee_postpone_seconds: self.max_base_fee_postpone_seconds,
         })
     }
 
@@ -137,6 +195,8 @@ impl ProtoRepr for proto::Sender {
             l1_batch_min_age_before_execute_seconds: this.l1_batch_min_age_before_execute_seconds,
             max_acceptable_priority_fee_in_gwei: Some(this.max_acceptable_priority_fee_in_gwei),
             proof_loading_mode: Some(proto::ProofLoadingMode::new(&this.proof_loading_mode).into()),
+            base_fee_postpone_ratio: this.base_fee_postpone_ratio,
+            max_base_fee_postpone_seconds: this.max_base_fee_postpone_seconds,
         }
     }
 }
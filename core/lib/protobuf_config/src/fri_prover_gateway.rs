@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::required;
 
-use crate::{proto, repr::ProtoRepr};
+use crate::{proto, repr::ProtoRepr, secret::expose_secret};
 
 impl ProtoRepr for proto::FriProverGateway {
     type Type = configs::FriProverGatewayConfig;
@@ -12,6 +12,10 @@ impl ProtoRepr for proto::FriProverGateway {
             api_poll_duration_secs: required(&self.api_poll_duration_secs)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("api_poll_duration_secs")?,
+            api_auth_mode: proto::ProofDataHandlerAuthMode::read_optional(&self.api_auth_mode)
+                .context("api_auth_mode")?,
+            api_client_id: self.api_client_id.clone(),
+            api_client_secret: self.api_client_secret.clone().map(Into::into),
             prometheus_listener_port: required(&self.prometheus_listener_port)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("prometheus_listener_port")?,
@@ -26,6 +30,9 @@ impl ProtoRepr for proto::FriProverGateway {
         Self {
             api_url: Some(this.api_url.clone()),
             api_poll_duration_secs: Some(this.api_poll_duration_secs.into()),
+            api_auth_mode: Some(proto::ProofDataHandlerAuthMode::new(&this.api_auth_mode).into()),
+            api_client_id: this.api_client_id.clone(),
+            api_client_secret: this.api_client_secret.as_ref().map(expose_secret),
             prometheus_listener_port: Some(this.prometheus_listener_port.into()),
             prometheus_pushgateway_url: Some(this.prometheus_pushgateway_url.clone()),
             prometheus_push_interval_ms: this.prometheus_push_interval_ms,
@@ -0,0 +1,88 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+
+use crate::{
+    proto,
+    repr::{read_optional_repr, ProtoRepr},
+};
+
+impl ProtoRepr for proto::GeneralConfig {
+    type Type = configs::GeneralConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            postgres_config: read_optional_repr(&self.postgres).context("postgres")?,
+            api_config: read_optional_repr(&self.api).context("api")?,
+            contracts_config: read_optional_repr(&self.contracts).context("contracts")?,
+            db_config: read_optional_repr(&self.db).context("db")?,
+            eth_client_config: read_optional_repr(&self.eth_client).context("eth_client")?,
+            eth_sender_config: read_optional_repr(&self.eth_sender).context("eth_sender")?,
+            eth_watch_config: read_optional_repr(&self.eth_watch).context("eth_watch")?,
+            object_store_config: read_optional_repr(&self.object_store).context("object_store")?,
+            circuit_breaker_config: read_optional_repr(&self.circuit_breaker)
+                .context("circuit_breaker")?,
+            mempool_config: read_optional_repr(&self.mempool).context("mempool")?,
+            network_config: read_optional_repr(&self.network).context("network")?,
+            operations_manager_config: read_optional_repr(&self.operations_manager)
+                .context("operations_manager")?,
+            state_keeper_config: read_optional_repr(&self.state_keeper).context("state_keeper")?,
+            house_keeper_config: read_optional_repr(&self.house_keeper).context("house_keeper")?,
+            proof_data_handler_config: read_optional_repr(&self.proof_data_handler)
+                .context("proof_data_handler")?,
+            witness_generator_config: read_optional_repr(&self.witness_generator)
+                .context("witness_generator")?,
+            fri_proof_compressor_config: read_optional_repr(&self.fri_proof_compressor)
+                .context("fri_proof_compressor")?,
+            fri_prover_config: read_optional_repr(&self.fri_prover).context("fri_prover")?,
+            fri_prover_group_config: read_optional_repr(&self.fri_prover_group)
+                .context("fri_prover_group")?,
+            fri_witness_generator_config: read_optional_repr(&self.fri_witness_generator)
+                .context("fri_witness_generator")?,
+            opentelemetry_config: read_optional_repr(&self.opentelemetry)
+                .context("opentelemetry")?,
+            withdrawal_finalizer_config: read_optional_repr(&self.withdrawal_finalizer)
+                .context("withdrawal_finalizer")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            postgres: this.postgres_config.as_ref().map(ProtoRepr::build),
+            api: this.api_config.as_ref().map(ProtoRepr::build),
+            contracts: this.contracts_config.as_ref().map(ProtoRepr::build),
+            db: this.db_config.as_ref().map(ProtoRepr::build),
+            eth_client: this.eth_client_config.as_ref().map(ProtoRepr::build),
+            eth_sender: this.eth_sender_config.as_ref().map(ProtoRepr::build),
+            eth_watch: this.eth_watch_config.as_ref().map(ProtoRepr::build),
+            object_store: this.object_store_config.as_ref().map(ProtoRepr::build),
+            circuit_breaker: this.circuit_breaker_config.as_ref().map(ProtoRepr::build),
+            mempool: this.mempool_config.as_ref().map(ProtoRepr::build),
+            network: this.network_config.as_ref().map(ProtoRepr::build),
+            operations_manager: this
+                .operations_manager_config
+                .as_ref()
+                .map(ProtoRepr::build),
+            state_keeper: this.state_keeper_config.as_ref().map(ProtoRepr::build),
+            house_keeper: this.house_keeper_config.as_ref().map(ProtoRepr::build),
+            proof_data_handler: this
+                .proof_data_handler_config
+                .as_ref()
+                .map(ProtoRepr::build),
+            witness_generator: this.witness_generator_config.as_ref().map(ProtoRepr::build),
+            fri_proof_compressor: this
+                .fri_proof_compressor_config
+                .as_ref()
+                .map(ProtoRepr::build),
+            fri_prover: this.fri_prover_config.as_ref().map(ProtoRepr::build),
+            fri_prover_group: this.fri_prover_group_config.as_ref().map(ProtoRepr::build),
+            fri_witness_generator: this
+                .fri_witness_generator_config
+                .as_ref()
+                .map(ProtoRepr::build),
+            opentelemetry: this.opentelemetry_config.as_ref().map(ProtoRepr::build),
+            withdrawal_finalizer: this
+                .withdrawal_finalizer_config
+                .as_ref()
+                .map(ProtoRepr::build),
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! * protobuf binary format
 //! * protobuf text format
 //! * protobuf json format
+//! * YAML, following the protobuf json mapping
 
 mod alerts;
 mod api;
@@ -19,21 +20,84 @@ mod fri_prover_gateway;
 mod fri_prover_group;
 mod fri_witness_generator;
 mod fri_witness_vector_generator;
+mod general;
 mod house_keeper;
 mod object_store;
+mod observability;
 mod proof_data_handler;
+mod secrets;
 mod snapshots_creator;
+mod withdrawal_finalizer;
 mod witness_generator;
 
 pub mod proto;
 mod repr;
+mod secret;
 #[cfg(test)]
 mod tests;
 mod utils;
 
 use anyhow::Context as _;
+use zksync_protobuf::build::prost_reflect::{DynamicMessage, SerializeOptions, Value};
 use zksync_types::{H160, H256};
 
+pub use crate::repr::ProtoRepr;
+
+/// Decodes a config from YAML (or JSON, which is a subset of YAML)
+/// using the protobuf json mapping of `P`.
+/// Unknown fields are rejected; errors name the offending field.
+pub fn decode_yaml_repr<P: ProtoRepr>(yaml: &str) -> anyhow::Result<P::Type> {
+    let d = serde_yaml::Deserializer::from_str(yaml);
+    let proto: P = zksync_protobuf::serde::deserialize_proto(d)?;
+    proto.read()
+}
+
+/// Encodes a config to YAML using the protobuf json mapping of `P` with the proto field names,
+/// so that the output can be read back by [`decode_yaml_repr()`].
+/// Optionally replaces secret values with a placeholder.
+pub fn encode_yaml_repr<P: ProtoRepr>(
+    config: &P::Type,
+    redact_secrets: bool,
+) -> anyhow::Result<String> {
+    let options = SerializeOptions::new()
+        .use_proto_field_name(true)
+        .stringify_64_bit_integers(false);
+    let encode = || {
+        P::build(config)
+            .transcode_to_dynamic()
+            .serialize_with_options(serde_json::value::Serializer, &options)
+    };
+    let mut value = encode()?;
+    if redact_secrets {
+        let substituted = secret::with_substituted_secrets(encode)?;
+        secret::redact(&mut value, &substituted);
+    }
+    Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Merges two configs field by field using the proto representation `P`: fields set in `overrides`
+/// take precedence over the ones in `base`, nested messages are merged recursively,
+/// and repeated fields are replaced as a whole.
+pub fn merge_repr<P: ProtoRepr>(base: &P::Type, overrides: &P::Type) -> anyhow::Result<P::Type> {
+    let mut merged = P::build(base).transcode_to_dynamic();
+    merge_message(&mut merged, &P::build(overrides).transcode_to_dynamic());
+    merged
+        .transcode_to::<P>()
+        .context("failed transcoding merged config")?
+        .read()
+}
+
+fn merge_message(base: &mut DynamicMessage, overrides: &DynamicMessage) {
+    for (field, value) in overrides.fields() {
+        match (base.get_field_mut(&field), value) {
+            (Value::Message(base_message), Value::Message(message)) => {
+                merge_message(base_message, message);
+            }
+            (base_value, _) => *base_value = value.clone(),
+        }
+    }
+}
+
 fn parse_h256(bytes: &[u8]) -> anyhow::Result<H256> {
     Ok(<[u8; 32]>::try_from(bytes).context("invalid size")?.into())
 }
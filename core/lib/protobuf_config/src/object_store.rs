@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::required;
 
-use crate::{proto, repr::ProtoRepr};
+use crate::{proto, repr::ProtoRepr, secret::expose_secret};
 
 impl proto::ObjectStoreMode {
     fn new(x: &configs::object_store::ObjectStoreMode) -> Self {
@@ -41,7 +41,8 @@ impl ProtoRepr for proto::ObjectStore {
                 .clone(),
             gcs_credential_file_path: required(&self.gcs_credential_file_path)
                 .context("gcs_credential_file_path")?
-                .clone(),
+                .clone()
+                .into(),
             max_retries: required(&self.max_retries)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_retries")?,
@@ -53,7 +54,7 @@ impl ProtoRepr for proto::ObjectStore {
             bucket_base_url: Some(this.bucket_base_url.clone()),
             mode: Some(proto::ObjectStoreMode::new(&this.mode).into()),
             file_backed_base_path: Some(this.file_backed_base_path.clone()),
-            gcs_credential_file_path: Some(this.gcs_credential_file_path.clone()),
+            gcs_credential_file_path: Some(expose_secret(&this.gcs_credential_file_path)),
             max_retries: Some(this.max_retries.into()),
         }
     }
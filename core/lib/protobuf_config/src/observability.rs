@@ -0,0 +1,31 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::required;
+
+use crate::{proto, repr::ProtoRepr};
+
+impl ProtoRepr for proto::Opentelemetry {
+    type Type = configs::OpentelemetryConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let config = Self::Type {
+            endpoint: required(&self.endpoint).context("endpoint")?.clone(),
+            service_name: self
+                .service_name
+                .clone()
+                .unwrap_or_else(Self::Type::default_service_name),
+            sampling_ratio: self
+                .sampling_ratio
+                .unwrap_or_else(Self::Type::default_sampling_ratio),
+        };
+        config.validate().context("sampling_ratio")?;
+        Ok(config)
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            endpoint: Some(this.endpoint.clone()),
+            service_name: Some(this.service_name.clone()),
+            sampling_ratio: Some(this.sampling_ratio),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::required;
 
-use crate::{proto, repr::ProtoRepr};
+use crate::{proto, repr::ProtoRepr, secret::expose_secret};
 
 impl proto::ProtocolVersionLoadingMode {
     fn new(x: &configs::proof_data_handler::ProtocolVersionLoadingMode) -> Self {
@@ -21,6 +21,35 @@ impl proto::ProtocolVersionLoadingMode {
     }
 }
 
+impl proto::ProofDataHandlerAuthMode {
+    pub(crate) fn new(x: &configs::proof_data_handler::ProofDataHandlerAuthMode) -> Self {
+        type From = configs::proof_data_handler::ProofDataHandlerAuthMode;
+        match x {
+            From::Disabled => Self::Disabled,
+            From::BearerToken => Self::BearerToken,
+            From::Hmac => Self::Hmac,
+        }
+    }
+    pub(crate) fn parse(&self) -> configs::proof_data_handler::ProofDataHandlerAuthMode {
+        type To = configs::proof_data_handler::ProofDataHandlerAuthMode;
+        match self {
+            Self::Disabled => To::Disabled,
+            Self::BearerToken => To::BearerToken,
+            Self::Hmac => To::Hmac,
+        }
+    }
+
+    /// Parses an optional auth mode field, falling back to the default mode if it's not set.
+    pub(crate) fn read_optional(
+        x: &Option<i32>,
+    ) -> anyhow::Result<configs::proof_data_handler::ProofDataHandlerAuthMode> {
+        Ok(x.map(Self::try_from)
+            .transpose()?
+            .map(|x| x.parse())
+            .unwrap_or_default())
+    }
+}
+
 impl ProtoRepr for proto::ProofDataHandler {
     type Type = configs::ProofDataHandlerConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
@@ -38,6 +67,13 @@ impl ProtoRepr for proto::ProofDataHandler {
             fri_protocol_version_id: required(&self.fri_protocol_version_id)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("fri_protocol_version_id")?,
+            auth_mode: proto::ProofDataHandlerAuthMode::read_optional(&self.auth_mode)
+                .context("auth_mode")?,
+            api_clients: self.api_clients.clone().into(),
+            hmac_max_clock_skew_secs: self.hmac_max_clock_skew_secs,
+            tls_cert_path: self.tls_cert_path.clone(),
+            tls_key_path: self.tls_key_path.clone(),
+            scheduler_vk_paths: self.scheduler_vk_paths.clone(),
         })
     }
 
@@ -49,6 +85,12 @@ impl ProtoRepr for proto::ProofDataHandler {
                 proto::ProtocolVersionLoadingMode::new(&this.protocol_version_loading_mode).into(),
             ),
             fri_protocol_version_id: Some(this.fri_protocol_version_id.into()),
+            auth_mode: Some(proto::ProofDataHandlerAuthMode::new(&this.auth_mode).into()),
+            api_clients: expose_secret(&this.api_clients),
+            hmac_max_clock_skew_secs: this.hmac_max_clock_skew_secs,
+            tls_cert_path: this.tls_cert_path.clone(),
+            tls_key_path: this.tls_key_path.clone(),
+            scheduler_vk_paths: this.scheduler_vk_paths.clone(),
         }
     }
 }
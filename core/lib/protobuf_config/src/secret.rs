@@ -0,0 +1,101 @@
+//! Redaction of the [`Secret`] config values.
+//!
+//! Secrets are recognized by their type rather than by the proto field names:
+//! every `build()` implementation exposes secret values through [`expose_secret()`],
+//! so building a config a second time with the values substituted reveals
+//! exactly the parts of the encoded config which originate from secrets.
+
+use std::cell::Cell;
+
+use zksync_config::configs::Secret;
+use zksync_types::H256;
+
+/// Placeholder for the redacted secret values.
+pub(crate) const REDACTED: &str = "<redacted>";
+
+thread_local! {
+    static SUBSTITUTE_SECRETS: Cell<bool> = Cell::new(false);
+}
+
+/// Secret value which can be substituted with a different value of the same shape.
+pub(crate) trait Substitute: Sized {
+    /// Returns a value guaranteed to differ from `self`, unless `self` is empty.
+    fn substitute(&self) -> Self;
+}
+
+impl Substitute for String {
+    fn substitute(&self) -> Self {
+        if self == REDACTED {
+            String::new()
+        } else {
+            REDACTED.to_owned()
+        }
+    }
+}
+
+impl Substitute for H256 {
+    fn substitute(&self) -> Self {
+        if self.is_zero() {
+            H256::repeat_byte(0xff)
+        } else {
+            H256::zero()
+        }
+    }
+}
+
+impl<T: Substitute> Substitute for Vec<T> {
+    fn substitute(&self) -> Self {
+        self.iter().map(Substitute::substitute).collect()
+    }
+}
+
+/// Returns the secret value to put into a proto message.
+/// While building a config with [`with_substituted_secrets()`], returns a substitute instead.
+pub(crate) fn expose_secret<T: Clone + Substitute>(secret: &Secret<T>) -> T {
+    if SUBSTITUTE_SECRETS.with(Cell::get) {
+        secret.expose().substitute()
+    } else {
+        secret.expose().clone()
+    }
+}
+
+pub(crate) fn with_substituted_secrets<R>(f: impl FnOnce() -> R) -> R {
+    struct Guard(bool);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SUBSTITUTE_SECRETS.with(|flag| flag.set(self.0));
+        }
+    }
+
+    let _guard = Guard(SUBSTITUTE_SECRETS.with(|flag| flag.replace(true)));
+    f()
+}
+
+/// Replaces the parts of `value` which differ from `substituted` with a placeholder.
+pub(crate) fn redact(value: &mut serde_json::Value, substituted: &serde_json::Value) {
+    use serde_json::Value;
+
+    match (value, substituted) {
+        (Value::Object(fields), Value::Object(substituted_fields)) => {
+            for (name, field) in fields {
+                match substituted_fields.get(name) {
+                    Some(substituted_field) => redact(field, substituted_field),
+                    None => *field = REDACTED.into(),
+                }
+            }
+        }
+        (Value::Array(items), Value::Array(substituted_items))
+            if items.len() == substituted_items.len() =>
+        {
+            for (item, substituted_item) in items.iter_mut().zip(substituted_items) {
+                redact(item, substituted_item);
+            }
+        }
+        (value, substituted) => {
+            if value != substituted {
+                *value = REDACTED.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::Context as _;
+use zksync_basic_types::H256;
+use zksync_config::configs::{
+    self,
+    secrets::{ApiSecrets, DatabaseSecrets, ObjectStoreSecrets, OperatorSecrets},
+    Secret,
+};
+
+use crate::{
+    api::{build_private_keys, read_private_keys},
+    parse_h256, proto,
+    repr::{read_optional_repr, ProtoRepr},
+    secret::expose_secret,
+};
+
+impl ProtoRepr for proto::DatabaseSecrets {
+    type Type = DatabaseSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            server_url: self.server_url.clone().map(Into::into),
+            server_replica_url: self.server_replica_url.clone().map(Into::into),
+            prover_url: self.prover_url.clone().map(Into::into),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            server_url: this.server_url.as_ref().map(expose_secret),
+            server_replica_url: this.server_replica_url.as_ref().map(expose_secret),
+            prover_url: this.prover_url.as_ref().map(expose_secret),
+        }
+    }
+}
+
+impl ProtoRepr for proto::OperatorSecrets {
+    type Type = OperatorSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            operator_private_key: read_private_key(&self.operator_private_key)
+                .context("operator_private_key")?,
+            prove_operator_private_key: read_private_key(&self.prove_operator_private_key)
+                .context("prove_operator_private_key")?,
+            execute_operator_private_key: read_private_key(&self.execute_operator_private_key)
+                .context("execute_operator_private_key")?,
+            withdrawal_finalizer_private_key: read_private_key(
+                &self.withdrawal_finalizer_private_key,
+            )
+            .context("withdrawal_finalizer_private_key")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            operator_private_key: build_private_key(&this.operator_private_key),
+            prove_operator_private_key: build_private_key(&this.prove_operator_private_key),
+            execute_operator_private_key: build_private_key(&this.execute_operator_private_key),
+            withdrawal_finalizer_private_key: build_private_key(
+                &this.withdrawal_finalizer_private_key,
+            ),
+        }
+    }
+}
+
+fn read_private_key(key: &Option<Vec<u8>>) -> anyhow::Result<Option<Secret<H256>>> {
+    Ok(key
+        .as_ref()
+        .map(|key| parse_h256(key))
+        .transpose()?
+        .map(Into::into))
+}
+
+fn build_private_key(key: &Option<Secret<H256>>) -> Option<Vec<u8>> {
+    key.as_ref().map(|key| expose_secret(key).as_bytes().into())
+}
+
+impl ProtoRepr for proto::ApiSecrets {
+    type Type = ApiSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            account_pks: read_private_keys(&self.account_pks).context("account_pks")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            account_pks: build_private_keys(&this.account_pks),
+        }
+    }
+}
+
+impl ProtoRepr for proto::ObjectStoreSecrets {
+    type Type = ObjectStoreSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            gcs_credential_file_path: self.gcs_credential_file_path.clone().map(Into::into),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            gcs_credential_file_path: this.gcs_credential_file_path.as_ref().map(expose_secret),
+        }
+    }
+}
+
+impl ProtoRepr for proto::Secrets {
+    type Type = configs::Secrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            database: read_optional_repr(&self.database).context("database")?,
+            operator: read_optional_repr(&self.operator).context("operator")?,
+            api: read_optional_repr(&self.api).context("api")?,
+            object_store: read_optional_repr(&self.object_store).context("object_store")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            database: this.database.as_ref().map(ProtoRepr::build),
+            operator: this.operator.as_ref().map(ProtoRepr::build),
+            api: this.api.as_ref().map(ProtoRepr::build),
+            object_store: this.object_store.as_ref().map(ProtoRepr::build),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::required;
+
+use crate::{proto, repr::ProtoRepr};
+
+impl ProtoRepr for proto::WithdrawalFinalizer {
+    type Type = configs::WithdrawalFinalizerConfig;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            poll_interval_ms: *required(&self.poll_interval_ms).context("poll_interval_ms")?,
+            batch_size: required(&self.batch_size)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("batch_size")?,
+            gas_limit_per_withdrawal: *required(&self.gas_limit_per_withdrawal)
+                .context("gas_limit_per_withdrawal")?,
+            max_attempts: *required(&self.max_attempts).context("max_attempts")?,
+            resend_timeout_ms: self
+                .resend_timeout_ms
+                .unwrap_or_else(Self::Type::default_resend_timeout_ms),
+            dropped_tx_grace_period_ms: self
+                .dropped_tx_grace_period_ms
+                .unwrap_or_else(Self::Type::default_dropped_tx_grace_period_ms),
+            start_from_l1_batch: self.start_from_l1_batch,
+            private_key: None,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            poll_interval_ms: Some(this.poll_interval_ms),
+            batch_size: Some(this.batch_size.try_into().unwrap()),
+            gas_limit_per_withdrawal: Some(this.gas_limit_per_withdrawal),
+            max_attempts: Some(this.max_attempts),
+            resend_timeout_ms: Some(this.resend_timeout_ms),
+            dropped_tx_grace_period_ms: Some(this.dropped_tx_grace_period_ms),
+            start_from_l1_batch: this.start_from_l1_batch,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //! Prover and server subsystems communicate via the API.
 //! This module defines the types used in the API.
 
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 use zksync_types::{
     protocol_version::{FriProtocolVersionId, L1VerifierConfig},
@@ -33,8 +35,38 @@ pub enum SubmitProofRequest {
     SkippedProofGeneration,
 }
 
+/// Reason for rejecting a submitted proof. If a proof is rejected, the prover subsystem
+/// should regenerate it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProofRejectionReason {
+    /// Auxiliary outputs of the proof differ from the values computed by the server.
+    AuxOutputMismatch {
+        server_values: String,
+        prover_values: String,
+    },
+    /// The proof (or its public input) is invalid.
+    VerificationFailed(String),
+}
+
+impl fmt::Display for ProofRejectionReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AuxOutputMismatch {
+                server_values,
+                prover_values,
+            } => write!(
+                formatter,
+                "auxiliary output doesn't match, server values: {server_values}, prover values: {prover_values}"
+            ),
+            Self::VerificationFailed(err) => write!(formatter, "proof verification failed: {err}"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum SubmitProofResponse {
     Success,
     Error(String),
+    /// The proof was rejected. Returned with the `422 Unprocessable Entity` HTTP status.
+    Rejected(ProofRejectionReason),
 }
@@ -0,0 +1,129 @@
+//! Request authentication for the proof data handler API.
+//!
+//! Two schemes are supported:
+//!
+//! - **Bearer token:** the client sends its secret in the `Authorization: Bearer <secret>` header.
+//! - **HMAC:** the client sends its ID, a UNIX timestamp and an HMAC-SHA256 signature
+//!   of the request (see [`hmac_signature()`]) in dedicated headers. The secret never leaves the client,
+//!   and captured requests cannot be replayed after the allowed clock skew elapses.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Standard HTTP authorization header used for bearer tokens.
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+/// Header containing the client ID for HMAC-authenticated requests.
+pub const CLIENT_ID_HEADER: &str = "x-zksync-client-id";
+/// Header containing the request UNIX timestamp (in seconds) for HMAC-authenticated requests.
+pub const TIMESTAMP_HEADER: &str = "x-zksync-timestamp";
+/// Header containing the hex-encoded HMAC-SHA256 signature of the request.
+pub const SIGNATURE_HEADER: &str = "x-zksync-signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn request_mac(secret: &[u8], timestamp: u64, method: &str, path: &str, body: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b"\n");
+    mac.update(method.to_ascii_uppercase().as_bytes());
+    mac.update(b"\n");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(body);
+    mac
+}
+
+/// Computes a hex-encoded HMAC-SHA256 signature of a request. The signed message is
+/// `"{timestamp}\n{METHOD}\n{path}\n"` followed by the raw request body.
+pub fn hmac_signature(
+    secret: &[u8],
+    timestamp: u64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> String {
+    let mac = request_mac(secret, timestamp, method, path, body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Checks a hex-encoded request signature produced by [`hmac_signature()`]. The comparison is constant-time.
+pub fn verify_hmac_signature(
+    secret: &[u8],
+    timestamp: u64,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    signature: &str,
+) -> bool {
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    request_mac(secret, timestamp, method, path, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+/// Compares two secrets in constant time (w.r.t. their contents).
+pub fn secrets_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0_u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_signature_roundtrip() {
+        let secret = b"secret";
+        let body = br#"{"Proof":{}}"#;
+        let signature = hmac_signature(secret, 1_700_000_000, "post", "/submit_proof/1", body);
+
+        assert!(verify_hmac_signature(
+            secret,
+            1_700_000_000,
+            "POST",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_hmac_signature(
+            secret,
+            1_700_000_001,
+            "POST",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_hmac_signature(
+            secret,
+            1_700_000_000,
+            "POST",
+            "/submit_proof/2",
+            body,
+            &signature
+        ));
+        assert!(!verify_hmac_signature(
+            b"other",
+            1_700_000_000,
+            "POST",
+            "/submit_proof/1",
+            body,
+            &signature
+        ));
+        assert!(!verify_hmac_signature(
+            secret,
+            1_700_000_000,
+            "POST",
+            "/submit_proof/1",
+            body,
+            "not hex"
+        ));
+    }
+
+    #[test]
+    fn comparing_secrets() {
+        assert!(secrets_eq(b"secret", b"secret"));
+        assert!(!secrets_eq(b"secret", b"secreT"));
+        assert!(!secrets_eq(b"secret", b"secret1"));
+    }
+}
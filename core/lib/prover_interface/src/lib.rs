@@ -3,6 +3,8 @@
 
 /// Types that define the API for interaction between prover and server subsystems.
 pub mod api;
+/// Request authentication for the API.
+pub mod auth;
 /// Inputs for proof generation provided by the core subsystem.
 pub mod inputs;
 /// Outputs of proof generation provided by the prover subsystem.
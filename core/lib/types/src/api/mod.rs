@@ -14,7 +14,7 @@ use crate::{
     protocol_version::L1VerifierConfig,
     vm_trace::{Call, CallType},
     web3::types::{AccessList, Index, H2048},
-    Address, MiniblockNumber, ProtocolVersionId,
+    Address, MiniblockNumber, PriorityOpId, ProtocolVersionId,
 };
 
 pub mod en;
@@ -687,6 +687,87 @@ pub struct L1BatchDetails {
     pub base: BlockDetailsBase,
 }
 
+/// Share of an aggregated L1 transaction cost attributed to an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1TxCost {
+    pub tx_hash: H256,
+    /// Whether the L1 transaction has succeeded. Failed transactions still consume gas.
+    pub succeeded: bool,
+    /// Share of gas used by the L1 transaction.
+    pub gas_used: U64,
+    pub effective_gas_price: U256,
+    /// Share of the L1 transaction cost, in wei.
+    pub cost: U256,
+}
+
+/// L1 costs of an L1 batch. Costs of L1 transactions covering several L1 batches are split evenly among them.
+///
+/// All mined L1 transactions for each operation are listed, including failed ones, in the order they were recorded.
+/// Costs are not available for L1 batches whose L1 transactions were mined before L1 cost accounting was introduced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchCosts {
+    pub number: L1BatchNumber,
+    pub commit: Vec<L1TxCost>,
+    pub prove: Vec<L1TxCost>,
+    pub execute: Vec<L1TxCost>,
+    /// Total cost of all mined L1 transactions for the batch, in wei.
+    pub total_cost: U256,
+}
+
+/// Identifier of a priority (L1→L2) operation.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PriorityOpIdentifier {
+    /// By hash of the L1 transaction that submitted the operation. Operations received before L1 transaction hashes
+    /// were persisted cannot be found this way.
+    L1TxHash(H256),
+    /// By serial ID of the operation in the priority queue.
+    SerialId(PriorityOpId),
+}
+
+/// Processing stage of a priority operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityOpStage {
+    /// Operation is received from L1, but not yet included into a miniblock.
+    Queued,
+    /// Operation is included into a miniblock.
+    Included,
+    /// L1 batch with the operation is committed on L1.
+    Committed,
+    /// L1 batch with the operation is proven on L1.
+    Proven,
+    /// L1 batch with the operation is executed on L1, i.e., the operation is removed from the L1 priority queue.
+    Executed,
+}
+
+/// Status of a priority operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpStatus {
+    pub serial_id: PriorityOpId,
+    /// Hash of the L1 transaction that submitted the operation. May be missing for operations
+    /// received before the hash was persisted.
+    pub l1_tx_hash: Option<H256>,
+    /// Hash of the corresponding L2 transaction.
+    pub l2_tx_hash: H256,
+    pub stage: PriorityOpStage,
+    /// Reason of the L2 execution failure, if any. Failed operations still go through all stages,
+    /// since they must be removed from the L1 priority queue.
+    pub failure_reason: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub l1_block_number: Option<U64>,
+    /// Deadline for processing the operation as specified in the `NewPriorityRequest` L1 event.
+    pub expiration_timestamp: Option<U64>,
+    pub miniblock_number: Option<MiniblockNumber>,
+    pub l1_batch_number: Option<L1BatchNumber>,
+    pub eth_commit_tx_hash: Option<H256>,
+    pub eth_prove_tx_hash: Option<H256>,
+    pub eth_execute_tx_hash: Option<H256>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
@@ -1,4 +1,6 @@
-use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256};
+use crate::{
+    aggregated_operations::AggregatedActionType, Address, L1BatchNumber, Nonce, H256, U256,
+};
 
 #[derive(Clone)]
 pub struct EthTx {
@@ -9,6 +11,8 @@ pub struct EthTx {
     pub tx_type: AggregatedActionType,
     pub created_at_timestamp: u64,
     pub predicted_gas_cost: u64,
+    /// Sender of the transaction; `None` corresponds to the main operator account.
+    pub from_addr: Option<Address>,
 }
 
 impl std::fmt::Debug for EthTx {
@@ -21,6 +25,7 @@ impl std::fmt::Debug for EthTx {
             .field("tx_type", &self.tx_type)
             .field("created_at_timestamp", &self.created_at_timestamp)
             .field("predicted_gas_cost", &self.predicted_gas_cost)
+            .field("from_addr", &self.from_addr)
             .finish()
     }
 }
@@ -46,3 +51,22 @@ pub struct TxHistoryToSend {
     pub signed_raw_tx: Vec<u8>,
     pub nonce: Nonce,
 }
+
+/// Share of the cost of a mined aggregated L1 transaction attributed to a single L1 batch.
+/// Every mined attempt of an operation is accounted for, including failed ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct L1BatchTxCost {
+    pub l1_batch_number: L1BatchNumber,
+    pub eth_tx_id: u32,
+    pub tx_type: AggregatedActionType,
+    /// Hash of the mined L1 transaction.
+    pub tx_hash: H256,
+    /// Whether the L1 transaction has succeeded.
+    pub succeeded: bool,
+    /// Share of gas used by the L1 transaction.
+    pub gas_used: u64,
+    /// Effective gas price paid for the L1 transaction, in wei.
+    pub effective_gas_price: U256,
+    /// Share of the L1 transaction cost, in wei.
+    pub cost: U256,
+}
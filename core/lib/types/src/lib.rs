@@ -54,6 +54,7 @@ pub mod proto;
 pub mod transaction_request;
 pub mod utils;
 pub mod vm_version;
+pub mod withdrawals;
 
 /// Denotes the first byte of the special zkSync's EIP-712-signed transaction.
 pub const EIP_712_TX_TYPE: u8 = 0x71;
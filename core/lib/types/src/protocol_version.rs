@@ -213,7 +213,8 @@ pub struct GovernanceOperation {
 /// Protocol upgrade proposal from L1.
 /// Most of the fields are optional meaning if value is none
 /// then this field is not changed within an upgrade.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProtocolUpgrade {
     /// New protocol version ID.
     pub id: ProtocolVersionId,
@@ -0,0 +1,81 @@
+//! Types related to finalization of L2→L1 withdrawals.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+
+use crate::{Address, L1BatchNumber, H256};
+
+/// Status of a withdrawal finalization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WithdrawalStatus {
+    /// Withdrawal is waiting to be finalized.
+    Pending,
+    /// Finalization transaction is sent to L1, but its outcome is not checked yet.
+    Sent,
+    /// Withdrawal is finalized on L1.
+    Finalized,
+    /// Withdrawal could not be finalized after the maximum allowed number of attempts.
+    Failed,
+}
+
+impl WithdrawalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sent => "sent",
+            Self::Finalized => "finalized",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for WithdrawalStatus {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WithdrawalStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "pending" => Self::Pending,
+            "sent" => Self::Sent,
+            "finalized" => Self::Finalized,
+            "failed" => Self::Failed,
+            _ => return Err(format!("unknown withdrawal status: {s}")),
+        })
+    }
+}
+
+/// L2→L1 message initiating a withdrawal, together with the data necessary to finalize it on L1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Withdrawal {
+    pub l1_batch_number: L1BatchNumber,
+    /// Index of the corresponding L2→L1 log among all L2→L1 logs in the L1 batch.
+    pub l2_message_index: u32,
+    /// Index of the initiating transaction in the L1 batch.
+    pub l1_batch_tx_index: u16,
+    pub tx_hash: H256,
+    /// L2 contract that has sent the message (the L2 base token contract or an L2 bridge).
+    pub sender: Address,
+    pub message: Vec<u8>,
+}
+
+/// Withdrawal together with its finalization state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredWithdrawal {
+    pub id: u64,
+    pub withdrawal: Withdrawal,
+    pub status: WithdrawalStatus,
+    /// Number of failed finalization attempts.
+    pub attempts: u32,
+    pub finalization_tx_hash: Option<H256>,
+    /// Nonce of the finalization transaction; it's retained when the transaction is resent with bumped fees.
+    pub finalization_tx_nonce: Option<u64>,
+    /// When the finalization transaction (or its latest replacement) was sent.
+    pub finalization_tx_sent_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
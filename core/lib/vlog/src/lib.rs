@@ -3,12 +3,23 @@
 
 use std::{backtrace::Backtrace, borrow::Cow, panic::PanicInfo};
 
+use anyhow::Context as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    trace::{self, Sampler},
+    Resource,
+};
 // Temporary re-export of `sentry::capture_message` aiming to simplify the transition from `vlog` to using
 // crates directly.
 pub use sentry::{capture_message, Level as AlertLevel};
 use sentry::{types::Dsn, ClientInitGuard};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
+pub use crate::span_links::SpanLinks;
+
+mod span_links;
+
 /// Specifies the format of the logs in stdout.
 #[derive(Debug, Clone, Copy, Default)]
 pub enum LogFormat {
@@ -17,19 +28,67 @@ pub enum LogFormat {
     Json,
 }
 
+/// Options of the OpenTelemetry traces export.
+#[derive(Debug, Clone)]
+struct OpenTelemetryOptions {
+    endpoint: String,
+    service_name: String,
+    sampling_ratio: f64,
+}
+
+impl OpenTelemetryOptions {
+    /// Installs the global tracer provider exporting spans over OTLP/HTTP and returns a tracer from it.
+    fn install_tracer(self) -> anyhow::Result<trace::Tracer> {
+        let endpoint = self.endpoint.clone();
+        let exporter = opentelemetry_otlp::new_exporter()
+            .http()
+            .with_endpoint(self.endpoint);
+        // Child spans follow the decision made for the root span, so that traces are never sampled partially.
+        let sampler =
+            Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(self.sampling_ratio)));
+        let resource = Resource::new([KeyValue::new(
+            opentelemetry_semantic_conventions::resource::SERVICE_NAME,
+            self.service_name,
+        )]);
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(exporter)
+            .with_trace_config(
+                trace::config()
+                    .with_sampler(sampler)
+                    .with_resource(resource),
+            )
+            .install_batch(opentelemetry_sdk::runtime::Tokio)
+            .with_context(|| {
+                format!("failed installing OpenTelemetry tracer exporting to `{endpoint}`")
+            })
+    }
+}
+
 /// Builder for the observability subsystem.
-/// Currently capable of configuring logging output and sentry integration.
+/// Currently capable of configuring logging output, sentry integration and OpenTelemetry traces export.
 #[derive(Debug, Default)]
 pub struct ObservabilityBuilder {
     log_format: LogFormat,
     sentry_url: Option<Dsn>,
     sentry_environment: Option<String>,
+    opentelemetry_options: Option<OpenTelemetryOptions>,
 }
 
 /// Guard for the observability subsystem.
 /// Releases configured integrations upon being dropped.
 pub struct ObservabilityGuard {
     _sentry_guard: Option<ClientInitGuard>,
+    opentelemetry_enabled: bool,
+}
+
+impl Drop for ObservabilityGuard {
+    fn drop(&mut self) {
+        if self.opentelemetry_enabled {
+            // Flushes the spans that are still buffered by the batch exporter.
+            opentelemetry::global::shutdown_tracer_provider();
+        }
+    }
 }
 
 impl std::fmt::Debug for ObservabilityGuard {
@@ -69,14 +128,50 @@ impl ObservabilityBuilder {
         self
     }
 
+    /// Enables export of the `tracing` spans to an OpenTelemetry collector over OTLP/HTTP.
+    /// `endpoint` is the base URL of the collector (e.g., `http://127.0.0.1:4318`); `sampling_ratio`
+    /// is the fraction of the root traces to be exported. The ratio is expected to be validated by the caller;
+    /// values outside the `[0.0, 1.0]` range are treated as the nearest bound.
+    pub fn with_opentelemetry(
+        mut self,
+        endpoint: impl Into<String>,
+        service_name: impl Into<String>,
+        sampling_ratio: f64,
+    ) -> Self {
+        self.opentelemetry_options = Some(OpenTelemetryOptions {
+            endpoint: endpoint.into(),
+            service_name: service_name.into(),
+            sampling_ratio,
+        });
+        self
+    }
+
     /// Initializes the observability subsystem.
-    pub fn build(self) -> ObservabilityGuard {
+    ///
+    /// Returns an error if OpenTelemetry export is enabled, but the exporter cannot be set up
+    /// (e.g., the collector endpoint is malformed).
+    ///
+    /// # Panics
+    ///
+    /// If OpenTelemetry export is enabled, this method must be called within a Tokio runtime,
+    /// since the batch span exporter is spawned on it.
+    pub fn build(self) -> anyhow::Result<ObservabilityGuard> {
+        let opentelemetry_enabled = self.opentelemetry_options.is_some();
+        let opentelemetry_tracer = self
+            .opentelemetry_options
+            .map(OpenTelemetryOptions::install_tracer)
+            .transpose()?;
+
         // Initialize logs.
         match self.log_format {
             LogFormat::Plain => {
                 tracing_subscriber::registry()
                     .with(tracing_subscriber::EnvFilter::from_default_env())
                     .with(fmt::Layer::default())
+                    .with(
+                        opentelemetry_tracer
+                            .map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)),
+                    )
                     .init();
             }
             LogFormat::Json => {
@@ -90,6 +185,10 @@ impl ObservabilityBuilder {
                             .with_timer(timer)
                             .json(),
                     )
+                    .with(
+                        opentelemetry_tracer
+                            .map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)),
+                    )
                     .init();
             }
         };
@@ -119,9 +218,10 @@ impl ObservabilityBuilder {
             None
         };
 
-        ObservabilityGuard {
+        Ok(ObservabilityGuard {
             _sentry_guard: sentry_guard,
-        }
+            opentelemetry_enabled,
+        })
     }
 }
 
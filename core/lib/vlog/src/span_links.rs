@@ -0,0 +1,77 @@
+//! Linking of `tracing` spans across asynchronous hand-offs.
+//!
+//! Some units of work (e.g., transactions) are passed between components via the database rather than
+//! via function calls, so the span processing the unit in one component cannot be the parent of the span
+//! processing it in the next one. [`SpanLinks`] remembers OpenTelemetry contexts of spans by a domain key
+//! (a transaction hash, an L1 batch number, etc.), so that the spans of the next stages can link to them.
+//!
+//! Contexts are stored in memory, so links only connect stages running in the same process. If OpenTelemetry
+//! export is not enabled, spans have no valid context, and all operations are no-ops.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    sync::Mutex,
+};
+
+use opentelemetry::trace::{SpanContext, TraceContextExt as _};
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+/// Bounded registry of span contexts keyed by `K`. Once the capacity is reached, the oldest contexts
+/// are evicted.
+#[derive(Debug)]
+pub struct SpanLinks<K> {
+    capacity: usize,
+    inner: Mutex<SpanLinksInner<K>>,
+}
+
+#[derive(Debug)]
+struct SpanLinksInner<K> {
+    contexts: HashMap<K, SpanContext>,
+    insertion_order: VecDeque<K>,
+}
+
+impl<K: Clone + Eq + Hash> SpanLinks<K> {
+    /// Creates an empty registry holding at most `capacity` contexts.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "span links capacity must be positive");
+        Self {
+            capacity,
+            inner: Mutex::new(SpanLinksInner {
+                contexts: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Remembers the context of `span` under `key`, overwriting the previously recorded context, if any.
+    pub fn record(&self, span: &tracing::Span, key: K) {
+        let span_context = span.context().span().span_context().clone();
+        if !span_context.is_valid() {
+            return;
+        }
+
+        let mut inner = self.inner.lock().expect("span links are poisoned");
+        if inner.contexts.insert(key.clone(), span_context).is_none() {
+            inner.insertion_order.push_back(key);
+        }
+        while inner.insertion_order.len() > self.capacity {
+            let evicted_key = inner.insertion_order.pop_front().unwrap();
+            inner.contexts.remove(&evicted_key);
+        }
+    }
+
+    /// Links `span` to the span recorded under `key`. Returns `false` if there is no such span.
+    pub fn link(&self, span: &tracing::Span, key: &K) -> bool {
+        if span.is_disabled() {
+            return false;
+        }
+        let inner = self.inner.lock().expect("span links are poisoned");
+        let Some(span_context) = inner.contexts.get(key).cloned() else {
+            return false;
+        };
+        drop(inner);
+        span.add_link(span_context);
+        true
+    }
+}
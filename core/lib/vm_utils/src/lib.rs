@@ -2,14 +2,16 @@ pub mod storage;
 
 use anyhow::{anyhow, Context};
 use multivm::{
-    interface::{VmInterface, VmInterfaceHistoryEnabled},
+    interface::{
+        L1BatchEnv, SystemEnv, VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled,
+    },
     vm_latest::HistoryEnabled,
     VmInstance,
 };
 use tokio::runtime::Handle;
 use zksync_dal::StorageProcessor;
 use zksync_state::{PostgresStorage, StoragePtr, StorageView, WriteStorage};
-use zksync_types::{L1BatchNumber, L2ChainId, Transaction};
+use zksync_types::{L1BatchNumber, L2ChainId, MiniblockNumber, Transaction};
 
 use crate::storage::L1BatchParamsProvider;
 
@@ -24,13 +26,29 @@ pub fn create_vm(
     mut connection: StorageProcessor<'_>,
     l2_chain_id: L2ChainId,
 ) -> anyhow::Result<VmAndStorage> {
+    let (system_env, l1_batch_env) =
+        load_vm_env(&rt_handle, l1_batch_number, &mut connection, l2_chain_id)?;
+    Ok(create_vm_with_env(
+        rt_handle,
+        connection,
+        system_env,
+        l1_batch_env,
+    ))
+}
+
+/// Loads VM environment for the specified L1 batch, which must have at least one miniblock persisted.
+pub fn load_vm_env(
+    rt_handle: &Handle,
+    l1_batch_number: L1BatchNumber,
+    connection: &mut StorageProcessor<'_>,
+    l2_chain_id: L2ChainId,
+) -> anyhow::Result<(SystemEnv, L1BatchEnv)> {
     let l1_batch_params_provider = rt_handle
-        .block_on(L1BatchParamsProvider::new(&mut connection))
+        .block_on(L1BatchParamsProvider::new(connection))
         .context("failed initializing L1 batch params provider")?;
     let first_miniblock_in_batch = rt_handle
         .block_on(
-            l1_batch_params_provider
-                .load_first_miniblock_in_batch(&mut connection, l1_batch_number),
+            l1_batch_params_provider.load_first_miniblock_in_batch(connection, l1_batch_number),
         )
         .with_context(|| format!("failed loading first miniblock in L1 batch #{l1_batch_number}"))?
         .with_context(|| format!("no miniblocks persisted for L1 batch #{l1_batch_number}"))?;
@@ -40,51 +58,51 @@ pub fn create_vm(
     // This means we don't want to reject any execution, therefore we're using MAX as an allow all.
     let validation_computational_gas_limit = u32::MAX;
 
-    let (system_env, l1_batch_env) = rt_handle
+    rt_handle
         .block_on(l1_batch_params_provider.load_l1_batch_params(
-            &mut connection,
+            connection,
             &first_miniblock_in_batch,
             validation_computational_gas_limit,
             l2_chain_id,
         ))
-        .context("expected miniblock to be executed and sealed")?;
+        .context("expected miniblock to be executed and sealed")
+}
 
-    let storage_miniblock_number = first_miniblock_in_batch.number() - 1;
-    let pg_storage = PostgresStorage::new(
-        rt_handle.clone(),
-        connection,
-        storage_miniblock_number,
-        true,
-    );
+/// Creates a VM with the provided environment on top of the Postgres storage as of the start of the L1 batch.
+/// The VM doesn't modify the storage; all writes are kept in memory by the returned storage view.
+pub fn create_vm_with_env(
+    rt_handle: Handle,
+    connection: StorageProcessor<'_>,
+    system_env: SystemEnv,
+    l1_batch_env: L1BatchEnv,
+) -> VmAndStorage {
+    let storage_miniblock_number = MiniblockNumber(l1_batch_env.first_l2_block.number - 1);
+    let pg_storage = PostgresStorage::new(rt_handle, connection, storage_miniblock_number, true);
     let storage_view = StorageView::new(pg_storage).to_rc_ptr();
     let vm = VmInstance::new(l1_batch_env, system_env, storage_view.clone());
-
-    Ok((vm, storage_view))
+    (vm, storage_view)
 }
 
+/// Executes a transaction in the VM, returning the execution result.
 pub fn execute_tx<S: WriteStorage>(
     tx: &Transaction,
     vm: &mut VmInstance<S, HistoryEnabled>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<VmExecutionResultAndLogs> {
     // Attempt to run VM with bytecode compression on.
     vm.make_snapshot();
-    if vm
-        .execute_transaction_with_bytecode_compression(tx.clone(), true)
-        .0
-        .is_ok()
-    {
+    let (compression_result, tx_result) =
+        vm.execute_transaction_with_bytecode_compression(tx.clone(), true);
+    if compression_result.is_ok() {
         vm.pop_snapshot_no_rollback();
-        return Ok(());
+        return Ok(tx_result);
     }
 
     // If failed with bytecode compression, attempt to run without bytecode compression.
     vm.rollback_to_the_latest_snapshot();
-    if vm
-        .execute_transaction_with_bytecode_compression(tx.clone(), false)
-        .0
-        .is_err()
-    {
+    let (compression_result, tx_result) =
+        vm.execute_transaction_with_bytecode_compression(tx.clone(), false);
+    if compression_result.is_err() {
         return Err(anyhow!("compression can't fail if we don't apply it"));
     }
-    Ok(())
+    Ok(tx_result)
 }
@@ -4,8 +4,8 @@ use bigdecimal::BigDecimal;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchCosts, L1BatchDetails, L2ToL1LogProof,
+        PriorityOpIdentifier, PriorityOpStatus, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -96,6 +96,15 @@ pub trait ZksNamespace {
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    #[method(name = "getL1BatchCosts")]
+    async fn get_l1_batch_costs(&self, batch: L1BatchNumber) -> RpcResult<Option<L1BatchCosts>>;
+
+    #[method(name = "getPriorityOpStatus")]
+    async fn get_priority_op_status(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> RpcResult<Option<PriorityOpStatus>>;
+
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
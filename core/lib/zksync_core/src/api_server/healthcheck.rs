@@ -1,19 +1,55 @@
 use std::{collections::HashSet, net::SocketAddr, sync::Arc, time::Duration};
 
-use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
 use tokio::sync::watch;
 use zksync_health_check::{AppHealth, CheckHealth};
 
+fn status_code(is_healthy: bool) -> StatusCode {
+    if is_healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// Readiness probe aggregating all health checks.
 async fn check_health<T: AsRef<dyn CheckHealth>>(
     health_checks: State<Arc<[T]>>,
 ) -> (StatusCode, Json<AppHealth>) {
     let response = AppHealth::new(&health_checks).await;
-    let response_code = if response.is_ready() {
-        StatusCode::OK
-    } else {
-        StatusCode::SERVICE_UNAVAILABLE
-    };
-    (response_code, Json(response))
+    (status_code(response.is_ready()), Json(response))
+}
+
+/// Liveness probe aggregating only liveness health checks. Unlike readiness, it doesn't fail
+/// if a component is not ready yet; only panicked or stale components fail it.
+async fn check_liveness<T: AsRef<dyn CheckHealth>>(
+    health_checks: State<Arc<[T]>>,
+) -> (StatusCode, Json<AppHealth>) {
+    let response = AppHealth::liveness(&health_checks).await;
+    (status_code(response.is_alive()), Json(response))
+}
+
+async fn check_component_health<T: AsRef<dyn CheckHealth>>(
+    health_checks: State<Arc<[T]>>,
+    Path(name): Path<String>,
+) -> Response {
+    match AppHealth::component(&health_checks, &name).await {
+        Some(health) => {
+            let response_code = status_code(health.status().is_ready());
+            (response_code, Json(health)).into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("Health check `{name}` is not defined"),
+        )
+            .into_response(),
+    }
 }
 
 async fn run_server<T>(
@@ -38,8 +74,13 @@ async fn run_server<T>(
     );
 
     let health_checks = Arc::from(health_checks);
+    // Static routes take precedence over `/health/:name`, so components named `ready` or `live`
+    // are only reachable via the aggregated endpoints.
     let app = Router::new()
         .route("/health", get(check_health))
+        .route("/health/ready", get(check_health))
+        .route("/health/live", get(check_liveness))
+        .route("/health/:name", get(check_component_health))
         .with_state(health_checks);
 
     axum::Server::bind(bind_address)
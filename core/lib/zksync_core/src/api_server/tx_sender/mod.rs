@@ -39,6 +39,7 @@ use crate::{
     fee_model::BatchFeeModelInputProvider,
     metrics::{TxStage, APP_METRICS},
     state_keeper::seal_criteria::{ConditionalSealer, NoopSealer, SealData},
+    utils::TX_SPAN_LINKS,
 };
 
 mod proxy;
@@ -284,7 +285,10 @@ impl TxSender {
             .context("failed acquiring connection to replica DB")
     }
 
-    #[tracing::instrument(skip(self, tx))]
+    #[tracing::instrument(
+        skip_all,
+        fields(tx.hash = ?tx.hash(), tx.initiator = ?tx.initiator_account())
+    )]
     pub async fn submit_tx(&self, tx: L2Tx) -> Result<L2TxSubmissionResult, SubmitTxError> {
         let stage_latency = SANDBOX_METRICS.submit_tx[&SubmitTxStage::Validate].start();
         self.validate_tx(&tx).await?;
@@ -401,6 +405,7 @@ impl TxSender {
             _ => {
                 SANDBOX_METRICS.submit_tx[&SubmitTxStage::DbInsert]
                     .observe(stage_started_at.elapsed());
+                TX_SPAN_LINKS.record(&tracing::Span::current(), hash);
                 Ok(submission_res_handle)
             }
         }
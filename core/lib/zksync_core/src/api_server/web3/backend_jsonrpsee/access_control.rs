@@ -0,0 +1,634 @@
+//! Access control for the JSON-RPC servers: API keys, per-client rate limits and method filtering.
+//!
+//! Access control consists of 2 parts:
+//!
+//! - [`AuthService`] is an HTTP service that authenticates the client (by the API key, or by the IP address
+//!   if the key is not supplied) and creates the request handler for the client identity. The IP address
+//!   is the address of the connection peer or, if the peer is a trusted reverse proxy, is determined
+//!   from the `x-forwarded-for` header (see [`AccessControl::client_id_by_ip()`]).
+//! - [`AccessControlMiddleware`] is an RPC-level middleware that checks every call (including calls in batches)
+//!   against the method filter and the rate limits for the client identity.
+//!
+//! WebSocket sessions are authenticated during the handshake; all calls in a session are rate-limited
+//! as the calls of the authenticated client, in addition to the per-session limit enforced by
+//! [`LimitMiddleware`](super::batch_limiter_middleware::LimitMiddleware).
+
+use std::{
+    collections::HashSet,
+    fmt,
+    net::IpAddr,
+    num::NonZeroU32,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future, future::BoxFuture, FutureExt};
+use governor::{clock::DefaultClock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
+use hyper::{
+    header::{self, HeaderMap},
+    http::uri::{PathAndQuery, Uri},
+    Body, Request, Response, StatusCode,
+};
+use tokio::sync::watch;
+use tower::ServiceExt as _;
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Metrics};
+use zksync_config::configs::api::Web3JsonRpcConfig;
+use zksync_web3_decl::jsonrpsee::{
+    server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
+    types::{error::ErrorCode, ErrorObject, Request as RpcRequest},
+    MethodResponse,
+};
+
+/// HTTP header containing the API key.
+pub(crate) const API_KEY_HEADER: &str = "x-api-key";
+/// HTTP header containing addresses of the client and the reverse proxies the request has passed through.
+const X_FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+/// Methods invoking the VM. Their weight for the purposes of rate limiting is determined by
+/// [`AccessControl::vm_request_weight`].
+const VM_METHODS: &[&str] = &[
+    "eth_call",
+    "eth_estimateGas",
+    "eth_sendRawTransaction",
+    "zks_estimateFee",
+    "zks_estimateGasL1ToL2",
+    "debug_*",
+];
+/// Interval between removing stale clients from rate limiters.
+const LIMITER_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "client", rename_all = "snake_case")]
+enum ClientKind {
+    ApiKey,
+    Ip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+enum AuthFailureReason {
+    UnknownApiKey,
+    MissingApiKey,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_jsonrpc_backend_access")]
+struct AccessControlMetrics {
+    /// Number of requests rejected because the client has exceeded its rate limit.
+    rate_limited: Family<ClientKind, Counter>,
+    /// Number of calls to methods not allowed by the method filter.
+    denied_methods: Counter,
+    /// Number of requests rejected during authentication.
+    auth_failures: Family<AuthFailureReason, Counter>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<AccessControlMetrics> = vise::Global::new();
+
+/// Returns whether `method` matches `pattern`. Patterns ending with `*` match by the method name prefix.
+fn method_matches(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
+
+/// Allow / deny lists for JSON-RPC methods.
+#[derive(Debug, Default)]
+struct MethodFilter {
+    allowed: Option<Vec<String>>,
+    denied: Vec<String>,
+}
+
+impl MethodFilter {
+    fn is_allowed(&self, method: &str) -> bool {
+        let is_allowed = self.allowed.as_ref().map_or(true, |allowed| {
+            allowed
+                .iter()
+                .any(|pattern| method_matches(pattern, method))
+        });
+        is_allowed
+            && !self
+                .denied
+                .iter()
+                .any(|pattern| method_matches(pattern, method))
+    }
+}
+
+/// Identity of a JSON-RPC client used for rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    ApiKey(Arc<str>),
+    Ip(IpAddr),
+    /// Client without an API key whose request was sent by a trusted proxy itself (i.e., an internal client).
+    /// Such clients are not rate-limited.
+    Unidentified,
+}
+
+/// Token-bucket rate limiter keyed by the client identity.
+struct ClientRateLimiter {
+    inner: RateLimiter<ClientId, DefaultKeyedStateStore<ClientId>, DefaultClock>,
+    requests_per_minute: NonZeroU32,
+}
+
+impl fmt::Debug for ClientRateLimiter {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ClientRateLimiter")
+            .field("requests_per_minute", &self.requests_per_minute)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ClientRateLimiter {
+    fn new(requests_per_minute: NonZeroU32) -> Self {
+        Self {
+            inner: RateLimiter::keyed(Quota::per_minute(requests_per_minute)),
+            requests_per_minute,
+        }
+    }
+
+    fn check(&self, client: &ClientId, weight: NonZeroU32) -> bool {
+        // Requests heavier than the bucket capacity would be rejected unconditionally otherwise.
+        let weight = weight.min(self.requests_per_minute);
+        self.inner.check_key_n(client, weight).is_ok()
+    }
+}
+
+/// Access control settings shared by all requests to a JSON-RPC server.
+///
+/// The default value doesn't restrict access in any way.
+#[derive(Debug)]
+pub struct AccessControl {
+    api_keys: HashSet<Arc<str>>,
+    api_key_required: bool,
+    api_key_limiter: Option<ClientRateLimiter>,
+    ip_limiter: Option<ClientRateLimiter>,
+    trusted_proxies: HashSet<IpAddr>,
+    vm_request_weight: NonZeroU32,
+    method_filter: MethodFilter,
+}
+
+impl Default for AccessControl {
+    fn default() -> Self {
+        Self {
+            api_keys: HashSet::new(),
+            api_key_required: false,
+            api_key_limiter: None,
+            ip_limiter: None,
+            trusted_proxies: HashSet::new(),
+            vm_request_weight: NonZeroU32::MIN,
+            method_filter: MethodFilter::default(),
+        }
+    }
+}
+
+impl AccessControl {
+    pub fn new(config: &Web3JsonRpcConfig) -> Self {
+        Self {
+            api_keys: config
+                .api_keys
+                .expose()
+                .iter()
+                .map(|key| key.as_str().into())
+                .collect(),
+            api_key_required: config.api_key_required,
+            api_key_limiter: config
+                .http_requests_per_minute_per_api_key
+                .map(ClientRateLimiter::new),
+            ip_limiter: config
+                .http_requests_per_minute_per_ip
+                .map(ClientRateLimiter::new),
+            trusted_proxies: config.trusted_proxies.iter().copied().collect(),
+            vm_request_weight: config.vm_request_weight(),
+            method_filter: MethodFilter {
+                allowed: config.allowed_methods.clone(),
+                denied: config.denied_methods.clone(),
+            },
+        }
+    }
+
+    /// Authenticates an HTTP request received from `peer_ip`. If the API key is supplied in the URL path,
+    /// the path is reset to `/` so that the request is routed as usual.
+    fn authenticate(
+        &self,
+        request: &mut Request<Body>,
+        peer_ip: IpAddr,
+    ) -> Result<ClientId, AuthFailureReason> {
+        if self.api_keys.is_empty() {
+            return Ok(self.client_id_by_ip(request.headers(), peer_ip));
+        }
+
+        let header_key = request
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let api_key = match header_key {
+            Some(key) => Some(key),
+            None => Self::take_path_key(request),
+        };
+
+        match api_key {
+            Some(key) => {
+                let key = self
+                    .api_keys
+                    .get(key.as_str())
+                    .ok_or(AuthFailureReason::UnknownApiKey)?;
+                Ok(ClientId::ApiKey(key.clone()))
+            }
+            None if self.api_key_required => Err(AuthFailureReason::MissingApiKey),
+            None => Ok(self.client_id_by_ip(request.headers(), peer_ip)),
+        }
+    }
+
+    fn take_path_key(request: &mut Request<Body>) -> Option<String> {
+        let key = request.uri().path().trim_matches('/');
+        if key.is_empty() {
+            return None;
+        }
+        let key = key.to_owned();
+
+        let mut uri_parts = request.uri().clone().into_parts();
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("/?{query}").parse().ok(),
+            None => Some(PathAndQuery::from_static("/")),
+        };
+        uri_parts.path_and_query = path_and_query;
+        if let Ok(uri) = Uri::from_parts(uri_parts) {
+            *request.uri_mut() = uri;
+        }
+        Some(key)
+    }
+
+    /// Determines the client IP address for a request received from `peer_ip`. If the peer is not a trusted proxy,
+    /// it is the client. Otherwise, the client address is determined from the `x-forwarded-for` header. Each reverse
+    /// proxy appends the address of its peer to the header, so the entries supplied by the client itself are
+    /// on the left. Thus, the header is traversed from the end, skipping trusted proxies; the first remaining entry
+    /// is the client address.
+    ///
+    /// Requests sent by trusted proxies themselves (i.e., ones without untrusted entries in the header)
+    /// are considered to be internal.
+    fn client_id_by_ip(&self, headers: &HeaderMap, peer_ip: IpAddr) -> ClientId {
+        if !self.trusted_proxies.contains(&peer_ip) {
+            return ClientId::Ip(peer_ip);
+        }
+
+        let forwarded_ips: Vec<_> = headers
+            .get_all(X_FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect();
+        let mut last_trusted_ip = peer_ip;
+        for ip in forwarded_ips.into_iter().rev() {
+            let Ok(ip) = ip.trim().parse::<IpAddr>() else {
+                // Since trusted proxies append well-formed entries, the entry must have been supplied
+                // by the last trusted proxy's peer, so the proxy is the closest known address of the client.
+                tracing::debug!("Malformed `{X_FORWARDED_FOR_HEADER}` entry: {ip:?}");
+                return ClientId::Ip(last_trusted_ip);
+            };
+            if !self.trusted_proxies.contains(&ip) {
+                return ClientId::Ip(ip);
+            }
+            last_trusted_ip = ip;
+        }
+        ClientId::Unidentified
+    }
+
+    fn request_weight(&self, method: &str) -> NonZeroU32 {
+        if VM_METHODS
+            .iter()
+            .any(|pattern| method_matches(pattern, method))
+        {
+            self.vm_request_weight
+        } else {
+            NonZeroU32::MIN
+        }
+    }
+
+    /// Checks a single JSON-RPC call against the method filter and the rate limit for the `client`.
+    fn check_call(&self, client: &ClientId, method: &str) -> Result<(), ErrorObject<'static>> {
+        if !self.method_filter.is_allowed(method) {
+            METRICS.denied_methods.inc();
+            return Err(ErrorObject::owned(
+                ErrorCode::MethodNotFound.code(),
+                format!("Method `{method}` is not allowed"),
+                None::<()>,
+            ));
+        }
+
+        let (limiter, client_kind) = match client {
+            ClientId::ApiKey(_) => (&self.api_key_limiter, ClientKind::ApiKey),
+            ClientId::Ip(_) => (&self.ip_limiter, ClientKind::Ip),
+            ClientId::Unidentified => return Ok(()),
+        };
+        let Some(limiter) = limiter else {
+            return Ok(());
+        };
+        if !limiter.check(client, self.request_weight(method)) {
+            METRICS.rate_limited[&client_kind].inc();
+            return Err(ErrorObject::borrowed(
+                ErrorCode::ServerError(StatusCode::TOO_MANY_REQUESTS.as_u16().into()).code(),
+                "Too many requests",
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Periodically removes stale clients from the rate limiters so that their state doesn't grow unboundedly.
+    pub(crate) async fn run_limiter_cleanup(
+        self: Arc<Self>,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let limiters = [&self.api_key_limiter, &self.ip_limiter];
+        if limiters.iter().all(|limiter| limiter.is_none()) {
+            return Ok(());
+        }
+        while !*stop_receiver.borrow() {
+            if tokio::time::timeout(LIMITER_CLEANUP_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+            for limiter in limiters.iter().copied().flatten() {
+                limiter.inner.retain_recent();
+            }
+        }
+        tracing::info!("Stop signal received, rate limiter cleanup is shutting down");
+        Ok(())
+    }
+}
+
+fn unauthorized_response(reason: AuthFailureReason) -> Response<Body> {
+    let message = match reason {
+        AuthFailureReason::UnknownApiKey => "Unknown API key",
+        AuthFailureReason::MissingApiKey => "API key is required",
+    };
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": ErrorCode::ServerError(StatusCode::UNAUTHORIZED.as_u16().into()).code(),
+            "message": message,
+        },
+        "id": null,
+    });
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = StatusCode::UNAUTHORIZED;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+/// HTTP service authenticating JSON-RPC clients connected from a certain peer address. For each authenticated request,
+/// the inner service is created by `make_inner` for the client identity. This allows to check calls of the client
+/// by the RPC middleware both for HTTP requests and for WebSocket sessions upgraded from HTTP requests.
+#[derive(Clone)]
+pub(crate) struct AuthService<F> {
+    make_inner: F,
+    access_control: Arc<AccessControl>,
+    peer_ip: IpAddr,
+}
+
+impl<F> fmt::Debug for AuthService<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AuthService")
+            .field("access_control", &self.access_control)
+            .field("peer_ip", &self.peer_ip)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> AuthService<F> {
+    pub(crate) fn new(make_inner: F, access_control: Arc<AccessControl>, peer_ip: IpAddr) -> Self {
+        Self {
+            make_inner,
+            access_control,
+            peer_ip,
+        }
+    }
+}
+
+impl<F, S> tower::Service<Request<Body>> for AuthService<F>
+where
+    F: Fn(ClientId) -> S,
+    S: tower::Service<Request<Body>, Response = Response<Body>> + Send + 'static,
+    S::Error: Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The inner service is created for each request, so its readiness is checked in `call()`.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: Request<Body>) -> Self::Future {
+        match self.access_control.authenticate(&mut request, self.peer_ip) {
+            Ok(client) => (self.make_inner)(client).oneshot(request).boxed(),
+            Err(reason) => {
+                tracing::debug!("Rejected JSON-RPC request: {reason:?}");
+                METRICS.auth_failures[&reason].inc();
+                future::ready(Ok(unauthorized_response(reason))).boxed()
+            }
+        }
+    }
+}
+
+/// RPC middleware checking calls of an authenticated client against the method filter and per-client rate limits.
+pub(crate) struct AccessControlMiddleware<S> {
+    inner: S,
+    access_control: Arc<AccessControl>,
+    client: ClientId,
+}
+
+impl<S> AccessControlMiddleware<S> {
+    pub(crate) fn new(inner: S, access_control: Arc<AccessControl>, client: ClientId) -> Self {
+        Self {
+            inner,
+            access_control,
+            client,
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for AccessControlMiddleware<S>
+where
+    S: Send + Clone + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: RpcRequest<'a>) -> Self::Future {
+        if let Err(err) = self
+            .access_control
+            .check_call(&self.client, request.method_name())
+        {
+            return ResponseFuture::ready(MethodResponse::error(request.id, err));
+        }
+        ResponseFuture::future(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> Web3JsonRpcConfig {
+        Web3JsonRpcConfig {
+            api_keys: vec!["key".into()].into(),
+            http_requests_per_minute_per_api_key: NonZeroU32::new(20),
+            http_requests_per_minute_per_ip: NonZeroU32::new(5),
+            vm_request_weight: NonZeroU32::new(4),
+            allowed_methods: Some(vec!["eth_*".into(), "net_version".into()]),
+            denied_methods: vec!["eth_getLogs".into()],
+            trusted_proxies: vec!["10.0.0.254".parse().unwrap()],
+            ..Web3JsonRpcConfig::for_tests()
+        }
+    }
+
+    fn request(uri: &str, headers: &[(&'static str, &str)]) -> Request<Body> {
+        let mut builder = Request::post(uri);
+        for &(name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn filtering_methods() {
+        let access_control = AccessControl::new(&test_config());
+        let filter = &access_control.method_filter;
+        assert!(filter.is_allowed("eth_call"));
+        assert!(filter.is_allowed("eth_blockNumber"));
+        assert!(filter.is_allowed("net_version"));
+        assert!(!filter.is_allowed("net_peerCount"));
+        assert!(!filter.is_allowed("eth_getLogs"));
+        assert!(!filter.is_allowed("zks_L1ChainId"));
+        assert!(MethodFilter::default().is_allowed("debug_traceCall"));
+
+        assert_eq!(access_control.request_weight("eth_call").get(), 4);
+        assert_eq!(
+            access_control
+                .request_weight("debug_traceBlockByNumber")
+                .get(),
+            4
+        );
+        assert_eq!(access_control.request_weight("eth_blockNumber").get(), 1);
+    }
+
+    #[test]
+    fn authenticating_clients() {
+        let mut access_control = AccessControl::new(&test_config());
+        let peer_ip: IpAddr = "1.2.3.4".parse().unwrap();
+
+        let mut req = request("/", &[(API_KEY_HEADER, "key")]);
+        let client = access_control.authenticate(&mut req, peer_ip).unwrap();
+        assert_eq!(client, ClientId::ApiKey("key".into()));
+
+        let mut req = request("/key", &[]);
+        let client = access_control.authenticate(&mut req, peer_ip).unwrap();
+        assert_eq!(client, ClientId::ApiKey("key".into()));
+        assert_eq!(req.uri().path(), "/");
+
+        let mut req = request("/other", &[]);
+        let err = access_control.authenticate(&mut req, peer_ip).unwrap_err();
+        assert_eq!(err, AuthFailureReason::UnknownApiKey);
+
+        let mut req = request("/", &[]);
+        let client = access_control.authenticate(&mut req, peer_ip).unwrap();
+        assert_eq!(client, ClientId::Ip(peer_ip));
+
+        access_control.api_key_required = true;
+        let mut req = request("/", &[]);
+        let err = access_control.authenticate(&mut req, peer_ip).unwrap_err();
+        assert_eq!(err, AuthFailureReason::MissingApiKey);
+    }
+
+    #[test]
+    fn determining_client_ip() {
+        let access_control = AccessControl::new(&test_config());
+        let proxy_ip: IpAddr = "10.0.0.254".parse().unwrap();
+        let client_ip = |headers: &[(&'static str, &str)]| {
+            let req = request("/", headers);
+            access_control.client_id_by_ip(req.headers(), proxy_ip)
+        };
+        let ip = |s: &str| ClientId::Ip(s.parse().unwrap());
+
+        // The leftmost entries are supplied by the client and must be ignored.
+        let client = client_ip(&[(X_FORWARDED_FOR_HEADER, "1.2.3.4, 10.0.0.1, 10.0.0.2")]);
+        assert_eq!(client, ip("10.0.0.2"));
+        // Trusted proxies are skipped.
+        let client = client_ip(&[(X_FORWARDED_FOR_HEADER, "1.2.3.4, 10.0.0.1, 10.0.0.254")]);
+        assert_eq!(client, ip("10.0.0.1"));
+        // Multiple header values are treated as a single list.
+        let client = client_ip(&[
+            (X_FORWARDED_FOR_HEADER, "1.2.3.4, ::1"),
+            (X_FORWARDED_FOR_HEADER, "10.0.0.254"),
+        ]);
+        assert_eq!(client, ip("::1"));
+        // Headers that can be set by the client are ignored.
+        let client = client_ip(&[("x-real-ip", "1.2.3.4")]);
+        assert_eq!(client, ClientId::Unidentified);
+        let client = client_ip(&[(X_FORWARDED_FOR_HEADER, "10.0.0.254")]);
+        assert_eq!(client, ClientId::Unidentified);
+        // Malformed entries are attributed to the closest trusted proxy.
+        let client = client_ip(&[(X_FORWARDED_FOR_HEADER, "1.2.3.4, garbage, 10.0.0.254")]);
+        assert_eq!(client, ip("10.0.0.254"));
+
+        // If the peer is not a trusted proxy, it is the client regardless of the headers.
+        let peer_ip = "5.6.7.8".parse().unwrap();
+        let req = request("/", &[(X_FORWARDED_FOR_HEADER, "1.2.3.4")]);
+        let client = access_control.client_id_by_ip(req.headers(), peer_ip);
+        assert_eq!(client, ClientId::Ip(peer_ip));
+        let req = request("/", &[]);
+        let client = access_control.client_id_by_ip(req.headers(), peer_ip);
+        assert_eq!(client, ClientId::Ip(peer_ip));
+    }
+
+    #[test]
+    fn rate_limiting_clients() {
+        let access_control = AccessControl::new(&test_config());
+
+        let ip_client = ClientId::Ip("10.0.0.1".parse().unwrap());
+        access_control.check_call(&ip_client, "eth_call").unwrap();
+        access_control
+            .check_call(&ip_client, "eth_blockNumber")
+            .unwrap();
+        let err = access_control
+            .check_call(&ip_client, "eth_blockNumber")
+            .unwrap_err();
+        assert_eq!(err.message(), "Too many requests");
+
+        // Other clients are not affected.
+        let other_ip_client = ClientId::Ip("10.0.0.2".parse().unwrap());
+        access_control
+            .check_call(&other_ip_client, "eth_blockNumber")
+            .unwrap();
+        let err = access_control
+            .check_call(&other_ip_client, "net_peerCount")
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MethodNotFound.code());
+
+        // Internal clients are not rate-limited.
+        for _ in 0..10 {
+            access_control
+                .check_call(&ClientId::Unidentified, "eth_call")
+                .unwrap();
+        }
+
+        let key_client = ClientId::ApiKey("key".into());
+        for _ in 0..5 {
+            access_control.check_call(&key_client, "eth_call").unwrap();
+        }
+        access_control
+            .check_call(&key_client, "eth_call")
+            .unwrap_err();
+    }
+}
@@ -11,6 +11,7 @@ use zksync_web3_decl::{
 
 use crate::api_server::{tx_sender::SubmitTxError, web3::metrics::API_METRICS};
 
+pub mod access_control;
 pub mod batch_limiter_middleware;
 pub mod namespaces;
 
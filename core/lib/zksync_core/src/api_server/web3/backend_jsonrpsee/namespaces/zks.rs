@@ -3,8 +3,8 @@ use std::collections::HashMap;
 use bigdecimal::BigDecimal;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        BlockDetails, BridgeAddresses, L1BatchCosts, L1BatchDetails, L2ToL1LogProof,
+        PriorityOpIdentifier, PriorityOpStatus, Proof, ProtocolVersion, TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -135,6 +135,24 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(into_jsrpc_error)
     }
 
+    async fn get_l1_batch_costs(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<L1BatchCosts>> {
+        self.get_l1_batch_costs_impl(batch_number)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_priority_op_status(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> RpcResult<Option<PriorityOpStatus>> {
+        self.get_priority_op_status_impl(id)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
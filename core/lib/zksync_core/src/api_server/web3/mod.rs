@@ -1,9 +1,14 @@
-use std::{net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
+use std::{convert::Infallible, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
-use futures::future;
-use serde::Deserialize;
+use futures::{future, future::BoxFuture, FutureExt};
+use hyper::{
+    server::conn::AddrStream,
+    service::{make_service_fn, Service},
+    Body, Request, Response,
+};
+use serde::{Deserialize, Serialize};
 use tokio::{
     sync::{mpsc, oneshot, watch, Mutex},
     task::JoinHandle,
@@ -14,7 +19,7 @@ use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::MiniblockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
-        server::{BatchRequestConfig, RpcServiceBuilder, ServerBuilder},
+        server::{stop_channel, BatchRequestConfig, RpcServiceBuilder, ServerBuilder, StopHandle},
         RpcModule,
     },
     namespaces::{
@@ -38,7 +43,10 @@ use crate::{
         execution_sandbox::{BlockStartInfo, VmConcurrencyBarrier},
         tree::TreeApiHttpClient,
         tx_sender::TxSender,
-        web3::backend_jsonrpsee::batch_limiter_middleware::LimitMiddleware,
+        web3::backend_jsonrpsee::{
+            access_control::{AccessControl, AccessControlMiddleware, AuthService, ClientId},
+            batch_limiter_middleware::LimitMiddleware,
+        },
     },
     sync_layer::SyncState,
 };
@@ -71,7 +79,7 @@ enum ApiTransport {
     Http(SocketAddr),
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum Namespace {
     Eth,
@@ -112,6 +120,7 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    access_control: Option<Arc<AccessControl>>,
     tree_api_url: Option<String>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
@@ -216,6 +225,12 @@ impl ApiBuilder {
         self
     }
 
+    /// Configures API keys, per-client rate limits and method filtering for the server.
+    pub fn with_access_control(mut self, access_control: AccessControl) -> Self {
+        self.optional.access_control = Some(Arc::new(access_control));
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -412,6 +427,12 @@ impl FullApiParams {
             stop_receiver.clone(),
         );
         let mut tasks = vec![tokio::spawn(update_task)];
+        if let Some(access_control) = &self.optional.access_control {
+            let cleanup_task = access_control
+                .clone()
+                .run_limiter_cleanup(stop_receiver.clone());
+            tasks.push(tokio::spawn(cleanup_task));
+        }
 
         let pub_sub = if matches!(transport, ApiTransport::WebSocket(_))
             && self.namespaces.contains(&Namespace::Pubsub)
@@ -480,6 +501,7 @@ impl FullApiParams {
             .response_body_size_limit
             .map_or(u32::MAX, |limit| limit as u32);
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let access_control = self.optional.access_control.clone().unwrap_or_default();
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.vm_barrier.clone();
 
@@ -510,10 +532,6 @@ impl FullApiParams {
                 future::ready(())
             }),
         );
-        // Assemble server middleware.
-        let middleware = tower::ServiceBuilder::new()
-            .layer(in_flight_requests)
-            .option_layer(cors);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -522,34 +540,70 @@ impl FullApiParams {
             .unwrap_or(5_000);
         let server_builder = ServerBuilder::default()
             .max_connections(max_connections as u32)
-            .set_http_middleware(middleware)
             .max_response_body_size(response_body_size_limit)
             .set_batch_request_config(batch_request_config);
-
-        let (local_addr, server_handle) = if is_http {
-            // HTTP-specific settings
-            let server = server_builder
-                .http_only()
-                .build(addr)
-                .await
-                .context("Failed building HTTP JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+        let server_builder = if is_http {
+            server_builder.http_only()
         } else {
-            // WS specific settings
-            let server = server_builder
-                .set_rpc_middleware(RpcServiceBuilder::new().layer_fn(move |a| {
-                    LimitMiddleware::new(a, websocket_requests_per_minute_limit)
-                }))
-                .set_id_provider(EthSubscriptionIdProvider)
-                .build(addr)
-                .await
-                .context("Failed building WS JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.set_id_provider(EthSubscriptionIdProvider)
         };
-        let local_addr = local_addr.with_context(|| {
-            format!("Failed getting local address for {transport_str} JSON-RPC server")
-        })?;
+        let service_builder = server_builder.to_service_builder();
+        let (stop_handle, server_handle) = stop_channel();
+        let server_stop_handle = stop_handle.clone();
+        let server_access_control = access_control.clone();
+
+        // The RPC middleware is created for each authenticated client, so that all calls of the client
+        // (including ones in WebSocket sessions) are checked against its rate limits.
+        let (local_addr, server) = if is_http {
+            let make_rpc_service = move |client: ClientId| {
+                let access_control = access_control.clone();
+                service_builder
+                    .clone()
+                    .set_rpc_middleware(RpcServiceBuilder::new().layer_fn(move |a| {
+                        AccessControlMiddleware::new(a, access_control.clone(), client.clone())
+                    }))
+                    .build(rpc.clone(), stop_handle.clone())
+            };
+            Self::bind_jsonrpsee_server(
+                addr,
+                in_flight_requests,
+                cors,
+                server_access_control,
+                make_rpc_service,
+                server_stop_handle,
+            )
+        } else {
+            let make_rpc_service = move |client: ClientId| {
+                let access_control = access_control.clone();
+                service_builder
+                    .clone()
+                    .set_rpc_middleware(
+                        RpcServiceBuilder::new()
+                            .layer_fn(move |a| {
+                                AccessControlMiddleware::new(
+                                    a,
+                                    access_control.clone(),
+                                    client.clone(),
+                                )
+                            })
+                            .layer_fn(move |a| {
+                                LimitMiddleware::new(a, websocket_requests_per_minute_limit)
+                            }),
+                    )
+                    .build(rpc.clone(), stop_handle.clone())
+            };
+            Self::bind_jsonrpsee_server(
+                addr,
+                in_flight_requests,
+                cors,
+                server_access_control,
+                make_rpc_service,
+                server_stop_handle,
+            )
+        }
+        .with_context(|| format!("Failed building {transport_str} JSON-RPC server"))?;
         local_addr_sender.send(local_addr).ok();
+        let server_task = tokio::spawn(server);
 
         let close_handle = server_handle.clone();
         let closing_vm_barrier = vm_barrier.clone();
@@ -568,10 +622,50 @@ impl FullApiParams {
         });
         health_updater.update(HealthStatus::Ready.into());
 
+        let server_result = server_task.await.context("JSON-RPC server panicked")?;
+        // WebSocket sessions are detached from the server, so we need to wait until they are terminated as well.
+        server_handle.stop().ok();
         server_handle.stopped().await;
         drop(health_updater);
         tracing::info!("{transport_str} JSON-RPC server stopped");
         Self::wait_for_vm(vm_barrier, transport_str).await;
-        Ok(())
+        server_result.with_context(|| format!("{transport_str} JSON-RPC server failed"))
+    }
+
+    /// Binds an HTTP server serving JSON-RPC requests on `addr`. Each request is authenticated based
+    /// on the peer address of its connection, and is then handled by the service created by `make_rpc_service`
+    /// for the client identity.
+    fn bind_jsonrpsee_server<F, S>(
+        addr: SocketAddr,
+        in_flight_requests: InFlightRequestsLayer,
+        cors: Option<CorsLayer>,
+        access_control: Arc<AccessControl>,
+        make_rpc_service: F,
+        stop_handle: StopHandle,
+    ) -> anyhow::Result<(SocketAddr, BoxFuture<'static, hyper::Result<()>>)>
+    where
+        F: Fn(ClientId) -> S + Clone + Send + 'static,
+        S: Service<Request<Body>, Response = Response<Body>, Error = tower::BoxError>
+            + Send
+            + 'static,
+        S::Future: Send + 'static,
+    {
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let service = tower::ServiceBuilder::new()
+                .layer(in_flight_requests.clone())
+                .option_layer(cors.clone())
+                .service(AuthService::new(
+                    make_rpc_service.clone(),
+                    access_control.clone(),
+                    conn.remote_addr().ip(),
+                ));
+            future::ready(Ok::<_, Infallible>(service))
+        });
+        let server = hyper::Server::try_bind(&addr)
+            .with_context(|| format!("failed binding to {addr}"))?
+            .serve(make_service);
+        let local_addr = server.local_addr();
+        let server = server.with_graceful_shutdown(stop_handle.shutdown());
+        Ok((local_addr, server.boxed()))
     }
 }
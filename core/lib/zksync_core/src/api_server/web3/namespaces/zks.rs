@@ -2,12 +2,12 @@ use std::{collections::HashMap, convert::TryInto};
 
 use bigdecimal::{BigDecimal, Zero};
 use zksync_dal::StorageProcessor;
-use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchCosts, L1BatchDetails, L2ToL1LogProof,
+        PriorityOpIdentifier, PriorityOpStatus, Proof, ProtocolVersion, StorageProof,
+        TransactionDetails,
     },
     fee::Fee,
     fee_model::FeeParams,
@@ -26,9 +26,12 @@ use zksync_web3_decl::{
     types::{Address, Token, H256},
 };
 
-use crate::api_server::{
-    tree::TreeApiClient,
-    web3::{backend_jsonrpsee::internal_error, metrics::API_METRICS, RpcState},
+use crate::{
+    api_server::{
+        tree::TreeApiClient,
+        web3::{backend_jsonrpsee::internal_error, metrics::API_METRICS, RpcState},
+    },
+    utils::l2_to_l1_log_proof,
 };
 
 #[derive(Debug)]
@@ -359,25 +362,11 @@ impl ZksNamespace {
             return Ok(None);
         };
 
-        let merkle_tree_leaves = all_l1_logs_in_batch.iter().map(L2ToL1Log::to_bytes);
-
-        let min_tree_size = if batch
-            .protocol_version
-            .map(|v| v.is_pre_boojum())
-            .unwrap_or(true)
-        {
-            Some(L2ToL1Log::PRE_BOOJUM_MIN_L2_L1_LOGS_TREE_SIZE)
-        } else {
-            Some(L2ToL1Log::MIN_L2_L1_LOGS_TREE_SIZE)
-        };
-
-        let (root, proof) = MiniMerkleTree::new(merkle_tree_leaves, min_tree_size)
-            .merkle_root_and_path(l1_log_index);
-        Ok(Some(L2ToL1LogProof {
-            proof,
-            root,
-            id: l1_log_index as u32,
-        }))
+        Ok(Some(l2_to_l1_log_proof(
+            &all_l1_logs_in_batch,
+            l1_log_index,
+            batch.protocol_version,
+        )))
     }
 
     #[tracing::instrument(skip(self))]
@@ -543,6 +532,61 @@ impl ZksNamespace {
         l1_batch
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn get_l1_batch_costs_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchCosts>, Web3Error> {
+        const METHOD_NAME: &str = "get_l1_batch_costs";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        self.state.start_info.ensure_not_pruned(batch_number)?;
+        let mut storage = self.access_storage(METHOD_NAME).await?;
+        let costs = storage
+            .blocks_web3_dal()
+            .get_l1_batch_costs(batch_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err));
+
+        method_latency.observe();
+        costs
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_priority_op_status_impl(
+        &self,
+        id: PriorityOpIdentifier,
+    ) -> Result<Option<PriorityOpStatus>, Web3Error> {
+        const METHOD_NAME: &str = "get_priority_op_status";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self.access_storage(METHOD_NAME).await?;
+        let serial_id = match id {
+            PriorityOpIdentifier::SerialId(serial_id) => Some(serial_id),
+            PriorityOpIdentifier::L1TxHash(l1_tx_hash) => storage
+                .transactions_web3_dal()
+                .get_priority_op_id_by_l1_tx_hash(l1_tx_hash)
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?,
+        };
+        let Some(serial_id) = serial_id else {
+            method_latency.observe();
+            return Ok(None);
+        };
+
+        let statuses = storage
+            .transactions_web3_dal()
+            .get_priority_op_statuses(serial_id, 1)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let status = statuses
+            .into_iter()
+            .find(|status| status.serial_id == serial_id);
+
+        method_latency.observe();
+        Ok(status)
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn get_bytecode_by_hash_impl(
         &self,
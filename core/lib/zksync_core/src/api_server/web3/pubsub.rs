@@ -1,5 +1,7 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
+use std::collections::HashMap;
+
 use anyhow::Context as _;
 use futures::FutureExt;
 use tokio::{
@@ -7,8 +9,11 @@ use tokio::{
     task::JoinHandle,
     time::{interval, Duration},
 };
-use zksync_dal::ConnectionPool;
-use zksync_types::{MiniblockNumber, H128, H256};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::{
+    api::{PriorityOpStage, PriorityOpStatus},
+    L1BatchNumber, MiniblockNumber, PriorityOpId, H128, H256,
+};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -27,6 +32,8 @@ use super::{
 use crate::api_server::execution_sandbox::BlockStartInfo;
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
+/// Maximum number of priority operations loaded by the priority operations notifier in a single query.
+const PRIORITY_OPS_PAGE_SIZE: usize = 1_000;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, Copy)]
@@ -213,6 +220,266 @@ impl PubSubNotifier {
             .await
             .context("events_web3_dal().get_all_logs()")
     }
+
+    /// Returns the serial ID of the first priority operation that isn't executed on L1, or the ID
+    /// of the next priority operation if all operations are executed.
+    async fn get_starting_priority_op_id(
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<PriorityOpId> {
+        let last_executed_l1_batch = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_executed_on_eth()")?
+            .unwrap_or_default();
+        let first_unexecuted_op = storage
+            .transactions_dal()
+            .get_first_unexecuted_priority_op(last_executed_l1_batch)
+            .await
+            .context("get_first_unexecuted_priority_op()")?;
+        if let Some((serial_id, _)) = first_unexecuted_op {
+            return Ok(serial_id);
+        }
+        let last_priority_id = storage.transactions_dal().last_priority_id().await;
+        Ok(next_priority_op_id(last_priority_id))
+    }
+
+    /// Notifies about status changes of priority operations. Statuses of non-executed operations are kept in memory;
+    /// executed operations are no longer tracked. On each poll, only the operations which could have changed
+    /// since the previous poll are loaded from Postgres, and only if the server has progressed in the meantime
+    /// (e.g., a new priority operation was received, or an L1 batch was committed).
+    ///
+    /// Polling is paused while there are no subscribers. Once subscribers appear, statuses of all tracked operations
+    /// are reloaded and sent to them.
+    async fn notify_priority_ops(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut tracked_ops = None;
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!(
+                    "Stop signal received, pubsub_priority_ops_notifier is shutting down"
+                );
+                break;
+            }
+            timer.tick().await;
+
+            if self.sender.receiver_count() == 0 {
+                tracked_ops = None;
+            } else {
+                let db_latency =
+                    PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::PriorityOps].start();
+                let changed_statuses = self.poll_priority_ops(&mut tracked_ops).await?;
+                db_latency.observe();
+
+                if !changed_statuses.is_empty() {
+                    let changed_statuses = changed_statuses
+                        .into_iter()
+                        .map(PubSubResult::PriorityOpStatus)
+                        .collect();
+                    self.send_pub_sub_results(changed_statuses, SubscriptionType::PriorityOps);
+                }
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::PriorityOps,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Loads statuses of priority operations that could have changed since the previous poll, updates `tracked_ops`
+    /// and returns the changed statuses.
+    async fn poll_priority_ops(
+        &self,
+        tracked_ops: &mut Option<TrackedPriorityOps>,
+    ) -> anyhow::Result<Vec<PriorityOpStatus>> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        // Progress must be loaded before the statuses, so that changes made in between are reloaded on the next poll.
+        let progress = PriorityOpsProgress::load(&mut storage).await?;
+        let statuses = match tracked_ops.as_ref() {
+            Some(tracked) if tracked.progress == progress => return Ok(vec![]),
+            Some(tracked) => {
+                let new_ops_from_id = next_priority_op_id(tracked.progress.last_priority_op_id);
+                let l1_batches = progress.changed_l1_batches(&tracked.progress);
+                Self::load_priority_op_statuses(
+                    &mut storage,
+                    tracked.from_id,
+                    new_ops_from_id,
+                    tracked.progress.sealed_miniblock,
+                    &l1_batches,
+                )
+                .await?
+            }
+            None => {
+                let from_id = Self::get_starting_priority_op_id(&mut storage).await?;
+                Self::load_priority_op_statuses(&mut storage, from_id, from_id, None, &[]).await?
+            }
+        };
+        drop(storage);
+
+        let tracked_ops = tracked_ops.get_or_insert_with(|| TrackedPriorityOps::new(progress));
+        Ok(tracked_ops.update(progress, statuses))
+    }
+
+    /// Loads statuses of priority operations matching the provided filter (see
+    /// `TransactionsWeb3Dal::get_updated_priority_op_statuses()`) page by page.
+    async fn load_priority_op_statuses(
+        storage: &mut StorageProcessor<'_>,
+        from_id: PriorityOpId,
+        new_ops_from_id: PriorityOpId,
+        miniblocks_after: Option<MiniblockNumber>,
+        l1_batches: &[L1BatchNumber],
+    ) -> anyhow::Result<Vec<PriorityOpStatus>> {
+        let mut statuses = vec![];
+        let mut page_start = from_id;
+        loop {
+            let page = storage
+                .transactions_web3_dal()
+                .get_updated_priority_op_statuses(
+                    page_start,
+                    new_ops_from_id,
+                    miniblocks_after,
+                    l1_batches,
+                    PRIORITY_OPS_PAGE_SIZE,
+                )
+                .await
+                .with_context(|| format!("get_updated_priority_op_statuses({page_start})"))?;
+            let is_last_page = page.len() < PRIORITY_OPS_PAGE_SIZE;
+            if let Some(status) = page.last() {
+                page_start = status.serial_id.next();
+            }
+            statuses.extend(page);
+            if is_last_page {
+                return Ok(statuses);
+            }
+        }
+    }
+}
+
+fn next_priority_op_id(last_priority_op_id: Option<PriorityOpId>) -> PriorityOpId {
+    last_priority_op_id.map_or(PriorityOpId(0), PriorityOpId::next)
+}
+
+/// Server progress affecting priority operation statuses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PriorityOpsProgress {
+    last_priority_op_id: Option<PriorityOpId>,
+    sealed_miniblock: Option<MiniblockNumber>,
+    sealed_l1_batch: Option<L1BatchNumber>,
+    committed_l1_batch: Option<L1BatchNumber>,
+    proven_l1_batch: Option<L1BatchNumber>,
+    executed_l1_batch: Option<L1BatchNumber>,
+}
+
+impl PriorityOpsProgress {
+    async fn load(storage: &mut StorageProcessor<'_>) -> anyhow::Result<Self> {
+        Ok(Self {
+            last_priority_op_id: storage.transactions_dal().last_priority_id().await,
+            sealed_miniblock: storage
+                .blocks_dal()
+                .get_sealed_miniblock_number()
+                .await
+                .context("get_sealed_miniblock_number()")?,
+            sealed_l1_batch: storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .context("get_sealed_l1_batch_number()")?,
+            committed_l1_batch: storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_committed_on_eth()
+                .await
+                .context("get_number_of_last_l1_batch_committed_on_eth()")?,
+            proven_l1_batch: storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_proven_on_eth()
+                .await
+                .context("get_number_of_last_l1_batch_proven_on_eth()")?,
+            executed_l1_batch: storage
+                .blocks_dal()
+                .get_number_of_last_l1_batch_executed_on_eth()
+                .await
+                .context("get_number_of_last_l1_batch_executed_on_eth()")?,
+        })
+    }
+
+    /// Returns L1 batches that were sealed, committed, proven or executed since the `previous` progress.
+    fn changed_l1_batches(&self, previous: &Self) -> Vec<L1BatchNumber> {
+        let ranges = [
+            (previous.sealed_l1_batch, self.sealed_l1_batch),
+            (previous.committed_l1_batch, self.committed_l1_batch),
+            (previous.proven_l1_batch, self.proven_l1_batch),
+            (previous.executed_l1_batch, self.executed_l1_batch),
+        ];
+        let mut l1_batches: Vec<_> = ranges
+            .into_iter()
+            .flat_map(|(previous, current)| {
+                let start = previous.map_or(0, |number| number.0 + 1);
+                let end = current.map_or(0, |number| number.0 + 1);
+                (start..end).map(L1BatchNumber)
+            })
+            .collect();
+        l1_batches.sort_unstable();
+        l1_batches.dedup();
+        l1_batches
+    }
+}
+
+/// Statuses of priority operations tracked by the notifier.
+#[derive(Debug)]
+struct TrackedPriorityOps {
+    progress: PriorityOpsProgress,
+    /// Serial ID of the first tracked operation.
+    from_id: PriorityOpId,
+    /// Statuses of non-executed operations.
+    statuses: HashMap<PriorityOpId, PriorityOpStatus>,
+}
+
+impl TrackedPriorityOps {
+    fn new(progress: PriorityOpsProgress) -> Self {
+        Self {
+            progress,
+            from_id: PriorityOpId(0),
+            statuses: HashMap::new(),
+        }
+    }
+
+    /// Updates tracked statuses with the loaded ones and returns the changed statuses.
+    fn update(
+        &mut self,
+        progress: PriorityOpsProgress,
+        loaded_statuses: Vec<PriorityOpStatus>,
+    ) -> Vec<PriorityOpStatus> {
+        if progress.last_priority_op_id < self.progress.last_priority_op_id {
+            // Operations were removed, e.g. after an L1 reorg.
+            self.statuses
+                .retain(|&serial_id, _| Some(serial_id) <= progress.last_priority_op_id);
+        }
+
+        let changed_statuses: Vec<_> = loaded_statuses
+            .into_iter()
+            .filter(|status| self.statuses.get(&status.serial_id) != Some(status))
+            .collect();
+        for status in &changed_statuses {
+            if status.stage == PriorityOpStage::Executed {
+                self.statuses.remove(&status.serial_id);
+            } else {
+                self.statuses.insert(status.serial_id, status.clone());
+            }
+        }
+
+        self.from_id = self
+            .statuses
+            .keys()
+            .copied()
+            .min()
+            .unwrap_or_else(|| next_priority_op_id(progress.last_priority_op_id));
+        self.progress = progress;
+        changed_statuses
+    }
 }
 
 /// Subscription support for Web3 APIs.
@@ -220,6 +487,7 @@ pub(super) struct EthSubscribe {
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    priority_ops: broadcast::Sender<Vec<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -228,11 +496,13 @@ impl EthSubscribe {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (priority_ops, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             blocks,
             transactions,
             logs,
+            priority_ops,
             events_sender: None,
         }
     }
@@ -385,6 +655,19 @@ impl EthSubscribe {
                     Some(SubscriptionType::Logs)
                 }
             }
+            "priorityOpStatus" => {
+                let Ok(sink) = pending_sink.accept().await else {
+                    return;
+                };
+                let priority_ops_rx = self.priority_ops.subscribe();
+                tokio::spawn(Self::run_subscriber(
+                    sink,
+                    SubscriptionType::PriorityOps,
+                    priority_ops_rx,
+                    None,
+                ));
+                Some(SubscriptionType::PriorityOps)
+            }
             "syncing" => {
                 let Ok(sink) = pending_sink.accept().await else {
                     return;
@@ -419,7 +702,7 @@ impl EthSubscribe {
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(4);
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
@@ -441,11 +724,20 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.priority_ops.clone(),
             connection_pool,
             polling_interval,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_priority_ops(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
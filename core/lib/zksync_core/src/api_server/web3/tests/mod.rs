@@ -42,8 +42,8 @@ use crate::{
     },
     genesis::{ensure_genesis_state, GenesisParams},
     utils::testonly::{
-        create_l1_batch, create_l1_batch_metadata, create_l2_transaction, create_miniblock,
-        prepare_recovery_snapshot,
+        create_l1_batch, create_l1_batch_metadata, create_l1_transaction, create_l2_transaction,
+        create_miniblock, prepare_recovery_snapshot,
     },
 };
 
@@ -104,6 +104,7 @@ pub(crate) async fn spawn_http_server(
         network_config,
         pool,
         None,
+        None,
         tx_executor,
         stop_receiver,
     )
@@ -116,12 +117,14 @@ async fn spawn_ws_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    access_control: Option<AccessControl>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     spawn_server(
         ApiTransportLabel::Ws,
         network_config,
         pool,
         websocket_requests_per_minute_limit,
+        access_control,
         MockTransactionExecutor::default(),
         stop_receiver,
     )
@@ -133,6 +136,7 @@ async fn spawn_server(
     network_config: &NetworkConfig,
     pool: ConnectionPool,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    access_control: Option<AccessControl>,
     tx_executor: MockTransactionExecutor,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
@@ -146,7 +150,7 @@ async fn spawn_server(
     let mut namespaces = Namespace::DEFAULT.to_vec();
     namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
 
-    let server_builder = match transport {
+    let mut server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
         ApiTransportLabel::Ws => {
             let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool)
@@ -159,6 +163,9 @@ async fn spawn_server(
             builder
         }
     };
+    if let Some(access_control) = access_control {
+        server_builder = server_builder.with_access_control(access_control);
+    }
     let server_handles = server_builder
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender, vm_barrier)
@@ -181,6 +188,10 @@ trait HttpTest: Send + Sync {
         MockTransactionExecutor::default()
     }
 
+    fn access_control(&self) -> Option<AccessControl> {
+        None
+    }
+
     async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()>;
 }
 
@@ -250,9 +261,12 @@ async fn test_http_server(test: impl HttpTest) {
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
-    let server_handles = spawn_http_server(
+    let (server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
         &network_config,
         pool.clone(),
+        None,
+        test.access_control(),
         test.transaction_executor(),
         stop_receiver,
     )
@@ -431,6 +445,43 @@ async fn http_server_basics() {
     test_http_server(HttpServerBasicsTest).await;
 }
 
+fn access_control_with_ip_limit(requests_per_minute: u32) -> AccessControl {
+    let config = Web3JsonRpcConfig {
+        http_requests_per_minute_per_ip: NonZeroU32::new(requests_per_minute),
+        ..Web3JsonRpcConfig::for_tests()
+    };
+    AccessControl::new(&config)
+}
+
+#[derive(Debug)]
+struct RateLimitingPerIpTest;
+
+#[async_trait]
+impl HttpTest for RateLimitingPerIpTest {
+    fn access_control(&self) -> Option<AccessControl> {
+        Some(access_control_with_ip_limit(3))
+    }
+
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        // The client connects directly rather than via a trusted proxy, so it must be identified
+        // by the connection peer address even though requests don't have the `x-forwarded-for` header.
+        for _ in 0..3 {
+            client.get_block_number().await?;
+        }
+        let err = client.get_block_number().await.unwrap_err();
+        assert_matches!(
+            err,
+            ClientError::Call(error) if error.message() == "Too many requests"
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn rate_limiting_per_ip() {
+    test_http_server(RateLimitingPerIpTest).await;
+}
+
 #[derive(Debug)]
 struct BlockMethodsWithSnapshotRecovery;
 
@@ -8,7 +8,7 @@ use reqwest::StatusCode;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H256, U64};
+use zksync_types::{api, Address, L1BatchNumber, L1BlockNumber, PriorityOpId, H256, U64};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::client::{Subscription, SubscriptionClientT},
@@ -159,6 +159,10 @@ trait WsTest: Send + Sync {
     fn websocket_requests_per_minute_limit(&self) -> Option<NonZeroU32> {
         None
     }
+
+    fn access_control(&self) -> Option<AccessControl> {
+        None
+    }
 }
 
 async fn test_ws_server(test: impl WsTest) {
@@ -177,6 +181,7 @@ async fn test_ws_server(test: impl WsTest) {
         pool.clone(),
         stop_receiver,
         test.websocket_requests_per_minute_limit(),
+        test.access_control(),
     )
     .await;
     server_handles.wait_until_ready().await;
@@ -313,6 +318,117 @@ async fn basic_subscriptions_after_snapshot_recovery() {
     .await;
 }
 
+#[derive(Debug)]
+struct PriorityOpSubscriptionTest;
+
+#[async_trait]
+impl WsTest for PriorityOpSubscriptionTest {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::PriorityOps]).await;
+
+        let params = rpc_params!["priorityOpStatus"];
+        let mut priority_ops_subscription = client
+            .subscribe::<api::PriorityOpStatus, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::PriorityOps).await;
+
+        let l1_tx = create_l1_transaction(0);
+        let l1_tx_hash = l1_tx.common_data.eth_hash;
+        let mut storage = pool.access_storage().await?;
+        storage
+            .transactions_dal()
+            .insert_transaction_l1(l1_tx.clone(), L1BlockNumber(1))
+            .await;
+        drop(storage);
+
+        let status = tokio::time::timeout(TEST_TIMEOUT, priority_ops_subscription.next())
+            .await
+            .context("Timed out waiting for priority op status")?
+            .context("Priority op subscription terminated")??;
+        assert_eq!(status.serial_id, PriorityOpId(0));
+        assert_eq!(status.l1_tx_hash, Some(l1_tx_hash));
+        assert_eq!(status.l2_tx_hash, l1_tx.hash());
+        assert_eq!(status.stage, api::PriorityOpStage::Queued);
+        assert_eq!(status.miniblock_number, None);
+
+        let status_by_id = client
+            .get_priority_op_status(api::PriorityOpIdentifier::SerialId(PriorityOpId(0)))
+            .await?
+            .context("missing priority op status")?;
+        assert_eq!(status_by_id, status);
+        let status_by_hash = client
+            .get_priority_op_status(api::PriorityOpIdentifier::L1TxHash(l1_tx_hash))
+            .await?
+            .context("missing priority op status")?;
+        assert_eq!(status_by_hash, status);
+        let missing_status = client
+            .get_priority_op_status(api::PriorityOpIdentifier::SerialId(PriorityOpId(1)))
+            .await?;
+        assert_eq!(missing_status, None);
+
+        // Check that status changes are picked up by incremental polling. Like in the state keeper,
+        // miniblocks and L1 batches are persisted atomically.
+        let mut storage = pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        let new_miniblock = create_miniblock(1);
+        transaction
+            .blocks_dal()
+            .insert_miniblock(&new_miniblock)
+            .await?;
+        let tx_result = TransactionExecutionResult {
+            hash: l1_tx.hash(),
+            transaction: l1_tx.into(),
+            execution_info: ExecutionMetrics::default(),
+            execution_status: TxExecutionStatus::Success,
+            refunded_gas: 0,
+            operator_suggested_refund: 0,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+        };
+        transaction
+            .transactions_dal()
+            .mark_txs_as_executed_in_miniblock(new_miniblock.number, &[tx_result], 1.into())
+            .await;
+        transaction.commit().await?;
+        drop(storage);
+
+        let status = tokio::time::timeout(TEST_TIMEOUT, priority_ops_subscription.next())
+            .await
+            .context("Timed out waiting for priority op status")?
+            .context("Priority op subscription terminated")??;
+        assert_eq!(status.serial_id, PriorityOpId(0));
+        assert_eq!(status.stage, api::PriorityOpStage::Included);
+        assert_eq!(status.miniblock_number, Some(new_miniblock.number));
+        assert_eq!(status.l1_batch_number, None);
+
+        let mut storage = pool.access_storage().await?;
+        let mut transaction = storage.start_transaction().await?;
+        seal_l1_batch(&mut transaction, L1BatchNumber(1)).await?;
+        transaction.commit().await?;
+        drop(storage);
+
+        let status = tokio::time::timeout(TEST_TIMEOUT, priority_ops_subscription.next())
+            .await
+            .context("Timed out waiting for priority op status")?
+            .context("Priority op subscription terminated")??;
+        assert_eq!(status.serial_id, PriorityOpId(0));
+        assert_eq!(status.stage, api::PriorityOpStage::Included);
+        assert_eq!(status.l1_batch_number, Some(L1BatchNumber(1)));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn priority_op_subscription() {
+    test_ws_server(PriorityOpSubscriptionTest).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptionsTest {
     snapshot_recovery: bool,
@@ -661,6 +777,42 @@ async fn rate_limiting() {
     test_ws_server(RateLimitingTest).await;
 }
 
+#[derive(Debug)]
+struct RateLimitingPerIpTest;
+
+#[async_trait]
+impl WsTest for RateLimitingPerIpTest {
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        _pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        // Calls in a WebSocket session are rate-limited as the calls of the client that has opened the session.
+        client.chain_id().await.unwrap();
+        client.chain_id().await.unwrap();
+        client.chain_id().await.unwrap();
+        let expected_err = client.chain_id().await.unwrap_err();
+
+        if let ClientError::Call(error) = expected_err {
+            assert_eq!(error.code() as u16, StatusCode::TOO_MANY_REQUESTS.as_u16());
+            assert_eq!(error.message(), "Too many requests");
+        } else {
+            panic!("Unexpected error returned: {expected_err}");
+        }
+        Ok(())
+    }
+
+    fn access_control(&self) -> Option<AccessControl> {
+        Some(access_control_with_ip_limit(3))
+    }
+}
+
+#[tokio::test]
+async fn rate_limiting_per_ip() {
+    test_ws_server(RateLimitingPerIpTest).await;
+}
+
 #[derive(Debug)]
 struct BatchGetsRateLimitedTest;
 
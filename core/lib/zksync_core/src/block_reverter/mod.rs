@@ -47,6 +47,8 @@ pub struct BlockReverterEthConfig {
     eth_client_url: String,
     reverter_private_key: H256,
     reverter_address: H160,
+    /// Addresses of the dedicated accounts sending prove / execute transactions.
+    dedicated_sender_addresses: Vec<H160>,
     diamond_proxy_addr: H160,
     validator_timelock_addr: H160,
     default_priority_fee_per_gas: u64,
@@ -61,10 +63,32 @@ impl BlockReverterEthConfig {
         let operator_address = PackedEthSignature::address_from_private_key(&pk)
             .expect("Failed to get address from private key");
 
+        let sender = &eth_config.sender;
+        let dedicated_private_keys = [sender.prove_private_key(), sender.execute_private_key()];
+        let dedicated_keys_addresses = dedicated_private_keys.into_iter().flatten().map(|pk| {
+            PackedEthSignature::address_from_private_key(&pk)
+                .expect("Failed to get address from private key")
+        });
+        let remote_signer_addresses = eth_config.remote_signer.iter().flat_map(|signer| {
+            [
+                signer.prove_operator_address,
+                signer.execute_operator_address,
+            ]
+            .into_iter()
+            .flatten()
+        });
+        let mut dedicated_sender_addresses: Vec<_> = dedicated_keys_addresses
+            .chain(remote_signer_addresses)
+            .filter(|&address| address != operator_address)
+            .collect();
+        dedicated_sender_addresses.sort_unstable();
+        dedicated_sender_addresses.dedup();
+
         Self {
             eth_client_url: web3_url,
             reverter_private_key: pk,
             reverter_address: operator_address,
+            dedicated_sender_addresses,
             diamond_proxy_addr: contract.diamond_proxy_addr,
             validator_timelock_addr: contract.validator_timelock_addr,
             default_priority_fee_per_gas: eth_config.gas_adjuster.default_priority_fee_per_gas,
@@ -413,31 +437,50 @@ impl BlockReverter {
         let priority_fee = eth_config.default_priority_fee_per_gas;
 
         let web3 = Web3::new(Http::new(&eth_config.eth_client_url).unwrap());
-        let nonce = web3
-            .eth()
-            .transaction_count(eth_config.reverter_address, Some(BlockNumber::Pending))
-            .await
-            .unwrap()
-            .as_u64();
+        let nonce = Self::get_pending_nonce(&web3, eth_config.reverter_address).await;
+        let mut dedicated_sender_nonces = vec![];
+        for &address in &eth_config.dedicated_sender_addresses {
+            let nonce = Self::get_pending_nonce(&web3, address).await;
+            dedicated_sender_nonces.push(SenderNonce { address, nonce });
+        }
 
         SuggestedRollbackValues {
             last_executed_l1_batch_number,
             nonce,
+            dedicated_sender_nonces,
             priority_fee,
         }
     }
 
-    /// Clears failed L1 transactions
-    pub async fn clear_failed_l1_transactions(&self) {
-        tracing::info!("clearing failed L1 transactions...");
-        self.connection_pool
-            .access_storage()
+    async fn get_pending_nonce(web3: &Web3<Http>, address: H160) -> u64 {
+        web3.eth()
+            .transaction_count(address, Some(BlockNumber::Pending))
             .await
             .unwrap()
+            .as_u64()
+    }
+
+    /// Clears failed L1 transactions. Transactions are cleared separately for each sender account,
+    /// since each account has its own nonce sequence.
+    pub async fn clear_failed_l1_transactions(&self) {
+        tracing::info!("clearing failed L1 transactions...");
+        let mut storage = self.connection_pool.access_storage().await.unwrap();
+        let senders = storage
             .eth_sender_dal()
-            .clear_failed_transactions()
+            .get_senders_with_failed_transactions()
             .await
             .unwrap();
+        for from_addr in senders {
+            let removed_count = storage
+                .eth_sender_dal()
+                .clear_failed_transactions(from_addr)
+                .await
+                .unwrap();
+            tracing::info!(
+                "removed {removed_count} L1 transactions starting from the first failed one for sender {}",
+                from_addr.map_or_else(|| "operator".to_owned(), |addr| format!("{addr:?}"))
+            );
+        }
     }
 
     pub fn change_rollback_executed_l1_batches_allowance(
@@ -451,6 +494,15 @@ impl BlockReverter {
 #[derive(Debug, Serialize)]
 pub struct SuggestedRollbackValues {
     pub last_executed_l1_batch_number: L1BatchNumber,
+    /// Nonce of the reverter (main operator) account.
     pub nonce: u64,
+    /// Nonces of the dedicated accounts sending prove / execute transactions.
+    pub dedicated_sender_nonces: Vec<SenderNonce>,
     pub priority_fee: u64,
 }
+
+#[derive(Debug, Serialize)]
+pub struct SenderNonce {
+    pub address: H160,
+    pub nonce: u64,
+}
@@ -17,10 +17,11 @@ use zksync_types::{
 use super::{
     aggregated_operations::AggregatedOperation,
     publish_criterion::{
-        DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
-        TimestampDeadlineCriterion,
+        BaseFeeCriterion, DataSizeCriterion, GasCriterion, L1BatchPublishCriterion,
+        NumberCriterion, TimestampDeadlineCriterion,
     },
 };
+use crate::l1_gas_price::L1TxParamsProvider;
 
 #[derive(Debug)]
 pub struct Aggregator {
@@ -91,6 +92,32 @@ impl Aggregator {
         }
     }
 
+    /// Makes the aggregator postpone publishing L1 batches while the L1 base fee reported by `gas_adjuster`
+    /// is high. Has no effect unless `base_fee_postpone_ratio` is set in the config.
+    pub fn with_gas_adjuster(mut self, gas_adjuster: Arc<dyn L1TxParamsProvider>) -> Self {
+        let Some(max_base_fee_ratio) = self.config.base_fee_postpone_ratio else {
+            return self;
+        };
+        let max_postpone = self.config.max_base_fee_postpone();
+        let criteria = [
+            (AggregatedActionType::Commit, &mut self.commit_criteria),
+            (
+                AggregatedActionType::PublishProofOnchain,
+                &mut self.proof_criteria,
+            ),
+            (AggregatedActionType::Execute, &mut self.execute_criteria),
+        ];
+        for (op, criteria) in criteria {
+            criteria.push(Box::new(BaseFeeCriterion::new(
+                op,
+                gas_adjuster.clone(),
+                max_base_fee_ratio,
+                max_postpone,
+            )));
+        }
+        self
+    }
+
     pub async fn get_next_ready_operation(
         &mut self,
         storage: &mut StorageProcessor<'_>,
@@ -404,7 +431,7 @@ async fn extract_ready_subrange(
     last_sealed_l1_batch: L1BatchNumber,
 ) -> Option<Vec<L1BatchWithMetadata>> {
     let mut last_l1_batch: Option<L1BatchNumber> = None;
-    for criterion in publish_criteria {
+    for criterion in publish_criteria.iter_mut() {
         let l1_batch_by_criterion = criterion
             .last_l1_batch_to_publish(storage, &unpublished_l1_batches, last_sealed_l1_batch)
             .await;
@@ -414,6 +441,11 @@ async fn extract_ready_subrange(
     }
 
     let last_l1_batch = last_l1_batch?;
+    for criterion in publish_criteria {
+        if criterion.should_postpone(&unpublished_l1_batches).await {
+            return None;
+        }
+    }
     Some(
         unpublished_l1_batches
             .into_iter()
@@ -1,4 +1,4 @@
-use zksync_types::web3::contract;
+use zksync_types::{web3::contract, Address};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ETHSenderError {
@@ -6,4 +6,6 @@ pub enum ETHSenderError {
     EthereumGateWayError(#[from] zksync_eth_client::Error),
     #[error("Token parsing Error: {0}")]
     ParseError(#[from] contract::Error),
+    #[error("No Ethereum client is configured for sender {0:?}")]
+    UnknownSender(Address),
 }
@@ -1,5 +1,6 @@
-use std::{convert::TryInto, sync::Arc};
+use std::{collections::HashMap, convert::TryInto, sync::Arc};
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
@@ -11,11 +12,12 @@ use zksync_l1_contract_interface::{
     Detokenize, Tokenizable, Tokenize,
 };
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     eth_sender::EthTx,
     ethabi::{Contract, Token},
     protocol_version::{L1VerifierConfig, VerifierParams},
     web3::contract::Error as Web3ContractError,
-    Address, ProtocolVersionId, H256, U256,
+    Address, L1BatchNumber, ProtocolVersionId, H256, U256,
 };
 
 use super::aggregated_operations::AggregatedOperation;
@@ -27,6 +29,7 @@ use crate::{
     },
     gas_tracker::agg_l1_batch_base_cost,
     metrics::BlockL1Stage,
+    utils::L1_BATCH_SPAN_LINKS,
 };
 
 /// Data queried from L1 using multicall contract.
@@ -38,6 +41,13 @@ pub struct MulticallData {
     pub protocol_version_id: ProtocolVersionId,
 }
 
+/// Account sending transactions of a certain type instead of the main operator account.
+#[derive(Debug, Clone, Copy)]
+struct DedicatedSender {
+    address: Address,
+    base_nonce: u64,
+}
+
 /// The component is responsible for aggregating l1 batches into eth_txs:
 /// Such as CommitBlocks, PublishProofBlocksOnchain and ExecuteBlock
 /// These eth_txs will be used as a queue for generating signed txs and send them later
@@ -51,9 +61,17 @@ pub struct EthTxAggregator {
     pub(super) main_zksync_contract_address: Address,
     functions: ZkSyncFunctions,
     base_nonce: u64,
+    dedicated_senders: HashMap<AggregatedActionType, DedicatedSender>,
 }
 
 impl EthTxAggregator {
+    /// Types of operations that can be sent from dedicated accounts; commits are always sent
+    /// from the main operator account.
+    pub const DEDICATED_SENDER_ACTIONS: [AggregatedActionType; 2] = [
+        AggregatedActionType::PublishProofOnchain,
+        AggregatedActionType::Execute,
+    ];
+
     pub fn new(
         config: SenderConfig,
         aggregator: Aggregator,
@@ -73,14 +91,84 @@ impl EthTxAggregator {
             main_zksync_contract_address,
             functions,
             base_nonce,
+            dedicated_senders: HashMap::new(),
         }
     }
 
+    /// Makes transactions of the specified type to be sent from a dedicated `address` rather than
+    /// from the main operator account. Such transactions have a separate nonce sequence, so that
+    /// e.g. a stuck `proveBatches` transaction doesn't block commits. `base_nonce` is the pending nonce
+    /// of the dedicated account.
+    pub fn with_dedicated_sender(
+        mut self,
+        action_type: AggregatedActionType,
+        address: Address,
+        base_nonce: u64,
+    ) -> Self {
+        if address == self.eth_client.sender_account() {
+            tracing::warn!(
+                "Dedicated sender for {action_type} transactions coincides with the main operator account"
+            );
+            return self;
+        }
+        self.dedicated_senders.insert(
+            action_type,
+            DedicatedSender {
+                address,
+                base_nonce,
+            },
+        );
+        self
+    }
+
+    /// Returns the sender for transactions of the specified type; `None` corresponds to the main operator account.
+    fn sender_for(&self, action_type: AggregatedActionType) -> Option<Address> {
+        self.dedicated_senders
+            .get(&action_type)
+            .map(|sender| sender.address)
+    }
+
+    /// Checks that all dedicated senders are allowed to send transactions by the `ValidatorTimelock` contract.
+    async fn check_dedicated_senders(&self) -> anyhow::Result<()> {
+        for (action_type, sender) in &self.dedicated_senders {
+            let args = CallFunctionArgs::new(&self.functions.validators.name, sender.address)
+                .for_contract(
+                    self.timelock_contract_address,
+                    self.functions.validator_timelock_contract.clone(),
+                );
+            let output = self
+                .eth_client
+                .call_contract_function(args)
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed checking validator permissions of {:?}",
+                        sender.address
+                    )
+                })?;
+            let is_validator = bool::from_tokens(output)
+                .with_context(|| format!("unexpected `validators({:?})` output", sender.address))?;
+            anyhow::ensure!(
+                is_validator,
+                "Dedicated sender {:?} for {action_type} transactions is not a validator \
+                 on ValidatorTimelock {:?}",
+                sender.address,
+                self.timelock_contract_address
+            );
+            tracing::info!(
+                "Using dedicated sender {:?} for {action_type} transactions",
+                sender.address
+            );
+        }
+        Ok(())
+    }
+
     pub async fn run(
         mut self,
         pool: ConnectionPool,
         stop_receiver: watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
+        self.check_dedicated_senders().await?;
         loop {
             let mut storage = pool.access_storage_tagged("eth_sender").await.unwrap();
 
@@ -451,6 +539,15 @@ impl EthTxAggregator {
         }
     }
 
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            op = aggregated_op.get_action_caption(),
+            l1_batch.start = %aggregated_op.l1_batch_range().start(),
+            l1_batch.end = %aggregated_op.l1_batch_range().end(),
+            eth_tx.id = tracing::field::Empty,
+        )
+    )]
     pub(super) async fn save_eth_tx(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -458,10 +555,15 @@ impl EthTxAggregator {
         contracts_are_pre_boojum: bool,
     ) -> Result<EthTx, ETHSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
-        let nonce = self.get_next_nonce(&mut transaction).await?;
+        let op_type = aggregated_op.get_action_type();
+        let from_addr = self.sender_for(op_type);
+        let nonce = self.get_next_nonce(&mut transaction, op_type).await?;
         let calldata = self.encode_aggregated_op(aggregated_op, contracts_are_pre_boojum);
         let l1_batch_number_range = aggregated_op.l1_batch_range();
-        let op_type = aggregated_op.get_action_type();
+        let current_span = tracing::Span::current();
+        for l1_batch_number in l1_batch_number_range.start().0..=l1_batch_number_range.end().0 {
+            L1_BATCH_SPAN_LINKS.link(&current_span, &L1BatchNumber(l1_batch_number));
+        }
 
         let predicted_gas_for_batches = transaction
             .blocks_dal()
@@ -478,6 +580,7 @@ impl EthTxAggregator {
                 op_type,
                 self.timelock_contract_address,
                 eth_tx_predicted_gas,
+                from_addr,
             )
             .await
             .unwrap();
@@ -488,21 +591,27 @@ impl EthTxAggregator {
             .await
             .unwrap();
         transaction.commit().await.unwrap();
+        tracing::Span::current().record("eth_tx.id", eth_tx.id);
         Ok(eth_tx)
     }
 
     async fn get_next_nonce(
         &self,
         storage: &mut StorageProcessor<'_>,
+        action_type: AggregatedActionType,
     ) -> Result<u64, ETHSenderError> {
+        let (from_addr, base_nonce) = match self.dedicated_senders.get(&action_type) {
+            Some(sender) => (Some(sender.address), sender.base_nonce),
+            None => (None, self.base_nonce),
+        };
         let db_nonce = storage
             .eth_sender_dal()
-            .get_next_nonce()
+            .get_next_nonce(from_addr)
             .await
             .unwrap()
             .unwrap_or(0);
         // Between server starts we can execute some txs using operator account or remove some txs from the database
         // At the start we have to consider this fact and get the max nonce.
-        Ok(db_nonce.max(self.base_nonce))
+        Ok(db_nonce.max(base_nonce))
     }
 }
@@ -1,12 +1,16 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
+use serde::Serialize;
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_eth_client::{
     BoundEthInterface, Error, EthInterface, ExecutedTxStatus, RawTransactionBytes, SignedCallResult,
 };
+use zksync_health_check::{
+    Health, HealthCheckOptions, HealthStatus, HealthUpdater, ReactiveHealthCheck,
+};
 use zksync_types::{
     eth_sender::EthTx,
     web3::{
@@ -14,11 +18,11 @@ use zksync_types::{
         error::Error as Web3Error,
         types::{BlockId, BlockNumber},
     },
-    L1BlockNumber, Nonce, H256, U256,
+    Address, L1BlockNumber, Nonce, H256, U256,
 };
 use zksync_utils::time::seconds_since_epoch;
 
-use super::{metrics::METRICS, ETHSenderError};
+use super::{l1_costs, metrics::METRICS, ETHSenderError};
 use crate::{l1_gas_price::L1TxParamsProvider, metrics::BlockL1Stage};
 
 #[derive(Debug)]
@@ -35,6 +39,16 @@ struct OperatorNonce {
     latest: Nonce,
 }
 
+/// Health details reported by [`EthTxManager`].
+#[derive(Debug, Serialize)]
+struct EthTxManagerHealthDetails {
+    last_known_l1_block: L1BlockNumber,
+    /// Number of consecutive failed loop iterations.
+    consecutive_errors: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(super) struct L1BlockNumbers {
     pub safe: L1BlockNumber,
@@ -46,32 +60,88 @@ pub(super) struct L1BlockNumbers {
 /// Based on eth_tx queue the component generates new attempt with the minimum possible fee,
 /// save it to the database, and send it to Ethereum.
 /// Based on eth_tx_history queue the component can mark txs as stuck and create the new attempt
-/// with higher gas price
+/// with higher gas price.
+/// Transactions from each sender account (the main operator and dedicated senders, if any) are tracked
+/// independently, each having its own nonce sequence and `max_txs_in_flight` limit.
 #[derive(Debug)]
 pub struct EthTxManager {
     ethereum_gateway: Arc<dyn BoundEthInterface>,
+    dedicated_gateways: HashMap<Address, Arc<dyn BoundEthInterface>>,
     config: SenderConfig,
     gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    health_updater: HealthUpdater,
 }
 
 impl EthTxManager {
+    /// Minimum time after which the manager health is considered stale if the manager loop doesn't make progress.
+    const MIN_HEALTH_DEADLINE: Duration = Duration::from_secs(120);
+    /// Number of consecutive failed loop iterations after which the manager is reported as not ready.
+    /// Isolated failures are usually caused by transient L1 client errors, so they only affect health details.
+    const MAX_CONSECUTIVE_ERRORS: usize = 5;
+
     pub fn new(
         config: SenderConfig,
         gas_adjuster: Arc<dyn L1TxParamsProvider>,
         ethereum_gateway: Arc<dyn BoundEthInterface>,
     ) -> Self {
+        // The loop updates health on each iteration, so we allow for several iterations being slow
+        // (e.g., because of L1 client timeouts) before marking the manager as stuck.
+        let health_deadline = (config.tx_poll_period() * 10).max(Self::MIN_HEALTH_DEADLINE);
+        let (_, health_updater) = ReactiveHealthCheck::with_options(
+            "eth_tx_manager",
+            HealthCheckOptions::liveness(health_deadline),
+        );
         Self {
             ethereum_gateway,
+            dedicated_gateways: HashMap::new(),
             config,
             gas_adjuster,
+            health_updater,
         }
     }
 
+    /// Adds a client for a dedicated sender account, which should be configured for the corresponding
+    /// `EthTxAggregator` as well.
+    pub fn with_dedicated_sender(mut self, ethereum_gateway: Arc<dyn BoundEthInterface>) -> Self {
+        let address = ethereum_gateway.sender_account();
+        if address != self.ethereum_gateway.sender_account() {
+            self.dedicated_gateways.insert(address, ethereum_gateway);
+        }
+        self
+    }
+
+    /// Returns all senders managed by this manager; `None` corresponds to the main operator account.
+    fn senders(&self) -> Vec<Option<Address>> {
+        let mut senders: Vec<_> = self.dedicated_gateways.keys().copied().map(Some).collect();
+        senders.sort_unstable();
+        senders.insert(0, None);
+        senders
+    }
+
+    fn gateway_for(
+        &self,
+        from_addr: Option<Address>,
+    ) -> Result<&dyn BoundEthInterface, ETHSenderError> {
+        let Some(address) = from_addr else {
+            return Ok(self.ethereum_gateway.as_ref());
+        };
+        match self.dedicated_gateways.get(&address) {
+            Some(gateway) => Ok(gateway.as_ref()),
+            None => Err(ETHSenderError::UnknownSender(address)),
+        }
+    }
+
+    /// Returns a health check for this manager.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
     async fn get_tx_status(
         &self,
+        from_addr: Option<Address>,
         tx_hash: H256,
     ) -> Result<Option<ExecutedTxStatus>, ETHSenderError> {
-        self.ethereum_gateway
+        self.gateway_for(from_addr)?
             .get_tx_status(tx_hash, "eth_tx_manager")
             .await
             .map_err(Into::into)
@@ -92,7 +162,7 @@ impl EthTxManager {
             // `status` is a Result here and we don't unwrap it with `?`
             // because if we do and get an `Err`, we won't finish the for loop,
             // which means we might miss the transaction that actually succeeded.
-            match self.get_tx_status(history_item.tx_hash).await {
+            match self.get_tx_status(op.from_addr, history_item.tx_hash).await {
                 Ok(Some(s)) => return Some(s),
                 Ok(_) => continue,
                 Err(err) => tracing::warn!(
@@ -199,7 +269,7 @@ impl EthTxManager {
 
         let signed_tx = self
             .sign_tx(tx, base_fee_per_gas, priority_fee_per_gas)
-            .await;
+            .await?;
 
         if let Some(tx_history_id) = storage
             .eth_sender_dal()
@@ -214,7 +284,13 @@ impl EthTxManager {
             .unwrap()
         {
             if let Err(error) = self
-                .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, current_block)
+                .send_raw_transaction(
+                    storage,
+                    tx.from_addr,
+                    tx_history_id,
+                    signed_tx.raw_tx,
+                    current_block,
+                )
                 .await
             {
                 tracing::warn!(
@@ -232,11 +308,12 @@ impl EthTxManager {
     async fn send_raw_transaction(
         &self,
         storage: &mut StorageProcessor<'_>,
+        from_addr: Option<Address>,
         tx_history_id: u32,
         raw_tx: RawTransactionBytes,
         current_block: L1BlockNumber,
     ) -> Result<H256, ETHSenderError> {
-        match self.ethereum_gateway.send_raw_tx(raw_tx).await {
+        match self.gateway_for(from_addr)?.send_raw_tx(raw_tx).await {
             Ok(tx_hash) => {
                 storage
                     .eth_sender_dal()
@@ -258,17 +335,17 @@ impl EthTxManager {
 
     async fn get_operator_nonce(
         &self,
+        from_addr: Option<Address>,
         block_numbers: L1BlockNumbers,
     ) -> Result<OperatorNonce, ETHSenderError> {
-        let finalized = self
-            .ethereum_gateway
+        let gateway = self.gateway_for(from_addr)?;
+        let finalized = gateway
             .nonce_at(block_numbers.finalized.0.into(), "eth_tx_manager")
             .await?
             .as_u32()
             .into();
 
-        let latest = self
-            .ethereum_gateway
+        let latest = gateway
             .nonce_at(block_numbers.latest.0.into(), "eth_tx_manager")
             .await?
             .as_u32()
@@ -323,20 +400,23 @@ impl EthTxManager {
         })
     }
 
-    // Monitors the in-flight transactions, marks mined ones as confirmed,
+    // Monitors the in-flight transactions from `from_addr`, marks mined ones as confirmed,
     // returns the one that has to be resent (if there is one).
     pub(super) async fn monitor_inflight_transactions(
         &mut self,
         storage: &mut StorageProcessor<'_>,
         l1_block_numbers: L1BlockNumbers,
+        from_addr: Option<Address>,
     ) -> Result<Option<(EthTx, u32)>, ETHSenderError> {
-        METRICS.track_block_numbers(&l1_block_numbers);
-        let operator_nonce = self.get_operator_nonce(l1_block_numbers).await?;
-        let inflight_txs = storage.eth_sender_dal().get_inflight_txs().await.unwrap();
-        METRICS.number_of_inflight_txs.set(inflight_txs.len());
+        let operator_nonce = self.get_operator_nonce(from_addr, l1_block_numbers).await?;
+        let inflight_txs = storage
+            .eth_sender_dal()
+            .get_inflight_txs(from_addr)
+            .await
+            .unwrap();
 
         tracing::trace!(
-            "Going through not confirmed txs. \
+            "Going through not confirmed txs from {from_addr:?}. \
              Block numbers: latest {}, finalized {}, \
              operator's nonce: latest {}, finalized {}",
             l1_block_numbers.latest,
@@ -403,8 +483,9 @@ impl EthTxManager {
         tx: &EthTx,
         base_fee_per_gas: u64,
         priority_fee_per_gas: u64,
-    ) -> SignedCallResult {
-        self.ethereum_gateway
+    ) -> Result<SignedCallResult, ETHSenderError> {
+        let signed_tx = self
+            .gateway_for(tx.from_addr)?
             .sign_prepared_tx_for_addr(
                 tx.raw_tx.clone(),
                 tx.contract_address,
@@ -417,8 +498,8 @@ impl EthTxManager {
                 }),
                 "eth_tx_manager",
             )
-            .await
-            .expect("Failed to sign transaction")
+            .await?;
+        Ok(signed_tx)
     }
 
     async fn send_unsent_txs(
@@ -427,10 +508,24 @@ impl EthTxManager {
         l1_block_numbers: L1BlockNumbers,
     ) {
         for tx in storage.eth_sender_dal().get_unsent_txs().await.unwrap() {
+            let eth_tx = storage
+                .eth_sender_dal()
+                .get_eth_tx(tx.eth_tx_id)
+                .await
+                .unwrap()
+                .expect("Eth tx should exist");
+            if let Err(err) = self.gateway_for(eth_tx.from_addr) {
+                tracing::warn!(
+                    "Skipping unsent tx {} for eth_tx {}: {err}",
+                    tx.id,
+                    eth_tx.id
+                );
+                continue;
+            }
             // Check already sent txs not marked as sent and mark them as sent.
             // The common reason for this behavior is that we sent tx and stop the server
             // before updating the database
-            let tx_status = self.get_tx_status(tx.tx_hash).await;
+            let tx_status = self.get_tx_status(eth_tx.from_addr, tx.tx_hash).await;
 
             if let Ok(Some(tx_status)) = tx_status {
                 tracing::info!("The tx {:?} has been already sent", tx.tx_hash);
@@ -440,18 +535,12 @@ impl EthTxManager {
                     .await
                     .unwrap();
 
-                let eth_tx = storage
-                    .eth_sender_dal()
-                    .get_eth_tx(tx.eth_tx_id)
-                    .await
-                    .unwrap()
-                    .expect("Eth tx should exist");
-
                 self.apply_tx_status(storage, &eth_tx, tx_status, l1_block_numbers.finalized)
                     .await;
             } else if let Err(error) = self
                 .send_raw_transaction(
                     storage,
+                    eth_tx.from_addr,
                     tx.id,
                     RawTransactionBytes::new_unchecked(tx.signed_raw_tx.clone()),
                     l1_block_numbers.latest,
@@ -497,13 +586,20 @@ impl EthTxManager {
             .mark_failed_transaction(tx.id)
             .await
             .unwrap();
-        let failure_reason = self
-            .ethereum_gateway
-            .failure_reason(tx_status.receipt.transaction_hash)
-            .await
-            .expect(
-                "Tx is already failed, it's safe to fail here and apply the status on the next run",
-            );
+        // Failed transactions consume gas as well.
+        l1_costs::record_l1_batch_costs(storage, tx, &tx_status).await;
+        let failure_reason = match self.gateway_for(tx.from_addr) {
+            Ok(gateway) => gateway
+                .failure_reason(tx_status.receipt.transaction_hash)
+                .await
+                .expect(
+                    "Tx is already failed, it's safe to fail here and apply the status on the next run",
+                ),
+            Err(err) => {
+                tracing::warn!("Cannot get failure reason for eth_tx {}: {err}", tx.id);
+                None
+            }
+        };
 
         tracing::error!(
             "Eth tx failed {:?}, {:?}, failure reason {:?}",
@@ -535,6 +631,7 @@ impl EthTxManager {
         METRICS
             .track_eth_tx_metrics(storage, BlockL1Stage::Mined, tx)
             .await;
+        l1_costs::record_l1_batch_costs(storage, tx, &tx_status).await;
 
         if gas_used > U256::from(tx.predicted_gas_cost) {
             tracing::error!(
@@ -582,6 +679,7 @@ impl EthTxManager {
         // It's mandatory to set `last_known_l1_block` to zero, otherwise the first iteration
         // will never check in-flight txs status
         let mut last_known_l1_block = L1BlockNumber(0);
+        let mut consecutive_errors = 0;
         loop {
             let mut storage = pool.access_storage_tagged("eth_sender").await.unwrap();
 
@@ -590,14 +688,33 @@ impl EthTxManager {
                 break;
             }
 
-            match self.loop_iteration(&mut storage, last_known_l1_block).await {
-                Ok(block) => last_known_l1_block = block,
+            let last_error = match self.loop_iteration(&mut storage, last_known_l1_block).await {
+                Ok(block) => {
+                    last_known_l1_block = block;
+                    consecutive_errors = 0;
+                    None
+                }
                 Err(e) => {
                     // Web3 API request failures can cause this,
                     // and anything more important is already properly reported.
                     tracing::warn!("eth_sender error {:?}", e);
+                    consecutive_errors += 1;
+                    Some(e.to_string())
                 }
-            }
+            };
+            let status = if consecutive_errors < Self::MAX_CONSECUTIVE_ERRORS {
+                HealthStatus::Ready
+            } else {
+                HealthStatus::NotReady
+            };
+            self.health_updater
+                .update(
+                    Health::from(status).with_details(EthTxManagerHealthDetails {
+                        last_known_l1_block,
+                        consecutive_errors,
+                        last_error,
+                    }),
+                );
 
             tokio::time::sleep(self.config.tx_poll_period()).await;
         }
@@ -608,10 +725,11 @@ impl EthTxManager {
         &mut self,
         storage: &mut StorageProcessor<'_>,
         current_block: L1BlockNumber,
-    ) {
+        from_addr: Option<Address>,
+    ) -> Result<(), ETHSenderError> {
         let number_inflight_txs = storage
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(from_addr)
             .await
             .unwrap()
             .len();
@@ -624,14 +742,17 @@ impl EthTxManager {
             // Get the new eth tx and create history item for them
             let new_eth_tx = storage
                 .eth_sender_dal()
-                .get_new_eth_txs(number_of_available_slots_for_eth_txs)
+                .get_new_eth_txs(number_of_available_slots_for_eth_txs, from_addr)
                 .await
                 .unwrap();
 
             for tx in new_eth_tx {
-                let _ = self.send_eth_tx(storage, &tx, 0, current_block).await;
+                // Transactions must be sent in the nonce order, so the remaining transactions
+                // are retried on the next iteration.
+                self.send_eth_tx(storage, &tx, 0, current_block).await?;
             }
         }
+        Ok(())
     }
 
     #[tracing::instrument(skip(self, storage))]
@@ -641,29 +762,43 @@ impl EthTxManager {
         previous_block: L1BlockNumber,
     ) -> Result<L1BlockNumber, ETHSenderError> {
         let l1_block_numbers = self.get_l1_block_numbers().await?;
+        let senders = self.senders();
 
-        self.send_new_eth_txs(storage, l1_block_numbers.latest)
-            .await;
+        for &from_addr in &senders {
+            self.send_new_eth_txs(storage, l1_block_numbers.latest, from_addr)
+                .await?;
+        }
 
         if l1_block_numbers.latest <= previous_block {
             // Nothing to do - no new blocks were mined.
             return Ok(previous_block);
         }
 
-        if let Some((tx, sent_at_block)) = self
-            .monitor_inflight_transactions(storage, l1_block_numbers)
-            .await?
-        {
-            // New gas price depends on the time this tx spent in mempool.
-            let time_in_mempool = l1_block_numbers.latest.0 - sent_at_block;
-
-            // We don't want to return early in case resend does not succeed -
-            // the error is logged anyway, but early returns will prevent
-            // sending new operations.
-            let _ = self
-                .send_eth_tx(storage, &tx, time_in_mempool, l1_block_numbers.latest)
-                .await;
+        METRICS.track_block_numbers(&l1_block_numbers);
+        let mut number_of_inflight_txs = 0;
+        for &from_addr in &senders {
+            if let Some((tx, sent_at_block)) = self
+                .monitor_inflight_transactions(storage, l1_block_numbers, from_addr)
+                .await?
+            {
+                // New gas price depends on the time this tx spent in mempool.
+                let time_in_mempool = l1_block_numbers.latest.0 - sent_at_block;
+
+                // We don't want to return early in case resend does not succeed -
+                // the error is logged anyway, but early returns will prevent
+                // sending new operations.
+                let _ = self
+                    .send_eth_tx(storage, &tx, time_in_mempool, l1_block_numbers.latest)
+                    .await;
+            }
+            number_of_inflight_txs += storage
+                .eth_sender_dal()
+                .get_inflight_txs(from_addr)
+                .await
+                .unwrap()
+                .len();
         }
+        METRICS.number_of_inflight_txs.set(number_of_inflight_txs);
 
         Ok(l1_block_numbers.latest)
     }
@@ -0,0 +1,179 @@
+//! Accounting of L1 costs per L1 batch.
+//!
+//! Costs of mined commit / prove / execute transactions are split evenly among the L1 batches
+//! covered by the transaction, so that fees collected on L2 can be reconciled against L1 spend.
+//! Failed transactions are accounted for as well, since they consume gas. All attempts of an operation share
+//! the same nonce, so only the mined attempt is accounted for.
+//!
+//! Costs are not backfilled for L1 transactions mined before L1 cost accounting was introduced.
+
+use zksync_dal::StorageProcessor;
+use zksync_eth_client::ExecutedTxStatus;
+use zksync_types::{
+    eth_sender::{EthTx, L1BatchTxCost, TxHistory},
+    H256, U256,
+};
+
+use super::metrics::METRICS;
+
+const WEI_IN_GWEI: u64 = 1_000_000_000;
+
+/// Mined attempt of an aggregated L1 transaction.
+#[derive(Debug)]
+struct MinedAttempt {
+    tx_hash: H256,
+    succeeded: bool,
+    gas_used: U256,
+    effective_gas_price: U256,
+}
+
+impl MinedAttempt {
+    fn new(attempt: &TxHistory, status: &ExecutedTxStatus) -> Option<Self> {
+        let receipt = &status.receipt;
+        receipt.block_number?;
+        let Some(gas_used) = receipt.gas_used else {
+            tracing::warn!(
+                "Cannot determine gas used by L1 transaction {:?}: light ETH clients are not supported",
+                attempt.tx_hash
+            );
+            return None;
+        };
+        // Some L1 clients don't report the effective gas price. In this case, we use the fees
+        // of the attempt, which is an upper bound for the price.
+        let effective_gas_price = receipt.effective_gas_price.unwrap_or_else(|| {
+            U256::from(attempt.base_fee_per_gas) + U256::from(attempt.priority_fee_per_gas)
+        });
+        Some(Self {
+            tx_hash: attempt.tx_hash,
+            succeeded: status.success,
+            gas_used,
+            effective_gas_price,
+        })
+    }
+}
+
+/// Splits `total` into `parts` shares that add up to `total`. Shares differ by at most 1;
+/// larger shares go first.
+fn split_evenly(total: U256, parts: usize) -> Vec<U256> {
+    assert!(parts > 0, "cannot split value into 0 parts");
+    let (share, remainder) = total.div_mod(U256::from(parts));
+    let remainder = remainder.as_usize();
+    (0..parts)
+        .map(|i| {
+            if i < remainder {
+                share + U256::one()
+            } else {
+                share
+            }
+        })
+        .collect()
+}
+
+fn wei_to_gwei(wei: U256) -> u64 {
+    (wei / U256::from(WEI_IN_GWEI)).low_u64()
+}
+
+/// Returns the mined attempt of `tx` with the specified `status`. All attempts share the same nonce,
+/// so at most one of them can be mined.
+async fn mined_attempt(
+    storage: &mut StorageProcessor<'_>,
+    tx: &EthTx,
+    status: &ExecutedTxStatus,
+) -> Option<MinedAttempt> {
+    let history = storage
+        .eth_sender_dal()
+        .get_tx_history_to_check(tx.id)
+        .await
+        .unwrap();
+    let Some(attempt) = history
+        .iter()
+        .find(|attempt| attempt.tx_hash == status.tx_hash)
+    else {
+        tracing::warn!(
+            "Cannot record costs of L1 transaction {:?} for eth_tx {}: attempt is not found",
+            status.tx_hash,
+            tx.id
+        );
+        return None;
+    };
+    MinedAttempt::new(attempt, status)
+}
+
+/// Records costs of the mined attempt of an L1 transaction for all L1 batches it covers.
+/// Should be called once the transaction is confirmed or has failed.
+pub(super) async fn record_l1_batch_costs(
+    storage: &mut StorageProcessor<'_>,
+    tx: &EthTx,
+    status: &ExecutedTxStatus,
+) {
+    let mut l1_batch_numbers: Vec<_> = storage
+        .blocks_dal()
+        .get_l1_batches_for_eth_tx_id(tx.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|header| header.number)
+        .collect();
+    l1_batch_numbers.sort_unstable();
+
+    let tx_type_label = tx.tx_type.into();
+    let Some(attempt) = mined_attempt(storage, tx, status).await else {
+        return;
+    };
+    let effective_gas_price = attempt.effective_gas_price;
+    METRICS.l1_effective_gas_price[&tx_type_label].observe(effective_gas_price.low_u64());
+    let total_cost = attempt.gas_used * effective_gas_price;
+    METRICS.l1_spent_gwei[&tx_type_label].inc_by(wei_to_gwei(total_cost));
+    if l1_batch_numbers.is_empty() {
+        // This should be only the case when some batches were reverted.
+        return;
+    }
+
+    let gas_shares = split_evenly(attempt.gas_used, l1_batch_numbers.len());
+    let cost_shares = split_evenly(total_cost, l1_batch_numbers.len());
+    let costs: Vec<_> = l1_batch_numbers
+        .iter()
+        .zip(gas_shares.into_iter().zip(cost_shares))
+        .map(|(&l1_batch_number, (gas_used, cost))| L1BatchTxCost {
+            l1_batch_number,
+            eth_tx_id: tx.id,
+            tx_type: tx.tx_type,
+            tx_hash: attempt.tx_hash,
+            succeeded: attempt.succeeded,
+            gas_used: gas_used.low_u64(),
+            effective_gas_price,
+            cost,
+        })
+        .collect();
+
+    for cost in &costs {
+        METRICS.l1_batch_cost_gwei[&tx_type_label].observe(wei_to_gwei(cost.cost) as f64);
+    }
+    storage
+        .eth_sender_dal()
+        .save_l1_batch_costs(&costs)
+        .await
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splitting_costs_evenly() {
+        assert_eq!(split_evenly(100.into(), 1), [U256::from(100_u64)]);
+        assert_eq!(
+            split_evenly(100.into(), 3),
+            [34_u64, 33, 33].map(U256::from)
+        );
+        assert_eq!(split_evenly(2.into(), 4), [1_u64, 1, 0, 0].map(U256::from));
+        assert_eq!(split_evenly(U256::zero(), 2), [U256::zero(); 2]);
+    }
+
+    #[test]
+    fn converting_wei_to_gwei() {
+        assert_eq!(wei_to_gwei(U256::from(999_999_999)), 0);
+        assert_eq!(wei_to_gwei(U256::from(21_000 * 30 * WEI_IN_GWEI)), 630_000);
+    }
+}
@@ -69,6 +69,10 @@ const FEE_BUCKETS: Buckets = Buckets::values(&[
 const GAS_BUCKETS: Buckets =
     Buckets::values(&[1e4, 2e4, 5e4, 1e5, 2e5, 5e5, 1e6, 2e6, 5e6, 1e7, 2e7, 5e7]);
 
+/// Roughly exponential buckets for L1 costs in gwei (10k gwei – 1 ETH).
+const L1_COST_BUCKETS: Buckets =
+    Buckets::values(&[1e4, 3e4, 1e5, 3e5, 1e6, 3e6, 1e7, 3e7, 1e8, 3e8, 1e9]);
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "server_eth_sender")]
 pub(super) struct EthSenderMetrics {
@@ -97,8 +101,18 @@ pub(super) struct EthSenderMetrics {
     pub l1_tx_mined_latency: Family<ActionTypeLabel, Histogram<Duration>>,
     #[metrics(buckets = & [1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 20.0, 30.0, 50.0])]
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Effective gas price of confirmed L1 transactions.
+    #[metrics(buckets = FEE_BUCKETS)]
+    pub l1_effective_gas_price: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Total cost of confirmed L1 transactions, in gwei.
+    pub l1_spent_gwei: Family<ActionTypeLabel, Counter>,
+    /// Share of a confirmed L1 transaction cost attributed to a single L1 batch, in gwei.
+    #[metrics(buckets = L1_COST_BUCKETS)]
+    pub l1_batch_cost_gwei: Family<ActionTypeLabel, Histogram<f64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Number of times aggregation was postponed because of the high L1 base fee.
+    pub block_aggregation_postponed: Family<ActionTypeLabel, Counter>,
 }
 
 impl EthSenderMetrics {
@@ -1,7 +1,8 @@
-use std::fmt;
+use std::{fmt, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use chrono::Utc;
+use tokio::time::Instant;
 use zksync_dal::StorageProcessor;
 use zksync_l1_contract_interface::{i_executor::structures::CommitBatchInfo, Tokenizable};
 use zksync_types::{
@@ -10,7 +11,7 @@ use zksync_types::{
 };
 
 use super::metrics::METRICS;
-use crate::gas_tracker::agg_l1_batch_base_cost;
+use crate::{gas_tracker::agg_l1_batch_base_cost, l1_gas_price::L1TxParamsProvider};
 
 #[async_trait]
 pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
@@ -25,6 +26,11 @@ pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
         consecutive_l1_batches: &[L1BatchWithMetadata],
         last_sealed_l1_batch: L1BatchNumber,
     ) -> Option<L1BatchNumber>;
+
+    /// Returns `true` if publishing L1 batches should be postponed even if other criteria are triggered.
+    async fn should_postpone(&mut self, _consecutive_l1_batches: &[L1BatchWithMetadata]) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -247,3 +253,99 @@ impl L1BatchPublishCriterion for DataSizeCriterion {
         None
     }
 }
+
+/// Postpones publishing while the current L1 base fee is well above the median base fee tracked
+/// by `GasAdjuster`. Publishing is never postponed for more than `max_postpone` since L1 batches
+/// became ready for the operation, so that they are not stuck during prolonged fee surges.
+///
+/// The moment L1 batches became ready is tracked in memory, so the postponement deadline
+/// is restarted if the server is restarted.
+#[derive(Debug)]
+pub struct BaseFeeCriterion {
+    op: AggregatedActionType,
+    gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    /// Publishing is postponed if the current base fee exceeds the median base fee more than this ratio.
+    max_base_fee_ratio: f64,
+    max_postpone: Duration,
+    /// First L1 batch in the range ready for the operation, and the moment it was first observed.
+    ready_since: Option<(L1BatchNumber, Instant)>,
+}
+
+impl BaseFeeCriterion {
+    pub fn new(
+        op: AggregatedActionType,
+        gas_adjuster: Arc<dyn L1TxParamsProvider>,
+        max_base_fee_ratio: f64,
+        max_postpone: Duration,
+    ) -> Self {
+        Self {
+            op,
+            gas_adjuster,
+            max_base_fee_ratio,
+            max_postpone,
+            ready_since: None,
+        }
+    }
+
+    /// Returns the time elapsed since the range starting with `first_l1_batch_number` became ready.
+    /// `should_postpone()` is only called once other criteria decide to publish L1 batches,
+    /// so the first call for a certain L1 batch marks the moment it became ready.
+    fn ready_for(&mut self, first_l1_batch_number: L1BatchNumber) -> Duration {
+        let now = Instant::now();
+        match self.ready_since {
+            Some((number, since)) if number == first_l1_batch_number => now - since,
+            _ => {
+                self.ready_since = Some((first_l1_batch_number, now));
+                Duration::ZERO
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl L1BatchPublishCriterion for BaseFeeCriterion {
+    fn name(&self) -> &'static str {
+        "base_fee"
+    }
+
+    async fn last_l1_batch_to_publish(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        _consecutive_l1_batches: &[L1BatchWithMetadata],
+        _last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<L1BatchNumber> {
+        None // This criterion can only postpone publishing
+    }
+
+    async fn should_postpone(&mut self, consecutive_l1_batches: &[L1BatchWithMetadata]) -> bool {
+        let Some(first_l1_batch) = consecutive_l1_batches.first() else {
+            return false;
+        };
+        let ready_for = self.ready_for(first_l1_batch.header.number);
+        let current_base_fee = self.gas_adjuster.get_current_base_fee();
+        let median_base_fee = self.gas_adjuster.get_median_base_fee();
+        if current_base_fee as f64 <= median_base_fee as f64 * self.max_base_fee_ratio {
+            return false;
+        }
+
+        if ready_for >= self.max_postpone {
+            tracing::info!(
+                "Publishing op {} despite high L1 base fee ({current_base_fee}, median {median_base_fee}): \
+                 L1 batch #{} has been ready for {ready_for:?}",
+                self.op,
+                first_l1_batch.header.number
+            );
+            return false;
+        }
+
+        tracing::debug!(
+            "`base_fee` publish criterion postponed op {} with first L1 batch #{}: \
+             current base fee {current_base_fee} exceeds median {median_base_fee} more than {}x",
+            self.op,
+            first_l1_batch.header.number,
+            self.max_base_fee_ratio
+        );
+        METRICS.block_aggregation_postponed[&self.op.into()].inc();
+        true
+    }
+}
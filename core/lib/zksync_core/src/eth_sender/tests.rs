@@ -1,7 +1,8 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
+use tokio::sync::watch;
 use zksync_config::{
     configs::eth_sender::{ProofSendingMode, SenderConfig},
     ContractsConfig, ETHSenderConfig, GasAdjusterConfig,
@@ -13,20 +14,23 @@ use zksync_l1_contract_interface::i_executor::methods::{
 };
 use zksync_object_store::ObjectStoreFactory;
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     block::L1BatchHeader,
     commitment::{L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata},
     ethabi::Token,
     helpers::unix_timestamp_ms,
     web3::contract::Error,
-    Address, L1BatchNumber, L1BlockNumber, ProtocolVersionId, H256,
+    Address, L1BatchNumber, L1BlockNumber, ProtocolVersionId, H256, U256,
 };
 
 use crate::{
     eth_sender::{
-        aggregated_operations::AggregatedOperation, eth_tx_manager::L1BlockNumbers, Aggregator,
-        ETHSenderError, EthTxAggregator, EthTxManager,
+        aggregated_operations::AggregatedOperation,
+        eth_tx_manager::L1BlockNumbers,
+        publish_criterion::{BaseFeeCriterion, L1BatchPublishCriterion},
+        Aggregator, ETHSenderError, EthTxAggregator, EthTxManager,
     },
-    l1_gas_price::GasAdjuster,
+    l1_gas_price::{GasAdjuster, L1TxParamsProvider},
     utils::testonly::create_l1_batch,
 };
 
@@ -179,7 +183,7 @@ async fn confirm_many() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -197,6 +201,7 @@ async fn confirm_many() -> anyhow::Result<()> {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             tester.get_block_numbers().await,
+            None,
         )
         .await?;
 
@@ -206,7 +211,7 @@ async fn confirm_many() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -256,7 +261,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -286,6 +291,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             block_numbers,
+            None,
         )
         .await?
         .unwrap();
@@ -307,7 +313,7 @@ async fn resend_each_block() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -363,7 +369,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -380,6 +386,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             tester.get_block_numbers().await,
+            None,
         )
         .await?;
 
@@ -389,7 +396,7 @@ async fn dont_resend_already_mined() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -450,6 +457,7 @@ async fn three_scenarios() -> anyhow::Result<()> {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             tester.get_block_numbers().await,
+            None,
         )
         .await?
         .expect("we should be trying to resend the last tx");
@@ -460,7 +468,7 @@ async fn three_scenarios() -> anyhow::Result<()> {
             .storage()
             .await
             .eth_sender_dal()
-            .get_inflight_txs()
+            .get_inflight_txs(None)
             .await
             .unwrap()
             .len(),
@@ -509,6 +517,7 @@ async fn failed_eth_tx() {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             tester.get_block_numbers().await,
+            None,
         )
         .await
         .unwrap();
@@ -607,6 +616,208 @@ async fn correct_order_for_confirmations() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn l1_batch_costs_are_recorded() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![100; 100], true).await;
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    let second_l1_batch = insert_l1_batch(&tester, L1BatchNumber(2)).await;
+
+    // Commit both batches in a single L1 transaction.
+    let operation = AggregatedOperation::Commit(CommitBatches {
+        last_committed_l1_batch: l1_batch_with_metadata(genesis_l1_batch),
+        l1_batches: vec![
+            l1_batch_with_metadata(first_l1_batch),
+            l1_batch_with_metadata(second_l1_batch),
+        ],
+    });
+    let tx_hash = send_operation(&mut tester, operation, true).await;
+
+    let mut storage = tester.storage().await;
+    let genesis_costs = storage
+        .blocks_web3_dal()
+        .get_l1_batch_costs(L1BatchNumber(0))
+        .await
+        .unwrap();
+    assert_eq!(genesis_costs, None);
+
+    let mut total_cost = U256::zero();
+    for number in [1, 2] {
+        let costs = storage
+            .blocks_web3_dal()
+            .get_l1_batch_costs(L1BatchNumber(number))
+            .await
+            .unwrap()
+            .expect("no costs for committed batch");
+        assert!(costs.prove.is_empty() && costs.execute.is_empty());
+        assert_eq!(costs.commit.len(), 1);
+        let commit_cost = &costs.commit[0];
+        assert_eq!(commit_cost.tx_hash, tx_hash);
+        assert!(commit_cost.succeeded);
+        // The mock L1 client reports 21,000 gas used for each transaction.
+        assert_eq!(commit_cost.gas_used, 10_500.into());
+        assert!(!commit_cost.effective_gas_price.is_zero());
+        assert_eq!(
+            commit_cost.cost,
+            commit_cost.effective_gas_price * U256::from(10_500_u64)
+        );
+        assert_eq!(costs.total_cost, commit_cost.cost);
+        total_cost += commit_cost.cost;
+    }
+    assert_eq!(
+        total_cost % U256::from(21_000_u64),
+        U256::zero(),
+        "{total_cost}"
+    );
+}
+
+#[tokio::test]
+async fn l1_batch_costs_are_recorded_for_mined_attempt_only() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![100; 100], true).await;
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+
+    let operation = AggregatedOperation::Commit(CommitBatches {
+        last_committed_l1_batch: l1_batch_with_metadata(genesis_l1_batch),
+        l1_batches: vec![l1_batch_with_metadata(first_l1_batch)],
+    });
+    let first_hash = send_operation(&mut tester, operation, false).await;
+    let tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs(None)
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    // Resend the transaction with increased fees.
+    let second_hash = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &tx,
+            100,
+            tester.get_block_numbers().await.latest,
+        )
+        .await
+        .unwrap();
+    assert_ne!(first_hash, second_hash);
+
+    // Only the resent attempt is mined.
+    confirm_tx(&mut tester, second_hash).await;
+
+    let costs = tester
+        .storage()
+        .await
+        .blocks_web3_dal()
+        .get_l1_batch_costs(L1BatchNumber(1))
+        .await
+        .unwrap()
+        .expect("no costs for committed batch");
+    assert_eq!(costs.commit.len(), 1);
+    let commit_cost = &costs.commit[0];
+    assert_eq!(commit_cost.tx_hash, second_hash);
+    assert!(commit_cost.succeeded);
+    // The mock L1 client reports 21,000 gas used for each transaction.
+    assert_eq!(commit_cost.gas_used, 21_000.into());
+    assert_eq!(costs.total_cost, commit_cost.cost);
+}
+
+/// Loads the synthetic L1 base fee trace (in wei) used to test the `base_fee` publish criterion.
+///
+/// The trace is not recorded from L1 (see the file header for how it's generated), so the savings asserted
+/// in tests only check the criterion logic and are not representative of savings on a real network.
+fn l1_base_fee_trace() -> Vec<u64> {
+    include_str!("../../tests/data/l1_base_fee_trace_synthetic.csv")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .map(|line| line.parse().unwrap())
+        .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn postponing_publishing_on_high_base_fee() {
+    const MAX_BASE_FEE_SAMPLES: usize = 100;
+    const L1_BLOCK_TIME: Duration = Duration::from_secs(12);
+    const L1_BATCH_INTERVAL_BLOCKS: usize = 10;
+    const MAX_POSTPONE_BLOCKS: usize = 40;
+    let max_postpone = L1_BLOCK_TIME * MAX_POSTPONE_BLOCKS as u32;
+
+    let trace = l1_base_fee_trace();
+    let gateway = Arc::new(MockEthereum::default().with_fee_history(trace.clone()));
+    gateway.advance_block_number(MAX_BASE_FEE_SAMPLES as u64);
+    let gas_adjuster = GasAdjuster::new(
+        gateway.clone(),
+        GasAdjusterConfig {
+            max_base_fee_samples: MAX_BASE_FEE_SAMPLES,
+            ..ETHSenderConfig::for_tests().gas_adjuster
+        },
+    )
+    .await
+    .unwrap();
+    let gas_adjuster = Arc::new(gas_adjuster);
+    let mut criterion = BaseFeeCriterion::new(
+        AggregatedActionType::Commit,
+        gas_adjuster.clone(),
+        1.5,
+        max_postpone,
+    );
+
+    // Costs are measured in the base fee per L1 batch; gas consumption is assumed to be equal for all batches.
+    // Each L1 batch becomes ready for commitment in the L1 block it's sealed in; without the criterion,
+    // it would be published in the same block.
+    let (mut immediate_cost, mut postponed_cost) = (0_u128, 0_u128);
+    let mut max_delay_blocks = 0;
+    let mut pending_l1_batches = vec![];
+    for block in MAX_BASE_FEE_SAMPLES..trace.len() {
+        // `GasAdjuster` ignores the latest L1 block, so this makes `block` the current one.
+        gateway.advance_block_number(1);
+        gas_adjuster.keep_updated().await.unwrap();
+        assert_eq!(gas_adjuster.get_current_base_fee(), trace[block]);
+
+        if block % L1_BATCH_INTERVAL_BLOCKS == 0 {
+            immediate_cost += u128::from(trace[block]);
+            let number = (block / L1_BATCH_INTERVAL_BLOCKS) as u32;
+            pending_l1_batches.push((number, block));
+        }
+        if !pending_l1_batches.is_empty() {
+            let l1_batches: Vec<_> = pending_l1_batches
+                .iter()
+                .map(|&(number, _)| l1_batch_with_metadata(create_l1_batch(number)))
+                .collect();
+            if !criterion.should_postpone(&l1_batches).await {
+                for (_, sealed_at) in pending_l1_batches.drain(..) {
+                    postponed_cost += u128::from(trace[block]);
+                    max_delay_blocks = max_delay_blocks.max(block - sealed_at);
+                }
+            }
+        }
+        // L1 batches that are still postponed must not have been waiting for longer than the bound.
+        if let Some(&(number, sealed_at)) = pending_l1_batches.first() {
+            let delay = L1_BLOCK_TIME * (block - sealed_at) as u32;
+            assert!(
+                delay < max_postpone,
+                "L1 batch #{number} is postponed for {delay:?}, max_postpone={max_postpone:?}"
+            );
+        }
+        tokio::time::advance(L1_BLOCK_TIME).await;
+    }
+
+    assert!(pending_l1_batches.is_empty());
+    // Short spikes are waited out, but the prolonged surge makes the criterion hit the deadline;
+    // L1 batches are published no later than the deadline.
+    assert_eq!(max_delay_blocks, MAX_POSTPONE_BLOCKS);
+    assert!(
+        postponed_cost * 100 < immediate_cost * 96,
+        "postponed_cost={postponed_cost}, immediate_cost={immediate_cost}"
+    );
+}
+
 #[tokio::test]
 async fn skipped_l1_batch_at_the_start() -> anyhow::Result<()> {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -787,6 +998,169 @@ async fn skipped_l1_batch_in_the_middle() -> anyhow::Result<()> {
     Ok(())
 }
 
+// Tests that transactions sent from a dedicated account have their own nonce sequence and are not blocked
+// by a stuck transaction from the main operator account.
+#[tokio::test]
+async fn dedicated_sender_for_proofs() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![100; 100], false).await;
+    let prove_sender = Address::repeat_byte(0x22);
+    let prove_gateway = Arc::new(MockEthereum::default().with_sender_account(prove_sender));
+    prove_gateway.advance_block_number(EthSenderTester::WAIT_CONFIRMATIONS);
+    tester.aggregator = tester.aggregator.with_dedicated_sender(
+        AggregatedActionType::PublishProofOnchain,
+        prove_sender,
+        0,
+    );
+    tester.manager = tester.manager.with_dedicated_sender(prove_gateway.clone());
+
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    // The commit transaction is never mined.
+    commit_l1_batch(
+        &mut tester,
+        genesis_l1_batch.clone(),
+        first_l1_batch.clone(),
+        false,
+    )
+    .await;
+    let prove_tx_hash = prove_l1_batch(&mut tester, genesis_l1_batch, first_l1_batch, false).await;
+    assert_eq!(tester.gateway.sent_tx_count(), 1);
+    assert_eq!(prove_gateway.sent_tx_count(), 1);
+
+    let prove_txs = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_inflight_txs(Some(prove_sender))
+        .await
+        .unwrap();
+    assert_eq!(prove_txs.len(), 1);
+    assert_eq!(prove_txs[0].nonce.0, 0);
+    assert_eq!(prove_txs[0].from_addr, Some(prove_sender));
+
+    // The mock checks that the transaction nonce matches the nonce of the sender account.
+    prove_gateway.execute_tx(prove_tx_hash, true, EthSenderTester::WAIT_CONFIRMATIONS);
+    tester
+        .gateway
+        .advance_block_number(EthSenderTester::WAIT_CONFIRMATIONS);
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.access_storage().await.unwrap(),
+            tester.get_block_numbers().await,
+            Some(prove_sender),
+        )
+        .await?;
+    assert!(to_resend.is_none());
+
+    let mut storage = tester.storage().await;
+    let prove_txs = storage
+        .eth_sender_dal()
+        .get_inflight_txs(Some(prove_sender))
+        .await
+        .unwrap();
+    assert!(prove_txs.is_empty());
+    let main_txs = storage
+        .eth_sender_dal()
+        .get_inflight_txs(None)
+        .await
+        .unwrap();
+    assert_eq!(main_txs.len(), 1);
+    assert_eq!(main_txs[0].tx_type, AggregatedActionType::Commit);
+    Ok(())
+}
+
+#[tokio::test]
+async fn sending_from_unconfigured_sender_is_an_error() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut tester = EthSenderTester::new(connection_pool, vec![100; 100], false).await;
+    let prove_sender = Address::repeat_byte(0x22);
+    tester.aggregator = tester.aggregator.with_dedicated_sender(
+        AggregatedActionType::PublishProofOnchain,
+        prove_sender,
+        0,
+    );
+
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    let operation = AggregatedOperation::PublishProofOnchain(ProveBatches {
+        prev_l1_batch: l1_batch_with_metadata(genesis_l1_batch),
+        l1_batches: vec![l1_batch_with_metadata(first_l1_batch)],
+        proofs: vec![],
+        should_verify: false,
+    });
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &operation,
+            false,
+        )
+        .await
+        .unwrap();
+    assert_eq!(tx.from_addr, Some(prove_sender));
+
+    let err = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.access_storage().await.unwrap(),
+            &tx,
+            0,
+            tester.get_block_numbers().await.latest,
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(err, ETHSenderError::UnknownSender(address) if address == prove_sender);
+    assert_eq!(tester.gateway.sent_tx_count(), 0);
+}
+
+#[tokio::test]
+async fn eth_tx_manager_survives_signer_errors() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let tester = EthSenderTester::new(connection_pool.clone(), vec![100; 100], false).await;
+    tester
+        .aggregator
+        .save_eth_tx(&mut tester.storage().await, &DUMMY_OPERATION, true)
+        .await
+        .unwrap();
+    tester.gateway.set_signer_failing(true);
+
+    let EthSenderTester {
+        gateway, manager, ..
+    } = tester;
+    let health_check = manager.health_check();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let manager_task = tokio::spawn(manager.run(connection_pool, stop_receiver));
+
+    loop {
+        let health = health_check.check_health().await;
+        let consecutive_errors = health
+            .details()
+            .and_then(|details| details["consecutive_errors"].as_u64());
+        if consecutive_errors.unwrap_or(0) > 0 {
+            let last_error = &health.details().unwrap()["last_error"];
+            assert!(last_error.as_str().unwrap().contains("500"), "{last_error}");
+            break;
+        }
+        assert!(!manager_task.is_finished(), "eth_tx_manager has stopped");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert_eq!(gateway.sent_tx_count(), 0);
+
+    // The transaction should be sent once the signer recovers.
+    gateway.set_signer_failing(false);
+    while gateway.sent_tx_count() == 0 {
+        assert!(!manager_task.is_finished(), "eth_tx_manager has stopped");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    stop_sender.send_replace(true);
+    manager_task.await.unwrap().unwrap();
+}
+
 #[tokio::test]
 async fn test_parse_multicall_data() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -990,6 +1364,7 @@ async fn confirm_tx(tester: &mut EthSenderTester, hash: H256) {
         .monitor_inflight_transactions(
             &mut tester.conn.access_storage().await.unwrap(),
             tester.get_block_numbers().await,
+            None,
         )
         .await
         .unwrap();
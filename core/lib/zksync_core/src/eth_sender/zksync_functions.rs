@@ -1,6 +1,7 @@
 use zksync_contracts::{
     multicall_contract, verifier_contract, zksync_contract, PRE_BOOJUM_COMMIT_FUNCTION,
     PRE_BOOJUM_EXECUTE_FUNCTION, PRE_BOOJUM_GET_VK_FUNCTION, PRE_BOOJUM_PROVE_FUNCTION,
+    VALIDATOR_TIMELOCK_VALIDATORS_FUNCTION,
 };
 use zksync_types::ethabi::{Contract, Function};
 
@@ -24,6 +25,9 @@ pub(super) struct ZkSyncFunctions {
 
     pub(super) multicall_contract: Contract,
     pub(super) aggregate3: Function,
+
+    pub(super) validator_timelock_contract: Contract,
+    pub(super) validators: Function,
 }
 
 fn get_function(contract: &Contract, name: &str) -> Function {
@@ -67,6 +71,11 @@ impl Default for ZkSyncFunctions {
         let aggregate3 = get_function(&multicall_contract, "aggregate3");
         let verification_key_hash =
             get_optional_function(&verifier_contract, "verificationKeyHash");
+        let validators = VALIDATOR_TIMELOCK_VALIDATORS_FUNCTION.clone();
+        let validator_timelock_contract = Contract {
+            functions: [(validators.name.clone(), vec![validators.clone()])].into(),
+            ..Contract::default()
+        };
 
         ZkSyncFunctions {
             pre_boojum_commit,
@@ -85,6 +94,8 @@ impl Default for ZkSyncFunctions {
             verification_key_hash,
             multicall_contract,
             aggregate3,
+            validator_timelock_contract,
+            validators,
         }
     }
 }
@@ -23,6 +23,17 @@ pub enum Error {
     EthClient(#[from] EthClientError),
     #[error("Infinite recursion caused by too many responses")]
     InfiniteRecursion,
+    #[error("L1 block #{0} is missing")]
+    MissingBlock(u64),
+    #[error("L1 reorg is deeper than all stored checkpoints (oldest checkpoint: L1 block #{0})")]
+    DeepReorg(u64),
+    #[error(
+        "L1 reorg affects {0} L1 transactions that are already executed or cannot be evicted from the mempool; \
+         manual intervention is required"
+    )]
+    ProcessedL1TxsReorged(usize),
+    #[error("Database error: {0}")]
+    Database(#[from] zksync_dal::SqlxError),
 }
 
 impl From<web3::contract::Error> for Error {
@@ -42,6 +53,8 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
     ) -> Result<Vec<Log>, Error>;
     /// Returns finalized L1 block number.
     async fn finalized_block_number(&self) -> Result<u64, Error>;
+    /// Returns hash of the canonical L1 block with the specified number, or `None` if there is no such block.
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error>;
     /// Returns scheduler verification key hash by verifier address.
     async fn scheduler_vk_hash(&self, verifier_address: Address) -> Result<H256, Error>;
     /// Sets list of topics to return events for.
@@ -228,6 +241,14 @@ impl EthClient for EthHttpQueryClient {
         }
     }
 
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error> {
+        let block = self
+            .client
+            .block(BlockId::Number(BlockNumber::Number(number.into())), "watch")
+            .await?;
+        Ok(block.and_then(|block| block.hash))
+    }
+
     fn set_topics(&mut self, topics: Vec<H256>) {
         self.topics = topics;
     }
@@ -3,7 +3,7 @@ use std::{convert::TryFrom, time::Instant};
 use zksync_dal::StorageProcessor;
 use zksync_types::{
     ethabi::Contract, protocol_version::GovernanceOperation, web3::types::Log, Address,
-    ProtocolUpgrade, ProtocolVersionId, H256,
+    L1BlockNumber, ProtocolUpgrade, ProtocolVersionId, H256,
 };
 
 use crate::eth_watch::{
@@ -50,6 +50,9 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
             .into_iter()
             .filter(|event| event.topics[0] == self.upgrade_proposal_signature)
         {
+            let l1_block_number = event
+                .block_number
+                .map(|number| L1BlockNumber(number.as_u32()));
             let governance_operation = GovernanceOperation::try_from(event)
                 .map_err(|err| Error::LogParse(format!("{:?}", err)))?;
             // Some calls can target other contracts than Diamond proxy, skip them.
@@ -72,7 +75,7 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
                 } else {
                     None
                 };
-                upgrades.push((upgrade, scheduler_vk_hash));
+                upgrades.push((upgrade, scheduler_vk_hash, l1_block_number));
             }
         }
 
@@ -82,13 +85,13 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
 
         let ids_str: Vec<_> = upgrades
             .iter()
-            .map(|(u, _)| format!("{}", u.id as u16))
+            .map(|(u, _, _)| format!("{}", u.id as u16))
             .collect();
         tracing::debug!("Received upgrades with ids: {}", ids_str.join(", "));
 
         let new_upgrades: Vec<_> = upgrades
             .into_iter()
-            .skip_while(|(v, _)| v.id as u16 <= self.last_seen_version_id as u16)
+            .skip_while(|(v, _, _)| v.id as u16 <= self.last_seen_version_id as u16)
             .collect();
         if new_upgrades.is_empty() {
             return Ok(());
@@ -96,7 +99,7 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
 
         let last_id = new_upgrades.last().unwrap().0.id;
         let stage_start = Instant::now();
-        for (upgrade, scheduler_vk_hash) in new_upgrades {
+        for (upgrade, scheduler_vk_hash, l1_block_number) in new_upgrades {
             let previous_version = storage
                 .protocol_versions_dal()
                 .load_previous_version(upgrade.id)
@@ -108,10 +111,18 @@ impl EventProcessor for GovernanceUpgradesEventProcessor {
                     )
                 });
             let new_version = previous_version.apply_upgrade(upgrade, scheduler_vk_hash);
+            let new_version_id = new_version.id;
             storage
                 .protocol_versions_dal()
                 .save_protocol_version_with_tx(new_version)
                 .await;
+            // The L1 block is used to roll back the upgrade if it gets reorged out.
+            if let Some(l1_block_number) = l1_block_number {
+                storage
+                    .protocol_versions_dal()
+                    .save_l1_block_number(new_version_id, l1_block_number)
+                    .await;
+            }
         }
         metrics::histogram!("eth_watcher.poll_eth_node", stage_start.elapsed(), "stage" => "persist_upgrades");
 
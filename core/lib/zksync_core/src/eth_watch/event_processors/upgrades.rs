@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use zksync_dal::StorageProcessor;
-use zksync_types::{web3::types::Log, ProtocolUpgrade, ProtocolVersionId, H256};
+use zksync_types::{web3::types::Log, L1BlockNumber, ProtocolUpgrade, ProtocolVersionId, H256};
 
 use crate::eth_watch::{
     client::{Error, EthClient},
@@ -41,6 +41,9 @@ impl EventProcessor for UpgradesEventProcessor {
             .into_iter()
             .filter(|event| event.topics[0] == UPGRADE_PROPOSAL_SIGNATURE)
         {
+            let l1_block_number = event
+                .block_number
+                .map(|number| L1BlockNumber(number.as_u32()));
             let upgrade = ProtocolUpgrade::try_from(event)
                 .map_err(|err| Error::LogParse(format!("{:?}", err)))?;
             // Scheduler VK is not present in proposal event. It is hard coded in verifier contract.
@@ -49,7 +52,7 @@ impl EventProcessor for UpgradesEventProcessor {
             } else {
                 None
             };
-            upgrades.push((upgrade, scheduler_vk_hash));
+            upgrades.push((upgrade, scheduler_vk_hash, l1_block_number));
         }
 
         if upgrades.is_empty() {
@@ -58,13 +61,13 @@ impl EventProcessor for UpgradesEventProcessor {
 
         let ids_str: Vec<_> = upgrades
             .iter()
-            .map(|(u, _)| format!("{}", u.id as u16))
+            .map(|(u, _, _)| format!("{}", u.id as u16))
             .collect();
         tracing::debug!("Received upgrades with ids: {}", ids_str.join(", "));
 
         let new_upgrades: Vec<_> = upgrades
             .into_iter()
-            .skip_while(|(v, _)| v.id as u16 <= self.last_seen_version_id as u16)
+            .skip_while(|(v, _, _)| v.id as u16 <= self.last_seen_version_id as u16)
             .collect();
         if new_upgrades.is_empty() {
             return Ok(());
@@ -72,17 +75,25 @@ impl EventProcessor for UpgradesEventProcessor {
 
         let last_id = new_upgrades.last().unwrap().0.id;
         let stage_latency = METRICS.poll_eth_node[&PollStage::PersistUpgrades].start();
-        for (upgrade, scheduler_vk_hash) in new_upgrades {
+        for (upgrade, scheduler_vk_hash, l1_block_number) in new_upgrades {
             let previous_version = storage
                 .protocol_versions_dal()
                 .load_previous_version(upgrade.id)
                 .await
                 .expect("Expected previous version to be present in DB");
             let new_version = previous_version.apply_upgrade(upgrade, scheduler_vk_hash);
+            let new_version_id = new_version.id;
             storage
                 .protocol_versions_dal()
                 .save_protocol_version_with_tx(new_version)
                 .await;
+            // The L1 block is used to roll back the upgrade if it gets reorged out.
+            if let Some(l1_block_number) = l1_block_number {
+                storage
+                    .protocol_versions_dal()
+                    .save_l1_block_number(new_version_id, l1_block_number)
+                    .await;
+            }
         }
         stage_latency.observe();
         self.last_seen_version_id = last_id;
@@ -20,6 +20,16 @@ pub(super) struct EthWatcherMetrics {
     pub poll_eth_node: Family<PollStage, Histogram<Duration>>,
     #[metrics(buckets = Buckets::LATENCIES)]
     pub get_priority_op_events: Histogram<Duration>,
+    /// Number of detected L1 reorgs affecting already processed L1 blocks.
+    pub reorgs: Counter,
+    /// Number of un-executed L1 transactions (priority operations and upgrade transactions)
+    /// removed because of L1 reorgs.
+    pub rolled_back_l1_txs: Counter,
+    /// Number of unused protocol upgrades removed because of L1 reorgs.
+    pub rolled_back_protocol_upgrades: Counter,
+    /// Number of L1 transactions affected by L1 reorgs that were already executed or couldn't be evicted from the mempool.
+    /// Such transactions cannot be rolled back automatically, so `eth_watch` stops once this is detected.
+    pub reorged_processed_l1_txs: Counter,
 }
 
 #[vise::register]
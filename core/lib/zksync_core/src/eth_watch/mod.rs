@@ -3,6 +3,14 @@
 //!
 //! Poll interval is configured using the `ETH_POLL_INTERVAL` constant.
 //! Number of confirmations is configured using the `CONFIRMATIONS_FOR_ETH_EVENT` environment variable.
+//!
+//! After each processed range, the hash of its last L1 block is stored as a checkpoint. On each iteration,
+//! the watcher compares the most recent checkpoint with L1; if it was reorged out, un-executed priority operations
+//! and protocol upgrades from L1 blocks after the latest canonical checkpoint are rolled back and re-processed.
+//! Rolled back priority operations are evicted from the state keeper mempool if it runs in the same process
+//! (see [`EthWatch::with_mempool()`]). If any of the affected L1 transactions are already executed or picked
+//! for execution, if they are loaded to a mempool that cannot be accessed, or if none of the checkpoints is canonical,
+//! the watcher stops with an error instead, since such reorgs cannot be handled automatically.
 
 use std::time::Duration;
 
@@ -12,18 +20,20 @@ use zksync_dal::{ConnectionPool, StorageProcessor};
 use zksync_eth_client::EthInterface;
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
-    ethabi::Contract, web3::types::BlockNumber as Web3BlockNumber, Address, PriorityOpId,
-    ProtocolVersionId,
+    ethabi::Contract, web3::types::BlockNumber as Web3BlockNumber, Address, L1BlockNumber,
+    PriorityOpId, ProtocolVersionId,
 };
 
+pub use self::client::EthHttpQueryClient;
 use self::{
-    client::{Error, EthClient, EthHttpQueryClient, RETRY_LIMIT},
+    client::{Error, EthClient, RETRY_LIMIT},
     event_processors::{
         governance_upgrades::GovernanceUpgradesEventProcessor,
         priority_ops::PriorityOpsEventProcessor, upgrades::UpgradesEventProcessor, EventProcessor,
     },
     metrics::{PollStage, METRICS},
 };
+use crate::state_keeper::MempoolGuard;
 
 mod client;
 mod event_processors;
@@ -31,6 +41,9 @@ mod metrics;
 #[cfg(test)]
 mod tests;
 
+/// Number of the most recent L1 block checkpoints kept in the DB for reorg detection.
+const L1_BLOCK_CHECKPOINTS_TO_KEEP: usize = 256;
+
 #[derive(Debug)]
 struct EthWatchState {
     last_seen_version_id: ProtocolVersionId,
@@ -42,7 +55,10 @@ struct EthWatchState {
 pub struct EthWatch {
     client: Box<dyn EthClient>,
     poll_interval: Duration,
+    diamond_proxy_address: Address,
+    governance_contract: Option<Contract>,
     event_processors: Vec<Box<dyn EventProcessor>>,
+    mempool: Option<MempoolGuard>,
 
     last_processed_ethereum_block: u64,
 }
@@ -61,6 +77,42 @@ impl EthWatch {
 
         tracing::info!("initialized state: {:?}", state);
 
+        let event_processors = Self::create_event_processors(
+            &state,
+            diamond_proxy_address,
+            governance_contract.as_ref(),
+        );
+
+        let topics = event_processors
+            .iter()
+            .map(|p| p.relevant_topic())
+            .collect();
+        client.set_topics(topics);
+
+        Self {
+            client,
+            poll_interval,
+            diamond_proxy_address,
+            governance_contract,
+            event_processors,
+            mempool: None,
+            last_processed_ethereum_block: state.last_processed_ethereum_block,
+        }
+    }
+
+    /// Sets the mempool of the state keeper running in the same process. Priority operations rolled back
+    /// because of L1 reorgs will be evicted from it. Without a mempool, reorgs affecting priority operations
+    /// loaded to the mempool cannot be handled.
+    pub fn with_mempool(mut self, mempool: MempoolGuard) -> Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    fn create_event_processors(
+        state: &EthWatchState,
+        diamond_proxy_address: Address,
+        governance_contract: Option<&Contract>,
+    ) -> Vec<Box<dyn EventProcessor>> {
         let priority_ops_processor =
             PriorityOpsEventProcessor::new(state.next_expected_priority_id);
         let upgrades_processor = UpgradesEventProcessor::new(state.last_seen_version_id);
@@ -73,23 +125,11 @@ impl EthWatch {
             let governance_upgrades_processor = GovernanceUpgradesEventProcessor::new(
                 diamond_proxy_address,
                 state.last_seen_version_id,
-                &governance_contract,
+                governance_contract,
             );
             event_processors.push(Box::new(governance_upgrades_processor))
         }
-
-        let topics = event_processors
-            .iter()
-            .map(|p| p.relevant_topic())
-            .collect();
-        client.set_topics(topics);
-
-        Self {
-            client,
-            poll_interval,
-            event_processors,
-            last_processed_ethereum_block: state.last_processed_ethereum_block,
-        }
+        event_processors
     }
 
     async fn initialize_state(
@@ -147,14 +187,21 @@ impl EthWatch {
             METRICS.eth_poll.inc();
 
             let mut storage = pool.access_storage_tagged("eth_watch").await.unwrap();
-            if let Err(error) = self.loop_iteration(&mut storage).await {
-                // This is an error because otherwise we could potentially miss a priority operation
-                // thus entering priority mode, which is not desired.
-                tracing::error!("Failed to process new blocks {}", error);
-                self.last_processed_ethereum_block =
-                    Self::initialize_state(&*self.client, &mut storage)
-                        .await
-                        .last_processed_ethereum_block;
+            match self.loop_iteration(&mut storage).await {
+                Ok(()) => {}
+                Err(error @ (Error::ProcessedL1TxsReorged(_) | Error::DeepReorg(_))) => {
+                    // Re-initializing the state wouldn't help; the next iteration would detect the same reorg.
+                    return Err(anyhow::anyhow!("eth_watch cannot proceed: {error}"));
+                }
+                Err(error) => {
+                    // This is an error because otherwise we could potentially miss a priority operation
+                    // thus entering priority mode, which is not desired.
+                    tracing::error!("Failed to process new blocks {}", error);
+                    self.last_processed_ethereum_block =
+                        Self::initialize_state(&*self.client, &mut storage)
+                            .await
+                            .last_processed_ethereum_block;
+                }
             }
         }
         Ok(())
@@ -162,11 +209,22 @@ impl EthWatch {
 
     #[tracing::instrument(skip(self, storage))]
     async fn loop_iteration(&mut self, storage: &mut StorageProcessor<'_>) -> Result<(), Error> {
+        if let Some(last_canonical_block) = self.detect_reorg(storage).await? {
+            self.handle_reorg(storage, last_canonical_block).await?;
+        }
+
         let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
         let to_block = self.client.finalized_block_number().await?;
         if to_block <= self.last_processed_ethereum_block {
             return Ok(());
         }
+        // The hash is fetched before the events, so that a reorg happening in between
+        // is detected on the next iteration.
+        let to_block_hash = self
+            .client
+            .block_hash(to_block)
+            .await?
+            .ok_or(Error::MissingBlock(to_block))?;
 
         let events = self
             .client
@@ -183,9 +241,121 @@ impl EthWatch {
                 .process_events(storage, &*self.client, events.clone())
                 .await?;
         }
+
+        let mut eth_watch_dal = storage.eth_watch_dal();
+        eth_watch_dal
+            .save_l1_block(L1BlockNumber(to_block as u32), to_block_hash)
+            .await?;
+        eth_watch_dal
+            .prune_l1_blocks(L1_BLOCK_CHECKPOINTS_TO_KEEP)
+            .await?;
         self.last_processed_ethereum_block = to_block;
         Ok(())
     }
+
+    /// Compares the most recent L1 block checkpoint with L1. If it was reorged out, returns the number
+    /// of the latest checkpoint that is still canonical.
+    async fn detect_reorg(&self, storage: &mut StorageProcessor<'_>) -> Result<Option<u64>, Error> {
+        let checkpoints = storage.eth_watch_dal().get_l1_blocks().await?;
+        let Some(&(last_number, last_hash)) = checkpoints.first() else {
+            return Ok(None);
+        };
+        if self.client.block_hash(last_number.0.into()).await? == Some(last_hash) {
+            return Ok(None);
+        }
+
+        METRICS.reorgs.inc();
+        tracing::warn!(
+            "L1 block #{last_number} with hash {last_hash:?} was reorged out; looking for the latest canonical checkpoint"
+        );
+        for &(number, hash) in &checkpoints[1..] {
+            if self.client.block_hash(number.0.into()).await? == Some(hash) {
+                return Ok(Some(number.0.into()));
+            }
+        }
+        let (oldest_number, _) = checkpoints[checkpoints.len() - 1];
+        Err(Error::DeepReorg(oldest_number.0.into()))
+    }
+
+    /// Rolls back un-executed L1 transactions and unused protocol versions received after
+    /// `last_canonical_block`, so that they are re-processed from the canonical chain.
+    ///
+    /// Returns an error without changing the state if any of the affected L1 transactions are already executed
+    /// or picked for execution by the state keeper, or if they are loaded to the mempool and the mempool is not set.
+    async fn handle_reorg(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        last_canonical_block: u64,
+    ) -> Result<(), Error> {
+        let l1_block_number = L1BlockNumber(last_canonical_block as u32);
+        let mut transaction = storage.start_transaction().await?;
+        // If the mempool is available, priority operations loaded to it are evicted below.
+        let include_mempool = self.mempool.is_none();
+        let processed_tx_hashes = transaction
+            .transactions_dal()
+            .get_processed_l1_txs_after(l1_block_number, include_mempool)
+            .await?;
+        if !processed_tx_hashes.is_empty() {
+            METRICS
+                .reorged_processed_l1_txs
+                .inc_by(processed_tx_hashes.len() as u64);
+            tracing::error!(
+                "L1 reorg after block #{last_canonical_block} affects L1 transactions that are already executed \
+                 or cannot be evicted from the mempool: {processed_tx_hashes:?}"
+            );
+            return Err(Error::ProcessedL1TxsReorged(processed_tx_hashes.len()));
+        }
+
+        // Protocol versions must be removed first since they reference upgrade transactions.
+        let removed_versions = transaction
+            .protocol_versions_dal()
+            .delete_unused_versions_after_l1_block(l1_block_number)
+            .await;
+        let removed_txs_count = transaction
+            .transactions_dal()
+            .remove_unexecuted_l1_txs_after(l1_block_number)
+            .await?;
+        transaction
+            .eth_watch_dal()
+            .delete_l1_blocks_after(l1_block_number)
+            .await?;
+        let state = Self::initialize_state(&*self.client, &mut transaction).await;
+        if let Some(mempool) = &mut self.mempool {
+            // Priority operations are removed from the end of the queue, so the first removed operation
+            // is the next expected one. Evicting them before committing the DB transaction ensures that
+            // the state keeper cannot pick them for execution in the meantime.
+            let first_removed_id = state.next_expected_priority_id;
+            let Some(evicted_count) = mempool.remove_l1_transactions_from(first_removed_id) else {
+                METRICS
+                    .reorged_processed_l1_txs
+                    .inc_by(removed_txs_count as u64);
+                tracing::error!(
+                    "L1 reorg after block #{last_canonical_block} affects priority operations starting from \
+                     #{first_removed_id} that are already picked for execution by the state keeper"
+                );
+                return Err(Error::ProcessedL1TxsReorged(removed_txs_count));
+            };
+            tracing::info!("Evicted {evicted_count} reorged priority operations from the mempool");
+        }
+        transaction.commit().await?;
+
+        tracing::warn!(
+            "Rolled back eth_watch to L1 block #{last_canonical_block}: removed {removed_txs_count} L1 transactions \
+             and protocol versions {removed_versions:?}; new state: {state:?}"
+        );
+        METRICS.rolled_back_l1_txs.inc_by(removed_txs_count as u64);
+        METRICS
+            .rolled_back_protocol_upgrades
+            .inc_by(removed_versions.len() as u64);
+
+        self.event_processors = Self::create_event_processors(
+            &state,
+            self.diamond_proxy_address,
+            self.governance_contract.as_ref(),
+        );
+        self.last_processed_ethereum_block = last_canonical_block;
+        Ok(())
+    }
 }
 
 pub async fn start_eth_watch(
@@ -194,6 +364,7 @@ pub async fn start_eth_watch(
     eth_gateway: Box<dyn EthInterface>,
     diamond_proxy_addr: Address,
     governance: (Contract, Address),
+    mempool: Option<MempoolGuard>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
     let eth_client = EthHttpQueryClient::new(
@@ -211,6 +382,9 @@ pub async fn start_eth_watch(
         config.poll_interval(),
     )
     .await;
+    if let Some(mempool) = mempool {
+        eth_watch = eth_watch.with_mempool(mempool);
+    }
 
     Ok(tokio::spawn(async move {
         eth_watch.run(pool, stop_receiver).await
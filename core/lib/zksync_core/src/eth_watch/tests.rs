@@ -1,20 +1,24 @@
 use std::{collections::HashMap, convert::TryInto, sync::Arc};
 
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use zksync_contracts::{governance_contract, zksync_contract};
 use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_mempool::L2TxFilter;
 use zksync_types::{
     ethabi::{encode, Hash, Token},
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     protocol_version::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     web3::types::{Address, BlockNumber, Log},
-    Execute, L1TxCommonData, PriorityOpId, ProtocolUpgrade, ProtocolVersion, ProtocolVersionId,
-    Transaction, H256, U256,
+    Execute, L1BlockNumber, L1TxCommonData, PriorityOpId, ProtocolUpgrade, ProtocolVersion,
+    ProtocolVersionId, Transaction, H256, U256,
 };
 
 use super::client::Error;
-use crate::eth_watch::{
-    client::EthClient, event_processors::upgrades::UPGRADE_PROPOSAL_SIGNATURE, EthWatch,
+use crate::{
+    eth_watch::{
+        client::EthClient, event_processors::upgrades::UPGRADE_PROPOSAL_SIGNATURE, EthWatch,
+    },
+    state_keeper::MempoolGuard,
 };
 
 #[derive(Debug)]
@@ -22,6 +26,7 @@ struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    reorged_block_hashes: HashMap<u64, H256>,
     last_finalized_block_number: u64,
 }
 
@@ -31,6 +36,7 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            reorged_block_hashes: Default::default(),
             last_finalized_block_number: 0,
         }
     }
@@ -66,6 +72,26 @@ impl FakeEthClientData {
     fn set_last_finalized_block_number(&mut self, number: u64) {
         self.last_finalized_block_number = number;
     }
+
+    fn block_hash(&self, number: u64) -> H256 {
+        self.reorged_block_hashes
+            .get(&number)
+            .copied()
+            .unwrap_or_else(|| H256::from_low_u64_be(number))
+    }
+
+    /// Replaces all blocks starting from `first_reorged_block` with new blocks without any events.
+    fn reorg(&mut self, first_reorged_block: u64) {
+        for number in first_reorged_block..=self.last_finalized_block_number {
+            self.reorged_block_hashes.insert(number, H256::random());
+        }
+        self.transactions
+            .retain(|&number, _| number < first_reorged_block);
+        self.diamond_upgrades
+            .retain(|&number, _| number < first_reorged_block);
+        self.governance_upgrades
+            .retain(|&number, _| number < first_reorged_block);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +125,10 @@ impl FakeEthClient {
             .set_last_finalized_block_number(number);
     }
 
+    async fn reorg(&mut self, first_reorged_block: u64) {
+        self.inner.write().await.reorg(first_reorged_block);
+    }
+
     async fn block_to_number(&self, block: BlockNumber) -> u64 {
         match block {
             BlockNumber::Earliest => 0,
@@ -145,6 +175,10 @@ impl EthClient for FakeEthClient {
     async fn finalized_block_number(&self) -> Result<u64, Error> {
         Ok(self.inner.read().await.last_finalized_block_number)
     }
+
+    async fn block_hash(&self, number: u64) -> Result<Option<H256>, Error> {
+        Ok(Some(self.inner.read().await.block_hash(number)))
+    }
 }
 
 fn build_l1_tx(serial_id: u64, eth_block: u64) -> L1Tx {
@@ -523,6 +557,287 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+#[tokio::test]
+async fn test_reorg_rolls_back_unexecuted_operations() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14), build_l1_tx(2, 18)])
+        .await;
+    client
+        .add_diamond_upgrades(&[
+            (
+                ProtocolUpgrade {
+                    id: ProtocolVersionId::latest(),
+                    tx: None,
+                    ..Default::default()
+                },
+                10,
+            ),
+            (
+                ProtocolUpgrade {
+                    id: ProtocolVersionId::next(),
+                    tx: Some(build_upgrade_tx(ProtocolVersionId::next(), 18)),
+                    ..Default::default()
+                },
+                18,
+            ),
+        ])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    let db_ids = storage.protocol_versions_dal().all_version_ids().await;
+    assert_eq!(db_ids.len(), 3);
+
+    // Blocks starting from #16 are reorged out; the priority op is re-included in block #19,
+    // and the second upgrade disappears.
+    client.reorg(16).await;
+    client.add_transactions(&[build_l1_tx(2, 19)]).await;
+    client.set_last_finalized_block_number(22).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_ids = storage.protocol_versions_dal().all_version_ids().await;
+    assert_eq!(db_ids.len(), 2);
+    assert_eq!(db_ids[1], ProtocolVersionId::latest());
+    let tx = storage
+        .protocol_versions_dal()
+        .get_protocol_upgrade_tx(ProtocolVersionId::next())
+        .await;
+    assert!(tx.is_none());
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    assert_eq!(db_txs.len(), 3);
+    assert_eq!(db_txs[1].eth_block().0, 14);
+    assert_eq!(db_txs[2].eth_block().0, 19);
+
+    let checkpoints = storage.eth_watch_dal().get_l1_blocks().await.unwrap();
+    let expected_hash = client.block_hash(22).await.unwrap().unwrap();
+    assert_eq!(checkpoints[0], (L1BlockNumber(22), expected_hash));
+    assert_eq!(
+        checkpoints[1],
+        (L1BlockNumber(15), H256::from_low_u64_be(15))
+    );
+}
+
+#[tokio::test]
+async fn test_reorg_of_processed_operations_is_an_error() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 18)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    // Load both priority ops to the mempool.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+
+    client.reorg(16).await;
+    client.set_last_finalized_block_number(22).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(matches!(err, Error::ProcessedL1TxsReorged(1)), "{err:?}");
+
+    // The state must not be changed.
+    let processed_txs = storage
+        .transactions_dal()
+        .get_processed_l1_txs_after(L1BlockNumber(15), true)
+        .await
+        .unwrap();
+    assert_eq!(processed_txs.len(), 1);
+    let checkpoints = storage.eth_watch_dal().get_l1_blocks().await.unwrap();
+    assert_eq!(checkpoints[0].0, L1BlockNumber(20));
+}
+
+/// Emulates `MempoolFetcher` loading all L1 transactions from the DB to the mempool.
+async fn load_l1_txs_to_mempool(storage: &mut StorageProcessor<'_>, mempool: &mut MempoolGuard) {
+    let transactions = storage
+        .transactions_dal()
+        .sync_mempool(&[], &[], 0, 0, 1000)
+        .await
+        .unwrap();
+    mempool.insert(transactions, HashMap::new());
+}
+
+fn next_priority_op_id(mempool: &mut MempoolGuard) -> Option<PriorityOpId> {
+    let tx = mempool.next_transaction(&L2TxFilter::default())?;
+    Some(L1Tx::try_from(tx).unwrap().common_data.serial_id)
+}
+
+#[tokio::test]
+async fn test_reorg_evicts_operations_from_mempool() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_mempool(mempool.clone());
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 18)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    load_l1_txs_to_mempool(&mut storage, &mut mempool).await;
+    // The state keeper picks the first priority op, which is not affected by the reorg.
+    assert_eq!(next_priority_op_id(&mut mempool), Some(PriorityOpId(0)));
+
+    client.reorg(16).await;
+    client.add_transactions(&[build_l1_tx(1, 19)]).await;
+    client.set_last_finalized_block_number(22).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    // The reorged priority op must be evicted from the mempool and replaced with the canonical one.
+    assert_eq!(mempool.stats().l1_transaction_count, 0);
+    load_l1_txs_to_mempool(&mut storage, &mut mempool).await;
+    let tx = mempool
+        .next_transaction(&L2TxFilter::default())
+        .expect("no canonical priority op in mempool");
+    let tx = L1Tx::try_from(tx).unwrap();
+    assert_eq!(tx.common_data.serial_id, PriorityOpId(1));
+    assert_eq!(tx.eth_block().0, 19);
+}
+
+#[tokio::test]
+async fn test_reorg_of_operation_picked_by_state_keeper_is_an_error() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await
+    .with_mempool(mempool.clone());
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 18)])
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    load_l1_txs_to_mempool(&mut storage, &mut mempool).await;
+    assert_eq!(next_priority_op_id(&mut mempool), Some(PriorityOpId(0)));
+    assert_eq!(next_priority_op_id(&mut mempool), Some(PriorityOpId(1)));
+
+    client.reorg(16).await;
+    client.set_last_finalized_block_number(22).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(matches!(err, Error::ProcessedL1TxsReorged(1)), "{err:?}");
+
+    // The state must not be changed.
+    assert_eq!(get_all_db_txs(&mut storage).await.len(), 2);
+    let checkpoints = storage.eth_watch_dal().get_l1_blocks().await.unwrap();
+    assert_eq!(checkpoints[0].0, L1BlockNumber(20));
+}
+
+#[tokio::test]
+async fn test_reorg_deeper_than_checkpoints() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    client.reorg(5).await;
+    let err = watcher.loop_iteration(&mut storage).await.unwrap_err();
+    assert!(matches!(err, Error::DeepReorg(15)), "{err:?}");
+}
+
+#[tokio::test]
+async fn eth_watch_stops_on_reorg_of_single_checkpoint() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    setup_db(&connection_pool).await;
+
+    let mut client = FakeEthClient::new();
+    let mut watcher = EthWatch::new(
+        Address::default(),
+        None,
+        Box::new(client.clone()),
+        &connection_pool,
+        std::time::Duration::from_nanos(1),
+    )
+    .await;
+
+    let mut storage = connection_pool.access_storage().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client.set_last_finalized_block_number(15).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    drop(storage);
+    // The only stored checkpoint is reorged out.
+    client.reorg(5).await;
+
+    let (_stop_sender, stop_receiver) = watch::channel(false);
+    let run = watcher.run(connection_pool, stop_receiver);
+    let err = tokio::time::timeout(std::time::Duration::from_secs(10), run)
+        .await
+        .expect("eth_watch didn't stop after a deep reorg")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("deeper than all stored checkpoints"), "{err}");
+}
+
 async fn get_all_db_txs(storage: &mut StorageProcessor<'_>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
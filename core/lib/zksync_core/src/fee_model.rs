@@ -52,7 +52,7 @@ pub trait BatchFeeModelInputProvider: fmt::Debug + 'static + Send + Sync {
 /// it explicitly gets the L1 gas price from the provider and uses it to calculate the batch fee input instead of getting
 /// it from other node.
 #[derive(Debug)]
-pub(crate) struct MainNodeFeeInputProvider {
+pub struct MainNodeFeeInputProvider {
     provider: Arc<dyn L1GasPriceProvider>,
     config: FeeModelConfig,
 }
@@ -74,7 +74,7 @@ impl BatchFeeModelInputProvider for MainNodeFeeInputProvider {
 }
 
 impl MainNodeFeeInputProvider {
-    pub(crate) fn new(provider: Arc<dyn L1GasPriceProvider>, config: FeeModelConfig) -> Self {
+    pub fn new(provider: Arc<dyn L1GasPriceProvider>, config: FeeModelConfig) -> Self {
         Self { provider, config }
     }
 }
@@ -82,19 +82,19 @@ impl MainNodeFeeInputProvider {
 /// The fee model provider to be used in the API. It returns the maximal batch fee input between the projected main node one and
 /// the one from the last sealed miniblock.
 #[derive(Debug)]
-pub(crate) struct ApiFeeInputProvider {
-    inner: MainNodeFeeInputProvider,
+pub struct ApiFeeInputProvider {
+    inner: Arc<dyn BatchFeeModelInputProvider>,
     connection_pool: ConnectionPool,
 }
 
 impl ApiFeeInputProvider {
+    /// Wraps the main node provider (usually, [`MainNodeFeeInputProvider`]).
     pub fn new(
-        provider: Arc<dyn L1GasPriceProvider>,
-        config: FeeModelConfig,
+        inner: Arc<dyn BatchFeeModelInputProvider>,
         connection_pool: ConnectionPool,
     ) -> Self {
         Self {
-            inner: MainNodeFeeInputProvider::new(provider, config),
+            inner,
             connection_pool,
         }
     }
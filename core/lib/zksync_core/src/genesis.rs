@@ -37,8 +37,8 @@ pub struct GenesisParams {
 }
 
 impl GenesisParams {
-    #[cfg(test)]
-    pub(crate) fn mock() -> Self {
+    #[cfg(any(test, feature = "testonly"))]
+    pub fn mock() -> Self {
         use zksync_types::system_contracts::get_system_smart_contracts;
 
         Self {
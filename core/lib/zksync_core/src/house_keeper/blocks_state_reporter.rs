@@ -96,6 +96,31 @@ impl L1BatchMetricsReporter {
             APP_METRICS.blocks_state_block_eth_stage_latency[&L1StageLatencyLabel::UnexecutedBlock]
                 .set(now.saturating_sub(timestamp));
         }
+
+        let last_executed_l1_batch = conn
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        let first_unexecuted_priority_op = conn
+            .transactions_dal()
+            .get_first_unexecuted_priority_op(last_executed_l1_batch)
+            .await
+            .unwrap();
+        let oldest_priority_op_age = first_unexecuted_priority_op
+            .map_or(0, |(_, received_at)| now.saturating_sub(received_at));
+        APP_METRICS
+            .oldest_unexecuted_priority_op_age
+            .set(oldest_priority_op_age);
+        let expired_priority_ops = conn
+            .transactions_dal()
+            .count_expired_unexecuted_priority_ops(last_executed_l1_batch, now)
+            .await
+            .unwrap();
+        APP_METRICS
+            .expired_unexecuted_priority_ops
+            .set(expired_priority_ops);
     }
 }
 
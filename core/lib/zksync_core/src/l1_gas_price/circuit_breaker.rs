@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use zksync_circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+
+use super::L1TxParamsProvider;
+
+/// Circuit breaker triggered if the L1 gas price observed by the gas adjuster exceeds the cap.
+///
+/// Unlike [`L1GasPriceProvider::estimate_effective_gas_price()`](super::L1GasPriceProvider), the checked price
+/// is not bounded by the gas adjuster config, so that the breaker reflects actual L1 conditions.
+#[derive(Debug)]
+pub struct L1GasPriceChecker {
+    gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    max_l1_gas_price: u64,
+}
+
+impl L1GasPriceChecker {
+    pub fn new(gas_adjuster: Arc<dyn L1TxParamsProvider>, max_l1_gas_price: u64) -> Self {
+        Self {
+            gas_adjuster,
+            max_l1_gas_price,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for L1GasPriceChecker {
+    fn name(&self) -> &'static str {
+        "l1_gas_price"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let gas_price = self.gas_adjuster.get_base_fee(0) + self.gas_adjuster.get_priority_fee();
+        if gas_price > self.max_l1_gas_price {
+            return Err(CircuitBreakerError::Custom {
+                name: self.name(),
+                reason: format!(
+                    "L1 gas price ({gas_price} wei) is above the cap ({} wei)",
+                    self.max_l1_gas_price
+                ),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::l1_gas_price::L1GasPriceProvider;
+
+    #[derive(Debug)]
+    struct MockTxParams(u64);
+
+    impl L1GasPriceProvider for MockTxParams {
+        fn estimate_effective_gas_price(&self) -> u64 {
+            self.0
+        }
+
+        fn estimate_effective_pubdata_price(&self) -> u64 {
+            self.0 * 17
+        }
+    }
+
+    impl L1TxParamsProvider for MockTxParams {
+        fn get_base_fee(&self, _time_in_mempool: u32) -> u64 {
+            self.0
+        }
+
+        fn get_priority_fee(&self) -> u64 {
+            1
+        }
+
+        fn get_next_block_minimal_base_fee(&self) -> u64 {
+            self.0
+        }
+
+        fn get_current_base_fee(&self) -> u64 {
+            self.0
+        }
+
+        fn get_median_base_fee(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn l1_gas_price_checker() {
+        let checker = L1GasPriceChecker::new(Arc::new(MockTxParams(99)), 100);
+        checker.check().await.unwrap();
+
+        let checker = L1GasPriceChecker::new(Arc::new(MockTxParams(100)), 100);
+        let err = checker.check().await.unwrap_err();
+        assert_matches!(
+            err,
+            CircuitBreakerError::Custom {
+                name: "l1_gas_price",
+                ..
+            }
+        );
+    }
+}
@@ -162,6 +162,14 @@ impl<E: EthInterface> L1TxParamsProvider for GasAdjuster<E> {
         last_block_base_fee * 875 / 1000
     }
 
+    fn get_current_base_fee(&self) -> u64 {
+        self.statistics.last_added_value()
+    }
+
+    fn get_median_base_fee(&self) -> u64 {
+        self.statistics.median()
+    }
+
     // Priority fee is set to constant, sourced from config.
     // Reasoning behind this is the following:
     // High `priority_fee` means high demand for block space,
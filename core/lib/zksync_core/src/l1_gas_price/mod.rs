@@ -2,10 +2,12 @@
 
 use std::fmt;
 
+pub use circuit_breaker::L1GasPriceChecker;
 pub use gas_adjuster::GasAdjuster;
 pub use main_node_fetcher::MainNodeFeeParamsFetcher;
 pub use singleton::GasAdjusterSingleton;
 
+mod circuit_breaker;
 mod gas_adjuster;
 mod main_node_fetcher;
 pub mod singleton;
@@ -36,4 +38,10 @@ pub trait L1TxParamsProvider: L1GasPriceProvider {
 
     /// Returns a lower bound for the `base_fee` value for the next L1 block.
     fn get_next_block_minimal_base_fee(&self) -> u64;
+
+    /// Returns the `base_fee` value of the last observed L1 block.
+    fn get_current_base_fee(&self) -> u64;
+
+    /// Returns the median `base_fee` value over the recent L1 blocks.
+    fn get_median_base_fee(&self) -> u64;
 }
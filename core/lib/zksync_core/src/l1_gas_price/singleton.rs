@@ -5,8 +5,8 @@ use tokio::{
     sync::{watch, OnceCell},
     task::JoinHandle,
 };
-use zksync_config::GasAdjusterConfig;
-use zksync_eth_client::clients::QueryClient;
+use zksync_config::{ETHClientConfig, GasAdjusterConfig};
+use zksync_eth_client::clients::FailoverClient;
 
 use crate::l1_gas_price::GasAdjuster;
 
@@ -14,9 +14,9 @@ use crate::l1_gas_price::GasAdjuster;
 /// This is needed only for running the server.
 #[derive(Debug)]
 pub struct GasAdjusterSingleton {
-    web3_url: String,
+    eth_client_config: ETHClientConfig,
     gas_adjuster_config: GasAdjusterConfig,
-    singleton: OnceCell<Result<Arc<GasAdjuster<QueryClient>>, Error>>,
+    singleton: OnceCell<Result<Arc<GasAdjuster<FailoverClient>>, Error>>,
 }
 
 #[derive(thiserror::Error, Debug, Clone)]
@@ -30,21 +30,21 @@ impl From<anyhow::Error> for Error {
 }
 
 impl GasAdjusterSingleton {
-    pub fn new(web3_url: String, gas_adjuster_config: GasAdjusterConfig) -> Self {
+    pub fn new(eth_client_config: ETHClientConfig, gas_adjuster_config: GasAdjusterConfig) -> Self {
         Self {
-            web3_url,
+            eth_client_config,
             gas_adjuster_config,
             singleton: OnceCell::new(),
         }
     }
 
-    pub async fn get_or_init(&mut self) -> Result<Arc<GasAdjuster<QueryClient>>, Error> {
+    pub async fn get_or_init(&mut self) -> Result<Arc<GasAdjuster<FailoverClient>>, Error> {
         let adjuster = self
             .singleton
             .get_or_init(|| async {
-                let query_client =
-                    QueryClient::new(&self.web3_url).context("QueryClient::new()")?;
-                let adjuster = GasAdjuster::new(query_client.clone(), self.gas_adjuster_config)
+                let query_client = FailoverClient::from_config(&self.eth_client_config)
+                    .context("FailoverClient::from_config()")?;
+                let adjuster = GasAdjuster::new(query_client, self.gas_adjuster_config)
                     .await
                     .context("GasAdjuster::new()")?;
                 Ok(Arc::new(adjuster))
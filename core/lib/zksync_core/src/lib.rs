@@ -9,7 +9,8 @@ use prometheus_exporter::PrometheusExporterConfig;
 use temp_config_store::TempConfigStore;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_circuit_breaker::{
-    l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker, CircuitBreaker,
+    l1_txs::FailedL1TransactionChecker, operator_balance::OperatorBalanceChecker,
+    prover_lag::ProverLagChecker, replication_lag::ReplicationLagChecker, CircuitBreaker,
     CircuitBreakerChecker, CircuitBreakerError,
 };
 use zksync_concurrency::{ctx, scope};
@@ -28,7 +29,7 @@ use zksync_config::{
 use zksync_contracts::{governance_contract, BaseSystemContracts};
 use zksync_dal::{healthcheck::ConnectionPoolHealthCheck, ConnectionPool};
 use zksync_eth_client::{
-    clients::{PKSigningClient, QueryClient},
+    clients::{FailoverClient, OperatorSigningClient, PKSigningClient, QueryClient},
     BoundEthInterface, CallFunctionArgs, EthInterface,
 };
 use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
@@ -40,7 +41,7 @@ use zksync_types::{
     protocol_version::{L1VerifierConfig, VerifierParams},
     system_contracts::get_system_smart_contracts,
     web3::contract::tokens::Detokenize,
-    L2ChainId, PackedEthSignature, ProtocolVersionId,
+    Address, L2ChainId, PackedEthSignature, ProtocolVersionId, U256,
 };
 
 use crate::{
@@ -50,7 +51,10 @@ use crate::{
         healthcheck::HealthCheckHandle,
         tx_sender::{ApiContracts, TxSender, TxSenderBuilder, TxSenderConfig},
         web3,
-        web3::{state::InternalApiConfig, ApiServerHandles, Namespace},
+        web3::{
+            backend_jsonrpsee::access_control::AccessControl, state::InternalApiConfig,
+            ApiServerHandles, Namespace,
+        },
     },
     basic_witness_input_producer::BasicWitnessInputProducer,
     eth_sender::{Aggregator, EthTxAggregator, EthTxManager},
@@ -67,12 +71,13 @@ use crate::{
         periodic_job::PeriodicJob,
         waiting_to_queued_fri_witness_job_mover::WaitingToQueuedFriWitnessJobMover,
     },
-    l1_gas_price::{GasAdjusterSingleton, L1GasPriceProvider},
+    l1_gas_price::{GasAdjusterSingleton, L1GasPriceChecker, L1GasPriceProvider},
     metadata_calculator::{MetadataCalculator, MetadataCalculatorConfig},
     metrics::{InitStage, APP_METRICS},
     state_keeper::{
         create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockSealer, SequencerSealer,
     },
+    withdrawal_finalizer::WithdrawalFinalizer,
 };
 
 pub mod api_server;
@@ -82,7 +87,7 @@ pub mod consensus;
 pub mod consistency_checker;
 pub mod eth_sender;
 pub mod eth_watch;
-mod fee_model;
+pub mod fee_model;
 pub mod gas_tracker;
 pub mod genesis;
 pub mod house_keeper;
@@ -94,7 +99,10 @@ pub mod reorg_detector;
 pub mod state_keeper;
 pub mod sync_layer;
 pub mod temp_config_store;
+#[cfg(any(test, feature = "testonly"))]
+pub mod testonly;
 mod utils;
+pub mod withdrawal_finalizer;
 
 /// Inserts the initial information about zkSync tokens into the database.
 pub async fn genesis_init(
@@ -110,13 +118,17 @@ pub async fn genesis_init(
         .await
         .context("failed to build connection_pool")?;
     let mut storage = pool.access_storage().await.context("access_storage()")?;
-    let operator_address = PackedEthSignature::address_from_private_key(
-        &eth_sender
-            .sender
-            .private_key()
-            .context("Private key is required for genesis init")?,
-    )
-    .context("Failed to restore operator address from private key")?;
+    let operator_address = if let Some(remote_signer) = &eth_sender.remote_signer {
+        remote_signer.operator_address
+    } else {
+        PackedEthSignature::address_from_private_key(
+            &eth_sender
+                .sender
+                .private_key()
+                .context("Private key or remote signer is required for genesis init")?,
+        )
+        .context("Failed to restore operator address from private key")?
+    };
 
     // Select the first prover to be used during genesis.
     // Later we can change provers using the system upgrades, but for genesis
@@ -233,6 +245,8 @@ pub enum Component {
     ProofDataHandler,
     /// Component generating BFT consensus certificates for miniblocks.
     Consensus,
+    /// Finalizes L2→L1 withdrawals on L1 after the corresponding L1 batches are executed.
+    WithdrawalFinalizer,
 }
 
 #[derive(Debug)]
@@ -268,6 +282,7 @@ impl FromStr for Components {
             "eth_tx_manager" => Ok(Components(vec![Component::EthTxManager])),
             "proof_data_handler" => Ok(Components(vec![Component::ProofDataHandler])),
             "consensus" => Ok(Components(vec![Component::Consensus])),
+            "withdrawal_finalizer" => Ok(Components(vec![Component::WithdrawalFinalizer])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -314,20 +329,29 @@ pub async fn initialize_components(
         .clone()
         .context("circuit_breaker_config")?;
 
+    let query_client = Arc::new(
+        FailoverClient::from_config(&eth_client_config).context("FailoverClient::from_config()")?,
+    );
+    let gas_adjuster_config = configs.gas_adjuster_config.context("gas_adjuster_config")?;
+    let mut gas_adjuster =
+        GasAdjusterSingleton::new(eth_client_config.clone(), gas_adjuster_config);
+
     let circuit_breaker_checker = CircuitBreakerChecker::new(
-        circuit_breakers_for_components(&components, &postgres_config, &circuit_breaker_config)
-            .await
-            .context("circuit_breakers_for_components")?,
+        circuit_breakers_for_components(
+            configs,
+            &components,
+            &postgres_config,
+            &circuit_breaker_config,
+            &mut gas_adjuster,
+        )
+        .await
+        .context("circuit_breakers_for_components")?,
         &circuit_breaker_config,
     );
     circuit_breaker_checker.check().await.unwrap_or_else(|err| {
         panic!("Circuit breaker triggered: {}", err);
     });
-
-    let query_client = QueryClient::new(&eth_client_config.web3_url).unwrap();
-    let gas_adjuster_config = configs.gas_adjuster_config.context("gas_adjuster_config")?;
-    let mut gas_adjuster =
-        GasAdjusterSingleton::new(eth_client_config.web3_url.clone(), gas_adjuster_config);
+    let sealing_pause_receiver = circuit_breaker_checker.sealing_pause_receiver();
 
     let (stop_sender, stop_receiver) = watch::channel(false);
     let (cb_sender, cb_receiver) = oneshot::channel();
@@ -479,6 +503,8 @@ pub async fn initialize_components(
         .context("object_store_config")?;
     let store_factory = ObjectStoreFactory::new(object_store_config);
 
+    // Shared with `eth_watch` so that it can evict priority operations reorged out on L1.
+    let mut state_keeper_mempool = None;
     if components.contains(&Component::StateKeeper) {
         let started_at = Instant::now();
         tracing::info!("initializing State Keeper");
@@ -486,8 +512,9 @@ pub async fn initialize_components(
             .get_or_init()
             .await
             .context("gas_adjuster.get_or_init()")?;
-        add_state_keeper_to_task_futures(
+        let mempool = add_state_keeper_to_task_futures(
             &mut task_futures,
+            &mut healthchecks,
             &postgres_config,
             &contracts_config,
             configs
@@ -499,10 +526,12 @@ pub async fn initialize_components(
             &configs.mempool_config.clone().context("mempool_config")?,
             bounded_gas_adjuster,
             store_factory.create_store().await,
+            sealing_pause_receiver,
             stop_receiver.clone(),
         )
         .await
         .context("add_state_keeper_to_task_futures()")?;
+        state_keeper_mempool = Some(mempool);
 
         let elapsed = started_at.elapsed();
         APP_METRICS.init_latency[&InitStage::StateKeeper].set(elapsed);
@@ -562,6 +591,7 @@ pub async fn initialize_components(
                 Box::new(query_client.clone()),
                 main_zksync_contract_address,
                 governance,
+                state_keeper_mempool.clone(),
                 stop_receiver.clone(),
             )
             .await
@@ -585,20 +615,50 @@ pub async fn initialize_components(
             .clone()
             .context("eth_sender_config")?;
         let eth_client =
-            PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
+            OperatorSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config)
+                .context("OperatorSigningClient::from_config()")?;
         let nonce = eth_client.pending_nonce("eth_sender").await.unwrap();
-        let eth_tx_aggregator_actor = EthTxAggregator::new(
+        let mut aggregator = Aggregator::new(
             eth_sender.sender.clone(),
-            Aggregator::new(
-                eth_sender.sender.clone(),
-                store_factory.create_store().await,
-            ),
+            store_factory.create_store().await,
+        );
+        if eth_sender.sender.base_fee_postpone_ratio.is_some() {
+            let gas_adjuster = gas_adjuster
+                .get_or_init()
+                .await
+                .context("gas_adjuster.get_or_init()")?;
+            aggregator = aggregator.with_gas_adjuster(gas_adjuster);
+        }
+        let mut eth_tx_aggregator_actor = EthTxAggregator::new(
+            eth_sender.sender.clone(),
+            aggregator,
             Arc::new(eth_client),
             contracts_config.validator_timelock_addr,
             contracts_config.l1_multicall3_addr,
             main_zksync_contract_address,
             nonce.as_u64(),
         );
+        for action_type in EthTxAggregator::DEDICATED_SENDER_ACTIONS {
+            let Some(client) = OperatorSigningClient::for_action(
+                &eth_sender,
+                &contracts_config,
+                &eth_client_config,
+                action_type,
+            )
+            .context("OperatorSigningClient::for_action()")?
+            else {
+                continue;
+            };
+            let nonce = client
+                .pending_nonce("eth_sender")
+                .await
+                .context("pending_nonce()")?;
+            eth_tx_aggregator_actor = eth_tx_aggregator_actor.with_dedicated_sender(
+                action_type,
+                client.sender_account(),
+                nonce.as_u64(),
+            );
+        }
         task_futures.push(tokio::spawn(
             eth_tx_aggregator_actor.run(eth_sender_pool, stop_receiver.clone()),
         ));
@@ -619,15 +679,31 @@ pub async fn initialize_components(
             .clone()
             .context("eth_sender_config")?;
         let eth_client =
-            PKSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config);
-        let eth_tx_manager_actor = EthTxManager::new(
-            eth_sender.sender,
+            OperatorSigningClient::from_config(&eth_sender, &contracts_config, &eth_client_config)
+                .context("OperatorSigningClient::from_config()")?;
+        eth_client.check_signer().await?;
+        let mut eth_tx_manager_actor = EthTxManager::new(
+            eth_sender.sender.clone(),
             gas_adjuster
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?,
             Arc::new(eth_client),
         );
+        for action_type in EthTxAggregator::DEDICATED_SENDER_ACTIONS {
+            let client = OperatorSigningClient::for_action(
+                &eth_sender,
+                &contracts_config,
+                &eth_client_config,
+                action_type,
+            )
+            .context("OperatorSigningClient::for_action()")?;
+            if let Some(client) = client {
+                client.check_signer().await?;
+                eth_tx_manager_actor = eth_tx_manager_actor.with_dedicated_sender(Arc::new(client));
+            }
+        }
+        healthchecks.push(Box::new(eth_tx_manager_actor.health_check()));
         task_futures.extend([tokio::spawn(
             eth_tx_manager_actor.run(eth_manager_pool, stop_receiver.clone()),
         )]);
@@ -636,6 +712,52 @@ pub async fn initialize_components(
         tracing::info!("initialized ETH-TxManager in {elapsed:?}");
     }
 
+    if components.contains(&Component::WithdrawalFinalizer) {
+        let started_at = Instant::now();
+        tracing::info!("initializing withdrawal finalizer");
+        let withdrawal_finalizer_pool = ConnectionPool::singleton(postgres_config.master_url()?)
+            .build()
+            .await
+            .context("failed to build withdrawal_finalizer_pool")?;
+        let withdrawal_finalizer_config = configs
+            .withdrawal_finalizer_config
+            .clone()
+            .context("withdrawal_finalizer_config")?;
+        let eth_sender = configs
+            .eth_sender_config
+            .clone()
+            .context("eth_sender_config")?;
+        let private_key = withdrawal_finalizer_config
+            .private_key()
+            .context("withdrawal finalizer private key is required")?;
+        // Sharing an account with `eth_sender` would lead to nonce conflicts.
+        let finalizer_address = PackedEthSignature::address_from_private_key(&private_key)
+            .context("failed restoring withdrawal finalizer address from private key")?;
+        let operator_addresses = eth_sender_operator_addresses(&eth_sender)?;
+        anyhow::ensure!(
+            !operator_addresses.contains(&finalizer_address),
+            "withdrawal finalizer account {finalizer_address:?} must differ from the operator accounts used by eth_sender"
+        );
+        let eth_client = PKSigningClient::from_private_key(
+            private_key,
+            &eth_sender,
+            &contracts_config,
+            &eth_client_config,
+        )
+        .context("PKSigningClient::from_private_key()")?;
+        let finalizer = WithdrawalFinalizer::new(
+            withdrawal_finalizer_config,
+            &contracts_config,
+            Arc::new(eth_client),
+        );
+        task_futures.push(tokio::spawn(
+            finalizer.run(withdrawal_finalizer_pool, stop_receiver.clone()),
+        ));
+        let elapsed = started_at.elapsed();
+        APP_METRICS.init_latency[&InitStage::WithdrawalFinalizer].set(elapsed);
+        tracing::info!("initialized withdrawal finalizer in {elapsed:?}");
+    }
+
     add_trees_to_task_futures(
         configs,
         &mut task_futures,
@@ -704,9 +826,34 @@ pub async fn initialize_components(
     Ok((task_futures, stop_sender, cb_receiver, health_check_handle))
 }
 
+/// Returns addresses of all accounts which can send L1 transactions in `eth_sender`
+/// (both the ones managed by the remote signer and the ones with configured private keys).
+fn eth_sender_operator_addresses(eth_sender: &ETHSenderConfig) -> anyhow::Result<Vec<Address>> {
+    let mut addresses = vec![];
+    if let Some(remote_signer) = &eth_sender.remote_signer {
+        addresses.push(remote_signer.operator_address);
+        addresses.extend(remote_signer.prove_operator_address);
+        addresses.extend(remote_signer.execute_operator_address);
+    }
+
+    let sender = &eth_sender.sender;
+    let private_keys = [
+        sender.private_key(),
+        sender.prove_private_key(),
+        sender.execute_private_key(),
+    ];
+    for private_key in private_keys.into_iter().flatten() {
+        let address = PackedEthSignature::address_from_private_key(&private_key)
+            .context("failed restoring operator address from private key")?;
+        addresses.push(address);
+    }
+    Ok(addresses)
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync + 'static>(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    healthchecks: &mut Vec<Box<dyn CheckHealth>>,
     postgres_config: &PostgresConfig,
     contracts_config: &ContractsConfig,
     state_keeper_config: StateKeeperConfig,
@@ -715,8 +862,9 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
     mempool_config: &MempoolConfig,
     gas_adjuster: Arc<E>,
     object_store: Arc<dyn ObjectStore>,
+    sealing_pause_receiver: watch::Receiver<bool>,
     stop_receiver: watch::Receiver<bool>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<MempoolGuard> {
     let pool_builder = ConnectionPool::singleton(postgres_config.master_url()?);
     let state_keeper_pool = pool_builder
         .build()
@@ -760,7 +908,8 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         object_store,
         stop_receiver.clone(),
     )
-    .await;
+    .await
+    .with_sealing_pause(sealing_pause_receiver);
 
     task_futures.push(tokio::spawn(
         state_keeper.run_fee_address_migration(state_keeper_pool),
@@ -771,11 +920,13 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         .build()
         .await
         .context("failed to build mempool_fetcher_pool")?;
-    let mempool_fetcher = MempoolFetcher::new(mempool, batch_fee_input_provider, mempool_config);
+    let mempool_fetcher =
+        MempoolFetcher::new(mempool.clone(), batch_fee_input_provider, mempool_config);
+    healthchecks.push(Box::new(mempool_fetcher.health_check()));
     let mempool_fetcher_handle =
         tokio::spawn(mempool_fetcher.run(mempool_fetcher_pool, stop_receiver));
     task_futures.push(mempool_fetcher_handle);
-    Ok(())
+    Ok(mempool)
 }
 
 async fn add_trees_to_task_futures(
@@ -1052,11 +1203,12 @@ async fn build_tx_sender(
     let max_concurrency = web3_json_config.vm_concurrency_limit();
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
 
-    let batch_fee_input_provider = ApiFeeInputProvider::new(
+    let main_node_fee_input_provider = MainNodeFeeInputProvider::new(
         l1_gas_price_provider,
         FeeModelConfig::from_state_keeper_config(state_keeper_config),
-        replica_pool,
     );
+    let batch_fee_input_provider =
+        ApiFeeInputProvider::new(Arc::new(main_node_fee_input_provider), replica_pool);
 
     let tx_sender = tx_sender_builder
         .build(
@@ -1113,6 +1265,7 @@ async fn run_http_api<G: L1GasPriceProvider>(
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
+            .with_access_control(AccessControl::new(&api_config.web3_json_rpc))
             .with_tx_sender(tx_sender, vm_barrier)
             .enable_api_namespaces(namespaces);
     api_builder.build(stop_receiver).await
@@ -1164,6 +1317,7 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
             )
             .with_polling_interval(api_config.web3_json_rpc.pubsub_interval())
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
+            .with_access_control(AccessControl::new(&api_config.web3_json_rpc))
             .with_tx_sender(tx_sender, vm_barrier)
             .enable_api_namespaces(namespaces);
 
@@ -1171,9 +1325,11 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
 }
 
 async fn circuit_breakers_for_components(
+    configs: &TempConfigStore,
     components: &[Component],
     postgres_config: &PostgresConfig,
     circuit_breaker_config: &CircuitBreakerConfig,
+    gas_adjuster: &mut GasAdjusterSingleton,
 ) -> anyhow::Result<Vec<Box<dyn CircuitBreaker>>> {
     let mut circuit_breakers: Vec<Box<dyn CircuitBreaker>> = Vec::new();
 
@@ -1202,5 +1358,81 @@ async fn circuit_breakers_for_components(
             replication_lag_limit_sec: circuit_breaker_config.replication_lag_limit_sec,
         }));
     }
+
+    if let Some(min_balance_gwei) = circuit_breaker_config.min_operator_balance_gwei {
+        if components.contains(&Component::EthTxManager) {
+            let eth_sender_config = configs
+                .eth_sender_config
+                .as_ref()
+                .context("eth_sender_config")?;
+            let contracts_config = configs
+                .contracts_config
+                .as_ref()
+                .context("contracts_config")?;
+            let eth_client_config = configs
+                .eth_client_config
+                .as_ref()
+                .context("eth_client_config")?;
+            let eth_client = OperatorSigningClient::from_config(
+                eth_sender_config,
+                contracts_config,
+                eth_client_config,
+            )
+            .context("OperatorSigningClient::from_config()")?;
+            let min_balance = U256::from(min_balance_gwei) * U256::exp10(9);
+            circuit_breakers.push(Box::new(OperatorBalanceChecker {
+                operator_address: eth_client.sender_account(),
+                eth_client: Arc::new(eth_client),
+                min_balance,
+            }));
+            for action_type in EthTxAggregator::DEDICATED_SENDER_ACTIONS {
+                let client = OperatorSigningClient::for_action(
+                    eth_sender_config,
+                    contracts_config,
+                    eth_client_config,
+                    action_type,
+                )
+                .context("OperatorSigningClient::for_action()")?;
+                if let Some(client) = client {
+                    circuit_breakers.push(Box::new(OperatorBalanceChecker {
+                        operator_address: client.sender_account(),
+                        eth_client: Arc::new(client),
+                        min_balance,
+                    }));
+                }
+            }
+        }
+    }
+
+    if components.contains(&Component::StateKeeper) {
+        let max_unproved_l1_batches = circuit_breaker_config.max_unproved_l1_batches;
+        let max_unproved_l1_batch_age_sec = circuit_breaker_config.max_unproved_l1_batch_age_sec;
+        if max_unproved_l1_batches.is_some() || max_unproved_l1_batch_age_sec.is_some() {
+            let pool = ConnectionPool::singleton(postgres_config.replica_url()?)
+                .build()
+                .await
+                .context("failed to build a connection pool")?;
+            let prover_pool = ConnectionPool::singleton(postgres_config.prover_url()?)
+                .build()
+                .await
+                .context("failed to build a prover connection pool")?;
+            circuit_breakers.push(Box::new(ProverLagChecker {
+                pool,
+                prover_pool,
+                max_unproved_l1_batches,
+                max_unproved_l1_batch_age_sec,
+            }));
+        }
+        if let Some(max_l1_gas_price) = circuit_breaker_config.max_l1_gas_price {
+            let gas_adjuster = gas_adjuster
+                .get_or_init()
+                .await
+                .context("gas_adjuster.get_or_init()")?;
+            circuit_breakers.push(Box::new(L1GasPriceChecker::new(
+                gas_adjuster,
+                max_l1_gas_price,
+            )));
+        }
+    }
     Ok(circuit_breakers)
 }
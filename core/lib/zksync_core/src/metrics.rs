@@ -19,6 +19,7 @@ pub(crate) enum InitStage {
     Tree,
     BasicWitnessInputProducer,
     Consensus,
+    WithdrawalFinalizer,
 }
 
 impl fmt::Display for InitStage {
@@ -34,6 +35,7 @@ impl fmt::Display for InitStage {
             Self::Tree => formatter.write_str("tree"),
             Self::BasicWitnessInputProducer => formatter.write_str("basic_witness_input_producer"),
             Self::Consensus => formatter.write_str("consensus"),
+            Self::WithdrawalFinalizer => formatter.write_str("withdrawal_finalizer"),
         }
     }
 }
@@ -141,6 +143,10 @@ pub(crate) struct AppMetrics {
     pub processed_l1_txs: Family<TxStage, Counter>,
 
     pub blocks_state_block_eth_stage_latency: Family<L1StageLatencyLabel, Gauge<u64>>,
+    /// Age (in seconds) of the oldest priority operation not executed on L1.
+    pub oldest_unexecuted_priority_op_age: Gauge<u64>,
+    /// Number of priority operations not executed on L1 with the expiration timestamp in the past.
+    pub expired_unexecuted_priority_ops: Gauge<usize>,
 }
 
 #[vise::register]
@@ -0,0 +1,383 @@
+//! Authentication of proof data handler clients.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{HeaderMap, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use http_body::{LengthLimitError, Limited};
+use zksync_config::configs::{
+    proof_data_handler::ProofDataHandlerAuthMode, ProofDataHandlerConfig, Secret,
+};
+use zksync_prover_interface::auth::{
+    secrets_eq, verify_hmac_signature, AUTHORIZATION_HEADER, CLIENT_ID_HEADER, SIGNATURE_HEADER,
+    TIMESTAMP_HEADER,
+};
+
+use super::metrics::{AuthFailureReason, METRICS};
+
+/// Maximum size of a request body buffered to check its HMAC signature. Matches the default body limit
+/// of the `Json` extractor used by request handlers.
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Identity of the client that sent a request, inserted into request extensions by [`authenticate()`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ClientIdentity(Option<String>);
+
+impl ClientIdentity {
+    /// Returns the client ID, or `None` if authentication is disabled.
+    pub fn id(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    /// Returns the label used for this client in metrics.
+    pub fn metrics_label(&self) -> String {
+        self.0.clone().unwrap_or_else(|| "anonymous".to_owned())
+    }
+}
+
+/// Checks client credentials. Intentionally doesn't implement `Debug` since it contains client secrets.
+pub(crate) struct Authenticator {
+    mode: ProofDataHandlerAuthMode,
+    /// Client secrets keyed by client ID.
+    secrets: HashMap<String, String>,
+    max_clock_skew: Duration,
+}
+
+impl Authenticator {
+    pub fn new(config: &ProofDataHandlerConfig) -> anyhow::Result<Self> {
+        let api_clients = config.api_clients.expose();
+        let mut secrets = HashMap::with_capacity(api_clients.len());
+        for (i, client) in api_clients.iter().enumerate() {
+            let (id, secret) = client.split_once(':').with_context(|| {
+                format!("api_clients[{i}] is not in the `<client_id>:<secret>` format")
+            })?;
+            anyhow::ensure!(
+                !id.is_empty() && !secret.is_empty(),
+                "api_clients[{i}] has an empty client ID or secret"
+            );
+            let prev = secrets.insert(id.to_owned(), secret.to_owned());
+            anyhow::ensure!(
+                prev.is_none(),
+                "client `{id}` is specified in api_clients several times"
+            );
+        }
+        if config.auth_mode != ProofDataHandlerAuthMode::Disabled {
+            anyhow::ensure!(
+                !secrets.is_empty(),
+                "api_clients must not be empty if proof data handler authentication is enabled"
+            );
+        }
+        Ok(Self {
+            mode: config.auth_mode,
+            secrets,
+            max_clock_skew: config.hmac_max_clock_skew(),
+        })
+    }
+
+    fn check_bearer_token(&self, headers: &HeaderMap) -> Result<ClientIdentity, AuthFailureReason> {
+        let header = headers
+            .get(AUTHORIZATION_HEADER)
+            .ok_or(AuthFailureReason::MissingCredentials)?;
+        let token = header
+            .to_str()
+            .ok()
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or(AuthFailureReason::MalformedRequest)?;
+        // Check all secrets so that the response time doesn't depend on the matching client.
+        let mut matching_client = None;
+        for (id, secret) in &self.secrets {
+            if secrets_eq(secret.as_bytes(), token.as_bytes()) {
+                matching_client = Some(id.clone());
+            }
+        }
+        matching_client
+            .map(|id| ClientIdentity(Some(id)))
+            .ok_or(AuthFailureReason::UnknownClient)
+    }
+
+    /// Checks HMAC credentials that don't depend on the request body, i.e., that the client is known
+    /// and the request timestamp is fresh.
+    fn check_hmac_headers<'a>(
+        &'a self,
+        headers: &'a HeaderMap,
+        now: SystemTime,
+    ) -> Result<HmacCredentials<'a>, AuthFailureReason> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .ok_or(AuthFailureReason::MissingCredentials)?
+                .to_str()
+                .map_err(|_| AuthFailureReason::MalformedRequest)
+        };
+        let client_id = header(CLIENT_ID_HEADER)?;
+        let timestamp: u64 = header(TIMESTAMP_HEADER)?
+            .parse()
+            .map_err(|_| AuthFailureReason::MalformedRequest)?;
+        let signature = header(SIGNATURE_HEADER)?;
+
+        let secret = self
+            .secrets
+            .get(client_id)
+            .ok_or(AuthFailureReason::UnknownClient)?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .expect("incorrect system time")
+            .as_secs();
+        if now.abs_diff(timestamp) > self.max_clock_skew.as_secs() {
+            return Err(AuthFailureReason::StaleTimestamp);
+        }
+        Ok(HmacCredentials {
+            client_id,
+            secret,
+            timestamp,
+            signature,
+        })
+    }
+
+    #[cfg(test)]
+    fn check_hmac(
+        &self,
+        headers: &HeaderMap,
+        method: &str,
+        path: &str,
+        body: &[u8],
+        now: SystemTime,
+    ) -> Result<ClientIdentity, AuthFailureReason> {
+        self.check_hmac_headers(headers, now)?
+            .verify(method, path, body)
+    }
+}
+
+/// HMAC credentials of a request that passed [`Authenticator::check_hmac_headers()`]. Intentionally doesn't
+/// implement `Debug` since it contains the client secret.
+struct HmacCredentials<'a> {
+    client_id: &'a str,
+    secret: &'a str,
+    timestamp: u64,
+    signature: &'a str,
+}
+
+impl HmacCredentials<'_> {
+    fn verify(
+        self,
+        method: &str,
+        path: &str,
+        body: &[u8],
+    ) -> Result<ClientIdentity, AuthFailureReason> {
+        let is_valid = verify_hmac_signature(
+            self.secret.as_bytes(),
+            self.timestamp,
+            method,
+            path,
+            body,
+            self.signature,
+        );
+        if !is_valid {
+            return Err(AuthFailureReason::InvalidSignature);
+        }
+        Ok(ClientIdentity(Some(self.client_id.to_owned())))
+    }
+}
+
+/// Reads the request body, failing with 413 if it exceeds [`MAX_BODY_SIZE`].
+async fn read_body(body: Body) -> Result<Bytes, (StatusCode, &'static str)> {
+    hyper::body::to_bytes(Limited::new(body, MAX_BODY_SIZE))
+        .await
+        .map_err(|err| {
+            if err.downcast_ref::<LengthLimitError>().is_some() {
+                (StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large")
+            } else {
+                tracing::warn!("Failed reading request body: {err}");
+                (StatusCode::BAD_REQUEST, "Failed reading request body")
+            }
+        })
+}
+
+/// Axum middleware checking request credentials according to the configured [`ProofDataHandlerAuthMode`].
+pub(crate) async fn authenticate(
+    State(authenticator): State<Arc<Authenticator>>,
+    mut request: Request<Body>,
+    next: Next<Body>,
+) -> Response {
+    let identity = match authenticator.mode {
+        ProofDataHandlerAuthMode::Disabled => Ok(ClientIdentity::default()),
+        ProofDataHandlerAuthMode::BearerToken => {
+            authenticator.check_bearer_token(request.headers())
+        }
+        ProofDataHandlerAuthMode::Hmac => {
+            // The signature covers the body, so we need to buffer it and then reassemble the request.
+            // The body is only read after checking the client and timestamp, and its size is limited,
+            // so that unauthenticated clients cannot make the server buffer large bodies.
+            let (parts, body) = request.into_parts();
+            match authenticator.check_hmac_headers(&parts.headers, SystemTime::now()) {
+                Ok(credentials) => {
+                    let body = match read_body(body).await {
+                        Ok(body) => body,
+                        Err(response) => return response.into_response(),
+                    };
+                    let identity =
+                        credentials.verify(parts.method.as_str(), parts.uri.path(), &body);
+                    request = Request::from_parts(parts, Body::from(body));
+                    identity
+                }
+                Err(reason) => {
+                    request = Request::from_parts(parts, body);
+                    Err(reason)
+                }
+            }
+        }
+    };
+
+    match identity {
+        Ok(identity) => {
+            request.extensions_mut().insert(identity);
+            next.run(request).await
+        }
+        Err(reason) => {
+            tracing::warn!(
+                "Rejected {} request to {}: {reason:?}",
+                request.method(),
+                request.uri().path()
+            );
+            METRICS.auth_failures[&reason].inc();
+            (StatusCode::UNAUTHORIZED, "Unauthorized").into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::proof_data_handler::ProtocolVersionLoadingMode;
+    use zksync_prover_interface::auth::hmac_signature;
+
+    use super::*;
+
+    fn config(auth_mode: ProofDataHandlerAuthMode) -> ProofDataHandlerConfig {
+        ProofDataHandlerConfig {
+            http_port: 3320,
+            proof_generation_timeout_in_secs: 18000,
+            protocol_version_loading_mode: ProtocolVersionLoadingMode::FromEnvVar,
+            fri_protocol_version_id: 2,
+            auth_mode,
+            api_clients: vec!["a:secret-a".to_owned(), "b:secret-b".to_owned()].into(),
+            hmac_max_clock_skew_secs: Some(30),
+            tls_cert_path: None,
+            tls_key_path: None,
+            scheduler_vk_paths: vec![],
+        }
+    }
+
+    #[test]
+    fn parsing_clients() {
+        let mut config = config(ProofDataHandlerAuthMode::BearerToken);
+        Authenticator::new(&config).unwrap();
+
+        let mut api_clients = config.api_clients.expose().clone();
+        api_clients.push("a:other".to_owned());
+        config.api_clients = api_clients.into();
+        let err = Authenticator::new(&config).unwrap_err().to_string();
+        assert!(err.contains("several times"), "{err}");
+
+        config.api_clients = vec!["secret".to_owned()].into();
+        let err = Authenticator::new(&config).unwrap_err().to_string();
+        assert!(err.contains("format"), "{err}");
+
+        config.api_clients = Secret::default();
+        Authenticator::new(&config).unwrap_err();
+        config.auth_mode = ProofDataHandlerAuthMode::Disabled;
+        Authenticator::new(&config).unwrap();
+    }
+
+    #[test]
+    fn bearer_token_authentication() {
+        let authenticator =
+            Authenticator::new(&config(ProofDataHandlerAuthMode::BearerToken)).unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            authenticator.check_bearer_token(&headers),
+            Err(AuthFailureReason::MissingCredentials)
+        );
+
+        headers.insert(AUTHORIZATION_HEADER, "Bearer secret-b".parse().unwrap());
+        let identity = authenticator.check_bearer_token(&headers).unwrap();
+        assert_eq!(identity.id(), Some("b"));
+
+        headers.insert(AUTHORIZATION_HEADER, "Bearer secret-c".parse().unwrap());
+        assert_eq!(
+            authenticator.check_bearer_token(&headers),
+            Err(AuthFailureReason::UnknownClient)
+        );
+        headers.insert(AUTHORIZATION_HEADER, "secret-b".parse().unwrap());
+        assert_eq!(
+            authenticator.check_bearer_token(&headers),
+            Err(AuthFailureReason::MalformedRequest)
+        );
+    }
+
+    #[tokio::test]
+    async fn limiting_request_body() {
+        let body = read_body(Body::from(vec![0_u8; MAX_BODY_SIZE]))
+            .await
+            .unwrap();
+        assert_eq!(body.len(), MAX_BODY_SIZE);
+
+        let (status, _) = read_body(Body::from(vec![0_u8; MAX_BODY_SIZE + 1]))
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[test]
+    fn hmac_authentication() {
+        let authenticator = Authenticator::new(&config(ProofDataHandlerAuthMode::Hmac)).unwrap();
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let body = b"{}";
+        let path = "/proof_generation_data";
+        let signature = hmac_signature(b"secret-a", 1_700_000_010, "POST", path, body);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CLIENT_ID_HEADER, "a".parse().unwrap());
+        headers.insert(TIMESTAMP_HEADER, "1700000010".parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+        let identity = authenticator
+            .check_hmac(&headers, "POST", path, body, now)
+            .unwrap();
+        assert_eq!(identity.id(), Some("a"));
+
+        assert_eq!(
+            authenticator.check_hmac(&headers, "POST", path, b"{} ", now),
+            Err(AuthFailureReason::InvalidSignature)
+        );
+        let later = now + Duration::from_secs(60);
+        assert_eq!(
+            authenticator.check_hmac(&headers, "POST", path, body, later),
+            Err(AuthFailureReason::StaleTimestamp)
+        );
+
+        headers.insert(CLIENT_ID_HEADER, "b".parse().unwrap());
+        assert_eq!(
+            authenticator.check_hmac(&headers, "POST", path, body, now),
+            Err(AuthFailureReason::InvalidSignature)
+        );
+        headers.insert(CLIENT_ID_HEADER, "c".parse().unwrap());
+        assert_eq!(
+            authenticator.check_hmac(&headers, "POST", path, body, now),
+            Err(AuthFailureReason::UnknownClient)
+        );
+        headers.remove(SIGNATURE_HEADER);
+        assert_eq!(
+            authenticator.check_hmac(&headers, "POST", path, body, now),
+            Err(AuthFailureReason::MissingCredentials)
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Metrics for the proof data handler.
+
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, LabeledFamily, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "reason", rename_all = "snake_case")]
+pub(super) enum AuthFailureReason {
+    MissingCredentials,
+    UnknownClient,
+    InvalidSignature,
+    StaleTimestamp,
+    MalformedRequest,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_proof_data_handler")]
+pub(super) struct ProofDataHandlerMetrics {
+    /// Number of rejected requests grouped by the rejection reason.
+    pub auth_failures: Family<AuthFailureReason, Counter>,
+    /// Number of L1 batches handed out for proving, grouped by the client.
+    #[metrics(labels = ["client"])]
+    pub batches_handed_out: LabeledFamily<String, Counter>,
+    /// Number of submitted proofs (incl. skipped proofs), grouped by the client.
+    #[metrics(labels = ["client"])]
+    pub proofs_submitted: LabeledFamily<String, Counter>,
+    /// Number of rejected proofs grouped by the rejection reason.
+    #[metrics(labels = ["reason"])]
+    pub proofs_rejected: LabeledFamily<&'static str, Counter>,
+    /// Number of proofs submitted by a client other than the one the L1 batch was handed out to.
+    #[metrics(labels = ["client"])]
+    pub foreign_proofs_submitted: LabeledFamily<String, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<ProofDataHandlerMetrics> = vise::Global::new();
@@ -1,13 +1,14 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use anyhow::Context as _;
-use axum::{extract::Path, routing::post, Json, Router};
+use axum::{extract::Path, middleware, routing::post, Extension, Json, Router};
 use tokio::sync::watch;
 use zksync_config::{
     configs::{proof_data_handler::ProtocolVersionLoadingMode, ProofDataHandlerConfig},
     ContractsConfig,
 };
 use zksync_dal::ConnectionPool;
+use zksync_l1_contract_interface::i_verifier::L1BatchProofVerifier;
 use zksync_object_store::ObjectStore;
 use zksync_prover_interface::api::{ProofGenerationDataRequest, SubmitProofRequest};
 use zksync_types::{
@@ -15,9 +16,17 @@ use zksync_types::{
     H256,
 };
 
-use crate::proof_data_handler::request_processor::RequestProcessor;
+use crate::proof_data_handler::{
+    auth::{authenticate, Authenticator, ClientIdentity},
+    request_processor::{ProofVerifier, RequestProcessor},
+};
 
+mod auth;
+mod metrics;
 mod request_processor;
+#[cfg(test)]
+mod tests;
+mod tls;
 
 fn fri_l1_verifier_config(contracts_config: &ContractsConfig) -> L1VerifierConfig {
     L1VerifierConfig {
@@ -31,6 +40,69 @@ fn fri_l1_verifier_config(contracts_config: &ContractsConfig) -> L1VerifierConfi
     }
 }
 
+/// Loads verifiers for all scheduler verification keys specified in the config.
+fn load_proof_verifiers(
+    config: &ProofDataHandlerConfig,
+) -> anyhow::Result<Vec<Arc<dyn ProofVerifier>>> {
+    anyhow::ensure!(
+        !config.scheduler_vk_paths.is_empty(),
+        "at least one scheduler verification key must be specified in `scheduler_vk_paths`"
+    );
+    config
+        .scheduler_vk_paths
+        .iter()
+        .map(|vk_path| {
+            let vk = std::fs::read_to_string(vk_path).with_context(|| {
+                format!("failed reading scheduler verification key from `{vk_path}`")
+            })?;
+            let verifier = L1BatchProofVerifier::from_json(&vk).with_context(|| {
+                format!("failed parsing scheduler verification key from `{vk_path}`")
+            })?;
+            tracing::info!(
+                "Loaded scheduler verification key with hash {:?} from `{vk_path}`",
+                verifier.vk_hash()
+            );
+            Ok(Arc::new(verifier) as Arc<dyn ProofVerifier>)
+        })
+        .collect()
+}
+
+/// Checks that the scheduler verification key for the current protocol version is loaded. Otherwise,
+/// no proofs for new batches could be accepted.
+async fn check_current_vk_hash(
+    pool: &ConnectionPool,
+    l1_verifier_config: Option<&L1VerifierConfig>,
+    proof_verifiers: &[Arc<dyn ProofVerifier>],
+) -> anyhow::Result<()> {
+    let expected_vk_hash = if let Some(config) = l1_verifier_config {
+        config.recursion_scheduler_level_vk_hash
+    } else {
+        let mut storage = pool.access_storage_tagged("proof_data_handler").await?;
+        let Some(version_id) = storage.protocol_versions_dal().last_version_id().await else {
+            tracing::warn!(
+                "No protocol versions in the DB; skipping scheduler verification key check"
+            );
+            return Ok(());
+        };
+        storage
+            .protocol_versions_dal()
+            .l1_verifier_config_for_version(version_id)
+            .await
+            .with_context(|| format!("protocol version {version_id:?} disappeared from the DB"))?
+            .recursion_scheduler_level_vk_hash
+    };
+    let loaded_vk_hashes: Vec<_> = proof_verifiers
+        .iter()
+        .map(|verifier| verifier.vk_hash())
+        .collect();
+    anyhow::ensure!(
+        loaded_vk_hashes.contains(&expected_vk_hash),
+        "scheduler verification key with hash {expected_vk_hash:?} for the current protocol version \
+         is not among configured keys (hashes: {loaded_vk_hashes:?})"
+    );
+    Ok(())
+}
+
 pub(crate) async fn run_server(
     config: ProofDataHandlerConfig,
     contracts_config: ContractsConfig,
@@ -44,8 +116,22 @@ pub(crate) async fn run_server(
         ProtocolVersionLoadingMode::FromDb => None,
         ProtocolVersionLoadingMode::FromEnvVar => Some(fri_l1_verifier_config(&contracts_config)),
     };
-    let get_proof_gen_processor =
-        RequestProcessor::new(blob_store, pool, config, l1_verifier_config);
+    let authenticator =
+        Arc::new(Authenticator::new(&config).context("invalid proof data handler auth config")?);
+    let tls_config = match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => Some(tls::load_server_config(cert_path, key_path)?),
+        (None, None) => None,
+        _ => anyhow::bail!("`tls_cert_path` and `tls_key_path` must be specified together"),
+    };
+    let proof_verifiers = load_proof_verifiers(&config)?;
+    check_current_vk_hash(&pool, l1_verifier_config.as_ref(), &proof_verifiers).await?;
+    let get_proof_gen_processor = RequestProcessor::new(
+        blob_store,
+        pool,
+        config,
+        l1_verifier_config,
+        proof_verifiers,
+    );
     let submit_proof_processor = get_proof_gen_processor.clone();
     let app = Router::new()
         .route(
@@ -53,9 +139,10 @@ pub(crate) async fn run_server(
             post(
                 // we use post method because the returned data is not idempotent,
                 // i.e we return different result on each call.
-                move |payload: Json<ProofGenerationDataRequest>| async move {
+                move |Extension(client): Extension<ClientIdentity>,
+                      payload: Json<ProofGenerationDataRequest>| async move {
                     get_proof_gen_processor
-                        .get_proof_generation_data(payload)
+                        .get_proof_generation_data(&client, payload)
                         .await
                 },
             ),
@@ -63,24 +150,39 @@ pub(crate) async fn run_server(
         .route(
             "/submit_proof/:l1_batch_number",
             post(
-                move |l1_batch_number: Path<u32>, payload: Json<SubmitProofRequest>| async move {
+                move |Extension(client): Extension<ClientIdentity>,
+                      l1_batch_number: Path<u32>,
+                      payload: Json<SubmitProofRequest>| async move {
                     submit_proof_processor
-                        .submit_proof(l1_batch_number, payload)
+                        .submit_proof(&client, l1_batch_number, payload)
                         .await
                 },
             ),
-        );
+        )
+        .route_layer(middleware::from_fn_with_state(authenticator, authenticate));
 
-    axum::Server::bind(&bind_address)
-        .serve(app.into_make_service())
-        .with_graceful_shutdown(async move {
-            if stop_receiver.changed().await.is_err() {
-                tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
-            }
-            tracing::info!("Stop signal received, proof data handler server is shutting down");
-        })
-        .await
-        .context("Proof data handler server failed")?;
+    let tls_stop_receiver = stop_receiver.clone();
+    let graceful_shutdown = async move {
+        if stop_receiver.changed().await.is_err() {
+            tracing::warn!("Stop signal sender for proof data handler server was dropped without sending a signal");
+        }
+        tracing::info!("Stop signal received, proof data handler server is shutting down");
+    };
+    if let Some(tls_config) = tls_config {
+        tracing::info!("Serving proof data handler API over TLS");
+        let incoming =
+            tls::incoming_connections(bind_address, tls_config, tls_stop_receiver).await?;
+        axum::Server::builder(hyper::server::accept::from_stream(incoming))
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(graceful_shutdown)
+            .await
+    } else {
+        axum::Server::bind(&bind_address)
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(graceful_shutdown)
+            .await
+    }
+    .context("Proof data handler server failed")?;
     tracing::info!("Proof data handler server shut down");
     Ok(())
 }
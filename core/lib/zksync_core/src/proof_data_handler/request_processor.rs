@@ -1,4 +1,4 @@
-use std::{convert::TryFrom, sync::Arc};
+use std::{collections::HashMap, convert::TryFrom, fmt, sync::Arc};
 
 use axum::{
     extract::Path,
@@ -9,31 +9,75 @@ use axum::{
 use zksync_config::configs::{
     proof_data_handler::ProtocolVersionLoadingMode, ProofDataHandlerConfig,
 };
-use zksync_dal::{ConnectionPool, SqlxError};
+use zksync_dal::{ConnectionPool, SqlxError, StorageProcessor};
+use zksync_l1_contract_interface::i_verifier::{
+    batch_proof_public_input, L1BatchProofVerifier, ProofVerificationError,
+};
 use zksync_object_store::{ObjectStore, ObjectStoreError};
-use zksync_prover_interface::api::{
-    ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
-    SubmitProofRequest, SubmitProofResponse,
+use zksync_prover_interface::{
+    api::{
+        ProofGenerationData, ProofGenerationDataRequest, ProofGenerationDataResponse,
+        ProofRejectionReason, SubmitProofRequest, SubmitProofResponse,
+    },
+    outputs::L1BatchProofForL1,
 };
 use zksync_types::{
-    commitment::serialize_commitments,
+    commitment::{serialize_commitments, L1BatchWithMetadata},
     protocol_version::{FriProtocolVersionId, L1VerifierConfig},
     web3::signing::keccak256,
-    L1BatchNumber, H256,
+    L1BatchNumber, ProtocolVersionId, H256, U256,
 };
 use zksync_utils::u256_to_h256;
 
+use super::{auth::ClientIdentity, metrics::METRICS};
+
+/// Verifier of submitted proofs. Abstracted so that it can be mocked in tests.
+pub(crate) trait ProofVerifier: 'static + fmt::Debug + Send + Sync {
+    /// Returns the hash of the scheduler verification key used by this verifier.
+    fn vk_hash(&self) -> H256;
+
+    fn verify(
+        &self,
+        proof: &L1BatchProofForL1,
+        expected_public_input: U256,
+    ) -> Result<(), ProofVerificationError>;
+}
+
+impl ProofVerifier for L1BatchProofVerifier {
+    fn vk_hash(&self) -> H256 {
+        L1BatchProofVerifier::vk_hash(self)
+    }
+
+    fn verify(
+        &self,
+        proof: &L1BatchProofForL1,
+        expected_public_input: U256,
+    ) -> Result<(), ProofVerificationError> {
+        L1BatchProofVerifier::verify(self, proof, expected_public_input)
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct RequestProcessor {
     blob_store: Arc<dyn ObjectStore>,
     pool: ConnectionPool,
     config: ProofDataHandlerConfig,
     l1_verifier_config: Option<L1VerifierConfig>,
+    /// Verifiers for all configured scheduler verification keys, keyed by the key hash.
+    proof_verifiers: Arc<HashMap<H256, Arc<dyn ProofVerifier>>>,
 }
 
 pub(crate) enum RequestProcessorError {
     ObjectStore(ObjectStoreError),
     Sqlx(SqlxError),
+    Dal(anyhow::Error),
+    MissingL1BatchMetadata(L1BatchNumber),
+    MissingVerificationKey {
+        l1_batch_number: L1BatchNumber,
+        protocol_version: ProtocolVersionId,
+        vk_hash: H256,
+    },
+    ProofRejected(ProofRejectionReason),
 }
 
 impl IntoResponse for RequestProcessorError {
@@ -58,6 +102,43 @@ impl IntoResponse for RequestProcessorError {
                     ),
                 }
             }
+            RequestProcessorError::Dal(err) => {
+                tracing::error!("DAL error: {err:#}");
+                (
+                    StatusCode::BAD_GATEWAY,
+                    "Failed fetching/saving from db".to_owned(),
+                )
+            }
+            RequestProcessorError::MissingL1BatchMetadata(l1_batch_number) => (
+                StatusCode::NOT_FOUND,
+                format!("L1 batch #{l1_batch_number} doesn't exist or doesn't have metadata yet"),
+            ),
+            RequestProcessorError::MissingVerificationKey {
+                l1_batch_number,
+                protocol_version,
+                vk_hash,
+            } => {
+                tracing::error!(
+                    "Cannot verify proof for L1 batch #{l1_batch_number}: scheduler verification key \
+                     with hash {vk_hash:?} for protocol version {protocol_version:?} is not configured"
+                );
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    format!(
+                        "Proofs for protocol version {protocol_version:?} cannot be verified yet"
+                    ),
+                )
+            }
+            RequestProcessorError::ProofRejected(reason) => {
+                tracing::warn!("Rejected submitted proof: {reason}");
+                let label = match &reason {
+                    ProofRejectionReason::AuxOutputMismatch { .. } => "aux_output_mismatch",
+                    ProofRejectionReason::VerificationFailed(_) => "verification_failed",
+                };
+                METRICS.proofs_rejected[&label].inc();
+                let response = SubmitProofResponse::Rejected(reason);
+                return (StatusCode::UNPROCESSABLE_ENTITY, Json(response)).into_response();
+            }
         };
         (status_code, message).into_response()
     }
@@ -69,20 +150,30 @@ impl RequestProcessor {
         pool: ConnectionPool,
         config: ProofDataHandlerConfig,
         l1_verifier_config: Option<L1VerifierConfig>,
+        proof_verifiers: Vec<Arc<dyn ProofVerifier>>,
     ) -> Self {
+        let proof_verifiers = proof_verifiers
+            .into_iter()
+            .map(|verifier| (verifier.vk_hash(), verifier))
+            .collect();
         Self {
             blob_store,
             pool,
             config,
             l1_verifier_config,
+            proof_verifiers: Arc::new(proof_verifiers),
         }
     }
 
     pub(crate) async fn get_proof_generation_data(
         &self,
+        client: &ClientIdentity,
         request: Json<ProofGenerationDataRequest>,
     ) -> Result<Json<ProofGenerationDataResponse>, RequestProcessorError> {
-        tracing::info!("Received request for proof generation data: {:?}", request);
+        tracing::info!(
+            "Received request for proof generation data from client {client:?}: {:?}",
+            request
+        );
 
         let l1_batch_number_result = self
             .pool
@@ -90,7 +181,7 @@ impl RequestProcessor {
             .await
             .unwrap()
             .proof_generation_dal()
-            .get_next_block_to_be_proven(self.config.proof_generation_timeout())
+            .get_next_block_to_be_proven(self.config.proof_generation_timeout(), client.id())
             .await;
 
         let l1_batch_number = match l1_batch_number_result {
@@ -118,6 +209,8 @@ impl RequestProcessor {
             }
         };
 
+        METRICS.batches_handed_out[&client.metrics_label()].inc();
+        tracing::info!("Handed out L1 batch #{l1_batch_number} to client {client:?}");
         let proof_gen_data = ProofGenerationData {
             l1_batch_number,
             data: blob,
@@ -132,92 +225,59 @@ impl RequestProcessor {
 
     pub(crate) async fn submit_proof(
         &self,
+        client: &ClientIdentity,
         Path(l1_batch_number): Path<u32>,
         Json(payload): Json<SubmitProofRequest>,
     ) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
-        tracing::info!("Received proof for block number: {:?}", l1_batch_number);
+        tracing::info!(
+            "Received proof for block number {:?} from client {client:?}",
+            l1_batch_number
+        );
         let l1_batch_number = L1BatchNumber(l1_batch_number);
+        self.check_batch_client(client, l1_batch_number).await?;
         match payload {
             SubmitProofRequest::Proof(proof) => {
+                let mut storage = self.pool.access_storage().await.unwrap();
+                let l1_batch = Self::load_l1_batch_metadata(&mut storage, l1_batch_number).await?;
+                let post_boojum_version = l1_batch
+                    .header
+                    .protocol_version
+                    .filter(|version| !version.is_pre_boojum());
+
+                if let Some(protocol_version) = post_boojum_version {
+                    Self::check_aux_outputs(&proof, &l1_batch)?;
+                    let proof_verifier = self
+                        .proof_verifier_for_version(&mut storage, l1_batch_number, protocol_version)
+                        .await?;
+                    // The genesis batch is never proven, so the previous batch always exists for valid requests.
+                    let prev_l1_batch_number =
+                        l1_batch_number.0.checked_sub(1).map(L1BatchNumber).ok_or(
+                            RequestProcessorError::MissingL1BatchMetadata(l1_batch_number),
+                        )?;
+                    let prev_l1_batch =
+                        Self::load_l1_batch_metadata(&mut storage, prev_l1_batch_number).await?;
+                    let public_input = batch_proof_public_input(
+                        prev_l1_batch.metadata.commitment,
+                        l1_batch.metadata.commitment,
+                    );
+                    drop(storage);
+                    Self::verify_proof(proof_verifier, proof.clone(), public_input)
+                        .await
+                        .map_err(RequestProcessorError::ProofRejected)?;
+                    tracing::info!("Proof for L1 batch #{l1_batch_number} is verified");
+                }
+
                 let blob_url = self
                     .blob_store
                     .put(l1_batch_number, &*proof)
                     .await
                     .map_err(RequestProcessorError::ObjectStore)?;
-
-                let system_logs_hash_from_prover =
-                    H256::from_slice(&proof.aggregation_result_coords[0]);
-                let state_diff_hash_from_prover =
-                    H256::from_slice(&proof.aggregation_result_coords[1]);
-                let bootloader_heap_initial_content_from_prover =
-                    H256::from_slice(&proof.aggregation_result_coords[2]);
-                let events_queue_state_from_prover =
-                    H256::from_slice(&proof.aggregation_result_coords[3]);
-
-                let mut storage = self.pool.access_storage().await.unwrap();
-
-                let l1_batch = storage
-                    .blocks_dal()
-                    .get_l1_batch_metadata(l1_batch_number)
+                self.pool
+                    .access_storage()
                     .await
                     .unwrap()
-                    .expect("Proved block without metadata");
-
-                let is_pre_boojum = l1_batch
-                    .header
-                    .protocol_version
-                    .map(|v| v.is_pre_boojum())
-                    .unwrap_or(true);
-                if !is_pre_boojum {
-                    let events_queue_state = l1_batch
-                        .metadata
-                        .events_queue_commitment
-                        .expect("No events_queue_commitment");
-                    let bootloader_heap_initial_content = l1_batch
-                        .metadata
-                        .bootloader_initial_content_commitment
-                        .expect("No bootloader_initial_content_commitment");
-
-                    if events_queue_state != events_queue_state_from_prover
-                        || bootloader_heap_initial_content
-                            != bootloader_heap_initial_content_from_prover
-                    {
-                        let server_values = format!("events_queue_state = {events_queue_state}, bootloader_heap_initial_content = {bootloader_heap_initial_content}");
-                        let prover_values = format!("events_queue_state = {events_queue_state_from_prover}, bootloader_heap_initial_content = {bootloader_heap_initial_content_from_prover}");
-                        panic!(
-                            "Auxilary output doesn't match, server values: {} prover values: {}",
-                            server_values, prover_values
-                        );
-                    }
-                }
-
-                let system_logs = serialize_commitments(&l1_batch.header.system_logs);
-                let system_logs_hash = H256(keccak256(&system_logs));
-
-                if !is_pre_boojum {
-                    let state_diff_hash = l1_batch
-                        .header
-                        .system_logs
-                        .into_iter()
-                        .find(|elem| elem.0.key == u256_to_h256(2.into()))
-                        .expect("No state diff hash key")
-                        .0
-                        .value;
-
-                    if state_diff_hash != state_diff_hash_from_prover
-                        || system_logs_hash != system_logs_hash_from_prover
-                    {
-                        let server_values = format!("system_logs_hash = {system_logs_hash}, state_diff_hash = {state_diff_hash}");
-                        let prover_values = format!("system_logs_hash = {system_logs_hash_from_prover}, state_diff_hash = {state_diff_hash_from_prover}");
-                        panic!(
-                            "Auxilary output doesn't match, server values: {} prover values: {}",
-                            server_values, prover_values
-                        );
-                    }
-                }
-                storage
                     .proof_generation_dal()
-                    .save_proof_artifacts_metadata(l1_batch_number, &blob_url)
+                    .save_proof_artifacts_metadata(l1_batch_number, &blob_url, client.id())
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
             }
@@ -227,12 +287,145 @@ impl RequestProcessor {
                     .await
                     .unwrap()
                     .proof_generation_dal()
-                    .mark_proof_generation_job_as_skipped(l1_batch_number)
+                    .mark_proof_generation_job_as_skipped(l1_batch_number, client.id())
                     .await
                     .map_err(RequestProcessorError::Sqlx)?;
             }
         }
 
+        METRICS.proofs_submitted[&client.metrics_label()].inc();
         Ok(Json(SubmitProofResponse::Success))
     }
+
+    async fn load_l1_batch_metadata(
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<L1BatchWithMetadata, RequestProcessorError> {
+        storage
+            .blocks_dal()
+            .get_l1_batch_metadata(l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Dal)?
+            .ok_or(RequestProcessorError::MissingL1BatchMetadata(
+                l1_batch_number,
+            ))
+    }
+
+    /// Returns the verifier for the scheduler verification key of the specified protocol version.
+    async fn proof_verifier_for_version(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+        protocol_version: ProtocolVersionId,
+    ) -> Result<Arc<dyn ProofVerifier>, RequestProcessorError> {
+        let vk_hash = storage
+            .protocol_versions_dal()
+            .l1_verifier_config_for_version(protocol_version)
+            .await
+            .ok_or_else(|| {
+                RequestProcessorError::Dal(anyhow::anyhow!(
+                    "protocol version {protocol_version:?} of L1 batch #{l1_batch_number} is missing in the DB"
+                ))
+            })?
+            .recursion_scheduler_level_vk_hash;
+        self.proof_verifiers.get(&vk_hash).cloned().ok_or(
+            RequestProcessorError::MissingVerificationKey {
+                l1_batch_number,
+                protocol_version,
+                vk_hash,
+            },
+        )
+    }
+
+    /// Checks that auxiliary outputs of a post-boojum proof match the L1 batch metadata.
+    fn check_aux_outputs(
+        proof: &L1BatchProofForL1,
+        l1_batch: &L1BatchWithMetadata,
+    ) -> Result<(), RequestProcessorError> {
+        let missing_metadata =
+            || RequestProcessorError::MissingL1BatchMetadata(l1_batch.header.number);
+        let system_logs_hash_from_prover = H256::from_slice(&proof.aggregation_result_coords[0]);
+        let state_diff_hash_from_prover = H256::from_slice(&proof.aggregation_result_coords[1]);
+        let bootloader_heap_initial_content_from_prover =
+            H256::from_slice(&proof.aggregation_result_coords[2]);
+        let events_queue_state_from_prover = H256::from_slice(&proof.aggregation_result_coords[3]);
+
+        let events_queue_state = l1_batch
+            .metadata
+            .events_queue_commitment
+            .ok_or_else(missing_metadata)?;
+        let bootloader_heap_initial_content = l1_batch
+            .metadata
+            .bootloader_initial_content_commitment
+            .ok_or_else(missing_metadata)?;
+        if events_queue_state != events_queue_state_from_prover
+            || bootloader_heap_initial_content != bootloader_heap_initial_content_from_prover
+        {
+            let reason = ProofRejectionReason::AuxOutputMismatch {
+                server_values: format!("events_queue_state = {events_queue_state}, bootloader_heap_initial_content = {bootloader_heap_initial_content}"),
+                prover_values: format!("events_queue_state = {events_queue_state_from_prover}, bootloader_heap_initial_content = {bootloader_heap_initial_content_from_prover}"),
+            };
+            return Err(RequestProcessorError::ProofRejected(reason));
+        }
+
+        let system_logs = serialize_commitments(&l1_batch.header.system_logs);
+        let system_logs_hash = H256(keccak256(&system_logs));
+        let state_diff_hash = l1_batch
+            .header
+            .system_logs
+            .iter()
+            .find(|elem| elem.0.key == u256_to_h256(2.into()))
+            .ok_or_else(missing_metadata)?
+            .0
+            .value;
+        if state_diff_hash != state_diff_hash_from_prover
+            || system_logs_hash != system_logs_hash_from_prover
+        {
+            let reason = ProofRejectionReason::AuxOutputMismatch {
+                server_values: format!("system_logs_hash = {system_logs_hash}, state_diff_hash = {state_diff_hash}"),
+                prover_values: format!("system_logs_hash = {system_logs_hash_from_prover}, state_diff_hash = {state_diff_hash_from_prover}"),
+            };
+            return Err(RequestProcessorError::ProofRejected(reason));
+        }
+        Ok(())
+    }
+
+    /// Verifies a proof on a blocking thread; verification takes a noticeable amount of CPU time.
+    async fn verify_proof(
+        verifier: Arc<dyn ProofVerifier>,
+        proof: Box<L1BatchProofForL1>,
+        public_input: U256,
+    ) -> Result<(), ProofRejectionReason> {
+        // A malformed proof may cause the verifier to panic; such a proof is rejected.
+        tokio::task::spawn_blocking(move || verifier.verify(&proof, public_input))
+            .await
+            .map_err(|_| ProofRejectionReason::VerificationFailed("verifier panicked".to_owned()))?
+            .map_err(|err| ProofRejectionReason::VerificationFailed(err.to_string()))
+    }
+
+    /// Checks whether the proof is submitted by the client that the batch was handed out to. A mismatch
+    /// isn't an error (e.g., the batch may have been reassigned after a timeout), but it's worth reporting.
+    async fn check_batch_client(
+        &self,
+        client: &ClientIdentity,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<(), RequestProcessorError> {
+        let batch_client = self
+            .pool
+            .access_storage()
+            .await
+            .unwrap()
+            .proof_generation_dal()
+            .get_batch_client(l1_batch_number)
+            .await
+            .map_err(RequestProcessorError::Sqlx)?;
+        if batch_client.as_deref() != client.id() {
+            tracing::warn!(
+                "L1 batch #{l1_batch_number} was handed out to client {batch_client:?}, \
+                 but its proof is submitted by client {client:?}"
+            );
+            METRICS.foreign_proofs_submitted[&client.metrics_label()].inc();
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,387 @@
+//! Tests for proof submission.
+
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Json};
+use zkevm_test_harness_1_3_3::bellman::{
+    bn256::Fr, plonk::better_better_cs::proof::Proof, PrimeField,
+};
+use zksync_config::configs::{
+    proof_data_handler::{ProofDataHandlerAuthMode, ProtocolVersionLoadingMode},
+    ProofDataHandlerConfig,
+};
+use zksync_dal::ConnectionPool;
+use zksync_l1_contract_interface::i_verifier::{
+    batch_proof_public_input, L1BatchProofVerifier, ProofVerificationError,
+};
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_prover_interface::{
+    api::{ProofRejectionReason, SubmitProofRequest, SubmitProofResponse},
+    outputs::L1BatchProofForL1,
+};
+use zksync_types::{
+    commitment::serialize_commitments,
+    l2_to_l1_log::{L2ToL1Log, SystemL2ToL1Log},
+    protocol_version::L1VerifierConfig,
+    web3::signing::keccak256,
+    L1BatchNumber, ProtocolVersion, H256, U256,
+};
+use zksync_utils::u256_to_h256;
+
+use super::{
+    auth::ClientIdentity,
+    request_processor::{ProofVerifier, RequestProcessor, RequestProcessorError},
+};
+use crate::utils::testonly::{create_l1_batch, create_l1_batch_metadata};
+
+/// Verifier recording public inputs of verified proofs. Uses the zero VK hash, which matches the default
+/// protocol version saved by [`prepare_l1_batches()`].
+#[derive(Debug)]
+struct MockProofVerifier {
+    vk_hash: H256,
+    is_valid: bool,
+    public_inputs: Mutex<Vec<U256>>,
+}
+
+impl MockProofVerifier {
+    fn new(is_valid: bool) -> Self {
+        Self {
+            vk_hash: H256::zero(),
+            is_valid,
+            public_inputs: Mutex::default(),
+        }
+    }
+}
+
+impl ProofVerifier for MockProofVerifier {
+    fn vk_hash(&self) -> H256 {
+        self.vk_hash
+    }
+
+    fn verify(
+        &self,
+        _proof: &L1BatchProofForL1,
+        expected_public_input: U256,
+    ) -> Result<(), ProofVerificationError> {
+        self.public_inputs
+            .lock()
+            .unwrap()
+            .push(expected_public_input);
+        if self.is_valid {
+            Ok(())
+        } else {
+            Err(ProofVerificationError::InvalidProof)
+        }
+    }
+}
+
+fn mock_config() -> ProofDataHandlerConfig {
+    ProofDataHandlerConfig {
+        http_port: 3320,
+        proof_generation_timeout_in_secs: 18000,
+        protocol_version_loading_mode: ProtocolVersionLoadingMode::FromEnvVar,
+        fri_protocol_version_id: 2,
+        auth_mode: ProofDataHandlerAuthMode::Disabled,
+        api_clients: Default::default(),
+        hmac_max_clock_skew_secs: None,
+        tls_cert_path: None,
+        tls_key_path: None,
+        scheduler_vk_paths: vec![],
+    }
+}
+
+fn create_processor(
+    pool: ConnectionPool,
+    blob_store: Arc<dyn ObjectStore>,
+    verifier: Arc<dyn ProofVerifier>,
+) -> RequestProcessor {
+    RequestProcessor::new(blob_store, pool, mock_config(), None, vec![verifier])
+}
+
+/// Loads the verifier for the scheduler verification key checked in to the repository.
+fn real_proof_verifier() -> L1BatchProofVerifier {
+    let vk_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../../prover/vk_setup_data_generator_server_fri/data/snark_verification_scheduler_key.json"
+    );
+    let vk = std::fs::read_to_string(vk_path).unwrap();
+    L1BatchProofVerifier::from_json(&vk).unwrap()
+}
+
+/// Inserts L1 batches #1 and #2 with metadata and returns a proof for batch #2 with matching aux outputs.
+async fn prepare_l1_batches(pool: &ConnectionPool) -> L1BatchProofForL1 {
+    prepare_l1_batches_with_vk_hash(pool, H256::zero()).await
+}
+
+/// Same as [`prepare_l1_batches()`], but the batches' protocol version uses the specified scheduler VK hash.
+async fn prepare_l1_batches_with_vk_hash(
+    pool: &ConnectionPool,
+    scheduler_vk_hash: H256,
+) -> L1BatchProofForL1 {
+    let mut storage = pool.access_storage().await.unwrap();
+    let protocol_version = ProtocolVersion {
+        l1_verifier_config: L1VerifierConfig {
+            recursion_scheduler_level_vk_hash: scheduler_vk_hash,
+            ..L1VerifierConfig::default()
+        },
+        ..ProtocolVersion::default()
+    };
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(protocol_version)
+        .await;
+
+    let mut system_logs = vec![];
+    for number in 1..=2 {
+        let mut header = create_l1_batch(number);
+        header.system_logs = vec![SystemL2ToL1Log(L2ToL1Log {
+            key: u256_to_h256(2.into()),
+            value: H256::repeat_byte(number as u8),
+            ..L2ToL1Log::default()
+        })];
+        system_logs = header.system_logs.clone();
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .save_l1_batch_metadata(
+                L1BatchNumber(number),
+                &create_l1_batch_metadata(number),
+                H256::zero(),
+                false,
+            )
+            .await
+            .unwrap();
+    }
+    storage
+        .proof_generation_dal()
+        .insert_proof_generation_details(L1BatchNumber(2), "proof_gen_data_2.bin")
+        .await;
+
+    // Events queue and bootloader heap commitments are zero in `create_l1_batch_metadata()`.
+    let system_logs_hash = keccak256(&serialize_commitments(&system_logs));
+    L1BatchProofForL1 {
+        aggregation_result_coords: [system_logs_hash, [2; 32], [0; 32], [0; 32]],
+        scheduler_proof: Proof::empty(),
+    }
+}
+
+async fn submit_proof(
+    processor: &RequestProcessor,
+    l1_batch_number: u32,
+    proof: L1BatchProofForL1,
+) -> Result<Json<SubmitProofResponse>, RequestProcessorError> {
+    let request = SubmitProofRequest::Proof(Box::new(proof));
+    processor
+        .submit_proof(
+            &ClientIdentity::default(),
+            Path(l1_batch_number),
+            Json(request),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn verified_proof_is_accepted() {
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches(&pool).await;
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(true));
+    let processor = create_processor(pool, blob_store.clone(), verifier.clone());
+
+    let Ok(Json(response)) = submit_proof(&processor, 2, proof).await else {
+        panic!("proof was not accepted");
+    };
+    assert!(matches!(response, SubmitProofResponse::Success));
+
+    let expected_public_input = batch_proof_public_input(
+        create_l1_batch_metadata(1).commitment,
+        create_l1_batch_metadata(2).commitment,
+    );
+    assert_eq!(
+        *verifier.public_inputs.lock().unwrap(),
+        [expected_public_input]
+    );
+    let stored_proof: L1BatchProofForL1 = blob_store.get(L1BatchNumber(2)).await.unwrap();
+    assert_eq!(stored_proof.aggregation_result_coords[1], [2; 32]);
+}
+
+#[tokio::test]
+async fn invalid_proof_is_rejected() {
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches(&pool).await;
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(false));
+    let processor = create_processor(pool, blob_store.clone(), verifier);
+
+    let Err(err) = submit_proof(&processor, 2, proof).await else {
+        panic!("invalid proof was accepted");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::ProofRejected(ProofRejectionReason::VerificationFailed(_))
+    ));
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    let stored_proof = blob_store.get::<L1BatchProofForL1>(L1BatchNumber(2)).await;
+    assert!(stored_proof.is_err());
+}
+
+#[tokio::test]
+async fn proof_with_mismatched_aux_outputs_is_rejected() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut proof = prepare_l1_batches(&pool).await;
+    proof.aggregation_result_coords[1] = [3; 32];
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(true));
+    let processor = create_processor(pool, blob_store, verifier.clone());
+
+    let Err(err) = submit_proof(&processor, 2, proof).await else {
+        panic!("proof with mismatched aux outputs was accepted");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::ProofRejected(ProofRejectionReason::AuxOutputMismatch { .. })
+    ));
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    assert!(verifier.public_inputs.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn proof_for_unknown_batch_is_not_found() {
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches(&pool).await;
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(true));
+    let processor = create_processor(pool, blob_store, verifier);
+
+    let Err(err) = submit_proof(&processor, 5, proof).await else {
+        panic!("proof for unknown batch was accepted");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::MissingL1BatchMetadata(L1BatchNumber(5))
+    ));
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn proof_for_batch_without_state_diff_hash_is_not_found() {
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches(&pool).await;
+    // Batch #3 has metadata, but lacks the system log with the state diff hash.
+    let mut storage = pool.access_storage().await.unwrap();
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&create_l1_batch(3))
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .save_l1_batch_metadata(
+            L1BatchNumber(3),
+            &create_l1_batch_metadata(3),
+            H256::zero(),
+            false,
+        )
+        .await
+        .unwrap();
+    drop(storage);
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(true));
+    let processor = create_processor(pool, blob_store, verifier.clone());
+
+    let Err(err) = submit_proof(&processor, 3, proof).await else {
+        panic!("proof for batch without state diff hash was accepted");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::MissingL1BatchMetadata(L1BatchNumber(3))
+    ));
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+    assert!(verifier.public_inputs.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn proof_for_version_without_configured_vk_is_not_verified() {
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches_with_vk_hash(&pool, H256::repeat_byte(0x11)).await;
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let verifier = Arc::new(MockProofVerifier::new(true));
+    let processor = create_processor(pool, blob_store.clone(), verifier.clone());
+
+    let Err(err) = submit_proof(&processor, 2, proof).await else {
+        panic!("proof was accepted without a matching verification key");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::MissingVerificationKey { l1_batch_number: L1BatchNumber(2), vk_hash, .. }
+            if *vk_hash == H256::repeat_byte(0x11)
+    ));
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert!(verifier.public_inputs.lock().unwrap().is_empty());
+    let stored_proof = blob_store.get::<L1BatchProofForL1>(L1BatchNumber(2)).await;
+    assert!(stored_proof.is_err());
+}
+
+#[tokio::test]
+async fn malformed_proof_is_rejected_by_real_verifier() {
+    let verifier = real_proof_verifier();
+    let pool = ConnectionPool::test_pool().await;
+    let proof = prepare_l1_batches_with_vk_hash(&pool, verifier.vk_hash()).await;
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let processor = create_processor(pool, blob_store.clone(), Arc::new(verifier));
+
+    // The proof has no public inputs.
+    let Err(err) = submit_proof(&processor, 2, proof).await else {
+        panic!("malformed proof was accepted");
+    };
+    let RequestProcessorError::ProofRejected(ProofRejectionReason::VerificationFailed(message)) =
+        &err
+    else {
+        panic!("unexpected error: {:?}", err.into_response());
+    };
+    assert!(message.contains("public inputs"), "{message}");
+    let stored_proof = blob_store.get::<L1BatchProofForL1>(L1BatchNumber(2)).await;
+    assert!(stored_proof.is_err());
+}
+
+#[tokio::test]
+async fn proof_with_invalid_commitments_is_rejected_by_real_verifier() {
+    let verifier = real_proof_verifier();
+    let pool = ConnectionPool::test_pool().await;
+    let mut proof = prepare_l1_batches_with_vk_hash(&pool, verifier.vk_hash()).await;
+    // The public input is correct, but the rest of the proof is not.
+    let public_input = batch_proof_public_input(
+        create_l1_batch_metadata(1).commitment,
+        create_l1_batch_metadata(2).commitment,
+    );
+    proof.scheduler_proof.inputs = vec![Fr::from_str(&public_input.to_string()).unwrap()];
+    let blob_store = ObjectStoreFactory::mock().create_store().await;
+    let processor = create_processor(pool, blob_store.clone(), Arc::new(verifier));
+
+    let Err(err) = submit_proof(&processor, 2, proof).await else {
+        panic!("invalid proof was accepted");
+    };
+    assert!(matches!(
+        &err,
+        RequestProcessorError::ProofRejected(ProofRejectionReason::VerificationFailed(_))
+    ));
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+    let stored_proof = blob_store.get::<L1BatchProofForL1>(L1BatchNumber(2)).await;
+    assert!(stored_proof.is_err());
+}
@@ -0,0 +1,98 @@
+//! Optional TLS termination for the proof data handler server.
+
+use std::{fs::File, io, io::BufReader, net::SocketAddr, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use futures::Stream;
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{mpsc, watch},
+};
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    server::TlsStream,
+    TlsAcceptor,
+};
+
+/// Maximum time a client may take to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(super) fn load_server_config(cert_path: &str, key_path: &str) -> anyhow::Result<ServerConfig> {
+    let cert_file = File::open(cert_path)
+        .with_context(|| format!("failed opening TLS certificate `{cert_path}`"))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(cert_file))
+        .with_context(|| format!("failed parsing TLS certificate `{cert_path}`"))?;
+    anyhow::ensure!(!certs.is_empty(), "no certificates in `{cert_path}`");
+    let certs = certs.into_iter().map(Certificate).collect();
+
+    let key_file =
+        File::open(key_path).with_context(|| format!("failed opening TLS key `{key_path}`"))?;
+    let key = rustls_pemfile::read_all(&mut BufReader::new(key_file))
+        .with_context(|| format!("failed parsing TLS key `{key_path}`"))?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .with_context(|| format!("no private key in `{key_path}`"))?;
+
+    ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("invalid TLS certificate / key")
+}
+
+/// Accepts TCP connections on the specified address and performs TLS handshakes. Connections
+/// are handshaken concurrently, so that a slow or malicious client cannot stall other clients.
+/// Failed handshakes are logged and dropped.
+pub(super) async fn incoming_connections(
+    bind_address: SocketAddr,
+    tls_config: ServerConfig,
+    mut stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<impl Stream<Item = io::Result<TlsStream<TcpStream>>>> {
+    let listener = TcpListener::bind(bind_address)
+        .await
+        .with_context(|| format!("failed binding proof data handler server to {bind_address}"))?;
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+    let (connections_sender, connections_receiver) =
+        mpsc::channel::<io::Result<TlsStream<TcpStream>>>(32);
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, peer_address) = tokio::select! {
+                _ = stop_receiver.changed() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        tracing::warn!("Failed accepting TCP connection: {err}");
+                        continue;
+                    }
+                },
+            };
+
+            let acceptor = acceptor.clone();
+            let connections_sender = connections_sender.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => {
+                        connections_sender.send(Ok(stream)).await.ok();
+                    }
+                    Ok(Err(err)) => {
+                        tracing::info!("TLS handshake with {peer_address} failed: {err}");
+                    }
+                    Err(_) => {
+                        tracing::info!("TLS handshake with {peer_address} timed out");
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(futures::stream::unfold(
+        connections_receiver,
+        |mut receiver| async move { Some((receiver.recv().await?, receiver)) },
+    ))
+}
@@ -13,7 +13,7 @@ use zksync_types::{
     Transaction,
 };
 
-pub(crate) use self::mempool::MempoolIO;
+pub use self::mempool::MempoolIO;
 use super::{
     metrics::{MiniblockQueueStage, MINIBLOCK_METRICS},
     seal_criteria::IoSealCriteria,
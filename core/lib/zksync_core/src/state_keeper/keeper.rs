@@ -7,6 +7,7 @@ use std::{
 use anyhow::Context as _;
 use multivm::interface::{Halt, L1BatchEnv, SystemEnv};
 use tokio::sync::watch;
+use tracing::Instrument as _;
 use zksync_dal::ConnectionPool;
 use zksync_types::{
     block::MiniblockExecutionData, l2::TransactionType, protocol_version::ProtocolUpgradeTx,
@@ -22,7 +23,11 @@ use super::{
     types::ExecutionMetricsForCriteria,
     updates::UpdatesManager,
 };
-use crate::{gas_tracker::gas_count_from_writes, state_keeper::io::fee_address_migration};
+use crate::{
+    gas_tracker::gas_count_from_writes,
+    state_keeper::io::fee_address_migration,
+    utils::{L1_BATCH_SPAN_LINKS, TX_SPAN_LINKS},
+};
 
 /// Amount of time to block on waiting for some resource. The exact value is not really important,
 /// we only need it to not block on waiting indefinitely and be able to process cancellation requests.
@@ -61,6 +66,7 @@ pub struct ZkSyncStateKeeper {
     io: Box<dyn StateKeeperIO>,
     batch_executor_base: Box<dyn L1BatchExecutorBuilder>,
     sealer: Box<dyn ConditionalSealer>,
+    sealing_pause_receiver: Option<watch::Receiver<bool>>,
 }
 
 impl ZkSyncStateKeeper {
@@ -75,9 +81,19 @@ impl ZkSyncStateKeeper {
             io,
             batch_executor_base,
             sealer,
+            sealing_pause_receiver: None,
         }
     }
 
+    /// Makes the state keeper defer sealing L1 batches while the provided flag is set (e.g., by pausing
+    /// circuit breakers). Transactions are still processed and miniblocks are sealed while the current L1 batch
+    /// has capacity; once the batch must be sealed, the state keeper waits for the pause to be lifted.
+    #[must_use]
+    pub fn with_sealing_pause(mut self, sealing_pause_receiver: watch::Receiver<bool>) -> Self {
+        self.sealing_pause_receiver = Some(sealing_pause_receiver);
+        self
+    }
+
     /// Temporary method to migrate fee addresses from L1 batches to miniblocks.
     pub fn run_fee_address_migration(
         &self,
@@ -203,6 +219,8 @@ impl ZkSyncStateKeeper {
             }
             let (finished_batch, witness_block_state) = batch_executor.finish_batch().await;
             let sealed_batch_protocol_version = updates_manager.protocol_version();
+            let seal_span = tracing::info_span!("seal_l1_batch", l1_batch = %l1_batch_env.number);
+            L1_BATCH_SPAN_LINKS.record(&seal_span, l1_batch_env.number);
             self.io
                 .seal_l1_batch(
                     witness_block_state,
@@ -210,6 +228,7 @@ impl ZkSyncStateKeeper {
                     &l1_batch_env,
                     finished_batch,
                 )
+                .instrument(seal_span)
                 .await
                 .context("seal_l1_batch")?;
             if let Some(delta) = l1_batch_seal_delta {
@@ -245,6 +264,47 @@ impl ZkSyncStateKeeper {
         *self.stop_receiver.borrow()
     }
 
+    fn is_sealing_paused(&self) -> bool {
+        self.sealing_pause_receiver
+            .as_ref()
+            .map_or(false, |receiver| *receiver.borrow())
+    }
+
+    /// Waits until sealing is no longer paused.
+    async fn wait_while_sealing_paused(&mut self) -> Result<(), Error> {
+        let Some(pause_receiver) = &mut self.sealing_pause_receiver else {
+            return Ok(());
+        };
+        if !*pause_receiver.borrow() {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Sealing L1 batch #{} is paused; waiting for it to be resumed",
+            self.io.current_l1_batch_number()
+        );
+        let started_at = Instant::now();
+        let mut pause_sender_dropped = false;
+        while *pause_receiver.borrow() {
+            if *self.stop_receiver.borrow() {
+                return Err(Error::Canceled);
+            }
+            let wait_result =
+                tokio::time::timeout(POLL_WAIT_DURATION, pause_receiver.changed()).await;
+            if let Ok(Err(_)) = wait_result {
+                // The pause cannot be lifted anymore, so we ignore it from now on.
+                tracing::warn!("Sealing pause sender was dropped; resuming sealing");
+                pause_sender_dropped = true;
+                break;
+            }
+        }
+        if pause_sender_dropped {
+            self.sealing_pause_receiver = None;
+        }
+        tracing::info!("Sealing is resumed after {:?}", started_at.elapsed());
+        Ok(())
+    }
+
     async fn wait_for_new_batch_params(&mut self) -> Result<(SystemEnv, L1BatchEnv), Error> {
         while !self.is_canceled() {
             if let Some(params) = self.io.wait_for_new_batch_params(POLL_WAIT_DURATION).await {
@@ -381,6 +441,7 @@ impl ZkSyncStateKeeper {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(l1_batch = %self.io.current_l1_batch_number()))]
     async fn process_l1_batch(
         &mut self,
         batch_executor: &BatchExecutorHandle,
@@ -393,9 +454,11 @@ impl ZkSyncStateKeeper {
         }
 
         while !self.is_canceled() {
-            if self
-                .io
-                .should_seal_l1_batch_unconditionally(updates_manager)
+            // While sealing is paused, the batch keeps accepting transactions until it's full.
+            if !self.is_sealing_paused()
+                && self
+                    .io
+                    .should_seal_l1_batch_unconditionally(updates_manager)
             {
                 tracing::debug!(
                     "L1 batch #{} should be sealed unconditionally as per sealing rules",
@@ -482,6 +545,7 @@ impl ZkSyncStateKeeper {
                      transaction {tx_hash}",
                     self.io.current_l1_batch_number()
                 );
+                self.wait_while_sealing_paused().await?;
                 return Ok(());
             }
         }
@@ -556,12 +620,21 @@ impl ZkSyncStateKeeper {
     /// 2. Seal manager decided that batch is ready to be sealed.
     /// Note: this method doesn't mutate `updates_manager` in the end. However, reference should be mutable
     /// because we use `apply_and_rollback` method of `updates_manager.storage_writes_deduplicator`.
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            tx.hash = ?tx.hash(),
+            miniblock = %self.io.current_miniblock_number(),
+            l1_batch = %self.io.current_l1_batch_number(),
+        )
+    )]
     async fn process_one_tx(
         &mut self,
         batch_executor: &BatchExecutorHandle,
         updates_manager: &mut UpdatesManager,
         tx: Transaction,
     ) -> (SealResolution, TxExecutionResult) {
+        TX_SPAN_LINKS.link(&tracing::Span::current(), &tx.hash());
         let exec_result = batch_executor.execute_tx(tx.clone()).await;
         let resolution = match &exec_result {
             TxExecutionResult::BootloaderOutOfGasForTx => {
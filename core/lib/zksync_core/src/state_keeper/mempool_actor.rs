@@ -2,18 +2,25 @@ use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use multivm::utils::derive_base_fee_and_gas_per_pubdata;
+use serde::Serialize;
 #[cfg(test)]
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_health_check::{
+    Health, HealthCheckOptions, HealthStatus, HealthUpdater, ReactiveHealthCheck,
+};
 use zksync_mempool::L2TxFilter;
 #[cfg(test)]
 use zksync_types::H256;
 use zksync_types::{get_nonce_key, Address, Nonce, Transaction, VmVersion};
 
 use super::{metrics::KEEPER_METRICS, types::MempoolGuard};
-use crate::{api_server::execution_sandbox::BlockArgs, fee_model::BatchFeeModelInputProvider};
+use crate::{
+    api_server::execution_sandbox::BlockArgs, fee_model::BatchFeeModelInputProvider,
+    utils::TX_SPAN_LINKS,
+};
 
 /// Creates a mempool filter for L2 transactions based on the current L1 gas price.
 /// The filter is used to filter out transactions from the mempool that do not cover expenses
@@ -32,34 +39,57 @@ pub async fn l2_tx_filter(
     }
 }
 
+/// Health details reported by [`MempoolFetcher`].
+#[derive(Debug, Serialize)]
+struct MempoolFetcherHealthDetails {
+    l1_transaction_count: usize,
+    l2_transaction_count: u64,
+    last_loaded_transaction_count: usize,
+}
+
 #[derive(Debug)]
-pub struct MempoolFetcher<G> {
+pub struct MempoolFetcher<G: ?Sized> {
     mempool: MempoolGuard,
     batch_fee_input_provider: Arc<G>,
     sync_interval: Duration,
     sync_batch_size: usize,
     stuck_tx_timeout: Option<Duration>,
+    health_updater: HealthUpdater,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
 
-impl<G: BatchFeeModelInputProvider> MempoolFetcher<G> {
+impl<G: BatchFeeModelInputProvider + ?Sized> MempoolFetcher<G> {
+    /// Minimum time after which the fetcher health is considered stale if mempool syncing doesn't make progress.
+    const MIN_HEALTH_DEADLINE: Duration = Duration::from_secs(60);
+
     pub fn new(
         mempool: MempoolGuard,
         batch_fee_input_provider: Arc<G>,
         config: &MempoolConfig,
     ) -> Self {
+        let health_deadline = (config.sync_interval() * 10).max(Self::MIN_HEALTH_DEADLINE);
+        let (_, health_updater) = ReactiveHealthCheck::with_options(
+            "mempool_fetcher",
+            HealthCheckOptions::liveness(health_deadline),
+        );
         Self {
             mempool,
             batch_fee_input_provider,
             sync_interval: config.sync_interval(),
             sync_batch_size: config.sync_batch_size,
             stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
+            health_updater,
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
     }
 
+    /// Returns a health check for this fetcher.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
     pub async fn run(
         mut self,
         pool: ConnectionPool,
@@ -124,10 +154,25 @@ impl<G: BatchFeeModelInputProvider> MempoolFetcher<G> {
                 let transaction_hashes = transactions.iter().map(Transaction::hash).collect();
                 self.transaction_hashes_sender.send(transaction_hashes).ok();
             }
-            let all_transactions_loaded = transactions.len() < self.sync_batch_size;
-            self.mempool.insert(transactions, nonces);
+            let loaded_transaction_count = transactions.len();
+            let all_transactions_loaded = loaded_transaction_count < self.sync_batch_size;
+            let insert_span =
+                tracing::info_span!("insert_into_mempool", tx.count = loaded_transaction_count);
+            for tx in &transactions {
+                TX_SPAN_LINKS.link(&insert_span, &tx.hash());
+            }
+            insert_span.in_scope(|| self.mempool.insert(transactions, nonces));
             latency.observe();
 
+            let stats = self.mempool.stats();
+            let health =
+                Health::from(HealthStatus::Ready).with_details(MempoolFetcherHealthDetails {
+                    l1_transaction_count: stats.l1_transaction_count,
+                    l2_transaction_count: stats.l2_transaction_count,
+                    last_loaded_transaction_count: loaded_transaction_count,
+                });
+            self.health_updater.update(health);
+
             if all_transactions_loaded {
                 tokio::time::sleep(self.sync_interval).await;
             }
@@ -167,6 +212,7 @@ async fn get_transaction_nonces(
 
 #[cfg(test)]
 mod tests {
+    use zksync_health_check::CheckHealth;
     use zksync_types::{
         fee::TransactionExecutionMetrics, L2ChainId, MiniblockNumber, PriorityOpId,
         ProtocolVersionId, StorageLog, H256,
@@ -239,6 +285,7 @@ mod tests {
             MempoolFetcher::new(mempool.clone(), fee_params_provider, &TEST_MEMPOOL_CONFIG);
         let (tx_hashes_sender, mut tx_hashes_receiver) = mpsc::unbounded_channel();
         fetcher.transaction_hashes_sender = tx_hashes_sender;
+        let health_check = fetcher.health_check();
         let (stop_sender, stop_receiver) = watch::channel(false);
         let fetcher_task = tokio::spawn(fetcher.run(pool.clone(), stop_receiver));
 
@@ -255,8 +302,23 @@ mod tests {
         assert_eq!(tx_hashes, [transaction_hash]);
         assert_eq!(mempool.stats().l2_transaction_count, 1);
 
+        // Health is updated after the transactions are inserted into the mempool.
+        loop {
+            let health = health_check.check_health().await;
+            if health.status() == HealthStatus::Ready
+                && health.details().unwrap()["l2_transaction_count"] == 1
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
         stop_sender.send_replace(true);
         fetcher_task.await.unwrap().expect("fetcher errored");
+        assert_eq!(
+            health_check.check_health().await.status(),
+            HealthStatus::ShutDown
+        );
     }
 
     async fn wait_for_new_transactions(
@@ -8,14 +8,13 @@ use zksync_config::{
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
 
-use self::io::MempoolIO;
 pub use self::{
     batch_executor::{L1BatchExecutorBuilder, MainBatchExecutorBuilder},
-    io::{MiniblockSealer, MiniblockSealerHandle},
+    io::{MempoolIO, MiniblockSealer, MiniblockSealerHandle, StateKeeperIO},
     keeper::ZkSyncStateKeeper,
-};
-pub(crate) use self::{
-    mempool_actor::MempoolFetcher, seal_criteria::SequencerSealer, types::MempoolGuard,
+    mempool_actor::MempoolFetcher,
+    seal_criteria::SequencerSealer,
+    types::MempoolGuard,
 };
 use crate::fee_model::BatchFeeModelInputProvider;
 
@@ -4,7 +4,7 @@
 //! The conditional sealer abstraction allows to implement different sealing strategies, e.g. the actual
 //! sealing strategy for the main node or noop sealer for the external node.
 
-use std::fmt;
+use std::{fmt, sync::Arc};
 
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_types::ProtocolVersionId;
@@ -34,6 +34,35 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
     ) -> SealResolution;
 }
 
+impl<T: ConditionalSealer + ?Sized> ConditionalSealer for Arc<T> {
+    fn find_unexecutable_reason(
+        &self,
+        data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> Option<&'static str> {
+        (**self).find_unexecutable_reason(data, protocol_version)
+    }
+
+    fn should_seal_l1_batch(
+        &self,
+        l1_batch_number: u32,
+        block_open_timestamp_ms: u128,
+        tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        (**self).should_seal_l1_batch(
+            l1_batch_number,
+            block_open_timestamp_ms,
+            tx_count,
+            block_data,
+            tx_data,
+            protocol_version,
+        )
+    }
+}
+
 /// Implementation of [`ConditionalSealer`] used by the main node.
 /// Internally uses a set of [`SealCriterion`]s to determine whether the batch should be sealed.
 ///
@@ -115,7 +144,7 @@ impl ConditionalSealer for SequencerSealer {
 }
 
 impl SequencerSealer {
-    pub(crate) fn new(config: StateKeeperConfig) -> Self {
+    pub fn new(config: StateKeeperConfig) -> Self {
         let sealers = Self::default_sealers();
         Self { config, sealers }
     }
@@ -14,6 +14,7 @@ use multivm::{
     vm_latest::{constants::BLOCK_GAS_LIMIT, VmExecutionLogs},
 };
 use once_cell::sync::Lazy;
+use tokio::sync::watch;
 use zksync_config::configs::chain::StateKeeperConfig;
 use zksync_contracts::BaseSystemContracts;
 use zksync_system_constants::ZKPORTER_IS_AVAILABLE;
@@ -213,6 +214,37 @@ async fn sealed_by_number_of_txs() {
         .await;
 }
 
+#[tokio::test]
+async fn paused_sealing_does_not_block_transactions() {
+    let sealer = SequencerSealer::with_sealers(StateKeeperConfig::default(), vec![]);
+    let (pause_sender, pause_receiver) = watch::channel(true);
+
+    // Without the pause, the state keeper would seal an L1 batch on each loop iteration.
+    TestScenario::new()
+        .pause_sealing_with(pause_receiver)
+        .seal_l1_batch_when(|_| true)
+        .seal_miniblock_when(|updates| updates.miniblock.executed_transactions.len() == 1)
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .miniblock_sealed("Miniblock 1 is sealed while sealing is paused")
+        .next_tx("Second tx", random_tx(2), successful_exec())
+        .miniblock_sealed_with("Miniblock 2 is sealed while sealing is paused", move |_| {
+            pause_sender.send_replace(false);
+        })
+        .no_txs_until_next_action("Give the state keeper time to seal the batch")
+        .batch_sealed_with(
+            "Batch is sealed after the pause is lifted",
+            |_, updates, _| {
+                assert_eq!(
+                updates.l1_batch.executed_transactions.len(),
+                2,
+                "Transactions processed while sealing is paused should be included into the batch"
+            );
+            },
+        )
+        .run(sealer)
+        .await;
+}
+
 #[tokio::test]
 async fn sealed_by_gas() {
     let config = StateKeeperConfig {
@@ -52,6 +52,7 @@ pub(crate) struct TestScenario {
     pending_batch: Option<PendingBatchData>,
     l1_batch_seal_fn: Box<SealFn>,
     miniblock_seal_fn: Box<SealFn>,
+    sealing_pause_receiver: Option<watch::Receiver<bool>>,
 }
 
 type SealFn = dyn FnMut(&UpdatesManager) -> bool + Send;
@@ -62,6 +63,7 @@ impl fmt::Debug for TestScenario {
             .debug_struct("TestScenario")
             .field("actions", &self.actions)
             .field("pending_batch", &self.pending_batch)
+            .field("sealing_pause_receiver", &self.sealing_pause_receiver)
             .finish_non_exhaustive()
     }
 }
@@ -73,6 +75,7 @@ impl TestScenario {
             pending_batch: None,
             l1_batch_seal_fn: Box::new(|_| false),
             miniblock_seal_fn: Box::new(|_| false),
+            sealing_pause_receiver: None,
         }
     }
 
@@ -188,20 +191,33 @@ impl TestScenario {
         self
     }
 
+    /// Makes the state keeper pause sealing L1 batches while the flag provided by the receiver is set.
+    pub(crate) fn pause_sealing_with(
+        mut self,
+        sealing_pause_receiver: watch::Receiver<bool>,
+    ) -> Self {
+        self.sealing_pause_receiver = Some(sealing_pause_receiver);
+        self
+    }
+
     /// Launches the test.
     /// Provided `SealManager` is expected to be externally configured to adhere the written scenario logic.
-    pub(crate) async fn run(self, sealer: SequencerSealer) {
+    pub(crate) async fn run(mut self, sealer: SequencerSealer) {
         assert!(!self.actions.is_empty(), "Test scenario can't be empty");
 
         let batch_executor_base = TestBatchExecutorBuilder::new(&self);
         let (stop_sender, stop_receiver) = watch::channel(false);
+        let sealing_pause_receiver = self.sealing_pause_receiver.take();
         let io = TestIO::new(stop_sender, self);
-        let sk = ZkSyncStateKeeper::new(
+        let mut sk = ZkSyncStateKeeper::new(
             stop_receiver,
             Box::new(io),
             Box::new(batch_executor_base),
             Box::new(sealer),
         );
+        if let Some(sealing_pause_receiver) = sealing_pause_receiver {
+            sk = sk.with_sealing_pause(sealing_pause_receiver);
+        }
         let sk_thread = tokio::spawn(sk.run());
 
         // We must assume that *theoretically* state keeper may ignore the stop signal from IO once scenario is
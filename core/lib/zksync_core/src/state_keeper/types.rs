@@ -49,6 +49,13 @@ impl MempoolGuard {
             .rollback(rejected);
     }
 
+    pub fn remove_l1_transactions_from(&mut self, first_id: PriorityOpId) -> Option<usize> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .remove_l1_transactions_from(first_id)
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         self.0
             .lock()
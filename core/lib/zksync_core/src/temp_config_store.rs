@@ -7,8 +7,9 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, PrometheusConfig,
-        ProofDataHandlerConfig, WitnessGeneratorConfig,
+        FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig, GeneralConfig,
+        PrometheusConfig, ProofDataHandlerConfig, WithdrawalFinalizerConfig,
+        WitnessGeneratorConfig,
     },
     ApiConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
     GasAdjusterConfig, ObjectStoreConfig, PostgresConfig,
@@ -46,5 +47,46 @@ pub struct TempConfigStore {
     pub eth_watch_config: Option<ETHWatchConfig>,
     pub gas_adjuster_config: Option<GasAdjusterConfig>,
     pub object_store_config: Option<ObjectStoreConfig>,
+    pub withdrawal_finalizer_config: Option<WithdrawalFinalizerConfig>,
     pub consensus_config: Option<consensus::MainNodeConfig>,
 }
+
+impl From<GeneralConfig> for TempConfigStore {
+    /// Splits the general config into the per-component configs. Consensus config is not a part
+    /// of the general config and should be set separately.
+    fn from(config: GeneralConfig) -> Self {
+        let api_config = config.api_config;
+        Self {
+            postgres_config: config.postgres_config,
+            health_check_config: api_config.as_ref().map(|api| api.healthcheck.clone()),
+            merkle_tree_api_config: api_config.as_ref().map(|api| api.merkle_tree.clone()),
+            web3_json_rpc_config: api_config.as_ref().map(|api| api.web3_json_rpc.clone()),
+            circuit_breaker_config: config.circuit_breaker_config,
+            mempool_config: config.mempool_config,
+            network_config: config.network_config,
+            operations_manager_config: config.operations_manager_config,
+            state_keeper_config: config.state_keeper_config,
+            house_keeper_config: config.house_keeper_config,
+            fri_proof_compressor_config: config.fri_proof_compressor_config,
+            fri_prover_config: config.fri_prover_config,
+            fri_prover_group_config: config.fri_prover_group_config,
+            fri_witness_generator_config: config.fri_witness_generator_config,
+            prometheus_config: api_config.as_ref().map(|api| api.prometheus.clone()),
+            proof_data_handler_config: config.proof_data_handler_config,
+            witness_generator_config: config.witness_generator_config,
+            api_config,
+            contracts_config: config.contracts_config,
+            db_config: config.db_config,
+            eth_client_config: config.eth_client_config,
+            gas_adjuster_config: config
+                .eth_sender_config
+                .as_ref()
+                .map(|eth_sender| eth_sender.gas_adjuster),
+            eth_sender_config: config.eth_sender_config,
+            eth_watch_config: config.eth_watch_config,
+            object_store_config: config.object_store_config,
+            withdrawal_finalizer_config: config.withdrawal_finalizer_config,
+            consensus_config: None,
+        }
+    }
+}
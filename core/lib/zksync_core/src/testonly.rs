@@ -0,0 +1,65 @@
+//! Test utilities for crates depending on `zksync_core`. Available with the `testonly` feature.
+
+use multivm::utils::get_max_gas_per_pubdata_byte;
+use zksync_dal::StorageProcessor;
+use zksync_types::{
+    block::{L1BatchHeader, MiniblockHeader},
+    fee_model::BatchFeeInput,
+    Address, L1BatchNumber, L2ChainId, MiniblockNumber, ProtocolVersionId, H256,
+};
+
+use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+/// Creates a genesis state with the latest protocol version and seals L1 batch #1 consisting of a single miniblock
+/// on top of it. The miniblock declares `l1_tx_count` L1 transactions; persisting them is up to the caller.
+/// Returns the header of the created miniblock.
+pub async fn seal_l1_batch_after_genesis(
+    storage: &mut StorageProcessor<'_>,
+    chain_id: L2ChainId,
+    l1_tx_count: u16,
+) -> MiniblockHeader {
+    const BASE_FEE_PER_GAS: u64 = 250_000_000;
+
+    let genesis_params = GenesisParams::mock();
+    ensure_genesis_state(storage, chain_id, &genesis_params)
+        .await
+        .unwrap();
+
+    let contracts_hashes = genesis_params.base_system_contracts.hashes();
+    let miniblock = MiniblockHeader {
+        number: MiniblockNumber(1),
+        timestamp: 1,
+        hash: H256::repeat_byte(1),
+        l1_tx_count,
+        l2_tx_count: 0,
+        fee_account_address: Address::repeat_byte(0x01),
+        base_fee_per_gas: BASE_FEE_PER_GAS,
+        gas_per_pubdata_limit: get_max_gas_per_pubdata_byte(ProtocolVersionId::latest().into()),
+        batch_fee_input: BatchFeeInput::l1_pegged(50_000_000_000, BASE_FEE_PER_GAS),
+        base_system_contracts_hashes: contracts_hashes,
+        protocol_version: Some(ProtocolVersionId::latest()),
+        virtual_blocks: 1,
+    };
+    storage
+        .blocks_dal()
+        .insert_miniblock(&miniblock)
+        .await
+        .unwrap();
+    let l1_batch = L1BatchHeader::new(
+        L1BatchNumber(1),
+        1,
+        contracts_hashes,
+        ProtocolVersionId::latest(),
+    );
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    miniblock
+}
@@ -4,13 +4,27 @@ use std::{future::Future, time::Duration};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
+use once_cell::sync::Lazy;
 use tokio::sync::watch;
+use vlog::SpanLinks;
 use zksync_dal::{ConnectionPool, StorageProcessor};
-use zksync_types::L1BatchNumber;
+use zksync_mini_merkle_tree::MiniMerkleTree;
+use zksync_types::{
+    api::L2ToL1LogProof, l2_to_l1_log::L2ToL1Log, L1BatchNumber, ProtocolVersionId, H256,
+};
 
 #[cfg(test)]
 pub(crate) mod testonly;
 
+/// Contexts of the API spans that submitted transactions, keyed by the transaction hash. Mempool and
+/// state keeper spans link to them, since transactions are passed between these components via Postgres.
+pub(crate) static TX_SPAN_LINKS: Lazy<SpanLinks<H256>> = Lazy::new(|| SpanLinks::new(100_000));
+
+/// Contexts of the state keeper spans that sealed L1 batches, keyed by the batch number. Linked to
+/// by the `eth_sender` spans creating L1 transactions for the batches.
+pub(crate) static L1_BATCH_SPAN_LINKS: Lazy<SpanLinks<L1BatchNumber>> =
+    Lazy::new(|| SpanLinks::new(1_000));
+
 /// Fallible and async predicate for binary search.
 #[async_trait]
 pub(crate) trait BinarySearchPredicate: Send {
@@ -126,6 +140,30 @@ pub(crate) async fn projected_first_l1_batch(
     Ok(snapshot_recovery.map_or(L1BatchNumber(0), |recovery| recovery.l1_batch_number + 1))
 }
 
+/// Builds a Merkle proof for the L2→L1 log with the specified index among all L2→L1 logs
+/// in an L1 batch. `protocol_version` is the protocol version of the L1 batch; it influences
+/// the minimum size of the Merkle tree.
+pub(crate) fn l2_to_l1_log_proof(
+    all_logs: &[L2ToL1Log],
+    log_index: usize,
+    protocol_version: Option<ProtocolVersionId>,
+) -> L2ToL1LogProof {
+    let merkle_tree_leaves = all_logs.iter().map(L2ToL1Log::to_bytes);
+    let min_tree_size = if protocol_version.map_or(true, |v| v.is_pre_boojum()) {
+        L2ToL1Log::PRE_BOOJUM_MIN_L2_L1_LOGS_TREE_SIZE
+    } else {
+        L2ToL1Log::MIN_L2_L1_LOGS_TREE_SIZE
+    };
+
+    let (root, proof) = MiniMerkleTree::new(merkle_tree_leaves, Some(min_tree_size))
+        .merkle_root_and_path(log_index);
+    L2ToL1LogProof {
+        proof,
+        root,
+        id: log_index as u32,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_types::L2ChainId;
@@ -12,12 +12,13 @@ use zksync_types::{
     commitment::{L1BatchMetaParameters, L1BatchMetadata},
     fee::Fee,
     fee_model::{BatchFeeInput, FeeParams},
+    l1::{L1Tx, L1TxCommonData, OpProcessingType, PriorityQueueType},
     l2::L2Tx,
     snapshots::SnapshotRecoveryStatus,
     transaction_request::PaymasterParams,
     tx::{tx_execution_info::TxExecutionStatus, ExecutionMetrics, TransactionExecutionResult},
-    Address, L1BatchNumber, L2ChainId, MiniblockNumber, Nonce, ProtocolVersion, ProtocolVersionId,
-    StorageLog, H256, U256,
+    Address, Execute, L1BatchNumber, L2ChainId, MiniblockNumber, Nonce, PriorityOpId,
+    ProtocolVersion, ProtocolVersionId, StorageLog, H256, U256,
 };
 
 use crate::{
@@ -104,6 +105,37 @@ pub(crate) fn create_l2_transaction(fee_per_gas: u64, gas_per_pubdata: u64) -> L
     tx
 }
 
+/// Creates an L1 transaction with the specified serial ID and deterministic L1 transaction hash.
+pub(crate) fn create_l1_transaction(serial_id: u64) -> L1Tx {
+    let common_data = L1TxCommonData {
+        sender: Address::random(),
+        serial_id: PriorityOpId(serial_id),
+        deadline_block: 100_000,
+        layer_2_tip_fee: U256::zero(),
+        full_fee: U256::zero(),
+        max_fee_per_gas: 1_u64.into(),
+        gas_limit: 100_000_u64.into(),
+        gas_per_pubdata_limit: 800_u64.into(),
+        op_processing_type: OpProcessingType::Common,
+        priority_queue_type: PriorityQueueType::Deque,
+        eth_hash: H256::from_low_u64_be(serial_id + 1),
+        eth_block: 1,
+        canonical_tx_hash: H256::random(),
+        to_mint: U256::zero(),
+        refund_recipient: Address::random(),
+    };
+    L1Tx {
+        execute: Execute {
+            contract_address: Address::random(),
+            calldata: vec![],
+            value: U256::zero(),
+            factory_deps: None,
+        },
+        common_data,
+        received_timestamp_ms: 0,
+    }
+}
+
 pub(crate) fn execute_l2_transaction(transaction: L2Tx) -> TransactionExecutionResult {
     TransactionExecutionResult {
         hash: transaction.hash(),
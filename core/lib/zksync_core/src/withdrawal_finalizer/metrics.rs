@@ -0,0 +1,36 @@
+//! Metrics for the withdrawal finalizer.
+
+use std::time::Duration;
+
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(super) enum FinalizerStage {
+    IndexWithdrawals,
+    CheckSentTransaction,
+    SendTransaction,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_withdrawal_finalizer")]
+pub(super) struct WithdrawalFinalizerMetrics {
+    /// Latency of the finalizer stages.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub stage_latency: Family<FinalizerStage, Histogram<Duration>>,
+    /// Latest L1 batch for which withdrawals were indexed.
+    pub last_indexed_l1_batch: Gauge<u64>,
+    /// Number of indexed withdrawals.
+    pub indexed_withdrawals: Counter,
+    /// Number of sent finalization transactions.
+    pub sent_transactions: Counter,
+    /// Number of finalization transactions resent with bumped fees.
+    pub resent_transactions: Counter,
+    /// Number of finalized withdrawals, including the ones finalized by third parties.
+    pub finalized_withdrawals: Counter,
+    /// Number of failed finalization attempts.
+    pub failed_attempts: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<WithdrawalFinalizerMetrics> = vise::Global::new();
@@ -0,0 +1,652 @@
+//! Withdrawal finalizer finalizes L2→L1 withdrawals (ETH and ERC20 bridge ones) on L1 once the L1 batches
+//! they were initiated in are executed, so that users don't have to send finalization transactions themselves.
+//!
+//! The finalizer works in three steps, each performed on every loop iteration:
+//!
+//! 1. Withdrawal messages from executed L1 batches are indexed and persisted in Postgres.
+//! 2. If a finalization transaction was sent previously, its outcome is checked. Since finalizations
+//!    are batched via `Multicall3` with failures allowed, the outcome is determined per withdrawal
+//!    by querying the `is*Finalized` getters of the corresponding L1 contracts. A transaction pending
+//!    for longer than the configured timeout is resent with the same nonce and bumped fees; a transaction
+//!    unknown to the L1 node is considered dropped only after a grace period.
+//! 3. If there is no finalization transaction in flight, pending withdrawals are finalized with a new transaction.
+//!
+//! Failed withdrawals are retried up to the configured number of attempts.
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::sync::watch;
+use zksync_config::{configs::WithdrawalFinalizerConfig, ContractsConfig};
+use zksync_contracts::{
+    multicall_contract, FINALIZE_ETH_WITHDRAWAL_FUNCTION, IS_ETH_WITHDRAWAL_FINALIZED_FUNCTION,
+    L1_BRIDGE_FINALIZE_WITHDRAWAL_FUNCTION, L1_BRIDGE_IS_WITHDRAWAL_FINALIZED_FUNCTION,
+};
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_eth_client::{BoundEthInterface, CallFunctionArgs};
+use zksync_l1_contract_interface::{
+    multicall3::{Multicall3Call, Multicall3Result},
+    Detokenize, Tokenizable,
+};
+use zksync_types::{
+    api::GetLogsFilter,
+    ethabi::{self, Contract, Function, ParamType, Token},
+    event::L1_MESSAGE_EVENT_SIGNATURE,
+    l2_to_l1_log::L2ToL1Log,
+    web3::{contract::Options, types::Transaction},
+    withdrawals::{StoredWithdrawal, Withdrawal, WithdrawalStatus},
+    Address, L1BatchNumber, ProtocolVersionId, H256, L1_MESSENGER_ADDRESS, L2_ETH_TOKEN_ADDRESS,
+    U256,
+};
+use zksync_utils::{address_to_h256, h256_to_account_address};
+
+use self::metrics::{FinalizerStage, METRICS};
+use crate::utils::{l2_to_l1_log_proof, projected_first_l1_batch};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of L1 batches indexed on a single loop iteration.
+const MAX_L1_BATCHES_PER_ITERATION: u32 = 10;
+/// Upper bound on the number of withdrawal messages in a single L1 batch.
+const MAX_WITHDRAWALS_PER_L1_BATCH: usize = 100_000;
+/// Percentage by which fees are increased when resending a pending transaction. L1 nodes require
+/// a replacement transaction to bump both fees by at least 10%.
+const FEE_BUMP_PERCENT: u64 = 20;
+const COMPONENT: &str = "withdrawal_finalizer";
+
+/// L1 contract functions used to finalize a withdrawal and to check whether it is finalized.
+#[derive(Debug, Clone, Copy)]
+struct FinalizationTarget {
+    contract_address: Address,
+    finalize: &'static Function,
+    is_finalized: &'static Function,
+}
+
+/// State of a sent finalization transaction which is not included into an L1 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PendingTxState {
+    /// Transaction is pending, or it may have not propagated to the L1 node yet.
+    InFlight,
+    /// Transaction nonce is used by another transaction, e.g. by the attempt this transaction has replaced.
+    NonceUsed,
+    /// Transaction is unknown to the L1 node for longer than the grace period.
+    Dropped,
+}
+
+/// Component finalizing L2→L1 withdrawals on L1. See the module docs for details.
+#[derive(Debug)]
+pub struct WithdrawalFinalizer {
+    config: WithdrawalFinalizerConfig,
+    eth_client: Arc<dyn BoundEthInterface>,
+    diamond_proxy_address: Address,
+    l1_erc20_bridge_address: Address,
+    l2_erc20_bridge_address: Address,
+    l1_multicall3_address: Address,
+    multicall_contract: Contract,
+    aggregate3: Function,
+}
+
+impl WithdrawalFinalizer {
+    pub fn new(
+        config: WithdrawalFinalizerConfig,
+        contracts_config: &ContractsConfig,
+        eth_client: Arc<dyn BoundEthInterface>,
+    ) -> Self {
+        let multicall_contract = multicall_contract();
+        let aggregate3 = multicall_contract
+            .function("aggregate3")
+            .expect("aggregate3 function not found")
+            .clone();
+        Self {
+            config,
+            eth_client,
+            diamond_proxy_address: contracts_config.diamond_proxy_addr,
+            l1_erc20_bridge_address: contracts_config.l1_erc20_bridge_proxy_addr,
+            l2_erc20_bridge_address: contracts_config.l2_erc20_bridge_addr,
+            l1_multicall3_address: contracts_config.l1_multicall3_addr,
+            multicall_contract,
+            aggregate3,
+        }
+    }
+
+    pub async fn run(
+        self,
+        pool: ConnectionPool,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting withdrawal finalizer with sender account {:?}",
+            self.eth_client.sender_account()
+        );
+        while !*stop_receiver.borrow_and_update() {
+            let mut storage = pool.access_storage_tagged(COMPONENT).await?;
+            if let Err(err) = self.loop_iteration(&mut storage).await {
+                // L1 request failures can cause this; the failed step is retried on the next iteration.
+                tracing::warn!("withdrawal finalizer error: {err:?}");
+            }
+            drop(storage);
+
+            tokio::time::timeout(self.config.poll_interval(), stop_receiver.changed())
+                .await
+                .ok();
+        }
+        tracing::info!("Stop signal received, withdrawal finalizer is shutting down");
+        Ok(())
+    }
+
+    async fn loop_iteration(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let latency = METRICS.stage_latency[&FinalizerStage::IndexWithdrawals].start();
+        self.index_withdrawals(storage).await?;
+        latency.observe();
+
+        let latency = METRICS.stage_latency[&FinalizerStage::CheckSentTransaction].start();
+        let has_inflight_tx = self.check_sent_transaction(storage).await?;
+        latency.observe();
+        if has_inflight_tx {
+            return Ok(());
+        }
+
+        let latency = METRICS.stage_latency[&FinalizerStage::SendTransaction].start();
+        self.send_finalization_transaction(storage).await?;
+        latency.observe();
+        Ok(())
+    }
+
+    /// Indexes withdrawals from the executed L1 batches that were not indexed yet.
+    async fn index_withdrawals(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let Some(last_executed_l1_batch) = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await?
+        else {
+            return Ok(());
+        };
+        let next_l1_batch = match storage
+            .withdrawals_dal()
+            .get_last_indexed_l1_batch()
+            .await?
+        {
+            Some(number) => number + 1,
+            None => match self.config.start_from_l1_batch {
+                Some(number) => L1BatchNumber(number),
+                None => projected_first_l1_batch(storage).await?,
+            },
+        };
+        if next_l1_batch > last_executed_l1_batch {
+            return Ok(());
+        }
+
+        let last_l1_batch_to_index =
+            last_executed_l1_batch.min(next_l1_batch + (MAX_L1_BATCHES_PER_ITERATION - 1));
+        for number in next_l1_batch.0..=last_l1_batch_to_index.0 {
+            let l1_batch_number = L1BatchNumber(number);
+            let withdrawals = self
+                .load_l1_batch_withdrawals(storage, l1_batch_number)
+                .await
+                .with_context(|| format!("failed loading withdrawals for L1 batch #{number}"))?;
+            tracing::debug!(
+                "Indexed {} withdrawals in L1 batch #{number}",
+                withdrawals.len()
+            );
+            storage
+                .withdrawals_dal()
+                .insert_l1_batch_withdrawals(l1_batch_number, &withdrawals)
+                .await?;
+            METRICS.indexed_withdrawals.inc_by(withdrawals.len() as u64);
+            METRICS.last_indexed_l1_batch.set(number.into());
+        }
+        Ok(())
+    }
+
+    /// Loads withdrawal messages sent in the specified L1 batch and matches them to the L2→L1 logs
+    /// of the batch, which are used to build the inclusion proofs.
+    async fn load_l1_batch_withdrawals(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Vec<Withdrawal>> {
+        let (from_block, to_block) = storage
+            .blocks_web3_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await?
+            .context("L1 batch has no miniblocks")?;
+        let senders = [L2_ETH_TOKEN_ADDRESS, self.l2_erc20_bridge_address];
+        let filter = GetLogsFilter {
+            from_block,
+            to_block,
+            addresses: vec![L1_MESSENGER_ADDRESS],
+            topics: vec![
+                (1, vec![*L1_MESSAGE_EVENT_SIGNATURE]),
+                (2, senders.iter().map(address_to_h256).collect()),
+            ],
+        };
+        let events = storage
+            .events_web3_dal()
+            .get_logs(filter, MAX_WITHDRAWALS_PER_L1_BATCH)
+            .await?;
+        if events.is_empty() {
+            return Ok(vec![]);
+        }
+        let l2_to_l1_logs = storage
+            .blocks_web3_dal()
+            .get_l2_to_l1_logs(l1_batch_number)
+            .await?;
+
+        // Events and L2→L1 logs are both ordered by their emission, so matching can be done in a single pass.
+        let mut logs_iter = l2_to_l1_logs.iter().enumerate();
+        let mut withdrawals = Vec::with_capacity(events.len());
+        for event in events {
+            anyhow::ensure!(
+                event.topics.len() == 3,
+                "unexpected L1MessageSent event: {event:?}"
+            );
+            let (key, hash) = (event.topics[1], event.topics[2]);
+            let (log_index, log) = logs_iter
+                .find(|(_, log)| {
+                    log.sender == L1_MESSENGER_ADDRESS && log.key == key && log.value == hash
+                })
+                .with_context(|| format!("no L2→L1 log for L1MessageSent event {event:?}"))?;
+            let message = ethabi::decode(&[ParamType::Bytes], &event.data.0)
+                .context("failed decoding L1MessageSent message")?
+                .pop()
+                .and_then(Token::into_bytes)
+                .context("unexpected L1MessageSent message")?;
+
+            withdrawals.push(Withdrawal {
+                l1_batch_number,
+                l2_message_index: log_index as u32,
+                l1_batch_tx_index: log.tx_number_in_block,
+                tx_hash: event.transaction_hash.unwrap_or_default(),
+                sender: h256_to_account_address(&key),
+                message,
+            });
+        }
+        Ok(withdrawals)
+    }
+
+    /// Checks the outcome of the previously sent finalization transaction. Returns `true` if the transaction
+    /// is still in flight.
+    async fn check_sent_transaction(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<bool> {
+        let sent_withdrawals = storage
+            .withdrawals_dal()
+            .get_withdrawals_with_status(WithdrawalStatus::Sent, self.config.batch_size)
+            .await?;
+        let mut withdrawals_by_tx = HashMap::<_, Vec<_>>::new();
+        for withdrawal in sent_withdrawals {
+            let tx_hash = withdrawal
+                .finalization_tx_hash
+                .context("sent withdrawal without finalization transaction")?;
+            withdrawals_by_tx
+                .entry(tx_hash)
+                .or_default()
+                .push(withdrawal);
+        }
+
+        let mut has_inflight_tx = false;
+        for (tx_hash, withdrawals) in withdrawals_by_tx {
+            let error = match self.eth_client.get_tx_status(tx_hash, COMPONENT).await? {
+                Some(status) if status.success => "finalization call failed",
+                Some(_) => "finalization transaction reverted",
+                None => match self
+                    .check_pending_transaction(storage, tx_hash, &withdrawals)
+                    .await?
+                {
+                    PendingTxState::InFlight => {
+                        has_inflight_tx = true;
+                        continue;
+                    }
+                    PendingTxState::NonceUsed => {
+                        "finalization transaction nonce was used by another transaction"
+                    }
+                    PendingTxState::Dropped => "finalization transaction was dropped",
+                },
+            };
+            tracing::info!("Finalization transaction {tx_hash:?} is processed");
+            self.record_finalization_results(storage, &withdrawals, error)
+                .await?;
+        }
+        Ok(has_inflight_tx)
+    }
+
+    /// Checks a sent finalization transaction that is not included into an L1 block. If the transaction
+    /// is pending for longer than the resend timeout, it is resent with bumped fees.
+    async fn check_pending_transaction(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        tx_hash: H256,
+        withdrawals: &[StoredWithdrawal],
+    ) -> anyhow::Result<PendingTxState> {
+        let nonce = withdrawals[0]
+            .finalization_tx_nonce
+            .context("sent withdrawal without finalization transaction nonce")?;
+        let sent_at = withdrawals[0]
+            .finalization_tx_sent_at
+            .context("sent withdrawal without finalization transaction timestamp")?;
+        // The transaction may have been replaced by another one with the same nonce,
+        // or the replaced transaction may have been mined instead of this one.
+        if self.eth_client.current_nonce(COMPONENT).await? > U256::from(nonce) {
+            return Ok(PendingTxState::NonceUsed);
+        }
+
+        let elapsed = (Utc::now() - sent_at).to_std().unwrap_or_default();
+        let Some(tx) = self.eth_client.get_tx(tx_hash, COMPONENT).await? else {
+            if elapsed < self.config.dropped_tx_grace_period() {
+                tracing::debug!(
+                    "Finalization transaction {tx_hash:?} is unknown to the L1 node; waiting for it to propagate"
+                );
+                return Ok(PendingTxState::InFlight);
+            }
+            return Ok(PendingTxState::Dropped);
+        };
+        if elapsed >= self.config.resend_timeout() {
+            self.resend_transaction(storage, tx, nonce, withdrawals)
+                .await?;
+        }
+        Ok(PendingTxState::InFlight)
+    }
+
+    /// Resends a pending finalization transaction with the same nonce and bumped fees.
+    async fn resend_transaction(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        tx: Transaction,
+        nonce: u64,
+        withdrawals: &[StoredWithdrawal],
+    ) -> anyhow::Result<()> {
+        let bump_fee = |fee: U256| fee + (fee * FEE_BUMP_PERCENT / 100).max(U256::one());
+        let max_priority_fee_per_gas = bump_fee(
+            tx.max_priority_fee_per_gas
+                .context("finalization transaction has no max priority fee")?,
+        );
+        let base_fee = self
+            .eth_client
+            .get_pending_block_base_fee_per_gas(COMPONENT)
+            .await?;
+        let max_fee_per_gas = bump_fee(
+            tx.max_fee_per_gas
+                .context("finalization transaction has no max fee")?,
+        )
+        .max(base_fee + max_priority_fee_per_gas);
+
+        let options = Options {
+            gas: Some(self.gas_limit(withdrawals.len())),
+            nonce: Some(nonce.into()),
+            max_fee_per_gas: Some(max_fee_per_gas),
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+            ..Options::default()
+        };
+        let signed_tx = self
+            .eth_client
+            .sign_prepared_tx_for_addr(tx.input.0, self.l1_multicall3_address, options, COMPONENT)
+            .await?;
+        match self.eth_client.send_raw_tx(signed_tx.raw_tx).await {
+            Ok(new_tx_hash) => {
+                tracing::info!(
+                    "Resent finalization transaction {:?} as {new_tx_hash:?} with nonce {nonce}, \
+                     max fee {max_fee_per_gas} and max priority fee {max_priority_fee_per_gas}",
+                    tx.hash
+                );
+                let ids: Vec<_> = withdrawals.iter().map(|withdrawal| withdrawal.id).collect();
+                storage
+                    .withdrawals_dal()
+                    .mark_withdrawals_as_sent(&ids, new_tx_hash, nonce)
+                    .await?;
+                METRICS.resent_transactions.inc();
+            }
+            Err(err) => {
+                // The original transaction is still pending, so it's not a failed attempt.
+                tracing::warn!(
+                    "Failed resending finalization transaction {:?}: {err}",
+                    tx.hash
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks which of the specified withdrawals are finalized on L1 and updates their status;
+    /// for the remaining withdrawals, a failed attempt with the specified `error` is recorded.
+    async fn record_finalization_results(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        withdrawals: &[StoredWithdrawal],
+        error: &str,
+    ) -> anyhow::Result<()> {
+        let finalized = self.check_finalized(withdrawals).await?;
+        let mut finalized_ids = vec![];
+        let mut failed_ids = vec![];
+        for (withdrawal, finalized) in withdrawals.iter().zip(finalized) {
+            if finalized {
+                finalized_ids.push(withdrawal.id);
+            } else {
+                failed_ids.push(withdrawal.id);
+            }
+        }
+
+        if !finalized_ids.is_empty() {
+            storage
+                .withdrawals_dal()
+                .mark_withdrawals_as_finalized(&finalized_ids)
+                .await?;
+            METRICS
+                .finalized_withdrawals
+                .inc_by(finalized_ids.len() as u64);
+        }
+        if !failed_ids.is_empty() {
+            tracing::warn!("Failed finalizing withdrawals with IDs {failed_ids:?}: {error}");
+            storage
+                .withdrawals_dal()
+                .record_failed_attempts(&failed_ids, error, self.config.max_attempts)
+                .await?;
+            METRICS.failed_attempts.inc_by(failed_ids.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// Sends a transaction finalizing pending withdrawals, if there are any.
+    async fn send_finalization_transaction(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> anyhow::Result<()> {
+        let pending_withdrawals = storage
+            .withdrawals_dal()
+            .get_withdrawals_with_status(WithdrawalStatus::Pending, self.config.batch_size)
+            .await?;
+        if pending_withdrawals.is_empty() {
+            return Ok(());
+        }
+
+        // Withdrawals may have been finalized by third parties (e.g., by users themselves).
+        let finalized = self.check_finalized(&pending_withdrawals).await?;
+        let mut finalized_ids = vec![];
+        let mut withdrawals = vec![];
+        for (withdrawal, finalized) in pending_withdrawals.into_iter().zip(finalized) {
+            if finalized {
+                finalized_ids.push(withdrawal.id);
+            } else {
+                withdrawals.push(withdrawal);
+            }
+        }
+        if !finalized_ids.is_empty() {
+            tracing::info!("Withdrawals with IDs {finalized_ids:?} are already finalized on L1");
+            storage
+                .withdrawals_dal()
+                .mark_withdrawals_as_finalized(&finalized_ids)
+                .await?;
+            METRICS
+                .finalized_withdrawals
+                .inc_by(finalized_ids.len() as u64);
+        }
+        if withdrawals.is_empty() {
+            return Ok(());
+        }
+
+        let mut calls = Vec::with_capacity(withdrawals.len());
+        let mut l2_to_l1_logs = HashMap::new();
+        for withdrawal in &withdrawals {
+            let l1_batch_number = withdrawal.withdrawal.l1_batch_number;
+            if !l2_to_l1_logs.contains_key(&l1_batch_number) {
+                let logs = Self::load_l2_to_l1_logs(storage, l1_batch_number).await?;
+                l2_to_l1_logs.insert(l1_batch_number, logs);
+            }
+            let (logs, protocol_version) = &l2_to_l1_logs[&l1_batch_number];
+            let proof = l2_to_l1_log_proof(
+                logs,
+                withdrawal.withdrawal.l2_message_index as usize,
+                *protocol_version,
+            );
+            calls.push(self.finalization_call(&withdrawal.withdrawal, proof.proof));
+        }
+
+        let ids: Vec<_> = withdrawals.iter().map(|withdrawal| withdrawal.id).collect();
+        let calldata = self
+            .aggregate3
+            .encode_input(&[Token::Array(calls)])
+            .context("failed encoding aggregate3 call")?;
+        let nonce = self.eth_client.pending_nonce(COMPONENT).await?;
+        let options = Options {
+            gas: Some(self.gas_limit(withdrawals.len())),
+            nonce: Some(nonce),
+            ..Options::default()
+        };
+        let signed_tx = self
+            .eth_client
+            .sign_prepared_tx_for_addr(calldata, self.l1_multicall3_address, options, COMPONENT)
+            .await?;
+        match self.eth_client.send_raw_tx(signed_tx.raw_tx).await {
+            Ok(tx_hash) => {
+                tracing::info!(
+                    "Sent finalization transaction {tx_hash:?} with nonce {nonce} for withdrawals with IDs {ids:?}"
+                );
+                storage
+                    .withdrawals_dal()
+                    .mark_withdrawals_as_sent(&ids, tx_hash, nonce.as_u64())
+                    .await?;
+                METRICS.sent_transactions.inc();
+            }
+            Err(err) => {
+                tracing::warn!("Failed sending finalization transaction: {err}");
+                storage
+                    .withdrawals_dal()
+                    .record_failed_attempts(&ids, &err.to_string(), self.config.max_attempts)
+                    .await?;
+                METRICS.failed_attempts.inc_by(ids.len() as u64);
+            }
+        }
+        Ok(())
+    }
+
+    fn gas_limit(&self, withdrawal_count: usize) -> U256 {
+        U256::from(self.config.gas_limit_per_withdrawal * withdrawal_count as u64)
+    }
+
+    async fn load_l2_to_l1_logs(
+        storage: &mut StorageProcessor<'_>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<(Vec<L2ToL1Log>, Option<ProtocolVersionId>)> {
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is missing"))?;
+        let logs = storage
+            .blocks_web3_dal()
+            .get_l2_to_l1_logs(l1_batch_number)
+            .await?;
+        Ok((logs, header.protocol_version))
+    }
+
+    fn finalization_target(&self, sender: Address) -> FinalizationTarget {
+        if sender == L2_ETH_TOKEN_ADDRESS {
+            FinalizationTarget {
+                contract_address: self.diamond_proxy_address,
+                finalize: &FINALIZE_ETH_WITHDRAWAL_FUNCTION,
+                is_finalized: &IS_ETH_WITHDRAWAL_FINALIZED_FUNCTION,
+            }
+        } else {
+            FinalizationTarget {
+                contract_address: self.l1_erc20_bridge_address,
+                finalize: &L1_BRIDGE_FINALIZE_WITHDRAWAL_FUNCTION,
+                is_finalized: &L1_BRIDGE_IS_WITHDRAWAL_FINALIZED_FUNCTION,
+            }
+        }
+    }
+
+    fn finalization_call(&self, withdrawal: &Withdrawal, proof: Vec<H256>) -> Token {
+        let target = self.finalization_target(withdrawal.sender);
+        let calldata = target
+            .finalize
+            .encode_input(&[
+                Token::Uint(withdrawal.l1_batch_number.0.into()),
+                Token::Uint(withdrawal.l2_message_index.into()),
+                Token::Uint(withdrawal.l1_batch_tx_index.into()),
+                Token::Bytes(withdrawal.message.clone()),
+                Token::Array(proof.into_iter().map(Token::FixedBytes).collect()),
+            ])
+            .expect("failed encoding finalization call");
+        Multicall3Call {
+            target: target.contract_address,
+            // A single invalid withdrawal must not prevent finalizing the others.
+            allow_failure: true,
+            calldata,
+        }
+        .into_token()
+    }
+
+    /// Checks whether the specified withdrawals are finalized on L1 using a single multicall.
+    async fn check_finalized(&self, withdrawals: &[StoredWithdrawal]) -> anyhow::Result<Vec<bool>> {
+        let calls: Vec<_> = withdrawals
+            .iter()
+            .map(|withdrawal| {
+                let withdrawal = &withdrawal.withdrawal;
+                let target = self.finalization_target(withdrawal.sender);
+                let calldata = target
+                    .is_finalized
+                    .encode_input(&[
+                        Token::Uint(withdrawal.l1_batch_number.0.into()),
+                        Token::Uint(withdrawal.l2_message_index.into()),
+                    ])
+                    .expect("failed encoding finalization check");
+                Multicall3Call {
+                    target: target.contract_address,
+                    allow_failure: true,
+                    calldata,
+                }
+                .into_token()
+            })
+            .collect();
+
+        let args = CallFunctionArgs::new(&self.aggregate3.name, calls)
+            .for_contract(self.l1_multicall3_address, self.multicall_contract.clone());
+        let output = self.eth_client.call_contract_function(args).await?;
+        let Token::Array(results) = Token::from_tokens(output)? else {
+            anyhow::bail!("unexpected aggregate3 output");
+        };
+        anyhow::ensure!(
+            results.len() == withdrawals.len(),
+            "unexpected number of aggregate3 results: expected {}, got {}",
+            withdrawals.len(),
+            results.len()
+        );
+
+        results
+            .into_iter()
+            .map(|token| {
+                let result = Multicall3Result::from_token(token)?;
+                if !result.success {
+                    // The check can fail e.g. if the bridge is not deployed; consider such withdrawals non-finalized.
+                    return Ok(false);
+                }
+                let is_finalized = ethabi::decode(&[ParamType::Bool], &result.return_data)
+                    .context("failed decoding finalization check result")?;
+                Ok(is_finalized[0] == Token::Bool(true))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,439 @@
+//! Tests for the withdrawal finalizer.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+
+use zksync_eth_client::{clients::MockEthereum, EthInterface};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, l2_to_l1_log::UserL2ToL1Log,
+    tx::IncludedTxLocation, web3::signing::keccak256, L2ChainId, MiniblockNumber, VmEvent,
+};
+
+use super::*;
+use crate::{
+    genesis::{ensure_genesis_state, GenesisParams},
+    utils::testonly::{create_l1_batch, create_miniblock},
+};
+
+/// L1 contract address, L1 batch number and message index of finalized withdrawals.
+type FinalizedSet = Arc<Mutex<HashSet<(Address, u32, u32)>>>;
+
+fn mock_config() -> WithdrawalFinalizerConfig {
+    WithdrawalFinalizerConfig {
+        poll_interval_ms: 10,
+        batch_size: 10,
+        gas_limit_per_withdrawal: 200_000,
+        max_attempts: 2,
+        resend_timeout_ms: 60_000,
+        dropped_tx_grace_period_ms: 60_000,
+        start_from_l1_batch: None,
+        private_key: None,
+    }
+}
+
+/// Answers `aggregate3` calls consisting of `is*Finalized` checks based on the `finalized` set.
+fn mock_client(finalized: FinalizedSet) -> Arc<MockEthereum> {
+    let client = MockEthereum::default().with_fee_history(vec![50]);
+    let client = client.with_call_handler(move |call| {
+        assert_eq!(call.function_name(), "aggregate3");
+        let [Token::Array(calls)] = call.args() else {
+            panic!("unexpected aggregate3 args: {:?}", call.args());
+        };
+        let finalized = finalized.lock().unwrap();
+        let results = calls.iter().map(|call| {
+            let call = Multicall3Call::from_token(call.clone()).unwrap();
+            let (l1_batch_number, message_index) =
+                decode_withdrawal_key(&IS_ETH_WITHDRAWAL_FINALIZED_FUNCTION, &call.calldata);
+            let is_finalized = finalized.contains(&(call.target, l1_batch_number, message_index));
+            Token::Tuple(vec![
+                Token::Bool(true),
+                Token::Bytes(ethabi::encode(&[Token::Bool(is_finalized)])),
+            ])
+        });
+        vec![Token::Array(results.collect())]
+    });
+    Arc::new(client)
+}
+
+/// Decodes the L1 batch number and message index from `finalize*` or `is*Finalized` calldata.
+/// Both ETH and ERC20 bridge functions start with the same arguments.
+fn decode_withdrawal_key(function: &Function, calldata: &[u8]) -> (u32, u32) {
+    let mut tokens = function.decode_input(&calldata[4..]).unwrap().into_iter();
+    let l1_batch_number = tokens.next().unwrap().into_uint().unwrap();
+    let message_index = tokens.next().unwrap().into_uint().unwrap();
+    (l1_batch_number.as_u32(), message_index.as_u32())
+}
+
+/// Returns the L1 contract address, L1 batch number and message index of all withdrawals
+/// finalized by the specified transaction.
+async fn finalized_by_tx(
+    client: &MockEthereum,
+    finalizer: &WithdrawalFinalizer,
+    tx_hash: H256,
+) -> Vec<(Address, u32, u32)> {
+    let tx = client.get_tx(tx_hash, "test").await.unwrap().unwrap();
+    let Token::Array(calls) = finalizer
+        .aggregate3
+        .decode_input(&tx.input.0[4..])
+        .unwrap()
+        .pop()
+        .unwrap()
+    else {
+        panic!("unexpected aggregate3 input");
+    };
+    calls
+        .into_iter()
+        .map(|call| {
+            let call = Multicall3Call::from_token(call).unwrap();
+            assert!(call.allow_failure);
+            let (l1_batch_number, message_index) =
+                decode_withdrawal_key(&FINALIZE_ETH_WITHDRAWAL_FUNCTION, &call.calldata);
+            (call.target, l1_batch_number, message_index)
+        })
+        .collect()
+}
+
+fn l1_message_event(sender: Address, message: &[u8]) -> VmEvent {
+    VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: L1_MESSENGER_ADDRESS,
+        indexed_topics: vec![
+            *L1_MESSAGE_EVENT_SIGNATURE,
+            address_to_h256(&sender),
+            H256(keccak256(message)),
+        ],
+        value: ethabi::encode(&[Token::Bytes(message.to_vec())]),
+    }
+}
+
+fn l1_message_log(tx_number_in_block: u16, event: &VmEvent) -> UserL2ToL1Log {
+    UserL2ToL1Log(L2ToL1Log {
+        shard_id: 0,
+        is_service: true,
+        tx_number_in_block,
+        sender: L1_MESSENGER_ADDRESS,
+        key: event.indexed_topics[1],
+        value: event.indexed_topics[2],
+    })
+}
+
+/// Persists an executed L1 batch #1 with an ETH withdrawal, a message not related to withdrawals
+/// and an ERC20 bridge withdrawal (in this order).
+async fn seal_l1_batch_with_withdrawals(storage: &mut StorageProcessor<'_>) {
+    ensure_genesis_state(storage, L2ChainId::default(), &GenesisParams::mock())
+        .await
+        .unwrap();
+
+    let contracts_config = ContractsConfig::for_tests();
+    let events = [
+        l1_message_event(L2_ETH_TOKEN_ADDRESS, b"eth withdrawal"),
+        l1_message_event(Address::repeat_byte(0xaa), b"unrelated message"),
+        l1_message_event(contracts_config.l2_erc20_bridge_addr, b"erc20 withdrawal"),
+    ];
+    let tx_locations: Vec<_> = (0..events.len() as u32)
+        .map(|i| IncludedTxLocation {
+            tx_hash: H256::from_low_u64_be(i.into()),
+            tx_index_in_miniblock: i,
+            tx_initiator_address: Address::repeat_byte(0x01),
+        })
+        .collect();
+
+    storage
+        .blocks_dal()
+        .insert_miniblock(&create_miniblock(1))
+        .await
+        .unwrap();
+    let all_events: Vec<_> = tx_locations
+        .iter()
+        .zip(&events)
+        .map(|(&location, event)| (location, vec![event]))
+        .collect();
+    storage
+        .events_dal()
+        .save_events(MiniblockNumber(1), &all_events)
+        .await;
+
+    let mut l1_batch = create_l1_batch(1);
+    l1_batch.l2_to_l1_logs = events
+        .iter()
+        .enumerate()
+        .map(|(i, event)| l1_message_log(i as u16, event))
+        .collect();
+    storage
+        .blocks_dal()
+        .insert_mock_l1_batch(&l1_batch)
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    storage
+        .eth_sender_dal()
+        .insert_bogus_confirmed_eth_tx(
+            L1BatchNumber(1),
+            AggregatedActionType::Execute,
+            H256::repeat_byte(0x01),
+            chrono::Utc::now(),
+        )
+        .await
+        .unwrap();
+}
+
+async fn withdrawals_with_status(
+    storage: &mut StorageProcessor<'_>,
+    status: WithdrawalStatus,
+) -> Vec<StoredWithdrawal> {
+    storage
+        .withdrawals_dal()
+        .get_withdrawals_with_status(status, 100)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn finalizing_withdrawals() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    seal_l1_batch_with_withdrawals(&mut storage).await;
+
+    let finalized = FinalizedSet::default();
+    let client = mock_client(finalized.clone());
+    let contracts_config = ContractsConfig::for_tests();
+    let finalizer = WithdrawalFinalizer::new(mock_config(), &contracts_config, client.clone());
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(
+        storage
+            .withdrawals_dal()
+            .get_last_indexed_l1_batch()
+            .await
+            .unwrap(),
+        Some(L1BatchNumber(1))
+    );
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].withdrawal.sender, L2_ETH_TOKEN_ADDRESS);
+    assert_eq!(sent[0].withdrawal.l2_message_index, 0);
+    assert_eq!(sent[0].withdrawal.message, b"eth withdrawal");
+    assert_eq!(
+        sent[1].withdrawal.sender,
+        contracts_config.l2_erc20_bridge_addr
+    );
+    assert_eq!(sent[1].withdrawal.l2_message_index, 2);
+    assert_eq!(sent[1].withdrawal.l1_batch_tx_index, 2);
+    assert_eq!(sent[1].withdrawal.message, b"erc20 withdrawal");
+    let tx_hash = sent[0].finalization_tx_hash.unwrap();
+    assert_eq!(sent[1].finalization_tx_hash, Some(tx_hash));
+    assert_eq!(client.sent_tx_count(), 1);
+
+    let finalized_withdrawals = finalized_by_tx(&client, &finalizer, tx_hash).await;
+    assert_eq!(
+        finalized_withdrawals,
+        [
+            (contracts_config.diamond_proxy_addr, 1, 0),
+            (contracts_config.l1_erc20_bridge_proxy_addr, 1, 2),
+        ]
+    );
+
+    // The transaction is in flight, so no new transactions should be sent.
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(client.sent_tx_count(), 1);
+    assert_eq!(
+        withdrawals_with_status(&mut storage, WithdrawalStatus::Sent)
+            .await
+            .len(),
+        2
+    );
+
+    finalized.lock().unwrap().extend(finalized_withdrawals);
+    client.execute_tx(tx_hash, true, 1);
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let finalized = withdrawals_with_status(&mut storage, WithdrawalStatus::Finalized).await;
+    assert_eq!(finalized.len(), 2);
+    assert!(finalized.iter().all(|withdrawal| withdrawal.attempts == 0));
+    assert_eq!(client.sent_tx_count(), 1);
+}
+
+#[tokio::test]
+async fn retrying_failed_finalization() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    seal_l1_batch_with_withdrawals(&mut storage).await;
+
+    let finalized = FinalizedSet::default();
+    let client = mock_client(finalized.clone());
+    let contracts_config = ContractsConfig::for_tests();
+    let finalizer = WithdrawalFinalizer::new(mock_config(), &contracts_config, client.clone());
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    let tx_hash = sent[0].finalization_tx_hash.unwrap();
+    // Only the ETH withdrawal is finalized by the transaction.
+    finalized
+        .lock()
+        .unwrap()
+        .insert((contracts_config.diamond_proxy_addr, 1, 0));
+    client.execute_tx(tx_hash, true, 1);
+
+    // The failed withdrawal should be retried in a new transaction right away.
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let finalized_withdrawals =
+        withdrawals_with_status(&mut storage, WithdrawalStatus::Finalized).await;
+    assert_eq!(finalized_withdrawals.len(), 1);
+    assert_eq!(finalized_withdrawals[0].withdrawal.l2_message_index, 0);
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].withdrawal.l2_message_index, 2);
+    assert_eq!(sent[0].attempts, 1);
+    assert_eq!(
+        sent[0].last_error.as_deref(),
+        Some("finalization call failed")
+    );
+    let retry_tx_hash = sent[0].finalization_tx_hash.unwrap();
+    assert_ne!(retry_tx_hash, tx_hash);
+    assert_eq!(client.sent_tx_count(), 2);
+
+    client.execute_tx(retry_tx_hash, false, 1);
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let failed = withdrawals_with_status(&mut storage, WithdrawalStatus::Failed).await;
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].attempts, 2);
+    assert_eq!(
+        failed[0].last_error.as_deref(),
+        Some("finalization transaction reverted")
+    );
+    // Withdrawals that have reached the max number of attempts are not retried.
+    assert_eq!(client.sent_tx_count(), 2);
+}
+
+#[tokio::test]
+async fn withdrawals_finalized_by_third_parties_are_not_sent() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    seal_l1_batch_with_withdrawals(&mut storage).await;
+
+    let contracts_config = ContractsConfig::for_tests();
+    let finalized = FinalizedSet::default();
+    finalized
+        .lock()
+        .unwrap()
+        .insert((contracts_config.l1_erc20_bridge_proxy_addr, 1, 2));
+    let client = mock_client(finalized);
+    let finalizer = WithdrawalFinalizer::new(mock_config(), &contracts_config, client.clone());
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let finalized = withdrawals_with_status(&mut storage, WithdrawalStatus::Finalized).await;
+    assert_eq!(finalized.len(), 1);
+    assert_eq!(finalized[0].withdrawal.l2_message_index, 2);
+    assert_eq!(finalized[0].finalization_tx_hash, None);
+
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 1);
+    let tx_hash = sent[0].finalization_tx_hash.unwrap();
+    assert_eq!(
+        finalized_by_tx(&client, &finalizer, tx_hash).await,
+        [(contracts_config.diamond_proxy_addr, 1, 0)]
+    );
+}
+
+#[tokio::test]
+async fn resending_pending_transaction_with_bumped_fees() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    seal_l1_batch_with_withdrawals(&mut storage).await;
+
+    let finalized = FinalizedSet::default();
+    let client = mock_client(finalized.clone());
+    let contracts_config = ContractsConfig::for_tests();
+    let config = WithdrawalFinalizerConfig {
+        resend_timeout_ms: 0,
+        ..mock_config()
+    };
+    let finalizer = WithdrawalFinalizer::new(config, &contracts_config, client.clone());
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    let tx_hash = sent[0].finalization_tx_hash.unwrap();
+    let nonce = sent[0].finalization_tx_nonce.unwrap();
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(client.sent_tx_count(), 2);
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 2);
+    let resent_tx_hash = sent[0].finalization_tx_hash.unwrap();
+    assert_ne!(resent_tx_hash, tx_hash);
+    assert_eq!(sent[1].finalization_tx_hash, Some(resent_tx_hash));
+    assert_eq!(sent[0].finalization_tx_nonce, Some(nonce));
+
+    let tx = client.get_tx(tx_hash, "test").await.unwrap().unwrap();
+    let resent_tx = client
+        .get_tx(resent_tx_hash, "test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(resent_tx.nonce, tx.nonce);
+    assert_eq!(resent_tx.input, tx.input);
+    assert_eq!(resent_tx.max_fee_per_gas, Some(120.into()));
+    assert_eq!(resent_tx.max_priority_fee_per_gas, Some(12.into()));
+
+    // The original transaction is mined instead of the replacement one.
+    let finalized_withdrawals = finalized_by_tx(&client, &finalizer, tx_hash).await;
+    finalized.lock().unwrap().extend(finalized_withdrawals);
+    client.execute_tx(tx_hash, true, 1);
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let finalized = withdrawals_with_status(&mut storage, WithdrawalStatus::Finalized).await;
+    assert_eq!(finalized.len(), 2);
+    assert!(finalized.iter().all(|withdrawal| withdrawal.attempts == 0));
+    assert_eq!(client.sent_tx_count(), 2);
+}
+
+#[tokio::test]
+async fn missing_transaction_is_considered_dropped_after_grace_period() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    seal_l1_batch_with_withdrawals(&mut storage).await;
+
+    let client = mock_client(FinalizedSet::default());
+    let contracts_config = ContractsConfig::for_tests();
+    let finalizer = WithdrawalFinalizer::new(mock_config(), &contracts_config, client.clone());
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+
+    // Emulate a transaction unknown to the L1 node.
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    let ids: Vec<_> = sent.iter().map(|withdrawal| withdrawal.id).collect();
+    let nonce = sent[0].finalization_tx_nonce.unwrap();
+    let missing_tx_hash = H256::repeat_byte(0xee);
+    storage
+        .withdrawals_dal()
+        .mark_withdrawals_as_sent(&ids, missing_tx_hash, nonce)
+        .await
+        .unwrap();
+
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 2);
+    assert_eq!(sent[0].finalization_tx_hash, Some(missing_tx_hash));
+    assert_eq!(sent[0].attempts, 0);
+    assert_eq!(client.sent_tx_count(), 1);
+
+    let config = WithdrawalFinalizerConfig {
+        dropped_tx_grace_period_ms: 0,
+        ..mock_config()
+    };
+    let finalizer = WithdrawalFinalizer::new(config, &contracts_config, client.clone());
+    finalizer.loop_iteration(&mut storage).await.unwrap();
+    // Withdrawals are retried in a new transaction right away.
+    let sent = withdrawals_with_status(&mut storage, WithdrawalStatus::Sent).await;
+    assert_eq!(sent.len(), 2);
+    assert_ne!(sent[0].finalization_tx_hash, Some(missing_tx_hash));
+    assert_eq!(sent[0].attempts, 1);
+    assert_eq!(
+        sent[0].last_error.as_deref(),
+        Some("finalization transaction was dropped")
+    );
+    assert_eq!(client.sent_tx_count(), 2);
+}
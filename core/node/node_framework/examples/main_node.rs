@@ -56,7 +56,7 @@ fn main() -> anyhow::Result<()> {
     let log_format = vlog::log_format_from_env();
     let _guard = vlog::ObservabilityBuilder::new()
         .with_log_format(log_format)
-        .build();
+        .build()?;
 
     // Create the node with specified resource provider. We don't need to add any resources explicitly,
     // the task will request what they actually need. The benefit here is that we won't instantiate resources
@@ -0,0 +1,393 @@
+use anyhow::Context as _;
+use zksync_config::{ContractsConfig, ETHSenderConfig};
+use zksync_core::eth_sender::{Aggregator, EthTxAggregator, EthTxManager};
+use zksync_dal::ConnectionPool;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::{BoundEthInterfaceResource, DedicatedBoundEthInterfacesResource},
+        healthcheck::HealthCheckResource,
+        l1_tx_params::L1TxParamsResource,
+        object_store::ObjectStoreResource,
+        pools::MasterPoolResource,
+    },
+    resource::{Resource, ResourceCollection},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Checks whether the config specifies dedicated accounts for sending some types of L1 transactions.
+/// Mirrors account selection in `OperatorSigningClient::for_action()`.
+fn has_dedicated_senders(eth_sender_config: &ETHSenderConfig) -> bool {
+    if let Some(remote_signer) = &eth_sender_config.remote_signer {
+        remote_signer.prove_operator_address.is_some()
+            || remote_signer.execute_operator_address.is_some()
+    } else {
+        let sender_config = &eth_sender_config.sender;
+        sender_config.prove_private_key().is_some() || sender_config.execute_private_key().is_some()
+    }
+}
+
+/// Resolves L1 clients for the dedicated senders. Fails if dedicated senders are configured, but
+/// the clients for them are not provided, since otherwise their transactions would never be sent.
+async fn dedicated_senders(
+    node: &mut ServiceContext<'_>,
+    eth_sender_config: &ETHSenderConfig,
+) -> Result<DedicatedBoundEthInterfacesResource, WiringError> {
+    let resource = node
+        .get_resource::<DedicatedBoundEthInterfacesResource>()
+        .await;
+    match resource {
+        Some(resource) => Ok(resource),
+        None if has_dedicated_senders(eth_sender_config) => Err(WiringError::ResourceLacking(
+            DedicatedBoundEthInterfacesResource::resource_id(),
+        )),
+        None => Ok(DedicatedBoundEthInterfacesResource(vec![])),
+    }
+}
+
+/// Builder for the component aggregating L1 batches into L1 transactions (commit, prove and execute operations).
+/// The aggregator doesn't send transactions itself; this is done by [`EthTxManagerLayer`], which can run
+/// in a separate process.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `ObjectStoreResource`.
+/// - Resolves `BoundEthInterfaceResource`.
+/// - Resolves `DedicatedBoundEthInterfacesResource` if present; it's required if dedicated senders are configured.
+/// - Resolves `L1TxParamsResource` if publishing is configured to be postponed on high L1 base fee.
+/// - Adds `eth_tx_aggregator` to the node.
+#[derive(Debug)]
+pub struct EthTxAggregatorLayer {
+    eth_sender_config: ETHSenderConfig,
+    contracts_config: ContractsConfig,
+}
+
+impl EthTxAggregatorLayer {
+    pub fn new(eth_sender_config: ETHSenderConfig, contracts_config: ContractsConfig) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for EthTxAggregatorLayer {
+    fn layer_name(&self) -> &'static str {
+        "eth_tx_aggregator_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let object_store = node
+            .get_resource::<ObjectStoreResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                ObjectStoreResource::resource_id(),
+            ))?
+            .0;
+        let eth_client = node
+            .get_resource::<BoundEthInterfaceResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                BoundEthInterfaceResource::resource_id(),
+            ))?
+            .0;
+        let pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building eth_tx_aggregator pool")?;
+
+        let nonce = eth_client
+            .pending_nonce("eth_sender")
+            .await
+            .context("failed getting pending operator nonce")?;
+        let sender_config = self.eth_sender_config.sender;
+        let mut aggregator = Aggregator::new(sender_config.clone(), object_store);
+        if sender_config.base_fee_postpone_ratio.is_some() {
+            let gas_adjuster = node
+                .get_resource::<L1TxParamsResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    L1TxParamsResource::resource_id(),
+                ))?
+                .0;
+            aggregator = aggregator.with_gas_adjuster(gas_adjuster);
+        }
+        let mut aggregator = EthTxAggregator::new(
+            sender_config,
+            aggregator,
+            eth_client,
+            self.contracts_config.validator_timelock_addr,
+            self.contracts_config.l1_multicall3_addr,
+            self.contracts_config.diamond_proxy_addr,
+            nonce.as_u64(),
+        );
+        let dedicated_senders = dedicated_senders(&mut node, &self.eth_sender_config).await?;
+        for (action_type, client) in dedicated_senders.0 {
+            let nonce = client
+                .pending_nonce("eth_sender")
+                .await
+                .context("failed getting pending nonce for dedicated sender")?;
+            aggregator = aggregator.with_dedicated_sender(
+                action_type,
+                client.sender_account(),
+                nonce.as_u64(),
+            );
+        }
+
+        node.add_task(Box::new(EthTxAggregatorTask { aggregator, pool }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct EthTxAggregatorTask {
+    aggregator: EthTxAggregator,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for EthTxAggregatorTask {
+    fn name(&self) -> &'static str {
+        "eth_tx_aggregator"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.aggregator.run(self.pool, stop_receiver.0).await
+    }
+}
+
+/// Builder for the component signing and sending L1 transactions created by the aggregator,
+/// and tracking their status on L1.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `BoundEthInterfaceResource`.
+/// - Resolves `DedicatedBoundEthInterfacesResource` if present; it's required if dedicated senders are configured.
+/// - Resolves `L1TxParamsResource`.
+/// - Adds `eth_tx_manager` health check to the `ResourceCollection<HealthCheckResource>`.
+/// - Adds `eth_tx_manager` to the node.
+#[derive(Debug)]
+pub struct EthTxManagerLayer {
+    eth_sender_config: ETHSenderConfig,
+}
+
+impl EthTxManagerLayer {
+    pub fn new(eth_sender_config: ETHSenderConfig) -> Self {
+        Self { eth_sender_config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for EthTxManagerLayer {
+    fn layer_name(&self) -> &'static str {
+        "eth_tx_manager_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let eth_client = node
+            .get_resource::<BoundEthInterfaceResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                BoundEthInterfaceResource::resource_id(),
+            ))?
+            .0;
+        let gas_adjuster = node
+            .get_resource::<L1TxParamsResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                L1TxParamsResource::resource_id(),
+            ))?
+            .0;
+        let pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building eth_tx_manager pool")?;
+
+        let dedicated_senders = dedicated_senders(&mut node, &self.eth_sender_config).await?;
+        let mut manager =
+            EthTxManager::new(self.eth_sender_config.sender, gas_adjuster, eth_client);
+        for (_, client) in dedicated_senders.0 {
+            manager = manager.with_dedicated_sender(client);
+        }
+        let healthchecks = node
+            .get_resource_or_default::<ResourceCollection<HealthCheckResource>>()
+            .await;
+        healthchecks
+            .push(HealthCheckResource::new(manager.health_check()))
+            .expect("Wiring stage");
+        node.add_task(Box::new(EthTxManagerTask { manager, pool }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct EthTxManagerTask {
+    manager: EthTxManager,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for EthTxManagerTask {
+    fn name(&self) -> &'static str {
+        "eth_tx_manager"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.manager.run(self.pool, stop_receiver.0).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use zksync_config::configs::chain::{NetworkConfig, StateKeeperConfig};
+    use zksync_eth_client::{clients::MockEthereum, BoundEthInterface};
+    use zksync_types::{aggregated_operations::AggregatedActionType, Address, H256};
+
+    use super::*;
+    use crate::{
+        implementations::{
+            layers::l1_gas::SequencerL1GasLayer, resources::eth_interface::EthInterfaceResource,
+        },
+        service::ZkStackService,
+        testonly::{prepare_database, StopAfterLayer, TestResourceProvider},
+    };
+
+    fn mock_eth_client() -> Arc<MockEthereum> {
+        let eth_client = MockEthereum::default()
+            .with_fee_history(vec![1_000; 16])
+            .with_multicall_address(ContractsConfig::for_tests().l1_multicall3_addr);
+        Arc::new(eth_client)
+    }
+
+    fn test_provider(eth_client: Arc<MockEthereum>) -> TestResourceProvider {
+        let (pool_builder, object_store) = prepare_database(&NetworkConfig::for_tests());
+        TestResourceProvider::default()
+            .with_resource(MasterPoolResource::new(pool_builder))
+            .with_resource(ObjectStoreResource(object_store))
+            .with_resource(EthInterfaceResource(eth_client.clone()))
+            .with_resource(BoundEthInterfaceResource(eth_client))
+    }
+
+    fn test_aggregator_layer() -> EthTxAggregatorLayer {
+        EthTxAggregatorLayer::new(ETHSenderConfig::for_tests(), ContractsConfig::for_tests())
+    }
+
+    #[test]
+    fn running_aggregator_only() {
+        let eth_client = mock_eth_client();
+        let mut service = ZkStackService::new(test_provider(eth_client.clone())).unwrap();
+        service
+            .add_layer(test_aggregator_layer())
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        service.run().unwrap();
+
+        // Transactions are only sent by `eth_tx_manager`, which isn't a part of this process.
+        assert_eq!(eth_client.sent_tx_count(), 0);
+    }
+
+    #[test]
+    fn aggregator_wiring_fails_without_tx_params_if_postponing_publishing() {
+        let mut eth_sender_config = ETHSenderConfig::for_tests();
+        eth_sender_config.sender.base_fee_postpone_ratio = Some(1.5);
+
+        let mut service = ZkStackService::new(test_provider(mock_eth_client())).unwrap();
+        service
+            .add_layer(EthTxAggregatorLayer::new(
+                eth_sender_config,
+                ContractsConfig::for_tests(),
+            ))
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        let err = service.run().unwrap_err().to_string();
+        assert!(err.contains("weren't able to start"), "{err}");
+    }
+
+    #[test]
+    fn running_eth_tx_manager_with_gas_adjuster() {
+        let mut eth_sender_config = ETHSenderConfig::for_tests();
+        eth_sender_config.gas_adjuster.poll_period = 1;
+
+        let mut service = ZkStackService::new(test_provider(mock_eth_client())).unwrap();
+        service
+            .add_layer(SequencerL1GasLayer::new(
+                eth_sender_config.gas_adjuster,
+                &StateKeeperConfig::for_tests(),
+            ))
+            .add_layer(EthTxManagerLayer::new(eth_sender_config))
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        service.run().unwrap();
+    }
+
+    fn eth_sender_config_with_prove_sender() -> ETHSenderConfig {
+        let mut eth_sender_config = ETHSenderConfig::for_tests();
+        eth_sender_config.gas_adjuster.poll_period = 1;
+        eth_sender_config.sender.prove_operator_private_key = Some(H256::repeat_byte(0x22).into());
+        eth_sender_config
+    }
+
+    fn eth_sender_service(
+        provider: TestResourceProvider,
+        eth_sender_config: ETHSenderConfig,
+    ) -> ZkStackService {
+        let mut service = ZkStackService::new(provider).unwrap();
+        service
+            .add_layer(SequencerL1GasLayer::new(
+                eth_sender_config.gas_adjuster,
+                &StateKeeperConfig::for_tests(),
+            ))
+            .add_layer(EthTxAggregatorLayer::new(
+                eth_sender_config.clone(),
+                ContractsConfig::for_tests(),
+            ))
+            .add_layer(EthTxManagerLayer::new(eth_sender_config))
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        service
+    }
+
+    #[test]
+    fn running_eth_sender_with_dedicated_sender() {
+        let prove_client = MockEthereum::default().with_sender_account(Address::repeat_byte(0x22));
+        let prove_client: Arc<dyn BoundEthInterface> = Arc::new(prove_client);
+        let provider =
+            test_provider(mock_eth_client()).with_resource(DedicatedBoundEthInterfacesResource(
+                vec![(AggregatedActionType::PublishProofOnchain, prove_client)],
+            ));
+
+        let service = eth_sender_service(provider, eth_sender_config_with_prove_sender());
+        service.run().unwrap();
+    }
+
+    #[test]
+    fn eth_sender_wiring_fails_without_configured_dedicated_sender() {
+        let provider = test_provider(mock_eth_client());
+        let service = eth_sender_service(provider, eth_sender_config_with_prove_sender());
+        let err = service.run().unwrap_err().to_string();
+        assert!(err.contains("weren't able to start"), "{err}");
+    }
+
+    #[test]
+    fn eth_tx_manager_wiring_fails_without_tx_params() {
+        let mut service = ZkStackService::new(test_provider(mock_eth_client())).unwrap();
+        service.add_layer(EthTxManagerLayer::new(ETHSenderConfig::for_tests()));
+        let err = service.run().unwrap_err().to_string();
+        assert!(err.contains("weren't able to start"), "{err}");
+    }
+}
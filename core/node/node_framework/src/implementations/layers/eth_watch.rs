@@ -0,0 +1,164 @@
+use anyhow::Context as _;
+use zksync_config::{ContractsConfig, ETHWatchConfig};
+use zksync_contracts::governance_contract;
+use zksync_core::eth_watch::{EthHttpQueryClient, EthWatch};
+use zksync_dal::ConnectionPool;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::EthInterfaceResource, pools::MasterPoolResource,
+        state_keeper::MempoolResource,
+    },
+    resource::Resource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the L1 watcher, which processes priority operations and protocol upgrades
+/// submitted to the diamond proxy and the governance contracts.
+///
+/// ## Effects
+///
+/// - Resolves `EthInterfaceResource`.
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `MempoolResource` if it is present, so that priority operations reorged out on L1 are evicted
+///   from the state keeper mempool. Thus, this layer should be added after `MempoolIOLayer` if the node runs
+///   the state keeper.
+/// - Adds `eth_watch` to the node.
+#[derive(Debug)]
+pub struct EthWatchLayer {
+    eth_watch_config: ETHWatchConfig,
+    contracts_config: ContractsConfig,
+}
+
+impl EthWatchLayer {
+    pub fn new(eth_watch_config: ETHWatchConfig, contracts_config: ContractsConfig) -> Self {
+        Self {
+            eth_watch_config,
+            contracts_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for EthWatchLayer {
+    fn layer_name(&self) -> &'static str {
+        "eth_watch_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let client = node
+            .get_resource::<EthInterfaceResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                EthInterfaceResource::resource_id(),
+            ))?
+            .0;
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building eth_watch pool")?;
+
+        let diamond_proxy_addr = self.contracts_config.diamond_proxy_addr;
+        let eth_client = EthHttpQueryClient::new(
+            Box::new(client),
+            diamond_proxy_addr,
+            Some(self.contracts_config.governance_addr),
+            self.eth_watch_config.confirmations_for_eth_event,
+        );
+        let eth_watch = EthWatch::new(
+            diamond_proxy_addr,
+            Some(governance_contract()),
+            Box::new(eth_client),
+            &pool,
+            self.eth_watch_config.poll_interval(),
+        )
+        .await;
+        let eth_watch = match node.get_resource::<MempoolResource>().await {
+            Some(MempoolResource(mempool)) => eth_watch.with_mempool(mempool),
+            None => eth_watch,
+        };
+
+        node.add_task(Box::new(EthWatchTask { eth_watch, pool }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct EthWatchTask {
+    eth_watch: EthWatch,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for EthWatchTask {
+    fn name(&self) -> &'static str {
+        "eth_watch"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let Self {
+            mut eth_watch,
+            pool,
+        } = *self;
+        eth_watch.run(pool, stop_receiver.0).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use zksync_config::configs::chain::NetworkConfig;
+    use zksync_eth_client::clients::MockEthereum;
+
+    use super::*;
+    use crate::{
+        service::ZkStackService,
+        testonly::{prepare_database, StopAfterLayer, TestResourceProvider},
+    };
+
+    fn test_eth_watch_layer() -> EthWatchLayer {
+        let eth_watch_config = ETHWatchConfig {
+            // Allows to avoid querying finalized blocks, which isn't supported by the mock client.
+            confirmations_for_eth_event: Some(0),
+            eth_node_poll_interval: 10,
+        };
+        EthWatchLayer::new(eth_watch_config, ContractsConfig::for_tests())
+    }
+
+    #[test]
+    fn running_eth_watch() {
+        let (pool_builder, _) = prepare_database(&NetworkConfig::for_tests());
+        let provider = TestResourceProvider::default()
+            .with_resource(MasterPoolResource::new(pool_builder))
+            .with_resource(EthInterfaceResource(Arc::new(MockEthereum::default())));
+
+        let mut service = ZkStackService::new(provider).unwrap();
+        service
+            .add_layer(test_eth_watch_layer())
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        service.run().unwrap();
+    }
+
+    #[test]
+    fn eth_watch_wiring_fails_without_eth_client() {
+        let (pool_builder, _) = prepare_database(&NetworkConfig::for_tests());
+        let provider =
+            TestResourceProvider::default().with_resource(MasterPoolResource::new(pool_builder));
+
+        let mut service = ZkStackService::new(provider).unwrap();
+        service
+            .add_layer(test_eth_watch_layer())
+            .add_layer(StopAfterLayer(Duration::from_secs(1)));
+        let err = service.run().unwrap_err().to_string();
+        assert!(err.contains("weren't able to start"), "{err}");
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::{configs::chain::StateKeeperConfig, GasAdjusterConfig};
+use zksync_core::{fee_model::MainNodeFeeInputProvider, l1_gas_price::GasAdjuster};
+use zksync_eth_client::EthInterface;
+use zksync_types::fee_model::FeeModelConfig;
+
+use crate::{
+    implementations::resources::{
+        eth_interface::EthInterfaceResource, fee_input::FeeInputResource,
+        l1_tx_params::L1TxParamsResource,
+    },
+    resource::Resource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the L1 gas price estimation used by the main node. It creates a single `GasAdjuster`
+/// shared by the fee model of the sequencer and by the components sending transactions to L1.
+///
+/// ## Effects
+///
+/// - Resolves `EthInterfaceResource`.
+/// - Adds `FeeInputResource`.
+/// - Adds `L1TxParamsResource`.
+/// - Adds `gas_adjuster` to the node.
+#[derive(Debug)]
+pub struct SequencerL1GasLayer {
+    gas_adjuster_config: GasAdjusterConfig,
+    state_keeper_config: StateKeeperConfig,
+}
+
+impl SequencerL1GasLayer {
+    pub fn new(
+        gas_adjuster_config: GasAdjusterConfig,
+        state_keeper_config: &StateKeeperConfig,
+    ) -> Self {
+        Self {
+            gas_adjuster_config,
+            state_keeper_config: state_keeper_config.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for SequencerL1GasLayer {
+    fn layer_name(&self) -> &'static str {
+        "sequencer_l1_gas_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let client = node
+            .get_resource::<EthInterfaceResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                EthInterfaceResource::resource_id(),
+            ))?
+            .0;
+        let adjuster = GasAdjuster::new(client, self.gas_adjuster_config)
+            .await
+            .context("GasAdjuster::new()")?;
+        let gas_adjuster = Arc::new(adjuster);
+
+        let batch_fee_input_provider = MainNodeFeeInputProvider::new(
+            gas_adjuster.clone(),
+            FeeModelConfig::from_state_keeper_config(&self.state_keeper_config),
+        );
+        node.insert_resource(FeeInputResource(Arc::new(batch_fee_input_provider)))
+            .await?;
+        node.insert_resource(L1TxParamsResource(gas_adjuster.clone()))
+            .await?;
+
+        node.add_task(Box::new(GasAdjusterTask(gas_adjuster)));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct GasAdjusterTask(Arc<GasAdjuster<Arc<dyn EthInterface>>>);
+
+#[async_trait::async_trait]
+impl Task for GasAdjusterTask {
+    fn name(&self) -> &'static str {
+        "gas_adjuster"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
@@ -1,3 +1,10 @@
+pub mod eth_sender;
+pub mod eth_watch;
 pub mod healtcheck_server;
+pub mod l1_gas;
 pub mod metadata_calculator;
+pub mod pk_signing_eth_client;
 pub mod prometheus_exporter;
+pub mod query_eth_client;
+pub mod state_keeper;
+pub mod web3_api;
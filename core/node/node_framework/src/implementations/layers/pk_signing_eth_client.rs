@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::{ContractsConfig, ETHClientConfig, ETHSenderConfig};
+use zksync_core::eth_sender::EthTxAggregator;
+use zksync_eth_client::{clients::OperatorSigningClient, BoundEthInterface};
+
+use crate::{
+    implementations::resources::eth_interface::{
+        BoundEthInterfaceResource, DedicatedBoundEthInterfacesResource,
+    },
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the L1 client signing transactions with the operator private key
+/// or with the remote signer, if it's configured.
+///
+/// ## Effects
+///
+/// - Adds `BoundEthInterfaceResource`.
+/// - Adds `DedicatedBoundEthInterfacesResource` if dedicated accounts for proofs or executions are configured.
+#[derive(Debug)]
+pub struct PKSigningEthClientLayer {
+    eth_sender_config: ETHSenderConfig,
+    contracts_config: ContractsConfig,
+    eth_client_config: ETHClientConfig,
+}
+
+impl PKSigningEthClientLayer {
+    pub fn new(
+        eth_sender_config: ETHSenderConfig,
+        contracts_config: ContractsConfig,
+        eth_client_config: ETHClientConfig,
+    ) -> Self {
+        Self {
+            eth_sender_config,
+            contracts_config,
+            eth_client_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PKSigningEthClientLayer {
+    fn layer_name(&self) -> &'static str {
+        "pk_signing_eth_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let signing_client = OperatorSigningClient::from_config(
+            &self.eth_sender_config,
+            &self.contracts_config,
+            &self.eth_client_config,
+        )
+        .context("OperatorSigningClient::from_config()")?;
+        signing_client.check_signer().await?;
+        node.insert_resource(BoundEthInterfaceResource(Arc::new(signing_client)))
+            .await?;
+
+        let mut dedicated_clients = vec![];
+        for action_type in EthTxAggregator::DEDICATED_SENDER_ACTIONS {
+            let client = OperatorSigningClient::for_action(
+                &self.eth_sender_config,
+                &self.contracts_config,
+                &self.eth_client_config,
+                action_type,
+            )
+            .context("OperatorSigningClient::for_action()")?;
+            if let Some(client) = client {
+                client.check_signer().await?;
+                let client: Arc<dyn BoundEthInterface> = Arc::new(client);
+                dedicated_clients.push((action_type, client));
+            }
+        }
+        if !dedicated_clients.is_empty() {
+            node.insert_resource(DedicatedBoundEthInterfacesResource(dedicated_clients))
+                .await?;
+        }
+        Ok(())
+    }
+}
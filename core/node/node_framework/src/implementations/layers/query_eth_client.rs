@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::ETHClientConfig;
+use zksync_eth_client::clients::FailoverClient;
+
+use crate::{
+    implementations::resources::eth_interface::EthInterfaceResource,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the read-only L1 client, which is used by components that only observe L1.
+/// The client fails over between the main and fallback L1 nodes specified in the config.
+///
+/// ## Effects
+///
+/// - Adds `EthInterfaceResource`.
+#[derive(Debug)]
+pub struct QueryEthClientLayer {
+    config: ETHClientConfig,
+}
+
+impl QueryEthClientLayer {
+    pub fn new(config: ETHClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for QueryEthClientLayer {
+    fn layer_name(&self) -> &'static str {
+        "query_eth_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let query_client =
+            FailoverClient::from_config(&self.config).context("FailoverClient::from_config()")?;
+        node.insert_resource(EthInterfaceResource(Arc::new(query_client)))
+            .await?;
+        Ok(())
+    }
+}
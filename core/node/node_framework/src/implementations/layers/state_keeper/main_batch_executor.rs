@@ -0,0 +1,66 @@
+use anyhow::Context as _;
+use zksync_config::{configs::chain::StateKeeperConfig, DBConfig};
+use zksync_core::state_keeper::MainBatchExecutorBuilder;
+
+use crate::{
+    implementations::resources::{pools::MasterPoolResource, state_keeper::BatchExecutorResource},
+    resource::{Resource, Unique},
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the batch executor used by the main node, which executes transactions in the VM
+/// on top of the state keeper RocksDB cache.
+///
+/// ## Effects
+///
+/// - Resolves `MasterPoolResource`.
+/// - Adds `BatchExecutorResource`.
+#[derive(Debug)]
+pub struct MainBatchExecutorLayer {
+    db_config: DBConfig,
+    state_keeper_config: StateKeeperConfig,
+}
+
+impl MainBatchExecutorLayer {
+    pub fn new(db_config: DBConfig, state_keeper_config: StateKeeperConfig) -> Self {
+        Self {
+            db_config,
+            state_keeper_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MainBatchExecutorLayer {
+    fn layer_name(&self) -> &'static str {
+        "main_batch_executor_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building batch executor pool")?;
+
+        let config = &self.state_keeper_config;
+        let builder = MainBatchExecutorBuilder::new(
+            self.db_config.state_keeper_db_path.clone(),
+            pool,
+            config.max_allowed_l2_tx_gas_limit.into(),
+            config.save_call_traces,
+            config.upload_witness_inputs_to_gcs,
+            config.enum_index_migration_chunk_size(),
+            false,
+        );
+        node.insert_resource(BatchExecutorResource(Unique::new(Box::new(builder))))
+            .await?;
+        Ok(())
+    }
+}
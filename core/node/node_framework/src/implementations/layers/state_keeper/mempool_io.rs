@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::{
+    configs::chain::{MempoolConfig, NetworkConfig, StateKeeperConfig},
+    ContractsConfig,
+};
+use zksync_core::{
+    fee_model::BatchFeeModelInputProvider,
+    state_keeper::{MempoolFetcher, MempoolGuard, MempoolIO, MiniblockSealer, SequencerSealer},
+};
+use zksync_dal::ConnectionPool;
+use zksync_types::L2ChainId;
+
+use crate::{
+    implementations::resources::{
+        fee_input::FeeInputResource,
+        healthcheck::HealthCheckResource,
+        object_store::ObjectStoreResource,
+        pools::MasterPoolResource,
+        state_keeper::{ConditionalSealerResource, MempoolResource, StateKeeperIOResource},
+    },
+    resource::{Resource, ResourceCollection, Unique},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the mempool-based state keeper I/O used by the main node.
+///
+/// ## Effects
+///
+/// - Resolves `FeeInputResource`.
+/// - Resolves `MasterPoolResource`.
+/// - Resolves `ObjectStoreResource`.
+/// - Adds `StateKeeperIOResource`.
+/// - Adds `ConditionalSealerResource`.
+/// - Adds `MempoolResource`.
+/// - Adds `mempool_fetcher` health check to the `ResourceCollection<HealthCheckResource>`.
+/// - Adds `mempool_fetcher` and `miniblock_sealer` to the node.
+#[derive(Debug)]
+pub struct MempoolIOLayer {
+    zksync_network_id: L2ChainId,
+    contracts_config: ContractsConfig,
+    state_keeper_config: StateKeeperConfig,
+    mempool_config: MempoolConfig,
+}
+
+impl MempoolIOLayer {
+    pub fn new(
+        network_config: &NetworkConfig,
+        contracts_config: ContractsConfig,
+        state_keeper_config: StateKeeperConfig,
+        mempool_config: MempoolConfig,
+    ) -> Self {
+        Self {
+            zksync_network_id: network_config.zksync_network_id,
+            contracts_config,
+            state_keeper_config,
+            mempool_config,
+        }
+    }
+
+    async fn build_mempool_guard(&self, pool: &ConnectionPool) -> anyhow::Result<MempoolGuard> {
+        let mut storage = pool
+            .access_storage()
+            .await
+            .context("failed accessing storage")?;
+        let next_priority_id = storage.transactions_dal().next_priority_id().await;
+        let mempool = MempoolGuard::new(next_priority_id, self.mempool_config.capacity);
+        mempool.register_metrics();
+        Ok(mempool)
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for MempoolIOLayer {
+    fn layer_name(&self) -> &'static str {
+        "mempool_io_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let batch_fee_input_provider = node
+            .get_resource::<FeeInputResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(FeeInputResource::resource_id()))?
+            .0;
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let object_store = node
+            .get_resource::<ObjectStoreResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                ObjectStoreResource::resource_id(),
+            ))?
+            .0;
+
+        // Create the miniblock sealer task.
+        let (miniblock_sealer, miniblock_sealer_handle) = MiniblockSealer::new(
+            master_pool
+                .get_singleton()
+                .await
+                .context("failed building miniblock sealer pool")?,
+            self.state_keeper_config.miniblock_seal_queue_capacity,
+        );
+        node.add_task(Box::new(MiniblockSealerTask(miniblock_sealer)));
+
+        // Create the mempool fetcher task.
+        let mempool_fetcher_pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building mempool fetcher pool")?;
+        let mempool_guard = self.build_mempool_guard(&mempool_fetcher_pool).await?;
+        node.insert_resource(MempoolResource(mempool_guard.clone()))
+            .await?;
+        let mempool_fetcher = MempoolFetcher::new(
+            mempool_guard.clone(),
+            batch_fee_input_provider.clone(),
+            &self.mempool_config,
+        );
+        let healthchecks = node
+            .get_resource_or_default::<ResourceCollection<HealthCheckResource>>()
+            .await;
+        healthchecks
+            .push(HealthCheckResource::new(mempool_fetcher.health_check()))
+            .expect("Wiring stage");
+        node.add_task(Box::new(MempoolFetcherTask {
+            mempool_fetcher,
+            pool: mempool_fetcher_pool,
+        }));
+
+        // Create the I/O and the sealer used by the state keeper.
+        let io_pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building state keeper I/O pool")?;
+        let io = MempoolIO::new(
+            mempool_guard,
+            object_store,
+            miniblock_sealer_handle,
+            batch_fee_input_provider,
+            io_pool,
+            &self.state_keeper_config,
+            self.mempool_config.delay_interval(),
+            self.contracts_config.l2_erc20_bridge_addr,
+            self.state_keeper_config.validation_computational_gas_limit,
+            self.zksync_network_id,
+        )
+        .await
+        .context("failed initializing mempool I/O")?;
+        node.insert_resource(StateKeeperIOResource(Unique::new(Box::new(io))))
+            .await?;
+
+        let sealer = SequencerSealer::new(self.state_keeper_config);
+        node.insert_resource(ConditionalSealerResource(Arc::new(sealer)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct MiniblockSealerTask(MiniblockSealer);
+
+#[async_trait::async_trait]
+impl Task for MiniblockSealerTask {
+    fn name(&self) -> &'static str {
+        "miniblock_sealer"
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        // The sealer doesn't listen to the stop signal; instead, it stops once the miniblock sealer handle
+        // owned by the I/O is dropped, i.e. after the state keeper has stopped. This ensures that all miniblocks
+        // produced by the state keeper are persisted.
+        self.0.run().await
+    }
+}
+
+#[derive(Debug)]
+struct MempoolFetcherTask {
+    mempool_fetcher: MempoolFetcher<dyn BatchFeeModelInputProvider>,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for MempoolFetcherTask {
+    fn name(&self) -> &'static str {
+        "mempool_fetcher"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.mempool_fetcher.run(self.pool, stop_receiver.0).await
+    }
+}
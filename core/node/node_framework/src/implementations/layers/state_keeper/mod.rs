@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_core::state_keeper::{
+    seal_criteria::ConditionalSealer, L1BatchExecutorBuilder, StateKeeperIO, ZkSyncStateKeeper,
+};
+use zksync_dal::ConnectionPool;
+use zksync_storage::RocksDB;
+
+use crate::{
+    implementations::resources::{
+        pools::MasterPoolResource,
+        state_keeper::{BatchExecutorResource, ConditionalSealerResource, StateKeeperIOResource},
+    },
+    resource::{Resource, Unique},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+pub mod main_batch_executor;
+pub mod mempool_io;
+#[cfg(test)]
+mod tests;
+
+/// Builder for the state keeper. The state keeper itself is generic over its I/O, batch executor and sealer,
+/// so these must be provided by other layers (e.g., [`mempool_io::MempoolIOLayer`] and
+/// [`main_batch_executor::MainBatchExecutorLayer`]).
+///
+/// ## Effects
+///
+/// - Resolves `StateKeeperIOResource`.
+/// - Resolves `BatchExecutorResource`.
+/// - Resolves `ConditionalSealerResource`.
+/// - Resolves `MasterPoolResource`.
+/// - Adds `state_keeper` to the node.
+#[derive(Debug)]
+pub struct StateKeeperLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for StateKeeperLayer {
+    fn layer_name(&self) -> &'static str {
+        "state_keeper_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let io = node.get_resource::<StateKeeperIOResource>().await.ok_or(
+            WiringError::ResourceLacking(StateKeeperIOResource::resource_id()),
+        )?;
+        let io = take_unique::<StateKeeperIOResource, _>(io.0)?;
+        let batch_executor_base = node.get_resource::<BatchExecutorResource>().await.ok_or(
+            WiringError::ResourceLacking(BatchExecutorResource::resource_id()),
+        )?;
+        let batch_executor_base = take_unique::<BatchExecutorResource, _>(batch_executor_base.0)?;
+        let sealer = node
+            .get_resource::<ConditionalSealerResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                ConditionalSealerResource::resource_id(),
+            ))?
+            .0;
+        let master_pool =
+            node.get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?;
+        let pool = master_pool
+            .get_singleton()
+            .await
+            .context("failed building state keeper pool")?;
+
+        node.add_task(Box::new(StateKeeperTask {
+            io,
+            batch_executor_base,
+            sealer,
+            pool,
+        }));
+        Ok(())
+    }
+}
+
+fn take_unique<R: Resource, T: 'static + Send>(unique: Unique<T>) -> Result<T, WiringError> {
+    unique
+        .take()
+        .ok_or(WiringError::ResourceAlreadyTaken(R::resource_id()))
+}
+
+#[derive(Debug)]
+struct StateKeeperTask {
+    io: Box<dyn StateKeeperIO>,
+    batch_executor_base: Box<dyn L1BatchExecutorBuilder>,
+    sealer: Arc<dyn ConditionalSealer>,
+    pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Task for StateKeeperTask {
+    fn name(&self) -> &'static str {
+        "state_keeper"
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let state_keeper = ZkSyncStateKeeper::new(
+            stop_receiver.0,
+            self.io,
+            self.batch_executor_base,
+            Box::new(self.sealer),
+        );
+        // The migration future only returns on error.
+        let fee_address_migration = state_keeper.run_fee_address_migration(self.pool);
+        let result = tokio::select! {
+            result = state_keeper.run() => result,
+            result = fee_address_migration => result.context("fee address migration failed"),
+        };
+
+        // Wait for all the instances of RocksDB to be destroyed.
+        tokio::task::spawn_blocking(RocksDB::await_rocksdb_termination)
+            .await
+            .context("failed waiting for RocksDB instances to terminate")?;
+        result
+    }
+}
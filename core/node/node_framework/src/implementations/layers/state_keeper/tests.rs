@@ -0,0 +1,92 @@
+//! Tests for the state keeper layers.
+
+use std::{sync::Arc, time::Duration};
+
+use zksync_config::{
+    configs::chain::{MempoolConfig, NetworkConfig, StateKeeperConfig},
+    ContractsConfig, DBConfig,
+};
+
+use super::{
+    main_batch_executor::MainBatchExecutorLayer, mempool_io::MempoolIOLayer, StateKeeperLayer,
+};
+use crate::{
+    implementations::resources::{
+        fee_input::FeeInputResource, object_store::ObjectStoreResource, pools::MasterPoolResource,
+    },
+    service::ZkStackService,
+    testonly::{prepare_database, MockFeeInputProvider, StopAfterLayer, TestResourceProvider},
+};
+
+fn test_mempool_config() -> MempoolConfig {
+    MempoolConfig {
+        sync_interval_ms: 10,
+        sync_batch_size: 100,
+        capacity: 1_000,
+        stuck_tx_timeout: 10,
+        remove_stuck_txs: false,
+        delay_interval: 100,
+    }
+}
+
+fn sequencer_service(
+    provider: TestResourceProvider,
+    network_config: &NetworkConfig,
+    state_keeper_db_path: String,
+) -> ZkStackService {
+    let state_keeper_config = StateKeeperConfig::for_tests();
+    let db_config = DBConfig {
+        state_keeper_db_path,
+        merkle_tree: Default::default(),
+    };
+
+    let mut service = ZkStackService::new(provider).unwrap();
+    service
+        .add_layer(MempoolIOLayer::new(
+            network_config,
+            ContractsConfig::for_tests(),
+            state_keeper_config.clone(),
+            test_mempool_config(),
+        ))
+        .add_layer(MainBatchExecutorLayer::new(db_config, state_keeper_config))
+        .add_layer(StateKeeperLayer);
+    service
+}
+
+#[test]
+fn running_sequencer() {
+    let network_config = NetworkConfig::for_tests();
+    let (pool_builder, object_store) = prepare_database(&network_config);
+    let state_keeper_db = tempfile::TempDir::new().unwrap();
+    let provider = TestResourceProvider::default()
+        .with_resource(MasterPoolResource::new(pool_builder))
+        .with_resource(ObjectStoreResource(object_store))
+        .with_resource(FeeInputResource(Arc::new(MockFeeInputProvider)));
+
+    let mut service = sequencer_service(
+        provider,
+        &network_config,
+        state_keeper_db.path().to_str().unwrap().to_owned(),
+    );
+    service.add_layer(StopAfterLayer(Duration::from_secs(1)));
+    // The node should start, and then gracefully stop all tasks once `stop_after` finishes.
+    service.run().unwrap();
+}
+
+#[test]
+fn sequencer_wiring_fails_without_fee_input() {
+    let network_config = NetworkConfig::for_tests();
+    let (pool_builder, object_store) = prepare_database(&network_config);
+    let state_keeper_db = tempfile::TempDir::new().unwrap();
+    let provider = TestResourceProvider::default()
+        .with_resource(MasterPoolResource::new(pool_builder))
+        .with_resource(ObjectStoreResource(object_store));
+
+    let service = sequencer_service(
+        provider,
+        &network_config,
+        state_keeper_db.path().to_str().unwrap().to_owned(),
+    );
+    let err = service.run().unwrap_err().to_string();
+    assert!(err.contains("weren't able to start"), "{err}");
+}
@@ -0,0 +1,112 @@
+use anyhow::Context as _;
+use zksync_config::configs::api::Web3JsonRpcConfig;
+use zksync_state::PostgresStorageCaches;
+
+use crate::{
+    implementations::resources::{
+        pools::ReplicaPoolResource, web3_api::PostgresStorageCachesResource,
+    },
+    resource::Resource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Capacities of the Postgres storage caches, in bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct PostgresStorageCachesConfig {
+    pub factory_deps_cache_size: u64,
+    pub initial_writes_cache_size: u64,
+    /// If set to 0, the storage values cache is disabled.
+    pub latest_values_cache_size: u64,
+}
+
+impl From<&Web3JsonRpcConfig> for PostgresStorageCachesConfig {
+    fn from(config: &Web3JsonRpcConfig) -> Self {
+        Self {
+            factory_deps_cache_size: config.factory_deps_cache_size() as u64,
+            initial_writes_cache_size: config.initial_writes_cache_size() as u64,
+            latest_values_cache_size: config.latest_values_cache_size() as u64,
+        }
+    }
+}
+
+/// Builder for the storage caches used in VM execution by the API servers.
+///
+/// ## Effects
+///
+/// - Resolves `ReplicaPoolResource`.
+/// - Adds `PostgresStorageCachesResource`.
+/// - Adds `values_cache_updater` to the node if the storage values cache is enabled.
+#[derive(Debug)]
+pub struct PostgresStorageCachesLayer {
+    config: PostgresStorageCachesConfig,
+}
+
+impl PostgresStorageCachesLayer {
+    pub fn new(config: PostgresStorageCachesConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PostgresStorageCachesLayer {
+    fn layer_name(&self) -> &'static str {
+        "postgres_storage_caches_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let replica_pool = node.get_resource::<ReplicaPoolResource>().await.ok_or(
+            WiringError::ResourceLacking(ReplicaPoolResource::resource_id()),
+        )?;
+
+        let mut storage_caches = PostgresStorageCaches::new(
+            self.config.factory_deps_cache_size,
+            self.config.initial_writes_cache_size,
+        );
+        if self.config.latest_values_cache_size > 0 {
+            let pool = replica_pool
+                .get()
+                .await
+                .context("failed building values cache pool")?;
+            let values_cache_task = storage_caches.configure_storage_values_cache(
+                self.config.latest_values_cache_size,
+                pool,
+                node.runtime_handle().clone(),
+            );
+            node.add_task(Box::new(ValuesCacheUpdaterTask(Box::new(
+                values_cache_task,
+            ))));
+        }
+        node.insert_resource(PostgresStorageCachesResource(storage_caches))
+            .await?;
+        Ok(())
+    }
+}
+
+struct ValuesCacheUpdaterTask(Box<dyn FnOnce() -> anyhow::Result<()> + Send>);
+
+impl std::fmt::Debug for ValuesCacheUpdaterTask {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("ValuesCacheUpdaterTask")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl Task for ValuesCacheUpdaterTask {
+    fn name(&self) -> &'static str {
+        "values_cache_updater"
+    }
+
+    async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let updater = tokio::task::spawn_blocking(self.0);
+        // The updater only terminates once all caches are dropped, which happens after the node is stopped.
+        // Thus, we don't wait for it after receiving a stop signal.
+        tokio::select! {
+            res = updater => res.context("values cache updater panicked")?,
+            _ = stop_receiver.0.changed() => Ok(()),
+        }
+    }
+}
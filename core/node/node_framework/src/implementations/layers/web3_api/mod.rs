@@ -0,0 +1,8 @@
+//! Layers for the Web3 API: the shared VM execution machinery (storage caches, the transaction sender)
+//! and the HTTP / WS JSON-RPC servers built on top of it.
+
+pub mod caches;
+pub mod server;
+#[cfg(test)]
+mod tests;
+pub mod tx_sender;
@@ -0,0 +1,197 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use anyhow::Context as _;
+use zksync_core::api_server::web3::{
+    backend_jsonrpsee::access_control::AccessControl, state::InternalApiConfig, ApiBuilder,
+    Namespace,
+};
+
+use crate::{
+    implementations::resources::{
+        pools::ReplicaPoolResource,
+        web3_api::{TxSenderResource, VmConcurrencyBarrierResource},
+    },
+    resource::Resource,
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Optional parameters of the Web3 API server. Parameters that are not set use the `ApiBuilder` defaults.
+#[derive(Debug, Default)]
+pub struct Web3ServerOptionalConfig {
+    pub namespaces: Option<Vec<Namespace>>,
+    pub filters_limit: Option<usize>,
+    pub subscriptions_limit: Option<usize>,
+    pub batch_request_size_limit: Option<usize>,
+    pub response_body_size_limit: Option<usize>,
+    pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub polling_interval: Option<Duration>,
+    pub tree_api_url: Option<String>,
+    pub access_control: Option<AccessControl>,
+}
+
+impl Web3ServerOptionalConfig {
+    fn apply(self, mut api_builder: ApiBuilder) -> ApiBuilder {
+        if let Some(namespaces) = self.namespaces {
+            api_builder = api_builder.enable_api_namespaces(namespaces);
+        }
+        if let Some(filters_limit) = self.filters_limit {
+            api_builder = api_builder.with_filter_limit(filters_limit);
+        }
+        if let Some(subscriptions_limit) = self.subscriptions_limit {
+            api_builder = api_builder.with_subscriptions_limit(subscriptions_limit);
+        }
+        if let Some(batch_request_size_limit) = self.batch_request_size_limit {
+            api_builder = api_builder.with_batch_request_size_limit(batch_request_size_limit);
+        }
+        if let Some(response_body_size_limit) = self.response_body_size_limit {
+            api_builder = api_builder.with_response_body_size_limit(response_body_size_limit);
+        }
+        if let Some(limit) = self.websocket_requests_per_minute_limit {
+            api_builder = api_builder.with_websocket_requests_per_minute_limit(limit);
+        }
+        if let Some(polling_interval) = self.polling_interval {
+            api_builder = api_builder.with_polling_interval(polling_interval);
+        }
+        if let Some(access_control) = self.access_control {
+            api_builder = api_builder.with_access_control(access_control);
+        }
+        api_builder.with_tree_api(self.tree_api_url)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Transport {
+    Http,
+    Ws,
+}
+
+impl Transport {
+    fn task_name(self) -> &'static str {
+        match self {
+            Self::Http => "web3_http_server",
+            Self::Ws => "web3_ws_server",
+        }
+    }
+}
+
+/// Builder for the HTTP or WS JSON-RPC server. Both servers can be added to the same node; they will share
+/// the transaction sender and the VM concurrency limiter.
+///
+/// ## Effects
+///
+/// - Resolves `ReplicaPoolResource`.
+/// - Resolves `TxSenderResource`.
+/// - Resolves `VmConcurrencyBarrierResource`.
+/// - Adds `web3_http_server` or `web3_ws_server` to the node.
+#[derive(Debug)]
+pub struct Web3ServerLayer {
+    transport: Transport,
+    port: u16,
+    internal_api_config: InternalApiConfig,
+    optional_config: Web3ServerOptionalConfig,
+}
+
+impl Web3ServerLayer {
+    pub fn http(
+        port: u16,
+        internal_api_config: InternalApiConfig,
+        optional_config: Web3ServerOptionalConfig,
+    ) -> Self {
+        Self {
+            transport: Transport::Http,
+            port,
+            internal_api_config,
+            optional_config,
+        }
+    }
+
+    pub fn ws(
+        port: u16,
+        internal_api_config: InternalApiConfig,
+        optional_config: Web3ServerOptionalConfig,
+    ) -> Self {
+        Self {
+            transport: Transport::Ws,
+            port,
+            internal_api_config,
+            optional_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for Web3ServerLayer {
+    fn layer_name(&self) -> &'static str {
+        match self.transport {
+            Transport::Http => "web3_http_server_layer",
+            Transport::Ws => "web3_ws_server_layer",
+        }
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let replica_pool_resource = node.get_resource::<ReplicaPoolResource>().await.ok_or(
+            WiringError::ResourceLacking(ReplicaPoolResource::resource_id()),
+        )?;
+        let tx_sender = node
+            .get_resource::<TxSenderResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(TxSenderResource::resource_id()))?
+            .0;
+        let vm_barrier = node
+            .get_resource::<VmConcurrencyBarrierResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                VmConcurrencyBarrierResource::resource_id(),
+            ))?
+            .0;
+        let replica_pool = replica_pool_resource
+            .get()
+            .await
+            .context("failed building API server pool")?;
+        let last_miniblock_pool = replica_pool_resource
+            .get_singleton()
+            .await
+            .context("failed building last miniblock pool")?;
+
+        let mut api_builder = ApiBuilder::jsonrpsee_backend(self.internal_api_config, replica_pool)
+            .with_last_miniblock_pool(last_miniblock_pool)
+            .with_tx_sender(tx_sender, vm_barrier);
+        api_builder = match self.transport {
+            Transport::Http => api_builder.http(self.port),
+            Transport::Ws => api_builder.ws(self.port),
+        };
+        let api_builder = self.optional_config.apply(api_builder);
+
+        node.add_task(Box::new(Web3ApiTask {
+            transport: self.transport,
+            api_builder,
+        }));
+        Ok(())
+    }
+}
+
+/// Task running the API server. The server is only built once the stop signal receiver is available.
+#[derive(Debug)]
+struct Web3ApiTask {
+    transport: Transport,
+    api_builder: ApiBuilder,
+}
+
+#[async_trait::async_trait]
+impl Task for Web3ApiTask {
+    fn name(&self) -> &'static str {
+        self.transport.task_name()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let server_handles = self.api_builder.build(stop_receiver.0).await?;
+        let tasks = server_handles
+            .tasks
+            .into_iter()
+            .map(|task| async move { task.await.context("API server task panicked")? });
+        futures::future::try_join_all(tasks).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! Tests for the Web3 API layers.
+
+use std::{sync::Arc, time::Duration};
+
+use zksync_config::{
+    configs::{
+        api::Web3JsonRpcConfig,
+        chain::{NetworkConfig, StateKeeperConfig},
+    },
+    ContractsConfig,
+};
+use zksync_core::api_server::{tx_sender::TxSenderConfig, web3::state::InternalApiConfig};
+use zksync_dal::connection::ConnectionPoolBuilder;
+
+use super::{
+    caches::{PostgresStorageCachesConfig, PostgresStorageCachesLayer},
+    server::{Web3ServerLayer, Web3ServerOptionalConfig},
+    tx_sender::TxSenderLayer,
+};
+use crate::{
+    implementations::resources::fee_input::FeeInputResource,
+    service::ZkStackService,
+    testonly::{prepare_database, MockFeeInputProvider, StopAfterLayer, TestResourceProvider},
+};
+
+fn test_provider(pool_builder: &ConnectionPoolBuilder) -> TestResourceProvider {
+    TestResourceProvider::default()
+        .with_pools(pool_builder)
+        .with_resource(FeeInputResource(Arc::new(MockFeeInputProvider)))
+}
+
+fn test_tx_sender_layer(network_config: &NetworkConfig) -> TxSenderLayer {
+    let web3_config = Web3JsonRpcConfig::for_tests();
+    let state_keeper_config = StateKeeperConfig::for_tests();
+    let tx_sender_config = TxSenderConfig::new(
+        &state_keeper_config,
+        &web3_config,
+        network_config.zksync_network_id,
+    );
+    TxSenderLayer::new(tx_sender_config, 4).with_sealer(state_keeper_config)
+}
+
+#[test]
+fn running_http_and_ws_servers() {
+    let network_config = NetworkConfig::for_tests();
+    let (pool_builder, _) = prepare_database(&network_config);
+    let web3_config = Web3JsonRpcConfig::for_tests();
+    let internal_api_config =
+        InternalApiConfig::new(&network_config, &web3_config, &ContractsConfig::for_tests());
+
+    let mut service = ZkStackService::new(test_provider(&pool_builder)).unwrap();
+    service
+        .add_layer(PostgresStorageCachesLayer::new(
+            PostgresStorageCachesConfig::from(&web3_config),
+        ))
+        .add_layer(test_tx_sender_layer(&network_config))
+        .add_layer(Web3ServerLayer::http(
+            0,
+            internal_api_config.clone(),
+            Web3ServerOptionalConfig::default(),
+        ))
+        .add_layer(Web3ServerLayer::ws(
+            0,
+            internal_api_config,
+            Web3ServerOptionalConfig {
+                subscriptions_limit: Some(10),
+                polling_interval: Some(Duration::from_millis(50)),
+                ..Web3ServerOptionalConfig::default()
+            },
+        ))
+        .add_layer(StopAfterLayer(Duration::from_secs(1)));
+    // Both servers should share the VM concurrency barrier and stop gracefully.
+    service.run().unwrap();
+}
+
+#[test]
+fn tx_sender_wiring_fails_without_storage_caches() {
+    let network_config = NetworkConfig::for_tests();
+    let (pool_builder, _) = prepare_database(&network_config);
+
+    let mut service = ZkStackService::new(test_provider(&pool_builder)).unwrap();
+    service
+        .add_layer(test_tx_sender_layer(&network_config))
+        .add_layer(StopAfterLayer(Duration::from_secs(1)));
+    let err = service.run().unwrap_err().to_string();
+    assert!(err.contains("weren't able to start"), "{err}");
+}
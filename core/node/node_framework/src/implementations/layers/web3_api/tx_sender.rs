@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use zksync_config::configs::chain::StateKeeperConfig;
+use zksync_core::{
+    api_server::{
+        execution_sandbox::VmConcurrencyLimiter,
+        tx_sender::{ApiContracts, TxSenderBuilder, TxSenderConfig},
+    },
+    fee_model::ApiFeeInputProvider,
+    state_keeper::SequencerSealer,
+};
+
+use crate::{
+    implementations::resources::{
+        fee_input::FeeInputResource,
+        pools::{MasterPoolResource, ReplicaPoolResource},
+        web3_api::{
+            PostgresStorageCachesResource, TxSenderResource, VmConcurrencyBarrierResource,
+            VmConcurrencyLimiterResource,
+        },
+    },
+    resource::Resource,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Builder for the transaction sender shared by the Web3 API servers. Transactions are either persisted
+/// to the master database, or (if configured using [`Self::with_tx_proxy()`]) proxied to the main node.
+/// Transactions are checked against the sequencer seal criteria only if configured using [`Self::with_sealer()`].
+///
+/// ## Effects
+///
+/// - Resolves `ReplicaPoolResource`.
+/// - Resolves `MasterPoolResource` unless the transaction proxy is used.
+/// - Resolves `FeeInputResource`.
+/// - Resolves `PostgresStorageCachesResource`.
+/// - Adds `VmConcurrencyLimiterResource`.
+/// - Adds `VmConcurrencyBarrierResource`.
+/// - Adds `TxSenderResource`.
+#[derive(Debug)]
+pub struct TxSenderLayer {
+    tx_sender_config: TxSenderConfig,
+    max_vm_concurrency: usize,
+    main_node_url: Option<String>,
+    state_keeper_config: Option<StateKeeperConfig>,
+}
+
+impl TxSenderLayer {
+    pub fn new(tx_sender_config: TxSenderConfig, max_vm_concurrency: usize) -> Self {
+        Self {
+            tx_sender_config,
+            max_vm_concurrency,
+            main_node_url: None,
+            state_keeper_config: None,
+        }
+    }
+
+    /// Configures the transaction sender to proxy transactions to the main node instead of persisting them.
+    /// This is used by the external node.
+    pub fn with_tx_proxy(mut self, main_node_url: String) -> Self {
+        self.main_node_url = Some(main_node_url);
+        self
+    }
+
+    /// Configures the transaction sender to reject transactions that cannot be executed by the sequencer
+    /// with the specified state keeper configuration. This is used by the main node.
+    pub fn with_sealer(mut self, state_keeper_config: StateKeeperConfig) -> Self {
+        self.state_keeper_config = Some(state_keeper_config);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TxSenderLayer {
+    fn layer_name(&self) -> &'static str {
+        "tx_sender_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        let replica_pool = node
+            .get_resource::<ReplicaPoolResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                ReplicaPoolResource::resource_id(),
+            ))?
+            .get()
+            .await
+            .context("failed building replica pool for tx sender")?;
+        let fee_input = node
+            .get_resource::<FeeInputResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(FeeInputResource::resource_id()))?
+            .0;
+        let storage_caches = node
+            .get_resource::<PostgresStorageCachesResource>()
+            .await
+            .ok_or(WiringError::ResourceLacking(
+                PostgresStorageCachesResource::resource_id(),
+            ))?
+            .0;
+        let mut tx_sender_builder =
+            TxSenderBuilder::new(self.tx_sender_config, replica_pool.clone());
+        if let Some(main_node_url) = &self.main_node_url {
+            tx_sender_builder = tx_sender_builder.with_tx_proxy(main_node_url);
+        } else {
+            let master_pool = node
+                .get_resource::<MasterPoolResource>()
+                .await
+                .ok_or(WiringError::ResourceLacking(
+                    MasterPoolResource::resource_id(),
+                ))?
+                .get()
+                .await
+                .context("failed building master pool for tx sender")?;
+            tx_sender_builder = tx_sender_builder.with_main_connection_pool(master_pool);
+        }
+        if let Some(state_keeper_config) = self.state_keeper_config {
+            let sealer = SequencerSealer::new(state_keeper_config);
+            tx_sender_builder = tx_sender_builder.with_sealer(Arc::new(sealer));
+        }
+
+        let (vm_concurrency_limiter, vm_barrier) =
+            VmConcurrencyLimiter::new(self.max_vm_concurrency);
+        let vm_concurrency_limiter = Arc::new(vm_concurrency_limiter);
+        let batch_fee_input_provider = ApiFeeInputProvider::new(fee_input, replica_pool);
+        let tx_sender = tx_sender_builder
+            .build(
+                Arc::new(batch_fee_input_provider),
+                vm_concurrency_limiter.clone(),
+                ApiContracts::load_from_disk(),
+                storage_caches,
+            )
+            .await;
+
+        node.insert_resource(VmConcurrencyLimiterResource(vm_concurrency_limiter))
+            .await?;
+        node.insert_resource(VmConcurrencyBarrierResource(vm_barrier))
+            .await?;
+        node.insert_resource(TxSenderResource(tx_sender)).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use zksync_eth_client::{BoundEthInterface, EthInterface};
+use zksync_types::aggregated_operations::AggregatedActionType;
+
+use crate::resource::Resource;
+
+/// A resource that provides L1 interface object to the service.
+#[derive(Debug, Clone)]
+pub struct EthInterfaceResource(pub Arc<dyn EthInterface>);
+
+impl Resource for EthInterfaceResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/eth_interface".into()
+    }
+}
+
+/// A resource that provides L1 interface with signing capabilities to the service.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceResource(pub Arc<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/bound_eth_interface".into()
+    }
+}
+
+/// A resource that provides L1 interfaces with signing capabilities for the accounts dedicated
+/// to sending specific types of L1 transactions.
+#[derive(Debug, Clone)]
+pub struct DedicatedBoundEthInterfacesResource(
+    pub Vec<(AggregatedActionType, Arc<dyn BoundEthInterface>)>,
+);
+
+impl Resource for DedicatedBoundEthInterfacesResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/dedicated_bound_eth_interfaces".into()
+    }
+}
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use zksync_core::l1_gas_price::L1TxParamsProvider;
+
+use crate::resource::Resource;
+
+/// Wrapper for the L1 transaction parameters provider used by the components sending transactions to L1.
+#[derive(Debug, Clone)]
+pub struct L1TxParamsResource(pub Arc<dyn L1TxParamsProvider>);
+
+impl Resource for L1TxParamsResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/l1_tx_params".into()
+    }
+}
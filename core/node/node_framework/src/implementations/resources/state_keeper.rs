@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use zksync_core::state_keeper::{
+    seal_criteria::ConditionalSealer, L1BatchExecutorBuilder, MempoolGuard, StateKeeperIO,
+};
+
+use crate::resource::{Resource, Unique};
+
+/// Wrapper for the state keeper I/O. Can only be consumed by a single state keeper.
+#[derive(Debug, Clone)]
+pub struct StateKeeperIOResource(pub Unique<Box<dyn StateKeeperIO>>);
+
+impl Resource for StateKeeperIOResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "state_keeper/io".into()
+    }
+}
+
+/// Wrapper for the L1 batch executor builder. Can only be consumed by a single state keeper.
+#[derive(Debug, Clone)]
+pub struct BatchExecutorResource(pub Unique<Box<dyn L1BatchExecutorBuilder>>);
+
+impl Resource for BatchExecutorResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "state_keeper/batch_executor".into()
+    }
+}
+
+/// Wrapper for the mempool used by the state keeper. Allows other components (e.g., `eth_watch`)
+/// to evict transactions from it.
+#[derive(Debug, Clone)]
+pub struct MempoolResource(pub MempoolGuard);
+
+impl Resource for MempoolResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "state_keeper/mempool".into()
+    }
+}
+
+/// Wrapper for the conditional sealer used by the state keeper.
+#[derive(Debug, Clone)]
+pub struct ConditionalSealerResource(pub Arc<dyn ConditionalSealer>);
+
+impl Resource for ConditionalSealerResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "common/conditional_sealer".into()
+    }
+}
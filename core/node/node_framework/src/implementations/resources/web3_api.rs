@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use zksync_core::api_server::{
+    execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
+    tx_sender::TxSender,
+};
+use zksync_state::PostgresStorageCaches;
+
+use crate::resource::Resource;
+
+/// Wrapper for the transaction sender used by the Web3 API servers.
+#[derive(Debug, Clone)]
+pub struct TxSenderResource(pub TxSender);
+
+impl Resource for TxSenderResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "api/tx_sender".into()
+    }
+}
+
+/// Wrapper for the limiter of concurrent VM invocations, shared by all components executing API requests in the VM.
+#[derive(Debug, Clone)]
+pub struct VmConcurrencyLimiterResource(pub Arc<VmConcurrencyLimiter>);
+
+impl Resource for VmConcurrencyLimiterResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "api/vm_concurrency_limiter".into()
+    }
+}
+
+/// Wrapper for the barrier attached to [`VmConcurrencyLimiterResource`]. API servers use it to wait
+/// for the VM invocations to finish during shutdown.
+#[derive(Debug, Clone)]
+pub struct VmConcurrencyBarrierResource(pub VmConcurrencyBarrier);
+
+impl Resource for VmConcurrencyBarrierResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "api/vm_concurrency_barrier".into()
+    }
+}
+
+/// Wrapper for the Postgres storage caches used in VM execution by the API.
+#[derive(Debug, Clone)]
+pub struct PostgresStorageCachesResource(pub PostgresStorageCaches);
+
+impl Resource for PostgresStorageCachesResource {
+    fn resource_id() -> crate::resource::ResourceId {
+        "api/postgres_storage_caches".into()
+    }
+}
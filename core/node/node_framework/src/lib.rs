@@ -22,4 +22,6 @@ pub mod implementations;
 pub mod resource;
 pub mod service;
 pub mod task;
+#[cfg(test)]
+mod testonly;
 pub mod wiring_layer;
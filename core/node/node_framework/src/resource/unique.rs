@@ -0,0 +1,42 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// Wrapper for resources that are not `Clone` and are expected to be consumed by a single task.
+///
+/// Since resources are required to be `Clone` (they can be requested by several tasks), such resources
+/// are stored in a shared slot. The first task that [takes](Self::take) the resource becomes its owner;
+/// subsequent attempts to take it return `None`.
+pub struct Unique<T: 'static + Send> {
+    inner: Arc<Mutex<Option<T>>>,
+}
+
+impl<T: 'static + Send> fmt::Debug for Unique<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Unique")
+            .field("taken", &self.inner.lock().unwrap().is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: 'static + Send> Clone for Unique<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: 'static + Send> Unique<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Some(inner))),
+        }
+    }
+
+    /// Takes the resource. Returns `None` if the resource was already taken.
+    pub fn take(&self) -> Option<T> {
+        self.inner.lock().unwrap().take()
+    }
+}
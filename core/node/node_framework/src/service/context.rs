@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use crate::{
     resource::{Resource, StoredResource},
-    service::ZkStackService,
-    task::Task,
+    service::{runner::TaskSpec, ZkStackService},
+    task::{Task, TaskOptions, TaskPolicy},
+    wiring_layer::WiringError,
 };
 
 /// An interface to the service's resources provided to the tasks during initialization.
@@ -28,8 +31,44 @@ impl<'a> ServiceContext<'a> {
 
     /// Adds a task to the service.
     /// Added tasks will be launched after the wiring process will be finished.
+    ///
+    /// The task is added with the default [`TaskOptions`], i.e. the node will shut down once the task exits.
     pub fn add_task(&mut self, task: Box<dyn Task>) -> &mut Self {
-        self.service.tasks.push(task);
+        self.service
+            .tasks
+            .push(TaskSpec::new(task, None, TaskOptions::default()));
+        self
+    }
+
+    /// Adds a task to the service with the specified options.
+    ///
+    /// Returns an error if the options specify [`TaskPolicy::Restart`], since the task cannot be recreated
+    /// after it exits; use [`Self::add_restartable_task()`] for such tasks.
+    pub fn add_task_with_options(
+        &mut self,
+        task: Box<dyn Task>,
+        options: TaskOptions,
+    ) -> Result<&mut Self, WiringError> {
+        if matches!(options.policy, TaskPolicy::Restart(_)) {
+            return Err(WiringError::UnsupportedTaskOptions {
+                task: task.name(),
+                reason: "restart policy requires a task factory; use `add_restartable_task()`",
+            });
+        }
+        self.service.tasks.push(TaskSpec::new(task, None, options));
+        Ok(self)
+    }
+
+    /// Adds a task that is created using the provided factory. The factory is invoked once during this call,
+    /// and then once per each restart of the task, if the `options` specify [`TaskPolicy::Restart`].
+    pub fn add_restartable_task<F>(&mut self, factory: F, options: TaskOptions) -> &mut Self
+    where
+        F: Fn() -> Box<dyn Task> + Send + Sync + 'static,
+    {
+        let task = factory();
+        self.service
+            .tasks
+            .push(TaskSpec::new(task, Some(Arc::new(factory)), options));
         self
     }
 
@@ -93,9 +132,75 @@ impl<'a> ServiceContext<'a> {
         resource
     }
 
+    /// Adds a resource to the service.
+    ///
+    /// Returns an error if the resource with the same ID is already available, either because it was inserted
+    /// by another layer, or because it can be provided by the resource provider.
+    pub async fn insert_resource<T: Resource>(&mut self, resource: T) -> Result<(), WiringError> {
+        let id = T::resource_id();
+        if self.service.resources.contains_key(&id) {
+            return Err(WiringError::ResourceAlreadyProvided(id));
+        }
+        if let Some(provided) = self.service.resource_provider.get_resource(&id).await {
+            // Cache the provided resource so that it's not requested from the provider again.
+            self.service.resources.insert(id.clone(), provided);
+            return Err(WiringError::ResourceAlreadyProvided(id));
+        }
+        self.service.resources.insert(id, Box::new(resource));
+        Ok(())
+    }
+
     /// Attempts to retrieve the resource with the specified name.
     /// If the resource is not available, it is created using `T::default()`.
     pub async fn get_resource_or_default<T: Resource + Clone + Default>(&mut self) -> T {
         self.get_resource_or_insert_with(T::default).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        service::StopReceiver, task::RestartPolicy, testonly::TestResourceProvider,
+        wiring_layer::WiringLayer,
+    };
+
+    #[derive(Debug)]
+    struct NoopTask;
+
+    #[async_trait::async_trait]
+    impl Task for NoopTask {
+        fn name(&self) -> &'static str {
+            "noop"
+        }
+
+        async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct RestartingTaskLayer;
+
+    #[async_trait::async_trait]
+    impl WiringLayer for RestartingTaskLayer {
+        fn layer_name(&self) -> &'static str {
+            "restarting_task_layer"
+        }
+
+        async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+            let options =
+                TaskOptions::default().with_policy(TaskPolicy::Restart(RestartPolicy::new(1)));
+            node.add_task_with_options(Box::new(NoopTask), options)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn restart_policy_is_rejected_for_non_restartable_task() {
+        let mut service = ZkStackService::new(TestResourceProvider::default()).unwrap();
+        service.add_layer(RestartingTaskLayer);
+        let err = service.run().unwrap_err().to_string();
+        assert!(err.contains("weren't able to start"), "{err}");
+    }
+}
@@ -1,16 +1,18 @@
 use std::{collections::HashMap, fmt};
 
-use futures::{future::BoxFuture, FutureExt};
-use tokio::{runtime::Runtime, sync::watch};
+use tokio::runtime::Runtime;
+use zksync_health_check::ReactiveHealthCheck;
 
+use self::runner::{TaskRunner, TaskSpec};
 pub use self::{context::ServiceContext, stop_receiver::StopReceiver};
 use crate::{
-    resource::{ResourceId, ResourceProvider, StoredResource},
-    task::Task,
+    implementations::resources::healthcheck::HealthCheckResource,
+    resource::{ResourceCollection, ResourceId, ResourceProvider, StoredResource},
     wiring_layer::{WiringError, WiringLayer},
 };
 
 mod context;
+mod runner;
 mod stop_receiver;
 
 /// "Manager" class for a set of tasks. Collects all the resources and tasks,
@@ -23,11 +25,15 @@ mod stop_receiver;
 ///   - invokes a `wire` method on each added wiring layer. If any of the layers fails,
 ///     the service will return an error. If no layers have added a task, the service will
 ///     also return an error.
-///   - waits for any of the tasks to finish.
-///   - sends stop signal to all the tasks.
-///   - waits for the remaining tasks to finish.
+///   - supervises the tasks according to their [`TaskPolicy`](crate::task::TaskPolicy) until a task exit
+///     requires the node to stop (by default, any task exit does).
+///   - sends stop signal to the remaining tasks in the order of their shutdown priority, and waits for them
+///     to finish (aborting the tasks that don't stop within their shutdown timeout, if one is configured).
 ///   - calls `after_node_shutdown` hook for every task that has provided it.
-///   - returns the result of the task that has finished.
+///   - returns the result of the task that has caused the node to stop.
+///
+/// The service adds a `tasks` health check to `ResourceCollection<HealthCheckResource>`, which reports the state
+/// of each task, including the number of restarts and the last error.
 pub struct ZkStackService {
     /// Primary source of resources for tasks.
     resource_provider: Box<dyn ResourceProvider>,
@@ -36,10 +42,8 @@ pub struct ZkStackService {
     /// List of wiring layers.
     layers: Vec<Box<dyn WiringLayer>>,
     /// Tasks added to the service.
-    tasks: Vec<Box<dyn Task>>,
+    tasks: Vec<TaskSpec>,
 
-    /// Sender used to stop the tasks.
-    stop_sender: watch::Sender<bool>,
     /// Tokio runtime used to spawn tasks.
     runtime: Runtime,
 }
@@ -62,13 +66,11 @@ impl ZkStackService {
             .build()
             .unwrap();
 
-        let (stop_sender, _stop_receiver) = watch::channel(false);
         let self_ = Self {
             resource_provider: Box::new(resource_provider),
             resources: HashMap::default(),
             layers: Vec::new(),
             tasks: Vec::new(),
-            stop_sender,
             runtime,
         };
 
@@ -91,6 +93,14 @@ impl ZkStackService {
         let mut errors: Vec<(String, WiringError)> = Vec::new();
 
         let runtime_handle = self.runtime.handle().clone();
+        let (tasks_health_check, health_updater) = ReactiveHealthCheck::new("tasks");
+        runtime_handle.block_on(async {
+            ServiceContext::new(&mut self)
+                .get_resource_or_default::<ResourceCollection<HealthCheckResource>>()
+                .await
+                .push(HealthCheckResource::new(tasks_health_check))
+                .expect("Wiring stage");
+        });
         for layer in wiring_layers {
             let name = layer.layer_name().to_string();
             let task_result = runtime_handle.block_on(layer.wire(ServiceContext::new(&mut self)));
@@ -111,18 +121,7 @@ impl ZkStackService {
             anyhow::bail!("One or more task weren't able to start");
         }
 
-        let mut tasks = Vec::new();
-        for task in std::mem::take(&mut self.tasks) {
-            let name = task.name().to_string();
-            let after_node_shutdown = task.after_node_shutdown();
-            let task_future = Box::pin(task.run(self.stop_receiver()));
-            let task_repr = TaskRepr {
-                name,
-                task: Some(task_future),
-                after_node_shutdown,
-            };
-            tasks.push(task_repr);
-        }
+        let tasks = std::mem::take(&mut self.tasks);
         if tasks.is_empty() {
             anyhow::bail!("No tasks to run");
         }
@@ -132,75 +131,26 @@ impl ZkStackService {
             resource.stored_resource_wired();
         }
 
-        // Prepare tasks for running.
-        let rt_handle = self.runtime.handle().clone();
-        let join_handles: Vec<_> = tasks
-            .iter_mut()
-            .map(|task| {
-                let task = task.task.take().expect(
-                    "Tasks are created by the node and must be Some prior to calling this method",
-                );
-                rt_handle.spawn(task).fuse()
-            })
-            .collect();
-
-        // Run the tasks until one of them exits.
-        // TODO (QIT-24): wrap every task into a timeout to prevent hanging.
-        let (resolved, idx, remaining) = self
-            .runtime
-            .block_on(futures::future::select_all(join_handles));
-        let task_name = tasks[idx].name.clone();
-        let failure = match resolved {
-            Ok(Ok(())) => {
-                tracing::info!("Task {task_name} completed");
-                false
-            }
-            Ok(Err(err)) => {
-                tracing::error!("Task {task_name} exited with an error: {err}");
-                true
-            }
-            Err(_) => {
-                tracing::error!("Task {task_name} panicked");
-                true
-            }
-        };
+        // Run the tasks until one of them requires the node to stop.
+        let mut runner = TaskRunner::new(self.runtime.handle().clone(), tasks, health_updater);
+        let outcome = self.runtime.block_on(runner.supervise());
 
-        // Send stop signal to remaining tasks and wait for them to finish.
+        // Stop remaining tasks and wait for them to finish.
         // Given that we are shutting down, we do not really care about returned values.
-        self.stop_sender.send(true).ok();
-        self.runtime.block_on(futures::future::join_all(remaining));
+        self.runtime.block_on(runner.shutdown());
 
         // Call after_node_shutdown hooks.
         let local_set = tokio::task::LocalSet::new();
-        let join_handles = tasks.iter_mut().filter_map(|task| {
-            task.after_node_shutdown
-                .take()
-                .map(|task| local_set.spawn_local(task))
-        });
+        let join_handles = runner
+            .take_shutdown_hooks()
+            .into_iter()
+            .map(|hook| local_set.spawn_local(hook));
         local_set.block_on(&self.runtime, futures::future::join_all(join_handles));
 
-        if failure {
-            anyhow::bail!("Task {task_name} failed");
+        if outcome.failed {
+            anyhow::bail!("Task {} failed", outcome.task_name);
         } else {
             Ok(())
         }
     }
-
-    pub(crate) fn stop_receiver(&self) -> StopReceiver {
-        StopReceiver(self.stop_sender.subscribe())
-    }
-}
-
-struct TaskRepr {
-    name: String,
-    task: Option<BoxFuture<'static, anyhow::Result<()>>>,
-    after_node_shutdown: Option<BoxFuture<'static, ()>>,
-}
-
-impl fmt::Debug for TaskRepr {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TaskRepr")
-            .field("name", &self.name)
-            .finish_non_exhaustive()
-    }
 }
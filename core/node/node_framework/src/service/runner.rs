@@ -0,0 +1,535 @@
+//! Supervision of the tasks launched by [`ZkStackService`](super::ZkStackService).
+
+use std::{fmt, sync::Arc};
+
+use futures::future::BoxFuture;
+use serde::Serialize;
+use tokio::{
+    runtime::Handle,
+    sync::{mpsc, watch},
+    task::{AbortHandle, JoinError, JoinHandle},
+};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater};
+
+use super::StopReceiver;
+use crate::task::{Task, TaskOptions, TaskPolicy};
+
+type TaskFactory = Arc<dyn Fn() -> Box<dyn Task> + Send + Sync>;
+
+/// Task added to the service together with the information on how to supervise it.
+pub(crate) struct TaskSpec {
+    name: &'static str,
+    task: Box<dyn Task>,
+    factory: Option<TaskFactory>,
+    options: TaskOptions,
+}
+
+impl fmt::Debug for TaskSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskSpec")
+            .field("name", &self.name)
+            .field("restartable", &self.factory.is_some())
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TaskSpec {
+    pub(super) fn new(
+        task: Box<dyn Task>,
+        factory: Option<TaskFactory>,
+        options: TaskOptions,
+    ) -> Self {
+        Self {
+            name: task.name(),
+            task,
+            factory,
+            options,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskState {
+    Running,
+    /// The task has exited and is waiting for a restart.
+    Restarting,
+    /// The task has exited successfully on its own.
+    Finished,
+    /// The task has exited with an error or panicked, and won't be restarted.
+    Failed,
+    /// The task was stopped or aborted during the node shutdown.
+    Stopped,
+}
+
+/// Health details for a single task reported by the runner.
+#[derive(Debug, Serialize)]
+struct TaskHealthDetails {
+    name: &'static str,
+    state: TaskState,
+    restarts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+}
+
+#[derive(Debug)]
+enum TaskEvent {
+    Exited {
+        idx: usize,
+        result: Result<anyhow::Result<()>, JoinError>,
+    },
+    RestartDue(usize),
+}
+
+struct TaskSlot {
+    name: &'static str,
+    options: TaskOptions,
+    factory: Option<TaskFactory>,
+    stop_sender: watch::Sender<bool>,
+    /// Handle to a Tokio task that either waits for the task to finish, or for the backoff before a restart to elapse.
+    watcher: Option<JoinHandle<()>>,
+    abort_handle: Option<AbortHandle>,
+    after_node_shutdown: Option<BoxFuture<'static, ()>>,
+    state: TaskState,
+    restarts: usize,
+    last_error: Option<String>,
+}
+
+/// Result of supervising the tasks.
+#[derive(Debug)]
+pub(super) struct RunOutcome {
+    /// Name of the task that has caused the node to stop.
+    pub task_name: &'static str,
+    pub failed: bool,
+}
+
+/// Launches tasks and applies their [`TaskPolicy`] once they exit. Each task receives its own stop signal,
+/// which allows stopping tasks in the order determined by their [`TaskOptions`].
+pub(super) struct TaskRunner {
+    rt_handle: Handle,
+    slots: Vec<TaskSlot>,
+    events_sender: mpsc::UnboundedSender<TaskEvent>,
+    events_receiver: mpsc::UnboundedReceiver<TaskEvent>,
+    health_updater: HealthUpdater,
+}
+
+impl fmt::Debug for TaskRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let task_names: Vec<_> = self.slots.iter().map(|slot| slot.name).collect();
+        f.debug_struct("TaskRunner")
+            .field("tasks", &task_names)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TaskRunner {
+    /// Creates a runner and launches all the provided tasks on the specified runtime.
+    pub(super) fn new(
+        rt_handle: Handle,
+        tasks: Vec<TaskSpec>,
+        health_updater: HealthUpdater,
+    ) -> Self {
+        let (events_sender, events_receiver) = mpsc::unbounded_channel();
+        let mut this = Self {
+            rt_handle,
+            slots: Vec::with_capacity(tasks.len()),
+            events_sender,
+            events_receiver,
+            health_updater,
+        };
+
+        for spec in tasks {
+            let (stop_sender, _) = watch::channel(false);
+            this.slots.push(TaskSlot {
+                name: spec.name,
+                options: spec.options,
+                factory: spec.factory,
+                stop_sender,
+                watcher: None,
+                abort_handle: None,
+                after_node_shutdown: None,
+                state: TaskState::Running,
+                restarts: 0,
+                last_error: None,
+            });
+            this.spawn(this.slots.len() - 1, spec.task);
+        }
+        this.report_health(HealthStatus::Ready);
+        this
+    }
+
+    fn spawn(&mut self, idx: usize, task: Box<dyn Task>) {
+        let slot = &mut self.slots[idx];
+        // If a task is restarted, the hook of its latest instance is used.
+        if let Some(hook) = task.after_node_shutdown() {
+            slot.after_node_shutdown = Some(hook);
+        }
+        let stop_receiver = StopReceiver(slot.stop_sender.subscribe());
+        let task_handle = self.rt_handle.spawn(task.run(stop_receiver));
+        slot.abort_handle = Some(task_handle.abort_handle());
+
+        let events_sender = self.events_sender.clone();
+        slot.watcher = Some(self.rt_handle.spawn(async move {
+            let result = task_handle.await;
+            events_sender.send(TaskEvent::Exited { idx, result }).ok();
+        }));
+        slot.state = TaskState::Running;
+    }
+
+    fn has_running_tasks(&self) -> bool {
+        self.slots
+            .iter()
+            .any(|slot| matches!(slot.state, TaskState::Running | TaskState::Restarting))
+    }
+
+    fn report_health(&self, status: HealthStatus) {
+        let details: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| TaskHealthDetails {
+                name: slot.name,
+                state: slot.state,
+                restarts: slot.restarts,
+                last_error: slot.last_error.clone(),
+            })
+            .collect();
+        self.health_updater
+            .update(Health::from(status).with_details(details));
+    }
+
+    fn report_running_health(&self) {
+        let is_restarting = self
+            .slots
+            .iter()
+            .any(|slot| slot.state == TaskState::Restarting);
+        let status = if is_restarting {
+            HealthStatus::NotReady
+        } else {
+            HealthStatus::Ready
+        };
+        self.report_health(status);
+    }
+
+    /// Runs until a task exit requires stopping the node.
+    pub(super) async fn supervise(&mut self) -> RunOutcome {
+        loop {
+            let event = self
+                .events_receiver
+                .recv()
+                .await
+                .expect("events sender is held by the runner");
+            match event {
+                TaskEvent::RestartDue(idx) => {
+                    let factory = self.slots[idx]
+                        .factory
+                        .clone()
+                        .expect("restarts are only scheduled for restartable tasks");
+                    tracing::info!("Restarting task {}", self.slots[idx].name);
+                    self.spawn(idx, factory());
+                }
+                TaskEvent::Exited { idx, result } => {
+                    if let Some(outcome) = self.handle_task_exit(idx, result) {
+                        return outcome;
+                    }
+                }
+            }
+            self.report_running_health();
+        }
+    }
+
+    fn handle_task_exit(
+        &mut self,
+        idx: usize,
+        result: Result<anyhow::Result<()>, JoinError>,
+    ) -> Option<RunOutcome> {
+        let slot = &mut self.slots[idx];
+        let name = slot.name;
+        slot.watcher = None;
+        slot.abort_handle = None;
+
+        let failed = match result {
+            Ok(Ok(())) => {
+                tracing::info!("Task {name} completed");
+                false
+            }
+            Ok(Err(err)) => {
+                tracing::error!("Task {name} exited with an error: {err:#}");
+                slot.last_error = Some(format!("{err:#}"));
+                true
+            }
+            Err(_) => {
+                tracing::error!("Task {name} panicked");
+                slot.last_error = Some("task panicked".to_owned());
+                true
+            }
+        };
+        let exited_state = if failed {
+            TaskState::Failed
+        } else {
+            TaskState::Finished
+        };
+
+        match slot.options.policy {
+            TaskPolicy::FailFast => {
+                slot.state = exited_state;
+                Some(RunOutcome {
+                    task_name: name,
+                    failed,
+                })
+            }
+            TaskPolicy::Ignore => {
+                slot.state = exited_state;
+                tracing::warn!("Exit of task {name} is ignored according to its policy");
+                if self.has_running_tasks() {
+                    None
+                } else {
+                    tracing::info!("All tasks have exited");
+                    Some(RunOutcome {
+                        task_name: name,
+                        failed: false,
+                    })
+                }
+            }
+            TaskPolicy::Restart(policy) => {
+                if slot.restarts >= policy.max_restarts {
+                    tracing::error!(
+                        "Task {name} has exhausted all {} restarts",
+                        policy.max_restarts
+                    );
+                    slot.state = TaskState::Failed;
+                    return Some(RunOutcome {
+                        task_name: name,
+                        failed: true,
+                    });
+                }
+
+                slot.restarts += 1;
+                slot.state = TaskState::Restarting;
+                let backoff = policy.backoff(slot.restarts);
+                tracing::warn!(
+                    "Task {name} will be restarted in {backoff:?} (restart {} of {})",
+                    slot.restarts,
+                    policy.max_restarts
+                );
+                let events_sender = self.events_sender.clone();
+                let mut stop_receiver = slot.stop_sender.subscribe();
+                slot.watcher = Some(self.rt_handle.spawn(async move {
+                    tokio::select! {
+                        _ = tokio::time::sleep(backoff) => {
+                            events_sender.send(TaskEvent::RestartDue(idx)).ok();
+                        }
+                        _ = stop_receiver.changed() => { /* The node is shutting down */ }
+                    }
+                }));
+                None
+            }
+        }
+    }
+
+    /// Stops all remaining tasks. Tasks are stopped in groups according to their shutdown priority,
+    /// with each task given its shutdown timeout (if any) to stop; after that, the task is aborted.
+    pub(super) async fn shutdown(&mut self) {
+        let mut priorities: Vec<_> = self
+            .slots
+            .iter()
+            .map(|slot| slot.options.shutdown_priority)
+            .collect();
+        priorities.sort_unstable_by(|a, b| b.cmp(a));
+        priorities.dedup();
+
+        for priority in priorities {
+            let mut stopped_tasks = vec![];
+            for (idx, slot) in self.slots.iter_mut().enumerate() {
+                if slot.options.shutdown_priority != priority {
+                    continue;
+                }
+                slot.stop_sender.send_replace(true);
+                if let Some(watcher) = slot.watcher.take() {
+                    let abort_handle = slot.abort_handle.take();
+                    let name = slot.name;
+                    let timeout = slot.options.shutdown_timeout;
+                    stopped_tasks.push(async move {
+                        let Some(timeout) = timeout else {
+                            watcher.await.ok();
+                            return idx;
+                        };
+                        if tokio::time::timeout(timeout, watcher).await.is_err() {
+                            tracing::error!(
+                                "Task {name} hasn't stopped in {timeout:?} after a stop signal; aborting it"
+                            );
+                            if let Some(abort_handle) = abort_handle {
+                                abort_handle.abort();
+                            }
+                        }
+                        idx
+                    });
+                }
+            }
+
+            for idx in futures::future::join_all(stopped_tasks).await {
+                let slot = &mut self.slots[idx];
+                if matches!(slot.state, TaskState::Running | TaskState::Restarting) {
+                    slot.state = TaskState::Stopped;
+                }
+            }
+        }
+        self.report_health(HealthStatus::ShutDown);
+    }
+
+    /// Takes `after_node_shutdown` hooks for all tasks.
+    pub(super) fn take_shutdown_hooks(&mut self) -> Vec<BoxFuture<'static, ()>> {
+        self.slots
+            .iter_mut()
+            .filter_map(|slot| slot.after_node_shutdown.take())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use zksync_health_check::{CheckHealth, ReactiveHealthCheck};
+
+    use super::*;
+    use crate::task::RestartPolicy;
+
+    #[derive(Debug)]
+    struct FailingTask(Arc<AtomicUsize>);
+
+    #[async_trait::async_trait]
+    impl Task for FailingTask {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            anyhow::bail!("oops")
+        }
+    }
+
+    #[derive(Debug)]
+    struct WaitingTask;
+
+    #[async_trait::async_trait]
+    impl Task for WaitingTask {
+        fn name(&self) -> &'static str {
+            "waiting"
+        }
+
+        async fn run(self: Box<Self>, mut stop_receiver: StopReceiver) -> anyhow::Result<()> {
+            stop_receiver.0.changed().await.ok();
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct StuckTask;
+
+    #[async_trait::async_trait]
+    impl Task for StuckTask {
+        fn name(&self) -> &'static str {
+            "stuck"
+        }
+
+        async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+            futures::future::pending().await
+        }
+    }
+
+    fn restartable_task(counter: &Arc<AtomicUsize>, max_restarts: usize) -> TaskSpec {
+        let counter = counter.clone();
+        let factory: TaskFactory = Arc::new(move || Box::new(FailingTask(counter.clone())));
+        let policy = RestartPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..RestartPolicy::new(max_restarts)
+        };
+        let options = TaskOptions::default().with_policy(TaskPolicy::Restart(policy));
+        TaskSpec::new(factory(), Some(factory), options)
+    }
+
+    #[test]
+    fn restart_backoff() {
+        let policy = RestartPolicy::new(100);
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(5), Duration::from_secs(16));
+        assert_eq!(policy.backoff(7), Duration::from_secs(60));
+        assert_eq!(policy.backoff(100), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn restarting_task_until_restarts_are_exhausted() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let (health_check, health_updater) = ReactiveHealthCheck::new("tasks");
+        let tasks = vec![
+            restartable_task(&counter, 3),
+            TaskSpec::new(Box::new(WaitingTask), None, TaskOptions::default()),
+        ];
+        let mut runner = TaskRunner::new(Handle::current(), tasks, health_updater);
+
+        let outcome = runner.supervise().await;
+        assert_eq!(outcome.task_name, "failing");
+        assert!(outcome.failed);
+        // The initial run + 3 restarts
+        assert_eq!(counter.load(Ordering::SeqCst), 4);
+
+        runner.shutdown().await;
+        let health = health_check.check_health().await;
+        assert_eq!(health.status(), HealthStatus::ShutDown);
+        let details = serde_json::to_value(&health).unwrap();
+        let tasks = &details["details"];
+        assert_eq!(tasks[0]["state"], "failed");
+        assert_eq!(tasks[0]["restarts"], 3);
+        assert_eq!(tasks[0]["last_error"], "oops");
+        assert_eq!(tasks[1]["state"], "stopped");
+    }
+
+    #[tokio::test]
+    async fn ignoring_task_exit() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let (_health_check, health_updater) = ReactiveHealthCheck::new("tasks");
+        let ignore_options = TaskOptions::default().with_policy(TaskPolicy::Ignore);
+        let tasks = vec![
+            TaskSpec::new(Box::new(FailingTask(counter.clone())), None, ignore_options),
+            TaskSpec::new(Box::new(FailingTask(counter.clone())), None, ignore_options),
+        ];
+        let mut runner = TaskRunner::new(Handle::current(), tasks, health_updater);
+
+        let outcome = runner.supervise().await;
+        assert!(!outcome.failed);
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+        assert!(runner
+            .slots
+            .iter()
+            .all(|slot| slot.state == TaskState::Failed));
+    }
+
+    #[tokio::test]
+    async fn aborting_stuck_task_on_shutdown() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let (_health_check, health_updater) = ReactiveHealthCheck::new("tasks");
+        let stuck_options = TaskOptions::default()
+            .with_shutdown_timeout(Duration::from_millis(10))
+            .with_shutdown_priority(1);
+        let tasks = vec![
+            TaskSpec::new(Box::new(FailingTask(counter)), None, TaskOptions::default()),
+            TaskSpec::new(Box::new(StuckTask), None, stuck_options),
+            TaskSpec::new(Box::new(WaitingTask), None, TaskOptions::default()),
+        ];
+        let mut runner = TaskRunner::new(Handle::current(), tasks, health_updater);
+
+        let outcome = runner.supervise().await;
+        assert_eq!(outcome.task_name, "failing");
+        tokio::time::timeout(Duration::from_secs(5), runner.shutdown())
+            .await
+            .expect("shutdown hung");
+        assert_eq!(runner.slots[1].state, TaskState::Stopped);
+        assert_eq!(runner.slots[2].state, TaskState::Stopped);
+    }
+}
@@ -1,6 +1,8 @@
 //! Tasks define the "runnable" concept of the node, e.g. something that can be launched and runs until the node
 //! is stopped.
 
+use std::time::Duration;
+
 use futures::future::BoxFuture;
 
 use crate::service::StopReceiver;
@@ -13,9 +15,9 @@ pub trait Task: 'static + Send + Sync {
 
     /// Runs the task.
     ///
-    /// Once any of the task returns, the node will shutdown.
-    /// If the task returns an error, the node will spawn an error-level log message and will return a non-zero
-    /// exit code.
+    /// What happens once the task returns is determined by the [`TaskPolicy`] it was added with. By default,
+    /// the node will shutdown, and if the task returns an error, the node will spawn an error-level log message
+    /// and will return a non-zero exit code.
     ///
     /// `stop_receiver` argument contains a channel receiver that will change its value once the node requests
     /// a shutdown. Every task is expected to either await or periodically check the state of channel and stop
@@ -38,3 +40,91 @@ pub trait Task: 'static + Send + Sync {
         None
     }
 }
+
+/// Action taken by [`ZkStackService`](crate::service::ZkStackService) once a task exits on its own,
+/// i.e., not as a result of the node shutdown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskPolicy {
+    /// Shut down the node. If the task has returned an error or panicked, the node will exit with an error.
+    FailFast,
+    /// Restart the task with a backoff. Once the number of restarts is exhausted, the task is treated as
+    /// a fail-fast one.
+    ///
+    /// This policy can only be used for tasks added with [`ServiceContext::add_restartable_task()`].
+    ///
+    /// [`ServiceContext::add_restartable_task()`]: crate::service::ServiceContext::add_restartable_task()
+    Restart(RestartPolicy),
+    /// Log the task exit and continue running the node. If all the remaining tasks are ignored on exit
+    /// and have exited, the node will shut down.
+    Ignore,
+}
+
+/// Parameters of [`TaskPolicy::Restart`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RestartPolicy {
+    /// Maximum number of restarts during the node lifetime.
+    pub max_restarts: usize,
+    /// Delay before the first restart. Each following restart doubles the delay until it reaches `max_backoff`.
+    pub initial_backoff: Duration,
+    /// Maximum delay before a restart.
+    pub max_backoff: Duration,
+}
+
+impl RestartPolicy {
+    pub fn new(max_restarts: usize) -> Self {
+        Self {
+            max_restarts,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    /// Returns the delay before the specified restart (1-based).
+    pub(crate) fn backoff(&self, restart: usize) -> Duration {
+        let exponent = restart.saturating_sub(1).min(31) as u32;
+        self.initial_backoff
+            .saturating_mul(2_u32.pow(exponent))
+            .min(self.max_backoff)
+    }
+}
+
+/// Options of a task added to the node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaskOptions {
+    /// Action taken once the task exits on its own.
+    pub policy: TaskPolicy,
+    /// Time given to the task to stop after receiving a stop signal. After that, the task is aborted.
+    /// If not set (which is the default), the node waits for the task to stop indefinitely.
+    pub shutdown_timeout: Option<Duration>,
+    /// Tasks are stopped in the descending order of this priority; tasks with the same priority are stopped
+    /// concurrently. The next group of tasks receives a stop signal only after all tasks in the previous group
+    /// have stopped or were aborted.
+    pub shutdown_priority: i32,
+}
+
+impl Default for TaskOptions {
+    fn default() -> Self {
+        Self {
+            policy: TaskPolicy::FailFast,
+            shutdown_timeout: None,
+            shutdown_priority: 0,
+        }
+    }
+}
+
+impl TaskOptions {
+    pub fn with_policy(mut self, policy: TaskPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_shutdown_priority(mut self, priority: i32) -> Self {
+        self.shutdown_priority = priority;
+        self
+    }
+}
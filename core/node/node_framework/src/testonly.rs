@@ -0,0 +1,143 @@
+//! Test utilities shared by the tests for different wiring layers.
+
+use std::{collections::HashMap, fmt, sync::Arc, time::Duration};
+
+use zksync_config::configs::chain::NetworkConfig;
+use zksync_contracts::BaseSystemContracts;
+use zksync_core::{
+    fee_model::BatchFeeModelInputProvider,
+    genesis::{ensure_genesis_state, GenesisParams},
+};
+use zksync_dal::connection::{ConnectionPoolBuilder, TestTemplate};
+use zksync_object_store::{ObjectStore, ObjectStoreFactory};
+use zksync_types::{
+    fee_model::FeeParams, protocol_version::L1VerifierConfig,
+    system_contracts::get_system_smart_contracts, Address, ProtocolVersionId,
+};
+
+use crate::{
+    implementations::resources::pools::{MasterPoolResource, ReplicaPoolResource},
+    resource::{Resource, ResourceId, ResourceProvider, StoredResource},
+    service::{ServiceContext, StopReceiver},
+    task::Task,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+#[derive(Debug)]
+pub(crate) struct MockFeeInputProvider;
+
+impl BatchFeeModelInputProvider for MockFeeInputProvider {
+    fn get_fee_model_params(&self) -> FeeParams {
+        FeeParams::sensible_v1_default()
+    }
+}
+
+type ResourceFactory = Box<dyn Fn() -> Box<dyn StoredResource> + Send + Sync>;
+
+/// Resource provider returning a fixed set of resources.
+#[derive(Default)]
+pub(crate) struct TestResourceProvider {
+    resources: HashMap<ResourceId, ResourceFactory>,
+}
+
+impl fmt::Debug for TestResourceProvider {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TestResourceProvider")
+            .field("resources", &self.resources.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl TestResourceProvider {
+    /// Adds a resource to the provider. Each request for the resource receives a clone of it.
+    pub fn with_resource<T: Resource + Clone>(mut self, resource: T) -> Self {
+        self.resources.insert(
+            T::resource_id(),
+            Box::new(move || Box::new(resource.clone())),
+        );
+        self
+    }
+
+    /// Adds master and replica pool resources connected to the specified database.
+    pub fn with_pools(self, pool_builder: &ConnectionPoolBuilder) -> Self {
+        self.with_resource(MasterPoolResource::new(pool_builder.clone()))
+            .with_resource(ReplicaPoolResource::new(pool_builder.clone()))
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceProvider for TestResourceProvider {
+    async fn get_resource(&self, name: &ResourceId) -> Option<Box<dyn StoredResource>> {
+        self.resources.get(name).map(|factory| factory())
+    }
+}
+
+/// Layer adding a task that stops the node after a delay.
+#[derive(Debug)]
+pub(crate) struct StopAfterLayer(pub Duration);
+
+#[async_trait::async_trait]
+impl WiringLayer for StopAfterLayer {
+    fn layer_name(&self) -> &'static str {
+        "stop_after_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut node: ServiceContext<'_>) -> Result<(), WiringError> {
+        node.add_task(Box::new(StopAfterTask(self.0)));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct StopAfterTask(Duration);
+
+#[async_trait::async_trait]
+impl Task for StopAfterTask {
+    fn name(&self) -> &'static str {
+        "stop_after"
+    }
+
+    async fn run(self: Box<Self>, _stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        tokio::time::sleep(self.0).await;
+        Ok(())
+    }
+}
+
+/// Prepares a test database with the genesis state and returns a builder for pools connected to it.
+/// Uses a separate runtime since `ZkStackService` manages its own runtime.
+pub(crate) fn prepare_database(
+    network_config: &NetworkConfig,
+) -> (ConnectionPoolBuilder, Arc<dyn ObjectStore>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let pool_builder = TestTemplate::empty()
+            .unwrap()
+            .create_db_builder()
+            .await
+            .unwrap();
+        let pool = pool_builder.build().await.unwrap();
+        let mut storage = pool.access_storage().await.unwrap();
+        let genesis_params = GenesisParams {
+            first_validator: Address::repeat_byte(0x01),
+            protocol_version: ProtocolVersionId::latest(),
+            base_system_contracts: BaseSystemContracts::load_from_disk(),
+            system_contracts: get_system_smart_contracts(),
+            first_verifier_address: Address::zero(),
+            first_l1_verifier_config: L1VerifierConfig::default(),
+        };
+        ensure_genesis_state(
+            &mut storage,
+            network_config.zksync_network_id,
+            &genesis_params,
+        )
+        .await
+        .unwrap();
+
+        let object_store = ObjectStoreFactory::mock().create_store().await;
+        (pool_builder, object_store)
+    })
+}
@@ -21,6 +21,15 @@ pub trait WiringLayer: 'static + Send + Sync {
 pub enum WiringError {
     #[error("Resource {0} is not provided")]
     ResourceLacking(ResourceId),
+    #[error("Resource {0} is already provided")]
+    ResourceAlreadyProvided(ResourceId),
+    #[error("Resource {0} has already been taken by another task")]
+    ResourceAlreadyTaken(ResourceId),
+    #[error("Task {task} cannot be added with the specified options: {reason}")]
+    UnsupportedTaskOptions {
+        task: &'static str,
+        reason: &'static str,
+    },
     #[error(transparent)]
     Internal(#[from] anyhow::Error),
 }
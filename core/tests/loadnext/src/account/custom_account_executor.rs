@@ -0,0 +1,196 @@
+use std::convert::TryFrom;
+
+use zksync::{
+    error::ClientError, operations::SyncTransactionHandle, web3::ethabi, EthNamespaceClient,
+};
+use zksync_system_constants::CONTRACT_DEPLOYER_ADDRESS;
+use zksync_types::{
+    api::{BlockIdVariant, BlockNumber, TransactionRequest},
+    fee::Fee,
+    l2::L2Tx,
+    Address, Bytes, Eip712Domain, L2ChainId, Nonce, PackedEthSignature, U256,
+};
+use zksync_utils::bytecode::hash_bytecode;
+
+use crate::{
+    account::{tx_command_executor::SubmitResult, AccountLifespan, PaymasterFlow},
+    command::{IncorrectnessModifier, TxCommand},
+    constants::{CUSTOM_ACCOUNT_VALIDATION_GAS, MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE},
+    report::ReportLabel,
+};
+
+impl AccountLifespan {
+    pub(super) async fn execute_deploy_custom_account(
+        &mut self,
+        command: &TxCommand,
+    ) -> Result<SubmitResult, ClientError> {
+        let tx = self.build_deploy_custom_account(command).await?;
+        self.execute_submit(tx, command.modifier).await
+    }
+
+    /// Builds a transaction deploying `CustomAccount` via `ContractDeployer.createAccount()`.
+    async fn build_deploy_custom_account(&self, command: &TxCommand) -> Result<L2Tx, ClientError> {
+        // Corresponds to `AccountAbstractionVersion.Version1` in `ContractDeployer`.
+        const ACCOUNT_ABSTRACTION_VERSION: u8 = 1;
+
+        let wallet = &self.wallet.wallet;
+        let bytecode = self
+            .wallet
+            .custom_account_contract
+            .as_ref()
+            .expect("custom account contract is not loaded, although custom account transactions are enabled")
+            .bytecode
+            .clone();
+        // The only constructor argument is `violateValidationRules`.
+        let constructor_input = ethabi::encode(&[ethabi::Token::Bool(false)]);
+        let calldata = zksync_contracts::deployer_contract()
+            .function("createAccount")
+            .unwrap()
+            .encode_input(&[
+                ethabi::Token::FixedBytes(vec![0; 32]),
+                ethabi::Token::FixedBytes(hash_bytecode(&bytecode).as_bytes().to_vec()),
+                ethabi::Token::Bytes(constructor_input),
+                ethabi::Token::Uint(ACCOUNT_ABSTRACTION_VERSION.into()),
+            ])
+            .expect("failed to encode parameters when creating calldata");
+
+        let mut builder = wallet
+            .start_execute_contract()
+            .contract_address(CONTRACT_DEPLOYER_ADDRESS)
+            .calldata(calldata)
+            .factory_deps(vec![bytecode]);
+
+        let fee = builder
+            .estimate_fee(Some(
+                self.paymaster_params(None, MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into()),
+            ))
+            .await?;
+        builder = builder.paymaster_params(self.paymaster_params(None, fee.max_total_fee()));
+        builder = builder.fee(fee);
+
+        if let Some(nonce) = self.current_nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        let tx = builder.tx().await.map_err(Self::tx_creation_error)?;
+
+        Ok(self.apply_modifier(tx, command.modifier).await)
+    }
+
+    /// Executes the loadnext contract from the custom account deployed by this account. The transaction
+    /// is paid for by the test paymaster using the general flow.
+    pub(super) async fn execute_custom_account(
+        &mut self,
+        command: &TxCommand,
+    ) -> Result<SubmitResult, ClientError> {
+        let Some(&contract_address) = self.wallet.deployed_contract_address.get() else {
+            let label =
+                ReportLabel::skipped("Account haven't successfully deployed a contract yet");
+            return Ok(SubmitResult::ReportLabel(label));
+        };
+        let Some(&custom_account) = self.wallet.custom_account_address.get() else {
+            let label =
+                ReportLabel::skipped("Account haven't successfully deployed a custom account yet");
+            return Ok(SubmitResult::ReportLabel(label));
+        };
+        if self.custom_paymaster_address.is_none() {
+            let label = ReportLabel::skipped("Test paymaster is not deployed");
+            return Ok(SubmitResult::ReportLabel(label));
+        }
+
+        let nonce = match self.custom_account_nonce {
+            Some(nonce) => nonce,
+            None => {
+                let block_id = BlockIdVariant::BlockNumber(BlockNumber::Committed);
+                let nonce = self
+                    .wallet
+                    .wallet
+                    .provider
+                    .get_transaction_count(custom_account, Some(block_id))
+                    .await?;
+                Nonce(nonce.as_u32())
+            }
+        };
+
+        let raw_tx = self
+            .build_custom_account_tx(command, custom_account, contract_address, nonce)
+            .await?;
+        let wallet = self.wallet.wallet.clone();
+        let send_result = wallet
+            .provider
+            .send_raw_transaction(raw_tx)
+            .await
+            .map(|tx_hash| SyncTransactionHandle::new(tx_hash, &wallet.provider))
+            .map_err(ClientError::from);
+        let result = self.submit(command.modifier, send_result).await?;
+
+        self.custom_account_nonce = match &result {
+            SubmitResult::TxHash(_) => Some(nonce + 1),
+            // The nonce will be re-fetched for the next transaction, in case it was the reason of the rejection.
+            SubmitResult::ReportLabel(_) => None,
+        };
+        Ok(result)
+    }
+
+    /// Builds and signs a raw transaction from the custom account. `CustomAccount` expects the signature
+    /// to consist of the signed transaction hash followed by the account address.
+    async fn build_custom_account_tx(
+        &self,
+        command: &TxCommand,
+        custom_account: Address,
+        contract_address: Address,
+        nonce: Nonce,
+    ) -> Result<Bytes, ClientError> {
+        let wallet = &self.wallet.wallet;
+        let calldata = self.prepare_calldata_for_loadnext_contract();
+        let factory_deps = self.wallet.test_contract.factory_deps.clone();
+        let paymaster_flow = Some(PaymasterFlow::General);
+
+        let mut fee = wallet
+            .start_execute_contract()
+            .contract_address(contract_address)
+            .calldata(calldata.clone())
+            .factory_deps(factory_deps.clone())
+            .estimate_fee(Some(self.paymaster_params(
+                paymaster_flow,
+                MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into(),
+            )))
+            .await?;
+        fee.gas_limit += U256::from(CUSTOM_ACCOUNT_VALIDATION_GAS);
+        if command.modifier == IncorrectnessModifier::ZeroFee {
+            fee = Fee::default();
+        }
+        let paymaster_params = self.paymaster_params(paymaster_flow, fee.max_total_fee());
+
+        let tx = L2Tx::new(
+            contract_address,
+            calldata,
+            nonce,
+            fee,
+            custom_account,
+            U256::zero(),
+            Some(factory_deps),
+            paymaster_params,
+        );
+        let mut request = TransactionRequest::from(tx);
+
+        let chain_id = L2ChainId::try_from(self.config.l2_chain_id).unwrap();
+        let signed_hash =
+            PackedEthSignature::typed_data_to_signed_bytes(&Eip712Domain::new(chain_id), &request);
+        let signed_address = if command.modifier == IncorrectnessModifier::IncorrectSignature {
+            Address::random()
+        } else {
+            custom_account
+        };
+        let signature = [signed_hash.as_bytes(), signed_address.as_bytes()].concat();
+        request
+            .eip712_meta
+            .as_mut()
+            .expect("EIP-712 transaction without metadata")
+            .custom_signature = Some(signature);
+
+        // The ECDSA signature is ignored by the server since the custom signature is provided.
+        let raw_tx = request.get_signed_bytes(&PackedEthSignature::default(), chain_id);
+        Ok(Bytes(raw_tx))
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 use futures::{channel::mpsc, SinkExt};
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc::UnboundedReceiver, Mutex, RwLock};
 use zksync::{error::ClientError, operations::SyncTransactionHandle, HttpClient};
 use zksync_contracts::test_contracts::LoadnextContractExecutionParams;
 use zksync_types::{api::TransactionReceipt, Address, Nonce, H256, U256, U64};
@@ -17,11 +17,13 @@ use crate::{
     command::{ExpectedOutcome, IncorrectnessModifier, TxCommand, TxType},
     config::{LoadtestConfig, RequestLimiters},
     constants::{MAX_L1_TRANSACTIONS, POLLING_INTERVAL},
-    report::{Report, ReportBuilder, ReportLabel},
+    load_profile::ScheduledSlot,
+    report::{InclusionStage, Report, ReportBuilder, ReportLabel},
     utils::format_gwei,
 };
 
 mod api_request_executor;
+mod custom_account_executor;
 mod pubsub_executor;
 mod tx_command_executor;
 
@@ -35,15 +37,41 @@ pub enum ExecutionType {
     L2,
 }
 
+/// Flow used to pay for a transaction with the test paymaster deployed by the loadtest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaymasterFlow {
+    /// The paymaster exchanges ERC-20 tokens approved by the sender for ETH.
+    ApprovalBased,
+    /// The paymaster pays for the transaction from its own balance.
+    General,
+}
+
+/// Prefixes of the API server errors caused by the failed account or paymaster validation.
+const VALIDATION_ERROR_PREFIXES: &[&str] = &[
+    "failed to validate the transaction",
+    "failed paymaster validation",
+    "failed pre-paymaster preparation",
+    "not enough balance to cover the fee",
+];
+
 /// Tx that has been sent to the server but has not yet received a receipt
 #[derive(Debug, Clone)]
 struct InflightTx {
     tx_hash: H256,
     attempt: usize,
     start: Instant,
+    /// Moment when the tx was accepted by the server. May differ from `start` in the open-loop mode.
+    submitted_at: Instant,
     command: TxCommand,
 }
 
+/// Tx that has been included into a miniblock, but not yet into a sealed L1 batch.
+#[derive(Debug, Clone, Copy)]
+struct UnbatchedTx {
+    tx_hash: H256,
+    submitted_at: Instant,
+}
+
 /// Account lifespan represents a flow of a single account:
 /// it will send transactions, both correct and incorrect, and will check
 /// whether outcome matches expected one.
@@ -66,15 +94,23 @@ pub struct AccountLifespan {
     main_l2_token: Address,
     /// Address of the paymaster used in the test.
     paymaster_address: Address,
+    /// Address of the test paymaster deployed by the loadtest, if paymaster or custom account transactions are enabled.
+    custom_paymaster_address: Option<Address>,
     /// Channel for sending reports about performed operations.
     report_sink: mpsc::Sender<Report>,
     /// Pool of sent but not yet executed txs
     inflight_txs: VecDeque<InflightTx>,
+    /// Executed txs that are not yet included into an L1 batch. Shared among clones of the lifespan, so that
+    /// the remaining txs can be reported once the test is finished.
+    unbatched_txs: Arc<Mutex<VecDeque<UnbatchedTx>>>,
     /// Current account nonce, it is None at the beginning and will be set after the first transaction
     current_nonce: Option<Nonce>,
+    /// Current nonce of the custom account; similar to `current_nonce`
+    custom_account_nonce: Option<Nonce>,
 }
 
 impl AccountLifespan {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: &LoadtestConfig,
         contract_execution_params: LoadnextContractExecutionParams,
@@ -83,6 +119,7 @@ impl AccountLifespan {
         report_sink: mpsc::Sender<Report>,
         main_l2_token: Address,
         paymaster_address: Address,
+        custom_paymaster_address: Option<Address>,
     ) -> Self {
         Self {
             wallet: test_account,
@@ -93,15 +130,24 @@ impl AccountLifespan {
             main_l1_token: config.main_token,
             main_l2_token,
             paymaster_address,
+            custom_paymaster_address,
             report_sink,
             inflight_txs: Default::default(),
+            unbatched_txs: Default::default(),
             current_nonce: None,
+            custom_account_nonce: None,
         }
     }
 
-    pub async fn run(self, limiters: &RequestLimiters) {
+    /// Runs the account flow. If `schedule` is provided, transactions are sent according to the slots
+    /// received from it (the open-loop mode); otherwise, transactions are sent as fast as `max_inflight_txs` allows.
+    pub async fn run(
+        mut self,
+        limiters: &RequestLimiters,
+        schedule: Option<UnboundedReceiver<ScheduledSlot>>,
+    ) {
         let duration = self.config.duration();
-        let tx_execution_task = self.clone().run_tx_execution();
+        let tx_execution_task = self.clone().run_tx_execution(schedule);
         let api_requests_task = self.clone().run_api_requests_task(limiters);
 
         tokio::select! {
@@ -116,27 +162,86 @@ impl AccountLifespan {
             },
             () = tokio::time::sleep(duration) => {}
         }
+
+        if self.flush_unbatched_txs().await.is_err() {
+            tracing::trace!(
+                "Account {:?}: report collector is gone, unbatched txs are not reported",
+                self.wallet.wallet.address()
+            );
+        }
     }
 
-    async fn run_tx_execution(mut self) -> Result<(), Aborted> {
+    async fn run_tx_execution(
+        mut self,
+        mut schedule: Option<UnboundedReceiver<ScheduledSlot>>,
+    ) -> Result<(), Aborted> {
         // Every account starts with deploying a contract.
         let deploy_command = TxCommand {
             command_type: TxType::DeployContract,
             modifier: IncorrectnessModifier::None,
             to: Address::zero(),
             amount: U256::zero(),
+            replayed_tx: None,
         };
-        self.execute_command(deploy_command.clone()).await?;
+        self.execute_command(deploy_command.clone(), None).await?;
+        if TxType::CustomAccountExecute.is_enabled() {
+            let deploy_command = TxCommand {
+                command_type: TxType::DeployCustomAccount,
+                ..deploy_command
+            };
+            self.execute_command(deploy_command, None).await?;
+        }
         self.wait_for_all_inflight_tx().await?;
 
         let mut timer = tokio::time::interval(POLLING_INTERVAL);
         let mut l1_tx_count = 0;
         loop {
-            let command = self.generate_command();
-            let is_l1_transaction =
-                matches!(command.command_type, TxType::L1Execute | TxType::Deposit);
-            if is_l1_transaction && l1_tx_count >= MAX_L1_TRANSACTIONS {
-                continue; // Skip command to not run out of Ethereum on L1
+            let slot = if let Some(schedule) = &mut schedule {
+                // Check inflight txs while waiting for the next slot, so that inclusion latencies
+                // are measured even if the target rate is low.
+                let slot = loop {
+                    tokio::select! {
+                        slot = schedule.recv() => break slot,
+                        _ = timer.tick() => self.check_inflight_txs().await?,
+                    }
+                };
+                let Some(slot) = slot else {
+                    // The dispatcher has finished; no more transactions will be scheduled.
+                    return self.wait_for_all_inflight_tx().await;
+                };
+                Some(slot)
+            } else {
+                None
+            };
+
+            let scheduled_at = slot.as_ref().map(|slot| slot.scheduled_at);
+
+            let (command, is_l1_transaction) =
+                if let Some(replayed_tx) = slot.and_then(|slot| slot.replayed_tx) {
+                    (TxCommand::replay(replayed_tx), false)
+                } else {
+                    loop {
+                        let command = self.generate_command();
+                        let is_l1_transaction =
+                            matches!(command.command_type, TxType::L1Execute | TxType::Deposit);
+                        // Skip command to not run out of Ethereum on L1
+                        if !is_l1_transaction || l1_tx_count < MAX_L1_TRANSACTIONS {
+                            break (command, is_l1_transaction);
+                        }
+                    }
+                };
+
+            if scheduled_at.is_some() && self.inflight_txs.len() >= self.config.max_inflight_txs {
+                // In the open-loop mode, waiting for a free in-flight slot would make the schedule depend
+                // on the node responses, so the scheduled transaction is dropped instead.
+                tracing::debug!(
+                    "Account {:?}: dropping scheduled transaction since {} transactions are in flight",
+                    self.wallet.wallet.address(),
+                    self.inflight_txs.len()
+                );
+                let label = ReportLabel::skipped("Too many transactions in flight");
+                self.report(label, Duration::ZERO, 0, command).await?;
+                continue;
             }
 
             // The new transaction should be sent only if mempool is not full
@@ -145,7 +250,7 @@ impl AccountLifespan {
                     timer.tick().await;
                     self.check_inflight_txs().await?;
                 } else {
-                    self.execute_command(command).await?;
+                    self.execute_command(command, scheduled_at).await?;
                     l1_tx_count += u64::from(is_l1_transaction);
                     break;
                 }
@@ -178,10 +283,13 @@ impl AccountLifespan {
             let receipt = self.get_tx_receipt_for_committed_block(tx.tx_hash).await;
             match receipt {
                 Ok(Some(transaction_receipt)) => {
-                    let label = self.verify_receipt(
-                        &transaction_receipt,
-                        &tx.command.modifier.expected_outcome(),
-                    );
+                    let label = if tx.command.command_type == TxType::Replay {
+                        // Replayed txs may legitimately revert (e.g., if they reverted in the recorded traffic,
+                        // or if the target node state differs), so we only check that they were included.
+                        ReportLabel::done()
+                    } else {
+                        self.verify_receipt(&transaction_receipt, &tx.command)
+                    };
                     let gas_used = transaction_receipt.gas_used.unwrap_or(U256::zero());
                     let effective_gas_price = transaction_receipt
                         .effective_gas_price
@@ -196,6 +304,27 @@ impl AccountLifespan {
                     );
                     self.report(label, tx.start.elapsed(), tx.attempt, tx.command)
                         .await?;
+
+                    let inclusion_latency = tx.submitted_at.elapsed();
+                    self.report_inclusion(
+                        InclusionStage::Miniblock,
+                        ReportLabel::done(),
+                        inclusion_latency,
+                    )
+                    .await?;
+                    if transaction_receipt.l1_batch_number.is_some() {
+                        self.report_inclusion(
+                            InclusionStage::L1Batch,
+                            ReportLabel::done(),
+                            inclusion_latency,
+                        )
+                        .await?;
+                    } else {
+                        self.unbatched_txs.lock().await.push_back(UnbatchedTx {
+                            tx_hash: tx.tx_hash,
+                            submitted_at: tx.submitted_at,
+                        });
+                    }
                 }
                 other => {
                     tracing::trace!(
@@ -207,6 +336,8 @@ impl AccountLifespan {
                 }
             }
         }
+        self.check_unbatched_txs().await?;
+
         tracing::trace!(
             "Account {:?}: check_inflight_txs complete {:?}",
             self.wallet.wallet.address(),
@@ -215,18 +346,66 @@ impl AccountLifespan {
         Ok(())
     }
 
+    async fn check_unbatched_txs(&mut self) -> Result<(), Aborted> {
+        // Txs are included into L1 batches in the order of execution, so we can stop
+        // at the first tx that isn't included yet.
+        let unbatched_txs = self.unbatched_txs.clone();
+        let mut unbatched_txs = unbatched_txs.lock().await;
+        while let Some(tx) = unbatched_txs.front().copied() {
+            match self.get_tx_l1_batch_number(tx.tx_hash).await {
+                Ok(Some(_)) => {
+                    unbatched_txs.pop_front();
+                    let latency = tx.submitted_at.elapsed();
+                    self.report_inclusion(InclusionStage::L1Batch, ReportLabel::done(), latency)
+                        .await?;
+                }
+                other => {
+                    tracing::trace!(
+                        "Account {:?}: tx {:?} not yet included into L1 batch: {other:?}",
+                        self.wallet.wallet.address(),
+                        tx.tx_hash
+                    );
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports txs that were not included into an L1 batch by the end of the test as unfinished, so that
+    /// they are accounted for when checking L1 batch inclusion SLOs.
+    async fn flush_unbatched_txs(&mut self) -> Result<(), Aborted> {
+        self.check_unbatched_txs().await?;
+
+        let unbatched_txs = std::mem::take(&mut *self.unbatched_txs.lock().await);
+        for tx in unbatched_txs {
+            let label = ReportLabel::skipped(format!(
+                "transaction {:?} was not included into an L1 batch before the end of the test",
+                tx.tx_hash
+            ));
+            self.report_inclusion(InclusionStage::L1Batch, label, tx.submitted_at.elapsed())
+                .await?;
+        }
+        Ok(())
+    }
+
     fn verify_receipt(
         &self,
         transaction_receipt: &TransactionReceipt,
-        expected_outcome: &ExpectedOutcome,
+        command: &TxCommand,
     ) -> ReportLabel {
+        let expected_outcome = &command.modifier.expected_outcome();
         match expected_outcome {
             ExpectedOutcome::TxSucceed if transaction_receipt.status == U64::one() => {
-                // If it was a successful `DeployContract` transaction, set the contract
+                // If it was a successful `DeployContract` or `DeployCustomAccount` transaction, set the contract
                 // address for subsequent usage by `Execute`.
                 if let Some(address) = transaction_receipt.contract_address {
                     // An error means that the contract is already initialized.
-                    self.wallet.deployed_contract_address.set(address).ok();
+                    if command.command_type == TxType::DeployCustomAccount {
+                        self.wallet.custom_account_address.set(address).ok();
+                    } else {
+                        self.wallet.deployed_contract_address.set(address).ok();
+                    }
                 }
 
                 // Transaction succeed and it should have.
@@ -251,14 +430,20 @@ impl AccountLifespan {
     /// If command fails due to the network/API error, it will be retried multiple times
     /// before considering it completely failed. Such an approach makes us a bit more resilient to
     /// volatile errors such as random connection drop or insufficient fee error.
-    async fn execute_command(&mut self, command: TxCommand) -> Result<(), Aborted> {
+    ///
+    /// If `scheduled_at` is provided, the reported latency is measured from it instead of the start of the attempt.
+    async fn execute_command(
+        &mut self,
+        command: TxCommand,
+        scheduled_at: Option<Instant>,
+    ) -> Result<(), Aborted> {
         // We consider API errors to be somewhat likely, thus we will retry the operation if it fails
         // due to connection issues.
         const MAX_RETRIES: usize = 3;
 
         let mut attempt = 0;
         loop {
-            let start = Instant::now();
+            let start = scheduled_at.unwrap_or_else(Instant::now);
             let result = self.execute_tx_command(&command).await;
 
             let submit_result = match result {
@@ -277,7 +462,7 @@ impl AccountLifespan {
                 }
                 Err(err) => {
                     // Other kinds of errors should not be handled, we will just report them.
-                    SubmitResult::ReportLabel(ReportLabel::failed(err.to_string()))
+                    SubmitResult::ReportLabel(Self::rejection_label(&err, err.to_string()))
                 }
             };
 
@@ -286,6 +471,7 @@ impl AccountLifespan {
                     self.inflight_txs.push_back(InflightTx {
                         tx_hash,
                         start,
+                        submitted_at: Instant::now(),
                         attempt,
                         command: command.clone(),
                     });
@@ -304,6 +490,21 @@ impl AccountLifespan {
         Ok(())
     }
 
+    /// Creates a label for an unexpected error returned by the API server. Errors caused by the account
+    /// or paymaster validation are reported separately from other failures.
+    fn rejection_label(err: &ClientError, error: String) -> ReportLabel {
+        match err {
+            ClientError::RpcError(CoreError::Call(err))
+                if VALIDATION_ERROR_PREFIXES
+                    .iter()
+                    .any(|prefix| err.message().starts_with(prefix)) =>
+            {
+                ReportLabel::validation_failed(error)
+            }
+            _ => ReportLabel::failed(error),
+        }
+    }
+
     fn should_retry(err: &ClientError) -> bool {
         matches!(
             err,
@@ -326,7 +527,7 @@ impl AccountLifespan {
         retries: usize,
         command: TxCommand,
     ) -> Result<(), Aborted> {
-        if let ReportLabel::ActionFailed { error } = &label {
+        if let Some(error) = label.error() {
             tracing::error!(
                 "Command failed: from {:?}, {command:#?} ({error})",
                 self.wallet.wallet.address()
@@ -343,6 +544,22 @@ impl AccountLifespan {
         self.send_report(report).await
     }
 
+    /// Builds a report on the inclusion of an executed transaction and sends it.
+    async fn report_inclusion(
+        &mut self,
+        stage: InclusionStage,
+        label: ReportLabel,
+        latency: Duration,
+    ) -> Result<(), Aborted> {
+        let report = ReportBuilder::default()
+            .label(label)
+            .reporter(self.wallet.wallet.address())
+            .time(latency)
+            .action(stage)
+            .finish();
+        self.send_report(report).await
+    }
+
     async fn send_report(&mut self, report: Report) -> Result<(), Aborted> {
         if self.report_sink.send(report).await.is_err() {
             Err(Aborted)
@@ -389,7 +606,7 @@ impl AccountLifespan {
 
                 let error =
                     format!("Tx should have been accepted, but got rejected. Reason: {err:?}");
-                SubmitResult::ReportLabel(ReportLabel::failed(error))
+                SubmitResult::ReportLabel(Self::rejection_label(&err, error))
             }
         };
         Ok(submit_result)
@@ -404,3 +621,45 @@ impl AccountLifespan {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_web3_decl::jsonrpsee::types::ErrorObjectOwned;
+
+    use super::*;
+
+    fn rpc_error(message: &str) -> ClientError {
+        let err = ErrorObjectOwned::owned(3, message, None::<()>);
+        ClientError::RpcError(CoreError::Call(err))
+    }
+
+    #[test]
+    fn validation_rejections_are_reported_separately() {
+        let validation_errors = [
+            "failed to validate the transaction. reason: Validation revert: Account validation error",
+            "failed paymaster validation. error message: Unsupported paymaster flow",
+            "not enough balance to cover the fee. error message: Insufficient balance",
+        ];
+        for message in validation_errors {
+            let err = rpc_error(message);
+            let label = AccountLifespan::rejection_label(&err, err.to_string());
+            assert!(
+                matches!(label, ReportLabel::ValidationFailed { .. }),
+                "{message}: {label:?}"
+            );
+        }
+
+        let other_errors = [
+            rpc_error("nonce too high"),
+            rpc_error("execution reverted: failed to validate the transaction"),
+            ClientError::OperationTimeout,
+        ];
+        for err in other_errors {
+            let label = AccountLifespan::rejection_label(&err, err.to_string());
+            assert!(
+                matches!(label, ReportLabel::ActionFailed { .. }),
+                "{err}: {label:?}"
+            );
+        }
+    }
+}
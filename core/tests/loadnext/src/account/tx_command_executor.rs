@@ -5,6 +5,7 @@ use zksync::{
     ethereum::PriorityOpHolder,
     utils::{
         get_approval_based_paymaster_input, get_approval_based_paymaster_input_for_estimation,
+        get_general_paymaster_input,
     },
     web3::ethabi,
     EthNamespaceClient,
@@ -14,16 +15,18 @@ use zksync_system_constants::MAX_L1_TRANSACTION_GAS_LIMIT;
 use zksync_types::{
     api::{BlockNumber, TransactionReceipt},
     l2::L2Tx,
-    Address, H256, U256,
+    transaction_request::PaymasterParams,
+    Address, H256, U256, U64,
 };
 
 use crate::{
-    account::{AccountLifespan, ExecutionType},
+    account::{AccountLifespan, ExecutionType, PaymasterFlow},
     command::{IncorrectnessModifier, TxCommand, TxType},
     constants::{
         ETH_CONFIRMATION_TIMEOUT, ETH_POLLING_INTERVAL, MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE,
     },
     corrupted_tx::Corrupted,
+    replay::RecordedTx,
     report::ReportLabel,
     utils::format_gwei,
 };
@@ -53,10 +56,21 @@ impl AccountLifespan {
                 self.execute_loadnext_contract(command, ExecutionType::L1)
                     .await
             }
+            TxType::ApprovalPaymasterExecute => {
+                self.execute_loadnext_contract_with_paymaster(command, PaymasterFlow::ApprovalBased)
+                    .await
+            }
+            TxType::GeneralPaymasterExecute => {
+                self.execute_loadnext_contract_with_paymaster(command, PaymasterFlow::General)
+                    .await
+            }
+            TxType::DeployCustomAccount => self.execute_deploy_custom_account(command).await,
+            TxType::CustomAccountExecute => self.execute_custom_account(command).await,
+            TxType::Replay => self.execute_replayed_tx(command).await,
         }
     }
 
-    fn tx_creation_error(err: ClientError) -> ClientError {
+    pub(super) fn tx_creation_error(err: ClientError) -> ClientError {
         // Translate network errors (so operation will be retried), but don't accept other ones.
         // For example, we will retry operation if fee ticker returned an error,
         // but will panic if transaction cannot be signed.
@@ -68,7 +82,49 @@ impl AccountLifespan {
         }
     }
 
-    async fn apply_modifier(&self, tx: L2Tx, modifier: IncorrectnessModifier) -> L2Tx {
+    /// Returns paymaster params for a transaction with the specified max total fee. If `custom_flow` is not specified,
+    /// the transaction is paid for by the testnet paymaster.
+    pub(super) fn paymaster_params(
+        &self,
+        custom_flow: Option<PaymasterFlow>,
+        max_total_fee: U256,
+    ) -> PaymasterParams {
+        let Some(flow) = custom_flow else {
+            return get_approval_based_paymaster_input(
+                self.paymaster_address,
+                self.main_l2_token,
+                max_total_fee,
+                Vec::new(),
+            );
+        };
+
+        let paymaster = self
+            .custom_paymaster_address
+            .expect("Test paymaster is not deployed");
+        match flow {
+            PaymasterFlow::ApprovalBased => {
+                // `LoadnextPaymaster` expects a 46-byte pseudo-signature and the exchange rate;
+                // we exchange the main token for ETH 1:1, like the testnet paymaster does.
+                const PSEUDO_SIGNATURE_LEN: usize = 46;
+
+                let inner_input = ethabi::encode(&[
+                    ethabi::Token::Bytes(vec![0; PSEUDO_SIGNATURE_LEN]),
+                    ethabi::Token::Uint(U256::one()),
+                    ethabi::Token::Uint(U256::one()),
+                    ethabi::Token::Uint(max_total_fee),
+                ]);
+                get_approval_based_paymaster_input(
+                    paymaster,
+                    self.main_l2_token,
+                    max_total_fee,
+                    inner_input,
+                )
+            }
+            PaymasterFlow::General => get_general_paymaster_input(paymaster, Vec::new()),
+        }
+    }
+
+    pub(super) async fn apply_modifier(&self, tx: L2Tx, modifier: IncorrectnessModifier) -> L2Tx {
         let wallet = &self.wallet.wallet;
         tx.apply_modifier(modifier, &wallet.signer).await
     }
@@ -183,7 +239,7 @@ impl AccountLifespan {
         }
     }
 
-    async fn execute_submit(
+    pub(super) async fn execute_submit(
         &mut self,
         tx: L2Tx,
         modifier: IncorrectnessModifier,
@@ -355,7 +411,7 @@ impl AccountLifespan {
             ExecutionType::L2 => {
                 let mut started_at = Instant::now();
                 let tx = self
-                    .build_execute_loadnext_contract(command, contract_address)
+                    .build_execute_loadnext_contract(command, contract_address, None)
                     .await?;
                 tracing::trace!(
                     "Account {:?}: execute_loadnext_contract: tx built in {:?}",
@@ -374,7 +430,28 @@ impl AccountLifespan {
         }
     }
 
-    fn prepare_calldata_for_loadnext_contract(&self) -> Vec<u8> {
+    async fn execute_loadnext_contract_with_paymaster(
+        &mut self,
+        command: &TxCommand,
+        paymaster_flow: PaymasterFlow,
+    ) -> Result<SubmitResult, ClientError> {
+        let Some(&contract_address) = self.wallet.deployed_contract_address.get() else {
+            let label =
+                ReportLabel::skipped("Account haven't successfully deployed a contract yet");
+            return Ok(SubmitResult::ReportLabel(label));
+        };
+        if self.custom_paymaster_address.is_none() {
+            let label = ReportLabel::skipped("Test paymaster is not deployed");
+            return Ok(SubmitResult::ReportLabel(label));
+        }
+
+        let tx = self
+            .build_execute_loadnext_contract(command, contract_address, Some(paymaster_flow))
+            .await?;
+        self.execute_submit(tx, command.modifier).await
+    }
+
+    pub(super) fn prepare_calldata_for_loadnext_contract(&self) -> Vec<u8> {
         let contract = &self.wallet.test_contract.contract;
         let function = contract.function("execute").unwrap();
         function
@@ -393,6 +470,7 @@ impl AccountLifespan {
         &mut self,
         command: &TxCommand,
         contract_address: Address,
+        paymaster_flow: Option<PaymasterFlow>,
     ) -> Result<L2Tx, ClientError> {
         let wallet = &self.wallet.wallet;
 
@@ -404,9 +482,8 @@ impl AccountLifespan {
             .factory_deps(self.wallet.test_contract.factory_deps.clone());
 
         let fee = builder
-            .estimate_fee(Some(get_approval_based_paymaster_input_for_estimation(
-                self.paymaster_address,
-                self.main_l2_token,
+            .estimate_fee(Some(self.paymaster_params(
+                paymaster_flow,
                 MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into(),
             )))
             .await?;
@@ -421,12 +498,7 @@ impl AccountLifespan {
         );
         builder = builder.fee(fee.clone());
 
-        let paymaster_params = get_approval_based_paymaster_input(
-            self.paymaster_address,
-            self.main_l2_token,
-            fee.max_total_fee(),
-            Vec::new(),
-        );
+        let paymaster_params = self.paymaster_params(paymaster_flow, fee.max_total_fee());
         builder = builder.fee(fee);
         builder = builder.paymaster_params(paymaster_params);
 
@@ -439,6 +511,57 @@ impl AccountLifespan {
         Ok(self.apply_modifier(tx, command.modifier).await)
     }
 
+    async fn execute_replayed_tx(
+        &mut self,
+        command: &TxCommand,
+    ) -> Result<SubmitResult, ClientError> {
+        let replayed_tx = command
+            .replayed_tx
+            .as_deref()
+            .expect("`Replay` command without a recorded transaction");
+        let tx = self.build_replayed_tx(replayed_tx).await?;
+        self.execute_submit(tx, command.modifier).await
+    }
+
+    /// Re-signs the recorded transaction with the account wallet, preserving its calldata and gas limits.
+    async fn build_replayed_tx(&self, replayed_tx: &RecordedTx) -> Result<L2Tx, ClientError> {
+        let wallet = &self.wallet.wallet;
+
+        let mut fee = replayed_tx.fee.clone();
+        // The recorded max fee may be too low for the target node, in which case the tx would be rejected.
+        let gas_price = wallet.provider.gas_price().await?;
+        fee.max_fee_per_gas = fee.max_fee_per_gas.max(gas_price);
+
+        let execute = &replayed_tx.execute;
+        let mut builder = wallet
+            .start_execute_contract()
+            .contract_address(execute.contract_address)
+            .calldata(execute.calldata.clone())
+            .value(execute.value);
+        if let Some(factory_deps) = &execute.factory_deps {
+            builder = builder.factory_deps(factory_deps.clone());
+        }
+
+        if !self.config.replay_preserve_paymaster {
+            let paymaster_params = get_approval_based_paymaster_input(
+                self.paymaster_address,
+                self.main_l2_token,
+                fee.max_total_fee(),
+                Vec::new(),
+            );
+            builder = builder.paymaster_params(paymaster_params);
+        } else if replayed_tx.has_paymaster() {
+            builder = builder.paymaster_params(replayed_tx.paymaster_params.clone());
+        }
+        builder = builder.fee(fee);
+
+        if let Some(nonce) = self.current_nonce {
+            builder = builder.nonce(nonce);
+        }
+
+        builder.tx().await.map_err(Self::tx_creation_error)
+    }
+
     pub(crate) async fn get_tx_receipt_for_committed_block(
         &mut self,
         tx_hash: H256,
@@ -469,4 +592,18 @@ impl AccountLifespan {
         }
         Ok(None)
     }
+
+    /// Returns the number of the L1 batch the transaction was included into, or `None` if the batch is not sealed yet.
+    pub(crate) async fn get_tx_l1_batch_number(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<U64>, ClientError> {
+        let receipt = self
+            .wallet
+            .wallet
+            .provider
+            .get_transaction_receipt(tx_hash)
+            .await?;
+        Ok(receipt.and_then(|receipt| receipt.l1_batch_number))
+    }
 }
@@ -8,9 +8,10 @@ use zksync_eth_signer::PrivateKeySigner;
 use zksync_types::{tx::primitives::PackedEthSignature, Address, L2ChainId, H256};
 
 use crate::{
+    command::TxType,
     config::LoadtestConfig,
     corrupted_tx::CorruptedSigner,
-    fs_utils::{loadnext_contract, TestContract},
+    fs_utils::{custom_account_contract, loadnext_contract, TestContract},
     rng::{LoadtestRng, Random},
 };
 
@@ -70,6 +71,11 @@ pub struct TestWallet {
     /// Address of the deployed contract to be used for sending
     /// `Execute` transaction.
     pub deployed_contract_address: Arc<OnceCell<Address>>,
+    /// Custom account contract to be deployed for sending transactions from custom accounts.
+    /// Only loaded if custom account transactions are enabled.
+    pub custom_account_contract: Option<TestContract>,
+    /// Address of the custom account deployed by this wallet.
+    pub custom_account_address: Arc<OnceCell<Address>>,
     /// RNG object derived from a common loadtest seed and the wallet private key.
     pub rng: LoadtestRng,
 }
@@ -107,6 +113,11 @@ impl AccountPool {
         }
 
         let test_contract = loadnext_contract(&config.test_contracts_path)?;
+        let custom_account_contract = if TxType::CustomAccountExecute.is_enabled() {
+            Some(custom_account_contract(&config.test_contracts_path)?)
+        } else {
+            None
+        };
 
         let master_wallet = {
             let eth_pk = H256::from_str(&config.master_wallet_pk)
@@ -155,6 +166,8 @@ impl AccountPool {
                     corrupted_wallet: Arc::new(corrupted_wallet),
                     test_contract: test_contract.clone(),
                     deployed_contract_address: deployed_contract_address.clone(),
+                    custom_account_contract: custom_account_contract.clone(),
+                    custom_account_address: Arc::default(),
                     rng: rng.derive(eth_credentials.eth_pk),
                 };
                 accounts.push_back(account);
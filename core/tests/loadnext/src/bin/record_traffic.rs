@@ -0,0 +1,41 @@
+//! Records the traffic from a range of miniblocks to be replayed by the loadtest.
+//!
+//! See the "Replaying traffic" section in `README.md` for details.
+
+use anyhow::Context as _;
+use loadnext::{config::TrafficRecordingConfig, replay::TrafficRecording};
+use zksync::HttpClientBuilder;
+use zksync_types::MiniblockNumber;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    let _guard = vlog::ObservabilityBuilder::new()
+        .with_log_format(log_format)
+        .build()?;
+
+    let config = TrafficRecordingConfig::from_env()
+        .context("Recording config should be provided via `RECORD_*` env variables")?;
+    anyhow::ensure!(
+        config.from_miniblock <= config.to_miniblock,
+        "Invalid miniblock range: {}..={}",
+        config.from_miniblock,
+        config.to_miniblock
+    );
+
+    let client = HttpClientBuilder::default()
+        .build(&config.rpc_address)
+        .context("failed creating L2 client")?;
+    let miniblocks = MiniblockNumber(config.from_miniblock)..=MiniblockNumber(config.to_miniblock);
+    let recording = TrafficRecording::record(&client, miniblocks).await?;
+    recording.save(&config.output_path)?;
+
+    tracing::info!(
+        "Saved {} transactions spanning {:?} to {}",
+        recording.transactions.len(),
+        recording.duration(),
+        config.output_path.display()
+    );
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use once_cell::sync::OnceCell;
 use rand::Rng;
 use static_assertions::const_assert;
@@ -7,10 +9,11 @@ use crate::{
     account_pool::AddressPool,
     all::{All, AllWeighted},
     config::TransactionWeights,
+    replay::RecordedTx,
     rng::{LoadtestRng, WeightedRandom},
 };
 
-static WEIGHTS: OnceCell<[(TxType, f32); 5]> = OnceCell::new();
+static WEIGHTS: OnceCell<[(TxType, f32); 8]> = OnceCell::new();
 
 /// Type of transaction. It doesn't copy the zkSync operation list, because
 /// it divides some transactions in subcategories (e.g. to new account / to existing account; to self / to other; etc)/
@@ -22,6 +25,16 @@ pub enum TxType {
     DeployContract,
     L1Execute,
     L2Execute,
+    /// Execution of the loadnext contract paid for by the test paymaster using the approval-based flow.
+    ApprovalPaymasterExecute,
+    /// Execution of the loadnext contract paid for by the test paymaster using the general flow.
+    GeneralPaymasterExecute,
+    /// Deployment of the custom account. Performed once by every account if custom account transactions are enabled.
+    DeployCustomAccount,
+    /// Execution of the loadnext contract from the custom account, paid for by the test paymaster.
+    CustomAccountExecute,
+    /// Replay of a recorded transaction. Never generated randomly.
+    Replay,
 }
 
 impl TxType {
@@ -36,9 +49,28 @@ impl TxType {
                     TxType::WithdrawToOther,
                     transaction_weights.withdrawal / 2.0,
                 ),
+                (
+                    TxType::ApprovalPaymasterExecute,
+                    transaction_weights.paymaster_transactions / 2.0,
+                ),
+                (
+                    TxType::GeneralPaymasterExecute,
+                    transaction_weights.paymaster_transactions / 2.0,
+                ),
+                (
+                    TxType::CustomAccountExecute,
+                    transaction_weights.custom_account_transactions,
+                ),
             ])
             .unwrap();
     }
+
+    /// Checks whether commands of this type can be generated randomly.
+    pub fn is_enabled(self) -> bool {
+        Self::all_weighted()
+            .iter()
+            .any(|&(tx_type, weight)| tx_type == self && weight > 0.0)
+    }
 }
 
 impl All for TxType {
@@ -61,6 +93,9 @@ impl TxType {
             Self::WithdrawToOther,
             Self::L1Execute,
             Self::L2Execute,
+            Self::ApprovalPaymasterExecute,
+            Self::GeneralPaymasterExecute,
+            Self::CustomAccountExecute,
         ]
     }
 
@@ -151,6 +186,8 @@ pub struct TxCommand {
     pub to: Address,
     /// Transaction amount (0 if not applicable).
     pub amount: U256,
+    /// Recorded transaction for the `Replay` commands.
+    pub replayed_tx: Option<Arc<RecordedTx>>,
 }
 
 impl TxCommand {
@@ -161,6 +198,17 @@ impl TxCommand {
         Self::new_with_type(rng, own_address, addresses, command_type)
     }
 
+    /// Creates a command replaying the recorded transaction.
+    pub fn replay(replayed_tx: Arc<RecordedTx>) -> Self {
+        Self {
+            command_type: TxType::Replay,
+            modifier: IncorrectnessModifier::None,
+            to: replayed_tx.execute.contract_address,
+            amount: replayed_tx.execute.value,
+            replayed_tx: Some(replayed_tx),
+        }
+    }
+
     fn new_with_type(
         rng: &mut LoadtestRng,
         own_address: Address,
@@ -172,6 +220,7 @@ impl TxCommand {
             modifier: IncorrectnessModifier::random(rng),
             to: addresses.random_address(rng),
             amount: Self::random_amount(rng),
+            replayed_tx: None,
         };
 
         // Check whether we should use a self as a target.
@@ -136,6 +136,94 @@ pub struct LoadtestConfig {
     /// in an eventual test failure anyway (e.g., a failure processing transactions).
     #[serde(default)]
     pub fail_fast: bool,
+
+    /// Shape of the load generated by the test.
+    ///
+    /// With the default `closed` profile, each account sends the next transaction as soon as it has a free
+    /// in-flight slot, so the achieved TPS is whatever the node manages to process. Other profiles are open-loop:
+    /// transactions are scheduled at `target_tps` independently of the node responses, and latencies are measured
+    /// from the scheduled time, so that the node falling behind shows up as growing latencies. If an account
+    /// already has `max_inflight_txs` transactions in flight when a transaction is scheduled for it,
+    /// the transaction is not sent and is reported as skipped.
+    #[serde(default)]
+    pub load_profile: LoadProfileKind,
+
+    /// Target transaction rate for the open-loop load profiles. For `step` and `ramp` profiles,
+    /// this is the rate at the start of the test.
+    #[serde(default = "default_target_tps")]
+    pub target_tps: f64,
+
+    /// Transaction rate at the end of the test for `step` and `ramp` load profiles.
+    /// If not set, `target_tps` is used, i.e. the load is constant.
+    #[serde(default)]
+    pub final_target_tps: Option<f64>,
+
+    /// Number of equal-duration steps used by the `step` load profile to get from `target_tps`
+    /// to `final_target_tps`.
+    #[serde(default = "default_rate_steps")]
+    pub rate_steps: u32,
+
+    /// Maximum acceptable 99th percentile of the latency of successfully processed transactions in milliseconds.
+    /// The latency is measured from the moment the transaction was scheduled (for open-loop profiles)
+    /// or sent (for the closed-loop profile) till its inclusion into a miniblock.
+    #[serde(default)]
+    pub slo_p99_tx_latency_ms: Option<u64>,
+
+    /// Maximum acceptable 99th percentile of the latency between submitting a transaction and its inclusion
+    /// into a miniblock in milliseconds.
+    #[serde(default)]
+    pub slo_p99_miniblock_inclusion_ms: Option<u64>,
+
+    /// Maximum acceptable 99th percentile of the latency between submitting a transaction and its inclusion
+    /// into a sealed L1 batch in milliseconds.
+    #[serde(default)]
+    pub slo_p99_l1_batch_inclusion_ms: Option<u64>,
+
+    /// Maximum acceptable ratio of failed transactions to all processed transactions, e.g. `0.01` for 1%.
+    /// If not set, any failed transaction fails the test.
+    #[serde(default)]
+    pub slo_max_failure_rate: Option<f64>,
+
+    /// Path to the traffic recording replayed with the `replay` load profile.
+    /// Recordings can be created with the `record_traffic` binary.
+    #[serde(default)]
+    pub replay_file: Option<PathBuf>,
+
+    /// Speed of the replay relative to the recorded traffic, e.g. `2.0` replays the traffic twice as fast.
+    #[serde(default = "default_replay_speed")]
+    pub replay_speed: f64,
+
+    /// Whether replayed transactions should keep their recorded paymaster params (the default). If set to `false`,
+    /// replayed transactions pay fees via the testnet paymaster with the main token instead, which is useful
+    /// if the recorded paymasters are not deployed or funded on the target network.
+    #[serde(default = "default_replay_preserve_paymaster")]
+    pub replay_preserve_paymaster: bool,
+}
+
+/// Kind of the load profile used in the test. See [`LoadtestConfig::load_profile`] for details.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadProfileKind {
+    /// Each account sends transactions as fast as `max_inflight_txs` allows.
+    #[default]
+    Closed,
+    /// Transactions are scheduled at a constant `target_tps` rate.
+    Constant,
+    /// Rate increases from `target_tps` to `final_target_tps` in `rate_steps` equal steps.
+    Step,
+    /// Rate linearly increases from `target_tps` to `final_target_tps` over the test duration.
+    Ramp,
+    /// Transactions from `replay_file` are replayed with the recorded timing, scaled by `replay_speed`.
+    Replay,
+}
+
+/// Service level objectives checked at the end of the test. Unset thresholds are not checked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SloThresholds {
+    pub p99_tx_latency: Option<Duration>,
+    pub p99_miniblock_inclusion: Option<Duration>,
+    pub p99_l1_batch_inclusion: Option<Duration>,
+    pub max_failure_rate: Option<f64>,
 }
 
 fn default_max_inflight_txs() -> usize {
@@ -255,6 +343,30 @@ fn default_expected_tx_count() -> Option<usize> {
     result
 }
 
+fn default_target_tps() -> f64 {
+    let result = 10.0;
+    tracing::info!("Using default TARGET_TPS: {result}");
+    result
+}
+
+fn default_rate_steps() -> u32 {
+    let result = 5;
+    tracing::info!("Using default RATE_STEPS: {result}");
+    result
+}
+
+fn default_replay_speed() -> f64 {
+    let result = 1.0;
+    tracing::info!("Using default REPLAY_SPEED: {result}");
+    result
+}
+
+fn default_replay_preserve_paymaster() -> bool {
+    let result = true;
+    tracing::info!("Using default REPLAY_PRESERVE_PAYMASTER: {result}");
+    result
+}
+
 fn default_prometheus_label() -> String {
     let result = "unset".to_string();
     tracing::info!("Using default PROMETHEUS_LABEL: {result:?}");
@@ -269,6 +381,19 @@ impl LoadtestConfig {
     pub fn duration(&self) -> Duration {
         Duration::from_secs(self.duration_sec)
     }
+
+    pub fn slo_thresholds(&self) -> SloThresholds {
+        SloThresholds {
+            p99_tx_latency: self.slo_p99_tx_latency_ms.map(Duration::from_millis),
+            p99_miniblock_inclusion: self
+                .slo_p99_miniblock_inclusion_ms
+                .map(Duration::from_millis),
+            p99_l1_batch_inclusion: self
+                .slo_p99_l1_batch_inclusion_ms
+                .map(Duration::from_millis),
+            max_failure_rate: self.slo_max_failure_rate,
+        }
+    }
 }
 
 /// Configuration for the weights of loadtest operations
@@ -301,6 +426,13 @@ pub struct TransactionWeights {
     pub withdrawal: f32,
     pub l1_transactions: f32,
     pub l2_transactions: f32,
+    /// Weight of L2 transactions paid for by the test paymaster deployed by the loadtest. Transactions are evenly
+    /// split between the approval-based and general paymaster flows.
+    #[serde(default)]
+    pub paymaster_transactions: f32,
+    /// Weight of L2 transactions sent from custom accounts deployed by the loadtest.
+    #[serde(default)]
+    pub custom_account_transactions: f32,
 }
 
 impl TransactionWeights {
@@ -316,6 +448,8 @@ impl Default for TransactionWeights {
             withdrawal: 0.5,
             l1_transactions: 0.05,
             l2_transactions: 1.0,
+            paymaster_transactions: 0.0,
+            custom_account_transactions: 0.0,
         }
     }
 }
@@ -332,6 +466,26 @@ fn default_contract_execution_params() -> LoadnextContractExecutionParams {
     result
 }
 
+/// Configuration for recording the traffic to be replayed by the loadtest.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrafficRecordingConfig {
+    /// RPC address of the L2 node to record the traffic from.
+    #[serde(default = "get_default_l2_rpc_address")]
+    pub rpc_address: String,
+    /// First miniblock to record transactions from.
+    pub from_miniblock: u32,
+    /// Last miniblock (inclusive) to record transactions from.
+    pub to_miniblock: u32,
+    /// Path to the file the recording will be saved to.
+    pub output_path: PathBuf,
+}
+
+impl TrafficRecordingConfig {
+    pub fn from_env() -> envy::Result<Self> {
+        envy::prefixed("RECORD_").from_env()
+    }
+}
+
 #[derive(Debug)]
 pub struct RequestLimiters {
     pub api_requests: Semaphore,
@@ -41,3 +41,11 @@ pub const TARGET_PAYMASTER_BALANCE: u128 = 10u128.pow(18) * 60;
 /// It should be roughly equal (or maybe a bit higher) than the actual used tokens in the transaction for the most precise
 /// estimations. Note, however that is must not be higher than the ERC20 balance of the account.
 pub const MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE: u128 = 10u128.pow(18);
+
+/// Amount of funds deposited to the test paymaster deployed by the loadtest.
+pub const CUSTOM_PAYMASTER_BALANCE: u128 = 10u128.pow(18) * 10;
+
+/// Fee for custom account transactions cannot be estimated directly, since the account checks the signature
+/// of the transaction, which depends on the fee. Instead, the fee is estimated for the same transaction sent by
+/// the EOA, and its gas limit is increased by this amount to cover the custom account validation.
+pub const CUSTOM_ACCOUNT_VALIDATION_GAS: u32 = 1_000_000;
@@ -23,6 +23,8 @@ use crate::{
     account_pool::AccountPool,
     config::{ExecutionConfig, LoadtestConfig, RequestLimiters},
     constants::*,
+    fs_utils::loadnext_paymaster_contract,
+    load_profile::Dispatcher,
     report::ReportBuilder,
     report_collector::{LoadtestResult, ReportCollector},
     utils::format_eth,
@@ -34,9 +36,11 @@ use crate::{
 ///
 /// - Minting the tokens on L1 for the main account.
 /// - Depositing tokens to the main account in L2 and unlocking it.
+/// - Deploying and funding the test paymaster if paymaster or custom account transactions are enabled.
 /// - Spawning the report collector.
 /// - Distributing the funds among the test wallets.
 /// - Spawning account lifespan futures.
+/// - Spawning the dispatcher for the open-loop load profiles.
 /// - Awaiting for all the account futures to complete.
 /// - Getting the final test resolution from the report collector.
 pub struct Executor {
@@ -44,6 +48,10 @@ pub struct Executor {
     execution_config: ExecutionConfig,
     l2_main_token: Address,
     pool: AccountPool,
+    /// Dispatcher for the open-loop load profiles; taken when the accounts are spawned.
+    dispatcher: Option<Dispatcher>,
+    /// Address of the test paymaster deployed by the loadtest.
+    custom_paymaster_address: Option<Address>,
 }
 
 impl Executor {
@@ -52,6 +60,7 @@ impl Executor {
         config: LoadtestConfig,
         execution_config: ExecutionConfig,
     ) -> anyhow::Result<Self> {
+        let dispatcher = Dispatcher::from_config(&config)?;
         let pool = AccountPool::new(&config).await?;
 
         // derive L2 main token address
@@ -69,6 +78,8 @@ impl Executor {
             execution_config,
             pool,
             l2_main_token,
+            dispatcher,
+            custom_paymaster_address: None,
         })
     }
 
@@ -89,7 +100,14 @@ impl Executor {
         self.mint().await?;
         self.deposit_to_master().await?;
 
-        self.deposit_eth_to_paymaster().await?;
+        let paymaster_address = self.testnet_paymaster_address().await?;
+        self.deposit_eth_to_paymaster(
+            paymaster_address,
+            MIN_PAYMASTER_BALANCE.into(),
+            TARGET_PAYMASTER_BALANCE.into(),
+        )
+        .await?;
+        self.deploy_custom_paymaster().await?;
 
         let final_result = self.send_initial_transfers().await?;
         Ok(final_result)
@@ -255,7 +273,80 @@ impl Executor {
         Ok(())
     }
 
-    async fn deposit_eth_to_paymaster(&mut self) -> anyhow::Result<()> {
+    async fn testnet_paymaster_address(&self) -> anyhow::Result<Address> {
+        let paymaster_address = self
+            .pool
+            .master_wallet
+            .provider
+            .get_testnet_paymaster()
+            .await?
+            .expect("No testnet paymaster is set");
+        Ok(paymaster_address)
+    }
+
+    /// Deploys the test paymaster used by paymaster and custom account transactions, and deposits ETH to it.
+    async fn deploy_custom_paymaster(&mut self) -> anyhow::Result<()> {
+        let weights = &self.execution_config.transaction_weights;
+        if weights.paymaster_transactions == 0.0 && weights.custom_account_transactions == 0.0 {
+            return Ok(());
+        }
+
+        tracing::info!("Master Account: Deploying test paymaster");
+        let contract = loadnext_paymaster_contract(&self.config.test_contracts_path)?;
+        let testnet_paymaster_address = self.testnet_paymaster_address().await?;
+        let master_wallet = &self.pool.master_wallet;
+        let mut builder = master_wallet
+            .start_deploy_contract()
+            .bytecode(contract.bytecode)
+            .constructor_calldata(vec![]);
+
+        let fee = builder
+            .estimate_fee(Some(get_approval_based_paymaster_input_for_estimation(
+                testnet_paymaster_address,
+                self.l2_main_token,
+                MIN_ALLOWANCE_FOR_PAYMASTER_ESTIMATE.into(),
+            )))
+            .await?;
+        let paymaster_params = get_approval_based_paymaster_input(
+            testnet_paymaster_address,
+            self.l2_main_token,
+            fee.max_total_fee(),
+            Vec::new(),
+        );
+        builder = builder.fee(fee).paymaster_params(paymaster_params);
+
+        let mut handle = builder.send().await?;
+        handle.polling_interval(POLLING_INTERVAL).unwrap();
+        let receipt = handle
+            .commit_timeout(COMMIT_TIMEOUT)
+            .wait_for_commit()
+            .await?;
+        anyhow::ensure!(
+            receipt.status == U64::one(),
+            "Test paymaster deployment failed: {receipt:?}"
+        );
+        let paymaster_address = receipt
+            .contract_address
+            .ok_or_else(|| anyhow!("No contract address in paymaster deployment receipt"))?;
+        tracing::info!("Master Account: Test paymaster deployed at {paymaster_address:?}");
+
+        self.deposit_eth_to_paymaster(
+            paymaster_address,
+            CUSTOM_PAYMASTER_BALANCE.into(),
+            CUSTOM_PAYMASTER_BALANCE.into(),
+        )
+        .await?;
+        self.custom_paymaster_address = Some(paymaster_address);
+        Ok(())
+    }
+
+    /// Deposits ETH to the paymaster if its balance is lower than `min_balance`, so that it reaches `target_balance`.
+    async fn deposit_eth_to_paymaster(
+        &mut self,
+        paymaster_address: Address,
+        min_balance: U256,
+        target_balance: U256,
+    ) -> anyhow::Result<()> {
         tracing::info!("Master Account: Checking paymaster balance");
         let mut ethereum = self
             .pool
@@ -265,14 +356,6 @@ impl Executor {
         ethereum.set_confirmation_timeout(ETH_CONFIRMATION_TIMEOUT);
         ethereum.set_polling_interval(ETH_POLLING_INTERVAL);
 
-        let paymaster_address = self
-            .pool
-            .master_wallet
-            .provider
-            .get_testnet_paymaster()
-            .await?
-            .expect("No testnet paymaster is set");
-
         let paymaster_balance: U256 = self
             .pool
             .master_wallet
@@ -283,14 +366,14 @@ impl Executor {
         tracing::info!(
             "Paymaster balance is {}. Minimum amount {}",
             format_eth(paymaster_balance),
-            format_eth(U256::from(MIN_PAYMASTER_BALANCE))
+            format_eth(min_balance)
         );
 
-        if paymaster_balance >= U256::from(MIN_PAYMASTER_BALANCE) {
+        if paymaster_balance >= min_balance {
             return Ok(());
         }
 
-        let deposit_amount = U256::from(TARGET_PAYMASTER_BALANCE) - paymaster_balance;
+        let deposit_amount = target_balance - paymaster_balance;
 
         // Perform the deposit itself.
         let receipt = deposit_with_attempts(
@@ -515,23 +598,20 @@ impl Executor {
             self.config.duration(),
             self.config.prometheus_label.clone(),
             self.config.fail_fast,
+            self.config.slo_thresholds(),
         );
         let report_collector_future = tokio::spawn(report_collector.run());
 
         let config = &self.config;
         let accounts_amount = config.accounts_amount;
         let addresses = self.pool.addresses.clone();
-        let paymaster_address = self
-            .pool
-            .master_wallet
-            .provider
-            .get_testnet_paymaster()
-            .await?
-            .expect("No testnet paymaster is set");
+        let paymaster_address = self.testnet_paymaster_address().await?;
+        let custom_paymaster_address = self.custom_paymaster_address;
 
         let mut retry_counter = 0;
         let mut accounts_processed = 0;
         let limiters = Arc::new(RequestLimiters::new(config));
+        let mut dispatcher = self.dispatcher.take();
 
         let mut account_tasks = vec![];
         while accounts_processed != accounts_amount {
@@ -576,9 +656,11 @@ impl Executor {
                             report_sender.clone(),
                             main_token,
                             paymaster_address,
+                            custom_paymaster_address,
                         );
                         let limiters = Arc::clone(&limiters);
-                        tokio::spawn(async move { account.run(&limiters).await })
+                        let schedule = dispatcher.as_mut().map(Dispatcher::add_account);
+                        tokio::spawn(async move { account.run(&limiters, schedule).await })
                     });
             account_tasks.extend(new_account_futures);
         }
@@ -596,6 +678,10 @@ impl Executor {
         );
         tracing::info!("All the initial transfers are completed");
 
+        if let Some(dispatcher) = dispatcher {
+            account_tasks.push(tokio::spawn(dispatcher.run()));
+        }
+
         tracing::info!("Waiting for the account futures to be completed...");
         future::try_join_all(account_tasks).await?;
         tracing::info!("All the spawned tasks are completed");
@@ -87,6 +87,37 @@ pub fn loadnext_contract(path: &Path) -> anyhow::Result<TestContract> {
     read_contract_dir(&path)
 }
 
+/// Reads a contract without factory dependencies from the `custom-account` test contracts.
+fn read_custom_account_contract(
+    path: &Path,
+    file_name: &str,
+    contract_name: &str,
+) -> anyhow::Result<TestContract> {
+    let path = path
+        .join("artifacts-zk/contracts/custom-account")
+        .join(file_name)
+        .join(format!("{contract_name}.json"));
+    let mut artifact: serde_json::Value = serde_json::from_reader(File::open(&path)?)?;
+
+    let bytecode = extract_bytecode(&artifact)?;
+    let contract: Contract = serde_json::from_value(artifact["abi"].take())?;
+    Ok(TestContract {
+        bytecode,
+        contract,
+        factory_deps: vec![],
+    })
+}
+
+/// Reads the test paymaster supporting both approval-based and general paymaster flows.
+pub fn loadnext_paymaster_contract(path: &Path) -> anyhow::Result<TestContract> {
+    read_custom_account_contract(path, "loadnext-paymaster.sol", "LoadnextPaymaster")
+}
+
+/// Reads the test custom account.
+pub fn custom_account_contract(path: &Path) -> anyhow::Result<TestContract> {
+    read_custom_account_contract(path, "custom-account.sol", "CustomAccount")
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -102,5 +133,18 @@ mod tests {
         };
 
         loadnext_contract(&test_contracts_path).unwrap();
+
+        let paymaster = loadnext_paymaster_contract(&test_contracts_path).unwrap();
+        assert!(!paymaster.bytecode.is_empty());
+        paymaster
+            .contract
+            .function("validateAndPayForPaymasterTransaction")
+            .unwrap();
+        let custom_account = custom_account_contract(&test_contracts_path).unwrap();
+        assert!(!custom_account.bytecode.is_empty());
+        custom_account
+            .contract
+            .function("validateTransaction")
+            .unwrap();
     }
 }
@@ -9,6 +9,8 @@ pub mod constants;
 pub mod corrupted_tx;
 pub mod executor;
 pub mod fs_utils;
+pub mod load_profile;
+pub mod replay;
 pub mod report;
 pub mod report_collector;
 pub mod rng;
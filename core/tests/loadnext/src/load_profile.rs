@@ -0,0 +1,451 @@
+//! Open-loop load generation.
+//!
+//! In the open-loop mode, transactions are scheduled by a single [`Dispatcher`] according to the configured
+//! [`RateProfile`] or the replayed traffic; the schedule doesn't depend on how fast the node processes transactions.
+//! Scheduled slots are distributed among accounts in a round-robin fashion, and each account executes its slots
+//! in order.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use tokio::sync::mpsc;
+
+use crate::{
+    config::{LoadProfileKind, LoadtestConfig},
+    replay::{RecordedTx, TrafficRecording},
+};
+
+/// Target transaction rate as a function of the time elapsed since the start of the test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateProfile {
+    Constant {
+        tps: f64,
+    },
+    Step {
+        initial_tps: f64,
+        final_tps: f64,
+        steps: u32,
+        duration: Duration,
+    },
+    Ramp {
+        initial_tps: f64,
+        final_tps: f64,
+        duration: Duration,
+    },
+}
+
+impl RateProfile {
+    /// Minimum rate to use for scheduling. Ensures that the dispatcher doesn't stall
+    /// if the profile starts with a zero rate.
+    const MIN_TPS: f64 = 0.1;
+
+    /// Creates a rate profile from the config. Returns `Ok(None)` for the profiles not driven by a target rate.
+    pub fn from_config(config: &LoadtestConfig) -> anyhow::Result<Option<Self>> {
+        let initial_tps = config.target_tps;
+        let final_tps = config.final_target_tps.unwrap_or(initial_tps);
+        anyhow::ensure!(
+            initial_tps.is_finite() && initial_tps >= 0.0,
+            "TARGET_TPS must be a non-negative number, got {initial_tps}"
+        );
+        anyhow::ensure!(
+            final_tps.is_finite() && final_tps >= 0.0,
+            "FINAL_TARGET_TPS must be a non-negative number, got {final_tps}"
+        );
+
+        let duration = config.duration();
+        Ok(match config.load_profile {
+            LoadProfileKind::Closed | LoadProfileKind::Replay => None,
+            LoadProfileKind::Constant => Some(Self::Constant { tps: initial_tps }),
+            LoadProfileKind::Step => {
+                anyhow::ensure!(config.rate_steps > 0, "RATE_STEPS must be positive");
+                Some(Self::Step {
+                    initial_tps,
+                    final_tps,
+                    steps: config.rate_steps,
+                    duration,
+                })
+            }
+            LoadProfileKind::Ramp => Some(Self::Ramp {
+                initial_tps,
+                final_tps,
+                duration,
+            }),
+        })
+    }
+
+    /// Returns the target rate at the specified moment of the test.
+    pub fn rate_at(&self, elapsed: Duration) -> f64 {
+        match *self {
+            Self::Constant { tps } => tps,
+            Self::Step {
+                initial_tps,
+                final_tps,
+                steps,
+                duration,
+            } => {
+                if steps == 1 {
+                    return initial_tps;
+                }
+                let progress = Self::progress(elapsed, duration);
+                let step = ((progress * f64::from(steps)) as u32).min(steps - 1);
+                initial_tps + (final_tps - initial_tps) * f64::from(step) / f64::from(steps - 1)
+            }
+            Self::Ramp {
+                initial_tps,
+                final_tps,
+                duration,
+            } => {
+                let progress = Self::progress(elapsed, duration);
+                initial_tps + (final_tps - initial_tps) * progress
+            }
+        }
+    }
+
+    /// Returns the moment (relative to the test start) of the slot following the one scheduled at `elapsed`.
+    pub fn next_slot(&self, elapsed: Duration) -> Duration {
+        let rate = self.rate_at(elapsed).max(Self::MIN_TPS);
+        elapsed + Duration::from_secs_f64(1.0 / rate)
+    }
+
+    fn progress(elapsed: Duration, duration: Duration) -> f64 {
+        if duration.is_zero() {
+            1.0
+        } else {
+            (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+        }
+    }
+}
+
+/// Slot for a transaction scheduled by the [`Dispatcher`].
+#[derive(Debug, Clone)]
+pub struct ScheduledSlot {
+    /// Moment at which the transaction should have been sent. Latencies are measured from this moment
+    /// rather than from the actual sending, so that the time spent waiting in the account queue is accounted for.
+    pub scheduled_at: Instant,
+    /// Transaction to replay. If not set, the account generates a random transaction.
+    pub replayed_tx: Option<Arc<RecordedTx>>,
+}
+
+/// Source of the transaction slots for the [`Dispatcher`].
+#[derive(Debug)]
+enum SlotSource {
+    Rate(RateProfile),
+    Replay {
+        recording: TrafficRecording,
+        speed: f64,
+    },
+}
+
+/// Dispatches transaction slots to accounts according to the rate profile or the replayed traffic.
+#[derive(Debug)]
+pub struct Dispatcher {
+    source: SlotSource,
+    duration: Duration,
+    accounts: Vec<mpsc::UnboundedSender<ScheduledSlot>>,
+}
+
+impl Dispatcher {
+    pub fn new(profile: RateProfile, duration: Duration) -> Self {
+        Self::with_source(SlotSource::Rate(profile), duration)
+    }
+
+    pub fn replay(recording: TrafficRecording, speed: f64, duration: Duration) -> Self {
+        Self::with_source(SlotSource::Replay { recording, speed }, duration)
+    }
+
+    fn with_source(source: SlotSource, duration: Duration) -> Self {
+        Self {
+            source,
+            duration,
+            accounts: vec![],
+        }
+    }
+
+    /// Creates a dispatcher from the config. Returns `Ok(None)` for the closed-loop profile.
+    pub fn from_config(config: &LoadtestConfig) -> anyhow::Result<Option<Self>> {
+        if config.load_profile != LoadProfileKind::Replay {
+            let profile = RateProfile::from_config(config)?;
+            return Ok(profile.map(|profile| Self::new(profile, config.duration())));
+        }
+
+        let speed = config.replay_speed;
+        anyhow::ensure!(
+            speed.is_finite() && speed > 0.0,
+            "REPLAY_SPEED must be a positive number, got {speed}"
+        );
+        let path = config
+            .replay_file
+            .as_ref()
+            .context("REPLAY_FILE must be set for the replay load profile")?;
+        let recording = TrafficRecording::load(path)?;
+        let replay_duration = recording.duration().div_f64(speed);
+        if replay_duration > config.duration() {
+            tracing::warn!(
+                "Replaying the recording will take {replay_duration:?}, which is longer than the test duration; \
+                 the recording will be truncated"
+            );
+        }
+        Ok(Some(Self::replay(recording, speed, config.duration())))
+    }
+
+    /// Registers a new account and returns the receiver of the slots scheduled for it.
+    pub fn add_account(&mut self) -> mpsc::UnboundedReceiver<ScheduledSlot> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        self.accounts.push(sender);
+        receiver
+    }
+
+    pub async fn run(self) {
+        let start = Instant::now();
+        let dispatched = match &self.source {
+            SlotSource::Rate(profile) => {
+                tracing::info!(
+                    "Dispatching transactions to {} accounts with {profile:?}",
+                    self.accounts.len()
+                );
+                self.run_with_rate(*profile, start).await
+            }
+            SlotSource::Replay { recording, speed } => {
+                tracing::info!(
+                    "Replaying {} transactions from miniblocks {:?} with {} accounts at {speed}x speed",
+                    recording.transactions.len(),
+                    recording.miniblocks,
+                    self.accounts.len()
+                );
+                self.run_replay(recording, *speed, start).await
+            }
+        };
+
+        let elapsed = start.elapsed();
+        tracing::info!(
+            "Dispatcher scheduled {dispatched} transactions in {elapsed:?} (average rate: {:.2} TPS)",
+            dispatched as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    async fn run_with_rate(&self, profile: RateProfile, start: Instant) -> u64 {
+        let mut accounts = self.accounts.clone();
+        let mut next_slot = Duration::ZERO;
+        let mut account_idx = 0;
+        let mut dispatched = 0_u64;
+
+        while next_slot < self.duration && !accounts.is_empty() {
+            let scheduled_at = start + next_slot;
+            tokio::time::sleep_until(scheduled_at.into()).await;
+
+            account_idx %= accounts.len();
+            let slot = ScheduledSlot {
+                scheduled_at,
+                replayed_tx: None,
+            };
+            if accounts[account_idx].send(slot).is_err() {
+                // The account has finished its lifespan; don't schedule any more transactions for it.
+                accounts.swap_remove(account_idx);
+                continue;
+            }
+            account_idx += 1;
+            dispatched += 1;
+
+            metrics::gauge!("loadtest.target_tps", profile.rate_at(next_slot));
+            next_slot = profile.next_slot(next_slot);
+        }
+        dispatched
+    }
+
+    async fn run_replay(&self, recording: &TrafficRecording, speed: f64, start: Instant) -> u64 {
+        if self.accounts.is_empty() {
+            return 0;
+        }
+
+        let mut accounts_by_initiator = HashMap::new();
+        let mut dispatched = 0_u64;
+        let mut dropped = 0_u64;
+
+        for tx in &recording.transactions {
+            let scheduled_at = tx.scheduled_at(start, speed);
+            if scheduled_at - start >= self.duration {
+                break;
+            }
+            tokio::time::sleep_until(scheduled_at.into()).await;
+
+            // Transactions of the same initiator are assigned to the same account to preserve their order.
+            let next_account_idx = accounts_by_initiator.len() % self.accounts.len();
+            let account_idx = *accounts_by_initiator
+                .entry(tx.initiator)
+                .or_insert(next_account_idx);
+            let slot = ScheduledSlot {
+                scheduled_at,
+                replayed_tx: Some(Arc::new(tx.clone())),
+            };
+            if self.accounts[account_idx].send(slot).is_err() {
+                dropped += 1;
+            } else {
+                dispatched += 1;
+            }
+        }
+
+        if dropped > 0 {
+            tracing::warn!(
+                "{dropped} transactions were not replayed because the assigned accounts have finished their lifespan"
+            );
+        }
+        dispatched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        fee::Fee, transaction_request::PaymasterParams, Address, Execute, MiniblockNumber,
+    };
+
+    use super::*;
+
+    const DURATION: Duration = Duration::from_secs(100);
+
+    #[test]
+    fn constant_rate() {
+        let profile = RateProfile::Constant { tps: 20.0 };
+        assert_eq!(profile.rate_at(Duration::ZERO), 20.0);
+        assert_eq!(profile.rate_at(DURATION), 20.0);
+        assert_eq!(
+            profile.next_slot(Duration::from_secs(1)),
+            Duration::from_millis(1_050)
+        );
+    }
+
+    #[test]
+    fn step_rate() {
+        let profile = RateProfile::Step {
+            initial_tps: 10.0,
+            final_tps: 40.0,
+            steps: 4,
+            duration: DURATION,
+        };
+        // Vector of `(elapsed_secs, expected_rate)`.
+        let test_vector = [
+            (0, 10.0),
+            (24, 10.0),
+            (25, 20.0),
+            (50, 30.0),
+            (99, 40.0),
+            (100, 40.0),
+            (1_000, 40.0),
+        ];
+        for (elapsed_secs, expected_rate) in test_vector {
+            let rate = profile.rate_at(Duration::from_secs(elapsed_secs));
+            assert!(
+                (rate - expected_rate).abs() < 1e-9,
+                "{rate} != {expected_rate} at {elapsed_secs}s"
+            );
+        }
+    }
+
+    #[test]
+    fn single_step_rate() {
+        let profile = RateProfile::Step {
+            initial_tps: 10.0,
+            final_tps: 40.0,
+            steps: 1,
+            duration: DURATION,
+        };
+        assert_eq!(profile.rate_at(DURATION), 10.0);
+    }
+
+    #[test]
+    fn ramp_rate() {
+        let profile = RateProfile::Ramp {
+            initial_tps: 0.0,
+            final_tps: 100.0,
+            duration: DURATION,
+        };
+        assert_eq!(profile.rate_at(Duration::ZERO), 0.0);
+        assert_eq!(profile.rate_at(Duration::from_secs(25)), 25.0);
+        assert_eq!(profile.rate_at(DURATION), 100.0);
+        assert_eq!(profile.rate_at(DURATION * 2), 100.0);
+
+        // Zero rate must not stall the schedule.
+        assert_eq!(
+            profile.next_slot(Duration::ZERO),
+            Duration::from_secs_f64(1.0 / RateProfile::MIN_TPS)
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatcher_distributes_slots_evenly() {
+        let profile = RateProfile::Constant { tps: 100.0 };
+        let mut dispatcher = Dispatcher::new(profile, Duration::from_millis(300));
+        let mut receivers: Vec<_> = (0..3).map(|_| dispatcher.add_account()).collect();
+        dispatcher.run().await;
+
+        for receiver in &mut receivers {
+            let mut slots = vec![];
+            while let Ok(slot) = receiver.try_recv() {
+                assert!(slot.replayed_tx.is_none());
+                slots.push(slot.scheduled_at);
+            }
+            assert_eq!(slots.len(), 10);
+            for window in slots.windows(2) {
+                assert_eq!(window[1] - window[0], Duration::from_millis(30));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatcher_replays_transactions() {
+        let recorded_tx = |offset_ms, initiator| RecordedTx {
+            offset_ms,
+            initiator,
+            execute: Execute {
+                contract_address: Address::repeat_byte(0x11),
+                calldata: vec![],
+                value: 0.into(),
+                factory_deps: None,
+            },
+            fee: Fee::default(),
+            paymaster_params: PaymasterParams::default(),
+        };
+        let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let recording = TrafficRecording {
+            miniblocks: MiniblockNumber(1)..=MiniblockNumber(1),
+            transactions: vec![
+                recorded_tx(0, alice),
+                recorded_tx(100, bob),
+                recorded_tx(200, alice),
+                // Should be truncated because of the test duration.
+                recorded_tx(10_000, bob),
+            ],
+        };
+
+        let mut dispatcher = Dispatcher::replay(recording, 2.0, Duration::from_secs(1));
+        let mut receivers: Vec<_> = (0..3).map(|_| dispatcher.add_account()).collect();
+        let start = Instant::now();
+        dispatcher.run().await;
+
+        let mut slots_by_account = vec![];
+        for receiver in &mut receivers {
+            let mut slots = vec![];
+            while let Ok(slot) = receiver.try_recv() {
+                slots.push(slot);
+            }
+            slots_by_account.push(slots);
+        }
+
+        let alice_slots = &slots_by_account[0];
+        assert_eq!(alice_slots.len(), 2);
+        for slot in alice_slots {
+            assert_eq!(slot.replayed_tx.as_ref().unwrap().initiator, alice);
+        }
+        let offset = alice_slots[1].scheduled_at - alice_slots[0].scheduled_at;
+        assert_eq!(offset, Duration::from_millis(100));
+
+        let bob_slots = &slots_by_account[1];
+        assert_eq!(bob_slots.len(), 1);
+        assert!(bob_slots[0].scheduled_at >= start + Duration::from_millis(50));
+        assert!(slots_by_account[2].is_empty());
+    }
+}
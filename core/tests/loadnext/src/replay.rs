@@ -0,0 +1,228 @@
+//! Recording and replaying real traffic.
+//!
+//! Traffic is recorded from the transactions included into a range of miniblocks on the source node. Only L2
+//! transactions are recorded; each of them keeps its calldata, factory dependencies, fee parameters and the time
+//! it was received by the source node. When replayed with the `replay` load profile, transactions are re-signed
+//! by the test accounts and sent with the recorded inter-arrival timing.
+
+use std::{
+    fs,
+    ops::RangeInclusive,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use zksync::ZksNamespaceClient;
+use zksync_types::{
+    fee::Fee, transaction_request::PaymasterParams, Address, Execute, ExecuteTransactionCommon,
+    MiniblockNumber, Transaction,
+};
+
+/// L2 transaction recorded from the source node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedTx {
+    /// Time since the first recorded transaction was received by the source node, in milliseconds.
+    pub offset_ms: u64,
+    /// Original initiator of the transaction. Transactions with the same initiator are replayed by the same
+    /// test account, so that their relative order is preserved.
+    pub initiator: Address,
+    pub execute: Execute,
+    pub fee: Fee,
+    pub paymaster_params: PaymasterParams,
+}
+
+impl RecordedTx {
+    /// Converts a transaction returned by the source node. Returns `None` for L1 and upgrade transactions,
+    /// since they cannot be replayed by L2 accounts.
+    fn new(tx: Transaction, first_received_timestamp_ms: u64) -> Option<Self> {
+        let ExecuteTransactionCommon::L2(common_data) = tx.common_data else {
+            return None;
+        };
+        Some(Self {
+            offset_ms: tx
+                .received_timestamp_ms
+                .saturating_sub(first_received_timestamp_ms),
+            initiator: common_data.initiator_address,
+            execute: tx.execute,
+            fee: common_data.fee,
+            paymaster_params: common_data.paymaster_params,
+        })
+    }
+
+    pub fn has_paymaster(&self) -> bool {
+        self.paymaster_params.paymaster != Address::zero()
+    }
+
+    /// Returns the moment at which the transaction should be replayed.
+    pub fn scheduled_at(&self, start: Instant, speed: f64) -> Instant {
+        start + Duration::from_millis(self.offset_ms).div_f64(speed)
+    }
+}
+
+/// Traffic recorded from a range of miniblocks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficRecording {
+    pub miniblocks: RangeInclusive<MiniblockNumber>,
+    /// Recorded transactions ordered by their offset.
+    pub transactions: Vec<RecordedTx>,
+}
+
+impl TrafficRecording {
+    /// Records L2 transactions from the specified range of miniblocks.
+    pub async fn record(
+        client: &impl ZksNamespaceClient,
+        miniblocks: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<Self> {
+        let mut raw_transactions = vec![];
+        for number in miniblocks.start().0..=miniblocks.end().0 {
+            let number = MiniblockNumber(number);
+            let transactions = client
+                .get_raw_block_transactions(number)
+                .await
+                .with_context(|| format!("failed getting transactions for miniblock #{number}"))?;
+            tracing::debug!(
+                "Fetched {} transactions from miniblock #{number}",
+                transactions.len()
+            );
+            raw_transactions.extend(transactions);
+        }
+        Ok(Self::from_transactions(miniblocks, raw_transactions))
+    }
+
+    fn from_transactions(
+        miniblocks: RangeInclusive<MiniblockNumber>,
+        raw_transactions: Vec<Transaction>,
+    ) -> Self {
+        let raw_count = raw_transactions.len();
+        let first_received_timestamp_ms = raw_transactions
+            .iter()
+            .map(|tx| tx.received_timestamp_ms)
+            .min()
+            .unwrap_or(0);
+        let mut transactions: Vec<_> = raw_transactions
+            .into_iter()
+            .filter_map(|tx| RecordedTx::new(tx, first_received_timestamp_ms))
+            .collect();
+        // Stable sort preserves the execution order of transactions received at the same time.
+        transactions.sort_by_key(|tx| tx.offset_ms);
+
+        tracing::info!(
+            "Recorded {} L2 transactions from miniblocks {miniblocks:?} ({} non-L2 transactions skipped)",
+            transactions.len(),
+            raw_count - transactions.len()
+        );
+        Self {
+            miniblocks,
+            transactions,
+        }
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read(path)
+            .with_context(|| format!("failed reading traffic recording {}", path.display()))?;
+        serde_json::from_slice(&contents)
+            .with_context(|| format!("failed parsing traffic recording {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let contents = serde_json::to_vec(self).context("failed serializing traffic recording")?;
+        fs::write(path, contents)
+            .with_context(|| format!("failed writing traffic recording {}", path.display()))
+    }
+
+    /// Returns the recorded duration of the traffic.
+    pub fn duration(&self) -> Duration {
+        let last_offset_ms = self.transactions.last().map_or(0, |tx| tx.offset_ms);
+        Duration::from_millis(last_offset_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{l2::TransactionType, L1TxCommonData, L2TxCommonData, Nonce};
+
+    use super::*;
+
+    fn test_execute() -> Execute {
+        Execute {
+            contract_address: Address::repeat_byte(0x11),
+            calldata: vec![1, 2, 3],
+            value: 0.into(),
+            factory_deps: None,
+        }
+    }
+
+    fn l2_transaction(initiator: Address, received_timestamp_ms: u64) -> Transaction {
+        let common_data = L2TxCommonData {
+            nonce: Nonce(0),
+            fee: Fee {
+                gas_limit: 1_000_000.into(),
+                max_fee_per_gas: 250_000_000.into(),
+                max_priority_fee_per_gas: 0.into(),
+                gas_per_pubdata_limit: 800.into(),
+            },
+            initiator_address: initiator,
+            signature: vec![],
+            transaction_type: TransactionType::EIP712Transaction,
+            input: None,
+            paymaster_params: PaymasterParams::default(),
+        };
+        Transaction {
+            common_data: ExecuteTransactionCommon::L2(common_data),
+            execute: test_execute(),
+            received_timestamp_ms,
+            raw_bytes: None,
+        }
+    }
+
+    #[test]
+    fn recording_transactions() {
+        let initiator = Address::repeat_byte(1);
+        let l1_transaction = Transaction {
+            common_data: ExecuteTransactionCommon::L1(L1TxCommonData::default()),
+            execute: test_execute(),
+            received_timestamp_ms: 500,
+            raw_bytes: None,
+        };
+        let raw_transactions = vec![
+            l2_transaction(initiator, 1_500),
+            l1_transaction,
+            l2_transaction(initiator, 1_000),
+            l2_transaction(Address::repeat_byte(2), 3_000),
+        ];
+
+        let miniblocks = MiniblockNumber(1)..=MiniblockNumber(2);
+        let recording = TrafficRecording::from_transactions(miniblocks, raw_transactions);
+        let offsets: Vec<_> = recording
+            .transactions
+            .iter()
+            .map(|tx| tx.offset_ms)
+            .collect();
+        // Offsets are counted from the first received transaction, including L1 ones.
+        assert_eq!(offsets, [500, 1_000, 2_500]);
+        assert_eq!(recording.duration(), Duration::from_millis(2_500));
+        assert!(!recording.transactions[0].has_paymaster());
+        assert_eq!(recording.transactions[0].execute.calldata, [1, 2, 3]);
+
+        let start = Instant::now();
+        let tx = &recording.transactions[2];
+        assert_eq!(
+            tx.scheduled_at(start, 1.0),
+            start + Duration::from_millis(2_500)
+        );
+        assert_eq!(
+            tx.scheduled_at(start, 2.0),
+            start + Duration::from_millis(1_250)
+        );
+
+        let serialized = serde_json::to_string(&recording).unwrap();
+        let deserialized: TrafficRecording = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.transactions.len(), 3);
+        assert_eq!(
+            deserialized.transactions[2].initiator,
+            Address::repeat_byte(2)
+        );
+    }
+}
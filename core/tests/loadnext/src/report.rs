@@ -3,7 +3,7 @@ use std::time::Duration;
 use zksync_types::Address;
 
 use crate::{
-    account::ExecutionType,
+    account::{ExecutionType, PaymasterFlow},
     all::All,
     command::{ApiRequest, ApiRequestType, SubscriptionType, TxCommand, TxType},
 };
@@ -94,8 +94,17 @@ impl ReportBuilder {
 #[derive(Debug, Clone)]
 pub enum ReportLabel {
     ActionDone,
-    ActionSkipped { reason: String },
-    ActionFailed { error: String },
+    ActionSkipped {
+        reason: String,
+    },
+    ActionFailed {
+        error: String,
+    },
+    /// Transaction was unexpectedly rejected because of a failed account or paymaster validation.
+    /// Counted separately from other failures, since validation is performed in a different way than execution.
+    ValidationFailed {
+        error: String,
+    },
 }
 
 impl ReportLabel {
@@ -114,6 +123,20 @@ impl ReportLabel {
             error: error.into(),
         }
     }
+
+    pub fn validation_failed(error: impl Into<String>) -> Self {
+        Self::ValidationFailed {
+            error: error.into(),
+        }
+    }
+
+    /// Returns the error message if the action has failed for any reason.
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Self::ActionFailed { error } | Self::ValidationFailed { error } => Some(error),
+            Self::ActionDone | Self::ActionSkipped { .. } => None,
+        }
+    }
 }
 
 /// Denotes the type of executed transaction.
@@ -123,6 +146,10 @@ pub enum TxActionType {
     Deposit,
     DeployContract,
     Execute(ExecutionType),
+    PaymasterExecute(PaymasterFlow),
+    DeployCustomAccount,
+    CustomAccountExecute,
+    Replay,
 }
 
 impl All for TxActionType {
@@ -133,6 +160,11 @@ impl All for TxActionType {
             TxActionType::DeployContract,
             TxActionType::Execute(ExecutionType::L2),
             TxActionType::Execute(ExecutionType::L1),
+            TxActionType::PaymasterExecute(PaymasterFlow::ApprovalBased),
+            TxActionType::PaymasterExecute(PaymasterFlow::General),
+            TxActionType::DeployCustomAccount,
+            TxActionType::CustomAccountExecute,
+            TxActionType::Replay,
         ];
 
         ALL
@@ -147,6 +179,13 @@ impl From<TxType> for TxActionType {
             TxType::L2Execute => Self::Execute(ExecutionType::L2),
             TxType::L1Execute => Self::Execute(ExecutionType::L1),
             TxType::DeployContract => Self::DeployContract,
+            TxType::ApprovalPaymasterExecute => {
+                Self::PaymasterExecute(PaymasterFlow::ApprovalBased)
+            }
+            TxType::GeneralPaymasterExecute => Self::PaymasterExecute(PaymasterFlow::General),
+            TxType::DeployCustomAccount => Self::DeployCustomAccount,
+            TxType::CustomAccountExecute => Self::CustomAccountExecute,
+            TxType::Replay => Self::Replay,
         }
     }
 }
@@ -181,11 +220,28 @@ impl From<ApiRequest> for ApiActionType {
     }
 }
 
+/// Denotes the stage of the transaction inclusion. Inclusion latencies are measured
+/// from the moment the transaction was accepted by the API server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InclusionStage {
+    Miniblock,
+    L1Batch,
+}
+
+impl All for InclusionStage {
+    fn all() -> &'static [Self] {
+        const ALL: &[InclusionStage] = &[InclusionStage::Miniblock, InclusionStage::L1Batch];
+
+        ALL
+    }
+}
+
 /// Generic wrapper of all the actions that can be done in loadtest.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ActionType {
     InitComplete,
     Tx(TxActionType),
+    TxInclusion(InclusionStage),
     Api(ApiActionType),
     Subscription(SubscriptionType),
 }
@@ -208,6 +264,12 @@ impl From<TxCommand> for ActionType {
     }
 }
 
+impl From<InclusionStage> for ActionType {
+    fn from(stage: InclusionStage) -> Self {
+        Self::TxInclusion(stage)
+    }
+}
+
 impl From<SubscriptionType> for ActionType {
     fn from(subscription_type: SubscriptionType) -> Self {
         Self::Subscription(subscription_type)
@@ -222,6 +284,7 @@ impl ActionType {
             .iter()
             .copied()
             .map(Self::from)
+            .chain(InclusionStage::all().iter().copied().map(Self::from))
             .chain(ApiActionType::all().iter().copied().map(Self::from))
             .collect()
     }
@@ -21,10 +21,20 @@ impl Default for TimeHistogram {
         let sub_sec_ranges = (0..10).map(|window_idx| Self::window(window_idx, 100));
         // Ranges from 1 second to 20 seconds with windows of 1 second.
         let sec_ranges = (1..20).map(|window_idx| Self::window(window_idx, 1000));
-        // Range for (20 sec; MAX).
-        let rest_range = [(20_000u64, u64::MAX)];
-
-        let ranges: Vec<_> = sub_sec_ranges.chain(sec_ranges).chain(rest_range).collect();
+        // Ranges from 20 seconds to 2 minutes with windows of 10 seconds. Such latencies are expected
+        // for inclusion into L1 batches, or if the node falls behind in the open-loop mode.
+        let ten_sec_ranges = (2..12).map(|window_idx| Self::window(window_idx, 10_000));
+        // Ranges from 2 minutes to 10 minutes with windows of 1 minute.
+        let min_ranges = (2..10).map(|window_idx| Self::window(window_idx, 60_000));
+        // Range for (10 min; MAX).
+        let rest_range = [(600_000u64, u64::MAX)];
+
+        let ranges: Vec<_> = sub_sec_ranges
+            .chain(sec_ranges)
+            .chain(ten_sec_ranges)
+            .chain(min_ranges)
+            .chain(rest_range)
+            .collect();
         let mut histogram = BTreeMap::new();
 
         for &(start, _) in &ranges {
@@ -129,18 +139,28 @@ impl MetricsCollector {
             .and_modify(|hist| hist.add_metric(time));
     }
 
+    /// Returns the histograms for actions matching the predicate that have at least one entry.
+    pub fn non_empty_stats<'a>(
+        &'a self,
+        predicate: impl Fn(ActionType) -> bool + 'a,
+    ) -> impl Iterator<Item = (ActionType, &'a TimeHistogram)> + 'a {
+        self.action_stats
+            .iter()
+            .filter(move |(action, histogram)| predicate(**action) && !histogram.is_empty())
+            .map(|(action, histogram)| (*action, histogram))
+    }
+
     pub fn report(&self) {
-        tracing::info!("Action: [10 percentile, 50 percentile, 90 percentile]");
-        for (action, histogram) in &self.action_stats {
-            // Only report data that was actually gathered.
-            if !histogram.is_empty() {
-                tracing::info!(
-                    "{action:?}: [>{}ms >{}ms >{}ms]",
-                    histogram.percentile(10).0.as_millis(),
-                    histogram.percentile(50).0.as_millis(),
-                    histogram.percentile(90).0.as_millis(),
-                );
-            }
+        tracing::info!("Action: [10 percentile, 50 percentile, 90 percentile, 99 percentile]");
+        // Only report data that was actually gathered.
+        for (action, histogram) in self.non_empty_stats(|_| true) {
+            tracing::info!(
+                "{action:?}: [>{}ms >{}ms >{}ms >{}ms]",
+                histogram.percentile(10).0.as_millis(),
+                histogram.percentile(50).0.as_millis(),
+                histogram.percentile(90).0.as_millis(),
+                histogram.percentile(99).0.as_millis(),
+            );
         }
     }
 }
@@ -4,8 +4,9 @@ use futures::{channel::mpsc::Receiver, StreamExt};
 use operation_results_collector::OperationResultsCollector;
 
 use crate::{
-    report::{ActionType, Report, ReportLabel},
-    report_collector::metrics_collector::MetricsCollector,
+    config::SloThresholds,
+    report::{ActionType, InclusionStage, Report, ReportLabel},
+    report_collector::metrics_collector::{MetricsCollector, TimeHistogram},
 };
 
 mod metrics_collector;
@@ -49,7 +50,11 @@ impl Collectors {
         self.operation_results.report(actual_duration);
     }
 
-    fn final_resolution(&self, expected_tx_count: Option<usize>) -> LoadtestResult {
+    fn final_resolution(
+        &self,
+        expected_tx_count: Option<usize>,
+        slo: &SloThresholds,
+    ) -> LoadtestResult {
         let is_tx_count_acceptable = expected_tx_count.map_or(true, |expected_count| {
             const MIN_ACCEPTABLE_DELTA: f64 = -10.0;
             const MAX_ACCEPTABLE_DELTA: f64 = 100.0;
@@ -63,12 +68,106 @@ impl Collectors {
             (MIN_ACCEPTABLE_DELTA..=MAX_ACCEPTABLE_DELTA).contains(&delta)
         });
 
-        if !is_tx_count_acceptable || self.operation_results.tx_results.failures() > 0 {
+        let tx_results = &self.operation_results.tx_results;
+        let is_failure_rate_acceptable = match slo.max_failure_rate {
+            None => tx_results.failures() == 0,
+            Some(max_failure_rate) => {
+                let failure_rate = if tx_results.total() == 0 {
+                    0.0
+                } else {
+                    tx_results.failures() as f64 / tx_results.total() as f64
+                };
+                tracing::info!(
+                    "Transaction failure rate: {failure_rate:.4} (max acceptable: {max_failure_rate})"
+                );
+                failure_rate <= max_failure_rate
+            }
+        };
+
+        if self.is_aborted
+            || !is_tx_count_acceptable
+            || !is_failure_rate_acceptable
+            || !self.are_latency_slos_met(slo)
+        {
             LoadtestResult::TestFailed
         } else {
             LoadtestResult::TestPassed
         }
     }
+
+    fn are_latency_slos_met(&self, slo: &SloThresholds) -> bool {
+        let checks: [(Option<Duration>, fn(ActionType) -> bool); 3] = [
+            (slo.p99_tx_latency, |action| {
+                matches!(action, ActionType::Tx(_))
+            }),
+            (slo.p99_miniblock_inclusion, |action| {
+                action == ActionType::TxInclusion(InclusionStage::Miniblock)
+            }),
+            (slo.p99_l1_batch_inclusion, |action| {
+                action == ActionType::TxInclusion(InclusionStage::L1Batch)
+            }),
+        ];
+
+        let mut are_met = true;
+        for (threshold, predicate) in checks {
+            let Some(threshold) = threshold else {
+                continue;
+            };
+            for (action, histogram) in self.metrics.non_empty_stats(predicate) {
+                are_met &= Self::is_p99_latency_acceptable(action, histogram, threshold);
+            }
+        }
+        if slo.p99_l1_batch_inclusion.is_some() {
+            are_met &= self.is_unfinished_l1_batch_inclusion_acceptable();
+        }
+        are_met
+    }
+
+    /// Transactions not included into an L1 batch by the end of the test have unknown inclusion latency, so they are
+    /// not present in the histogram. If there are more than 1% of them, the 99 percentile cannot be established.
+    fn is_unfinished_l1_batch_inclusion_acceptable(&self) -> bool {
+        let results = &self.operation_results.l1_batch_inclusion_results;
+        let unfinished = results.skipped();
+        if unfinished * 100 > results.total() {
+            tracing::error!(
+                "SLO violated: {unfinished} out of {} transactions were not included into an L1 batch \
+                 by the end of the test, so 99 percentile of L1 batch inclusion latency is unknown",
+                results.total()
+            );
+            false
+        } else {
+            if unfinished > 0 {
+                tracing::info!(
+                    "{unfinished} out of {} transactions were not included into an L1 batch by the end of the test",
+                    results.total()
+                );
+            }
+            true
+        }
+    }
+
+    fn is_p99_latency_acceptable(
+        action: ActionType,
+        histogram: &TimeHistogram,
+        threshold: Duration,
+    ) -> bool {
+        // The histogram only knows the window the percentile falls into, so we compare its upper bound
+        // with the threshold to not pass the test because of the lack of precision.
+        let (_, p99_upper_bound) = histogram.percentile(99);
+        if p99_upper_bound > threshold {
+            tracing::error!(
+                "SLO violated: 99 percentile of {action:?} latency is up to {p99_upper_bound:?}, \
+                 which exceeds {threshold:?}"
+            );
+            false
+        } else {
+            tracing::info!(
+                "SLO met: 99 percentile of {action:?} latency is at most {p99_upper_bound:?} \
+                 (threshold: {threshold:?})"
+            );
+            true
+        }
+    }
 }
 
 /// ReportCollector is an entity capable of analyzing everything that happens in the loadtest.
@@ -83,6 +182,9 @@ impl Collectors {
 /// - OperationResultsCollector, a primitive collector that counts the amount of failures and decides whether
 ///   test is passed.
 ///
+/// The final decision also takes into account the configured service level objectives (see `SloThresholds`),
+/// which are checked against the gathered latency histograms and failure counts.
+///
 /// Other possible collectors that can be implemented:
 ///
 /// - ScriptCollector, which records all the actions (including wallet private keys and signatures), which makes it
@@ -103,6 +205,7 @@ pub struct ReportCollector {
     loadtest_duration: Duration,
     prometheus_label: String,
     fail_fast: bool,
+    slo: SloThresholds,
 }
 
 impl ReportCollector {
@@ -112,6 +215,7 @@ impl ReportCollector {
         loadtest_duration: Duration,
         prometheus_label: String,
         fail_fast: bool,
+        slo: SloThresholds,
     ) -> Self {
         Self {
             reports_stream,
@@ -119,6 +223,7 @@ impl ReportCollector {
             loadtest_duration,
             prometheus_label,
             fail_fast,
+            slo,
         }
     }
 
@@ -162,7 +267,7 @@ impl ReportCollector {
             }
 
             // Report failure, if it exists.
-            if let ReportLabel::ActionFailed { error } = &report.label {
+            if let Some(error) = report.label.error() {
                 tracing::warn!("Operation failed: {error}");
                 if self.fail_fast && matches!(report.action, ActionType::Tx(_)) {
                     tracing::error!("Test aborted because of an error in transaction processing");
@@ -178,7 +283,7 @@ impl ReportCollector {
         // Now we can output the statistics.
         if let Some(collectors) = collectors {
             collectors.report(self.prometheus_label);
-            collectors.final_resolution(self.expected_tx_count)
+            collectors.final_resolution(self.expected_tx_count, &self.slo)
         } else {
             tracing::error!("Test failed before initialization was completed");
             LoadtestResult::TestFailed
@@ -1,6 +1,6 @@
 use std::{fmt, time::Duration};
 
-use crate::report::{ActionType, ReportLabel};
+use crate::report::{ActionType, InclusionStage, ReportLabel};
 
 /// Collector that analyzes the outcomes of the performed operations.
 /// Currently it's solely capable of deciding whether test was failed or not.
@@ -10,6 +10,9 @@ pub struct OperationResultsCollector {
     pub(super) tx_results: ResultCollector,
     api_requests_results: ResultCollector,
     subscriptions_results: ResultCollector,
+    /// Inclusions of executed transactions into L1 batches. Transactions not included by the end of the test
+    /// are counted as skipped.
+    pub(super) l1_batch_inclusion_results: ResultCollector,
     loadtest_duration: Duration,
 }
 
@@ -18,6 +21,7 @@ pub(super) struct ResultCollector {
     successes: u64,
     skipped: u64,
     failures: u64,
+    validation_failures: u64,
 }
 
 impl ResultCollector {
@@ -26,6 +30,7 @@ impl ResultCollector {
             ReportLabel::ActionDone => self.successes += 1,
             ReportLabel::ActionSkipped { .. } => self.skipped += 1,
             ReportLabel::ActionFailed { .. } => self.failures += 1,
+            ReportLabel::ValidationFailed { .. } => self.validation_failures += 1,
         }
     }
 
@@ -33,12 +38,17 @@ impl ResultCollector {
         self.successes
     }
 
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Returns the total number of failures, including validation ones.
     pub fn failures(&self) -> u64 {
-        self.failures
+        self.failures + self.validation_failures
     }
 
     pub fn total(&self) -> u64 {
-        self.successes + self.skipped + self.failures
+        self.successes + self.skipped + self.failures()
     }
 }
 
@@ -46,10 +56,11 @@ impl fmt::Display for ResultCollector {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             formatter,
-            "{} successful, {} skipped, {} failures. {} total.",
+            "{} successful, {} skipped, {} failures, {} validation failures. {} total.",
             self.successes,
             self.skipped,
             self.failures,
+            self.validation_failures,
             self.total()
         )
     }
@@ -68,7 +79,11 @@ impl OperationResultsCollector {
             ActionType::Tx(_) => self.tx_results.add_status(status),
             ActionType::Api(_) => self.api_requests_results.add_status(status),
             ActionType::Subscription(_) => self.subscriptions_results.add_status(status),
-            ActionType::InitComplete => {}
+            ActionType::TxInclusion(InclusionStage::L1Batch) => {
+                self.l1_batch_inclusion_results.add_status(status);
+            }
+            // Miniblock inclusion reports only complement successful transaction reports.
+            ActionType::InitComplete | ActionType::TxInclusion(InclusionStage::Miniblock) => {}
         }
     }
 
@@ -91,5 +106,10 @@ impl OperationResultsCollector {
         tracing::info!("Transaction execution stats: {}", self.tx_results);
         tracing::info!("API requests stats: {}", self.api_requests_results);
         tracing::info!("Subscriptions stats: {}", self.subscriptions_results);
+        tracing::info!(
+            "L1 batch inclusion stats: {} included, {} unfinished",
+            self.l1_batch_inclusion_results.successes(),
+            self.l1_batch_inclusion_results.skipped()
+        );
     }
 }
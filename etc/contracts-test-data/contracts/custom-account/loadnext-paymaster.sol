@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.0;
+
+import "./interfaces/IPaymaster.sol";
+import "./interfaces/IPaymasterFlow.sol";
+import "./TransactionHelper.sol";
+import "./Constants.sol";
+
+// Paymaster used by the load test. For the approval-based flow, it expects the paymasterInput to contain a "signature"
+// as well as the needed exchange rate, like `CustomPaymaster`. For the general flow, it sponsors the transaction
+// from its own balance.
+contract LoadnextPaymaster is IPaymaster {
+    using TransactionHelper for Transaction;
+
+    function validateSignature(bytes memory _signature) internal pure {
+        // For the purpose of this test, any signature of length 46 is fine.
+        require(_signature.length == 46);
+    }
+
+    function validateAndPayForPaymasterTransaction(bytes32, bytes32, Transaction calldata _transaction) override external payable returns (bytes4 magic, bytes memory context) {
+        // By default we consider the transaction as passed
+        magic = PAYMASTER_VALIDATION_SUCCESS_MAGIC;
+
+        require(_transaction.paymasterInput.length >= 4, "The standard paymaster input must be at least 4 bytes long");
+
+        bytes4 paymasterInputSelector = bytes4(_transaction.paymasterInput[0:4]);
+        if (paymasterInputSelector == IPaymasterFlow.approvalBased.selector) {
+            // While the actual data consists of address, uint256 and bytes data,
+            // the data is needed only for the paymaster, so we ignore it here for the sake of optimization
+            (address token,, bytes memory input) = abi.decode(_transaction.paymasterInput[4:], (address, uint256, bytes));
+
+            (bytes memory pseudoSignature, uint256 rateNumerator, uint256 rateDenominator, uint256 amount) = abi.decode(input, (bytes, uint256, uint256, uint256));
+            validateSignature(pseudoSignature);
+
+            // Firstly, we verify that the user has provided enough allowance
+            address userAddress = address(uint160(_transaction.from));
+            address thisAddress = address(this);
+
+            uint256 providedAllowance = IERC20(token).allowance(userAddress, thisAddress);
+            require(providedAllowance >= amount, "The user did not provide enough allowance");
+
+            uint256 requiredETH = _transaction.gasLimit * _transaction.maxFeePerGas;
+            uint256 ethExchanged = amount * rateNumerator / rateDenominator;
+            require(ethExchanged >= requiredETH, "User does not provide enough tokens to exchange");
+
+            // Pulling all the tokens from the user
+            IERC20(token).transferFrom(userAddress, thisAddress, amount);
+        } else if (paymasterInputSelector != IPaymasterFlow.general.selector) {
+            revert("Unsupported paymaster flow");
+        }
+
+        // In both flows, the transaction is paid for from the paymaster balance.
+        bool success = _transaction.payToTheBootloader();
+        require(success, "Failed to transfer funds to the bootloader");
+    }
+
+    function postTransaction(
+        bytes calldata,
+        Transaction calldata,
+        bytes32,
+        bytes32,
+        ExecutionResult,
+        uint256
+    ) override external payable {
+        // Refunds are not supported
+    }
+
+    receive() external payable {}
+}
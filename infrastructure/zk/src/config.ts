@@ -15,6 +15,7 @@ const CONFIG_FILES = [
     'eth_watch.toml',
     'misc.toml',
     'object_store.toml',
+    'opentelemetry.toml',
     'nfs.toml',
     'rust.toml',
     'private.toml',
@@ -26,7 +27,8 @@ const CONFIG_FILES = [
     'proof_data_handler.toml',
     'fri_witness_vector_generator.toml',
     'fri_prover_gateway.toml',
-    'fri_proof_compressor.toml'
+    'fri_proof_compressor.toml',
+    'withdrawal_finalizer.toml'
 ];
 
 function loadConfigFile(path: string) {
@@ -1,11 +1,21 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
+use anyhow::Context as _;
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{header::HeaderValue, Client, Request, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{sync::watch, time::sleep};
+use zksync_config::configs::{
+    proof_data_handler::ProofDataHandlerAuthMode, FriProverGatewayConfig,
+};
 use zksync_dal::ConnectionPool;
 use zksync_object_store::ObjectStore;
+use zksync_prover_interface::auth::{
+    hmac_signature, AUTHORIZATION_HEADER, CLIENT_ID_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
 
 use crate::metrics::METRICS;
 
@@ -15,12 +25,90 @@ pub(crate) const PROOF_GENERATION_DATA_PATH: &str = "/proof_generation_data";
 /// The path to the API endpoint that submits the proof.
 pub(crate) const SUBMIT_PROOF_PATH: &str = "/submit_proof";
 
+/// Credentials used to authenticate requests to the proof data handler API.
+#[derive(Clone)]
+pub(crate) enum ApiAuth {
+    None,
+    BearerToken(HeaderValue),
+    Hmac {
+        client_id: HeaderValue,
+        secret: String,
+    },
+}
+
+impl ApiAuth {
+    pub(crate) fn from_config(config: &FriProverGatewayConfig) -> anyhow::Result<Self> {
+        let secret = || {
+            config
+                .api_client_secret
+                .as_ref()
+                .map(|secret| secret.expose().as_str())
+                .context("api_client_secret must be set if API authentication is enabled")
+        };
+        Ok(match config.api_auth_mode {
+            ProofDataHandlerAuthMode::Disabled => Self::None,
+            ProofDataHandlerAuthMode::BearerToken => {
+                let mut header = HeaderValue::try_from(format!("Bearer {}", secret()?))
+                    .context("api_client_secret is not a valid header value")?;
+                header.set_sensitive(true);
+                Self::BearerToken(header)
+            }
+            ProofDataHandlerAuthMode::Hmac => {
+                let client_id = config
+                    .api_client_id
+                    .as_deref()
+                    .context("api_client_id must be set for HMAC authentication")?;
+                Self::Hmac {
+                    client_id: HeaderValue::from_str(client_id)
+                        .context("api_client_id is not a valid header value")?,
+                    secret: secret()?,
+                }
+            }
+        })
+    }
+
+    fn apply(&self, request: &mut Request) {
+        match self {
+            Self::None => { /* no credentials */ }
+            Self::BearerToken(header) => {
+                request
+                    .headers_mut()
+                    .insert(AUTHORIZATION_HEADER, header.clone());
+            }
+            Self::Hmac { client_id, secret } => {
+                let timestamp = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("incorrect system time")
+                    .as_secs();
+                let body = request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .unwrap_or_default();
+                let signature = hmac_signature(
+                    secret.as_bytes(),
+                    timestamp,
+                    request.method().as_str(),
+                    request.url().path(),
+                    body,
+                );
+
+                let headers = request.headers_mut();
+                headers.insert(CLIENT_ID_HEADER, client_id.clone());
+                headers.insert(TIMESTAMP_HEADER, timestamp.into());
+                // A hex-encoded signature is always a valid header value.
+                headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+            }
+        }
+    }
+}
+
 pub(crate) struct PeriodicApiStruct {
     pub(crate) blob_store: Arc<dyn ObjectStore>,
     pub(crate) pool: ConnectionPool,
     pub(crate) api_url: String,
     pub(crate) poll_duration: Duration,
     pub(crate) client: Client,
+    pub(crate) auth: ApiAuth,
 }
 
 impl PeriodicApiStruct {
@@ -35,14 +123,14 @@ impl PeriodicApiStruct {
     {
         tracing::info!("Sending request to {}", endpoint);
 
-        self.client
-            .post(endpoint)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?
-            .json::<Resp>()
-            .await
+        let mut request = self.client.post(endpoint).json(&request).build()?;
+        self.auth.apply(&mut request);
+        let mut response = self.client.execute(request).await?;
+        // Unprocessable requests (e.g., rejected proofs) are reported with a structured response body.
+        if response.status() != StatusCode::UNPROCESSABLE_ENTITY {
+            response = response.error_for_status()?;
+        }
+        response.json::<Resp>().await
     }
 
     pub(crate) async fn run<Req>(
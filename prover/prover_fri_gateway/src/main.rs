@@ -9,7 +9,9 @@ use zksync_object_store::ObjectStoreFactory;
 use zksync_prover_interface::api::{ProofGenerationDataRequest, SubmitProofRequest};
 use zksync_utils::wait_for_tasks::wait_for_tasks;
 
-use crate::api_data_fetcher::{PeriodicApiStruct, PROOF_GENERATION_DATA_PATH, SUBMIT_PROOF_PATH};
+use crate::api_data_fetcher::{
+    ApiAuth, PeriodicApiStruct, PROOF_GENERATION_DATA_PATH, SUBMIT_PROOF_PATH,
+};
 
 mod api_data_fetcher;
 mod metrics;
@@ -32,7 +34,7 @@ async fn main() -> anyhow::Result<()> {
             .context("Invalid Sentry URL")?
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     let config =
         FriProverGatewayConfig::from_env().context("FriProverGatewayConfig::from_env()")?;
@@ -47,6 +49,7 @@ async fn main() -> anyhow::Result<()> {
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
     let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let api_auth = ApiAuth::from_config(&config).context("invalid API auth config")?;
 
     let proof_submitter = PeriodicApiStruct {
         blob_store: store_factory.create_store().await,
@@ -54,6 +57,7 @@ async fn main() -> anyhow::Result<()> {
         api_url: format!("{}{SUBMIT_PROOF_PATH}", config.api_url),
         poll_duration: config.api_poll_duration(),
         client: Client::new(),
+        auth: api_auth.clone(),
     };
     let proof_gen_data_fetcher = PeriodicApiStruct {
         blob_store: store_factory.create_store().await,
@@ -61,6 +65,7 @@ async fn main() -> anyhow::Result<()> {
         api_url: format!("{}{PROOF_GENERATION_DATA_PATH}", config.api_url),
         poll_duration: config.api_poll_duration(),
         client: Client::new(),
+        auth: api_auth,
     };
 
     let (stop_sender, stop_receiver) = watch::channel(false);
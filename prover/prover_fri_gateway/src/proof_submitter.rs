@@ -3,7 +3,10 @@ use zksync_dal::fri_proof_compressor_dal::ProofCompressionJobStatus;
 use zksync_prover_interface::api::{SubmitProofRequest, SubmitProofResponse};
 use zksync_types::L1BatchNumber;
 
-use crate::api_data_fetcher::{PeriodicApi, PeriodicApiStruct};
+use crate::{
+    api_data_fetcher::{PeriodicApi, PeriodicApiStruct},
+    metrics::METRICS,
+};
 
 impl PeriodicApiStruct {
     async fn next_submit_proof_request(&self) -> Option<(L1BatchNumber, SubmitProofRequest)> {
@@ -44,6 +47,18 @@ impl PeriodicApiStruct {
             .mark_proof_sent_to_server(l1_batch_number)
             .await;
     }
+
+    /// Marks the compression job for a rejected proof as failed, so that it's retried
+    /// by the proof compressor job retry manager.
+    async fn requeue_rejected_proof(&self, l1_batch_number: L1BatchNumber, reason: &str) {
+        self.pool
+            .access_storage()
+            .await
+            .unwrap()
+            .fri_proof_compressor_dal()
+            .mark_proof_compression_job_failed(reason, l1_batch_number)
+            .await;
+    }
 }
 
 #[async_trait]
@@ -68,6 +83,13 @@ impl PeriodicApi<SubmitProofRequest> for PeriodicApiStruct {
 
     async fn handle_response(&self, job_id: L1BatchNumber, response: Self::Response) {
         tracing::info!("Received response: {:?}", response);
-        self.save_successful_sent_proof(job_id).await;
+        if let SubmitProofResponse::Rejected(reason) = response {
+            tracing::error!("Proof for L1 batch #{job_id} was rejected by the server: {reason}");
+            METRICS.rejected_proofs.inc();
+            self.requeue_rejected_proof(job_id, &reason.to_string())
+                .await;
+        } else {
+            self.save_successful_sent_proof(job_id).await;
+        }
     }
 }
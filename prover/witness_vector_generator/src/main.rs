@@ -48,7 +48,7 @@ async fn main() -> anyhow::Result<()> {
             .context("Invalid Sentry URL")?
             .with_sentry_environment(environment);
     }
-    let _guard = builder.build();
+    let _guard = builder.build()?;
 
     let opt = Opt::from_args();
     let config = FriWitnessVectorGeneratorConfig::from_env()
@@ -62,9 +62,10 @@ where
 
         self.wallet
             .signer
-            .sign_execute_contract(
+            .sign_execute_contract_with_value(
                 contract_address,
                 calldata,
+                self.value.unwrap_or_default(),
                 fee,
                 nonce,
                 self.factory_deps,
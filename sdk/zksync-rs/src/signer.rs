@@ -101,9 +101,10 @@ impl<S: EthereumSigner> Signer<S> {
         factory_deps: Option<Vec<Vec<u8>>>,
         paymaster_params: PaymasterParams,
     ) -> Result<L2Tx, SignerError> {
-        self.sign_execute_contract_for_deploy(
+        self.sign_execute_contract_with_value(
             contract,
             calldata,
+            U256::zero(),
             fee,
             nonce,
             factory_deps,
@@ -112,10 +113,13 @@ impl<S: EthereumSigner> Signer<S> {
         .await
     }
 
-    pub async fn sign_execute_contract_for_deploy(
+    /// Same as [`Self::sign_execute_contract()`], but also transfers the specified `value` to the contract.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sign_execute_contract_with_value(
         &self,
         contract: Address,
         calldata: Vec<u8>,
+        value: U256,
         fee: Fee,
         nonce: Nonce,
         factory_deps: Option<Vec<Vec<u8>>>,
@@ -127,7 +131,7 @@ impl<S: EthereumSigner> Signer<S> {
             nonce,
             fee,
             self.eth_signer.get_address().await?,
-            U256::zero(),
+            value,
             factory_deps,
             paymaster_params,
         );
@@ -140,4 +144,24 @@ impl<S: EthereumSigner> Signer<S> {
 
         Ok(execute_contract)
     }
+
+    pub async fn sign_execute_contract_for_deploy(
+        &self,
+        contract: Address,
+        calldata: Vec<u8>,
+        fee: Fee,
+        nonce: Nonce,
+        factory_deps: Option<Vec<Vec<u8>>>,
+        paymaster_params: PaymasterParams,
+    ) -> Result<L2Tx, SignerError> {
+        self.sign_execute_contract(
+            contract,
+            calldata,
+            fee,
+            nonce,
+            factory_deps,
+            paymaster_params,
+        )
+        .await
+    }
 }